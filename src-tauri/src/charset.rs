@@ -0,0 +1,333 @@
+use encoding_rs::{DecoderResult, EncoderResult};
+use serde::{Deserialize, Serialize};
+
+///
+/// 文本字符集
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Charset {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Gbk,
+    Gb18030,
+    Big5,
+    ShiftJis,
+    EucJp,
+    EucKr,
+    /// 按 WHATWG 的约定实际使用 Windows-1252
+    Latin1,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CharsetError {
+    #[error("invalid {0:?} byte sequence at offset {1}")]
+    Malformed(Charset, usize),
+    #[error("character `{1}` cannot be represented in {0:?}")]
+    Unmappable(Charset, char),
+}
+
+///
+/// 字符集探测结果，`candidates` 按可信度从高到低排列
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection {
+    pub charset: Charset,
+    pub confidence: f64,
+    pub bom: bool,
+    pub candidates: Vec<Candidate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    pub charset: Charset,
+    pub confidence: f64,
+}
+
+/// 没有 BOM 时参与探测的非 Unicode 字符集，GB18030 与 GBK 解码结果相同，单独判断
+const LEGACY: [Charset; 6] = [
+    Charset::Gbk,
+    Charset::Big5,
+    Charset::ShiftJis,
+    Charset::EucJp,
+    Charset::EucKr,
+    Charset::Latin1,
+];
+
+/// Windows-1252 在 0x80-0x9F 区间比 ISO-8859-1 多出的字符
+const WINDOWS_1252: &str = "€‚ƒ„…†‡ˆ‰Š‹ŒŽ‘’“”•–—˜™š›œžŸ";
+
+impl Charset {
+    pub fn encoding(self) -> &'static encoding_rs::Encoding {
+        match self {
+            Charset::Utf8 => encoding_rs::UTF_8,
+            Charset::Utf16Le => encoding_rs::UTF_16LE,
+            Charset::Utf16Be => encoding_rs::UTF_16BE,
+            Charset::Gbk => encoding_rs::GBK,
+            Charset::Gb18030 => encoding_rs::GB18030,
+            Charset::Big5 => encoding_rs::BIG5,
+            Charset::ShiftJis => encoding_rs::SHIFT_JIS,
+            Charset::EucJp => encoding_rs::EUC_JP,
+            Charset::EucKr => encoding_rs::EUC_KR,
+            Charset::Latin1 => encoding_rs::WINDOWS_1252,
+        }
+    }
+
+    fn from_encoding(encoding: &'static encoding_rs::Encoding) -> Option<Charset> {
+        [
+            Charset::Utf8,
+            Charset::Utf16Le,
+            Charset::Utf16Be,
+            Charset::Gb18030,
+        ]
+        .into_iter()
+        .chain(LEGACY)
+        .find(|charset| charset.encoding() == encoding)
+    }
+
+    pub fn bom(self) -> &'static [u8] {
+        match self {
+            Charset::Utf8 => &[0xEF, 0xBB, 0xBF],
+            Charset::Utf16Le => &[0xFF, 0xFE],
+            Charset::Utf16Be => &[0xFE, 0xFF],
+            _ => &[],
+        }
+    }
+
+    ///
+    /// 严格解码，遇到非法字节时报告其偏移量，开头与本字符集一致的 BOM 会被去掉
+    ///
+    pub fn decode(self, bytes: &[u8]) -> Result<String, CharsetError> {
+        self.decode_strict(bytes, true)
+    }
+
+    ///
+    /// 非法字节替换为 U+FFFD，同时返回是否发生了替换
+    ///
+    pub fn decode_lossy(self, bytes: &[u8]) -> (String, bool) {
+        let (text, replaced) = self.encoding().decode_with_bom_removal(bytes);
+        (text.into_owned(), replaced)
+    }
+
+    ///
+    /// 严格编码，遇到目标字符集无法表示的字符时报错
+    ///
+    pub fn encode(self, text: &str) -> Result<Vec<u8>, CharsetError> {
+        match self {
+            Charset::Utf8 => return Ok(text.as_bytes().to_vec()),
+            // encoding_rs 只能输出 ASCII 兼容的字符集，UTF-16 需要手动处理
+            Charset::Utf16Le => {
+                return Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect())
+            }
+            Charset::Utf16Be => {
+                return Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect())
+            }
+            _ => {}
+        }
+        let mut encoder = self.encoding().new_encoder();
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut offset = 0;
+        loop {
+            let (result, read) = encoder.encode_from_utf8_to_vec_without_replacement(
+                &text[offset..],
+                &mut bytes,
+                true,
+            );
+            offset += read;
+            match result {
+                EncoderResult::InputEmpty => return Ok(bytes),
+                EncoderResult::OutputFull => bytes.reserve(text.len() - offset + 16),
+                EncoderResult::Unmappable(c) => return Err(CharsetError::Unmappable(self, c)),
+            }
+        }
+    }
+
+    fn decode_strict(self, bytes: &[u8], last: bool) -> Result<String, CharsetError> {
+        let mut decoder = self.encoding().new_decoder_with_bom_removal();
+        let mut text = String::with_capacity(bytes.len());
+        let mut offset = 0;
+        loop {
+            let (result, read) =
+                decoder.decode_to_string_without_replacement(&bytes[offset..], &mut text, last);
+            offset += read;
+            match result {
+                DecoderResult::InputEmpty => return Ok(text),
+                DecoderResult::OutputFull => text.reserve((bytes.len() - offset) * 3 + 16),
+                DecoderResult::Malformed(malformed, extra) => {
+                    let position = offset - malformed as usize - extra as usize;
+                    return Err(CharsetError::Malformed(self, position));
+                }
+            }
+        }
+    }
+
+    ///
+    /// 探测字节的字符集，`bytes` 可以是截断的采样
+    ///
+    /// 依次判断 BOM、UTF-16 的零字节分布和合法的 UTF-8，其余交给 chardetng 猜测，
+    /// 再结合解码出的字符是否属于该字符集常用的文字给出可信度
+    ///
+    pub fn detect(bytes: &[u8]) -> Detection {
+        for charset in [Charset::Utf8, Charset::Utf16Le, Charset::Utf16Be] {
+            if bytes.starts_with(charset.bom()) {
+                return Detection {
+                    charset,
+                    confidence: 1.0,
+                    bom: true,
+                    candidates: vec![Candidate {
+                        charset,
+                        confidence: 1.0,
+                    }],
+                };
+            }
+        }
+
+        let mut candidates = Vec::new();
+        let unicode = match utf16(bytes).or_else(|| utf8(bytes)) {
+            Some(candidate) => {
+                let confidence = candidate.confidence;
+                candidates.push(candidate);
+                confidence
+            }
+            None => 0.0,
+        };
+        if unicode < 1.0 {
+            candidates.extend(legacy(bytes).into_iter().map(|candidate| Candidate {
+                confidence: candidate.confidence * (1.0 - unicode),
+                ..candidate
+            }));
+        }
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+        let best = candidates.first().cloned().unwrap_or(Candidate {
+            charset: Charset::Utf8,
+            confidence: 0.0,
+        });
+        Detection {
+            charset: best.charset,
+            confidence: best.confidence,
+            bom: false,
+            candidates,
+        }
+    }
+}
+
+///
+/// 没有 BOM 的 UTF-16 靠 ASCII 字符高位的零字节识别
+///
+fn utf16(bytes: &[u8]) -> Option<Candidate> {
+    let units = bytes.len() / 2;
+    if units == 0 {
+        return None;
+    }
+    let zeros = |parity: usize| {
+        let count = bytes
+            .iter()
+            .skip(parity)
+            .step_by(2)
+            .filter(|&&b| b == 0)
+            .count();
+        count as f64 / units as f64
+    };
+    let (even, odd) = (zeros(0), zeros(1));
+    let (charset, score) = match odd > even {
+        true => (Charset::Utf16Le, odd - even),
+        false => (Charset::Utf16Be, even - odd),
+    };
+    if score < 0.2 || charset.decode_strict(bytes, false).is_err() {
+        return None;
+    }
+    Some(Candidate {
+        charset,
+        confidence: (0.5 + score / 2.0).min(0.99),
+    })
+}
+
+///
+/// 合法的 UTF-8 多字节序列很少是巧合，序列越多越可信
+///
+fn utf8(bytes: &[u8]) -> Option<Candidate> {
+    let text = Charset::Utf8.decode_strict(bytes, false).ok()?;
+    let sequences = text.chars().filter(|c| !c.is_ascii()).count();
+    let confidence = match sequences {
+        0 => 1.0,
+        n => (1.0 - 0.5f64.powi(n.min(64) as i32 + 1)).min(0.99),
+    };
+    Some(Candidate {
+        charset: Charset::Utf8,
+        confidence,
+    })
+}
+
+fn legacy(bytes: &[u8]) -> Vec<Candidate> {
+    let mut detector = chardetng::EncodingDetector::new();
+    // 采样可能截断在多字节字符中间
+    detector.feed(bytes, false);
+    let (guess, assured) = detector.guess_assess(None, false);
+    let guess = Charset::from_encoding(guess);
+
+    LEGACY
+        .into_iter()
+        .filter_map(|charset| {
+            let text = charset.decode_strict(bytes, false).ok()?;
+            let weight = match guess == Some(charset) {
+                true if assured => 0.95,
+                true => 0.7,
+                false => 0.45,
+            };
+            // GBK 无法表示的字符只能来自 GB18030 的四字节序列
+            let charset = match charset {
+                Charset::Gbk if Charset::Gbk.encode(&text).is_err() => Charset::Gb18030,
+                charset => charset,
+            };
+            Some(Candidate {
+                charset,
+                confidence: plausibility(charset, &text) * weight,
+            })
+        })
+        .collect()
+}
+
+///
+/// 非 ASCII 字符中属于该字符集常用文字的比例
+///
+fn plausibility(charset: Charset, text: &str) -> f64 {
+    let chars: Vec<char> = text.chars().collect();
+    let mut total = 0;
+    let mut typical = 0;
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_ascii() {
+            continue;
+        }
+        total += 1;
+        let common = matches!(c, '\u{3000}'..='\u{303F}' | '\u{FF01}'..='\u{FF5E}');
+        let han = matches!(c, '\u{4E00}'..='\u{9FFF}');
+        let matched = match charset {
+            Charset::Gbk | Charset::Gb18030 | Charset::Big5 => han || common,
+            Charset::ShiftJis | Charset::EucJp => {
+                han || common || matches!(c, '\u{3040}'..='\u{30FF}' | '\u{FF61}'..='\u{FF9F}')
+            }
+            Charset::EucKr => {
+                han || common || matches!(c, '\u{AC00}'..='\u{D7A3}' | '\u{3130}'..='\u{318F}')
+            }
+            // 西欧文字中的重音字母通常与 ASCII 字母相邻，不会成串出现
+            Charset::Latin1 => {
+                let isolated = |index: Option<usize>| {
+                    index
+                        .and_then(|index| chars.get(index))
+                        .map_or(true, char::is_ascii)
+                };
+                (matches!(c, '\u{A0}'..='\u{FF}') || WINDOWS_1252.contains(c))
+                    && (isolated(index.checked_sub(1)) || isolated(Some(index + 1)))
+            }
+            _ => true,
+        };
+        if matched {
+            typical += 1;
+        }
+    }
+    match total {
+        0 => 1.0,
+        total => typical as f64 / total as f64,
+    }
+}
@@ -0,0 +1,265 @@
+use crate::command::codec::hexdump::{identify, FileType, MAGIC_SIZE};
+use crate::command_error;
+use crate::encoding::{Encoding, EncodingError};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Cursor, Read, Write};
+
+/// MIME 规定每行最多 76 个字符，对应 57 个字节
+const LINE_BYTES: usize = 57;
+
+/// 每次编码读取的字节数，取 57 的整数倍，保证分块编码后仍按 76 个字符换行
+const ENCODE_CHUNK: usize = LINE_BYTES * 16 * 1024;
+
+/// 每次解码读取的字符数
+const DECODE_CHUNK: usize = 1024 * 1024;
+
+/// 无法识别文件类型时 data URI 使用的 MIME
+const OCTET_STREAM: &str = "application/octet-stream";
+
+///
+/// 待解码的 Base64，可以是文本或者保存了 Base64 的文件
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Source {
+    Text(String),
+    File(String),
+}
+
+#[derive(Debug, Serialize)]
+pub struct Encoded {
+    /// 根据魔数识别出的文件类型
+    kind: Option<FileType>,
+    /// 原文件的字节数
+    size: u64,
+    /// 未指定输出文件时直接返回编码结果
+    text: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Decoded {
+    /// 根据解码后的魔数识别出的文件类型
+    kind: Option<FileType>,
+    /// data URI 中声明的 MIME
+    declared: Option<String>,
+    /// 解码后的字节数
+    size: u64,
+}
+
+///
+/// 将任意文件编码为 Base64，分块读取以支持大文件
+///
+/// `wrap` 时按 MIME 的要求每 76 个字符换行，`data_uri` 时根据魔数识别的类型加上 `data:` 前缀，
+/// 指定 `output` 时结果写入该文件
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn encode_file_base64(
+    path: &str,
+    mode: Encoding,
+    wrap: bool,
+    data_uri: bool,
+    output: Option<String>,
+) -> Result<Encoded, Error> {
+    check(mode)?;
+    let reader = BufReader::new(File::open(path)?);
+    match output {
+        Some(output) => {
+            let mut writer = BufWriter::new(File::create(&output)?);
+            let (kind, size) = encode(reader, mode, wrap, data_uri, &mut writer)?;
+            writer.flush()?;
+            Ok(Encoded {
+                kind,
+                size,
+                text: None,
+            })
+        }
+        None => {
+            let mut buffer = Vec::new();
+            let (kind, size) = encode(reader, mode, wrap, data_uri, &mut buffer)?;
+            let text = String::from_utf8(buffer).expect("base64 output is ascii");
+            Ok(Encoded {
+                kind,
+                size,
+                text: Some(text),
+            })
+        }
+    }
+}
+
+///
+/// 将 Base64 或 `data:` URI 解码为文件，忽略其中的空白和换行
+///
+/// 未指定 `output` 时只校验并识别文件类型，不保存结果，解码失败时删除不完整的输出文件
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, skip(source), ret, err(level = tracing::Level::ERROR))]
+pub fn decode_file_base64(
+    source: Source,
+    mode: Encoding,
+    output: Option<String>,
+) -> Result<Decoded, Error> {
+    check(mode)?;
+    let reader: Box<dyn Read> = match source {
+        Source::Text(text) => Box::new(Cursor::new(text.into_bytes())),
+        Source::File(path) => Box::new(BufReader::new(File::open(path)?)),
+    };
+    let Some(output) = output else {
+        return decode(reader, mode, &mut std::io::sink());
+    };
+    let mut writer = BufWriter::new(File::create(&output)?);
+    let result = decode(reader, mode, &mut writer).and_then(|decoded| {
+        writer.flush()?;
+        Ok(decoded)
+    });
+    if result.is_err() {
+        drop(writer);
+        let _ = std::fs::remove_file(&output);
+    }
+    result
+}
+
+fn check(mode: Encoding) -> Result<(), Error> {
+    match mode {
+        Encoding::Base64
+        | Encoding::Base64NoPad
+        | Encoding::Base64Url
+        | Encoding::Base64UrlNoPad => Ok(()),
+        mode => Err(Error::Unsupported(mode)),
+    }
+}
+
+fn encode(
+    mut reader: impl Read,
+    mode: Encoding,
+    wrap: bool,
+    data_uri: bool,
+    writer: &mut impl Write,
+) -> Result<(Option<FileType>, u64), Error> {
+    let mut kind = None;
+    let mut size = 0;
+    let mut first = true;
+    // 已经写出过数据行，之后的行前面需要换行
+    let mut written = false;
+    loop {
+        let mut chunk = Vec::with_capacity(ENCODE_CHUNK);
+        reader
+            .by_ref()
+            .take(ENCODE_CHUNK as u64)
+            .read_to_end(&mut chunk)?;
+        if first {
+            kind = identify(&chunk[..chunk.len().min(MAGIC_SIZE)]);
+            if data_uri {
+                let mime = kind.as_ref().map_or(OCTET_STREAM, |kind| kind.mime);
+                write!(writer, "data:{mime};base64,")?;
+            }
+            first = false;
+        }
+        if chunk.is_empty() {
+            break;
+        }
+        size += chunk.len() as u64;
+
+        let text = mode.encode(&chunk)?;
+        match wrap {
+            true => {
+                for line in text.as_bytes().chunks(LINE_BYTES / 3 * 4) {
+                    if written {
+                        writer.write_all(b"\r\n")?;
+                    }
+                    writer.write_all(line)?;
+                    written = true;
+                }
+            }
+            false => writer.write_all(text.as_bytes())?,
+        }
+        if chunk.len() < ENCODE_CHUNK {
+            break;
+        }
+    }
+    Ok((kind, size))
+}
+
+fn decode(
+    mut reader: impl Read,
+    mode: Encoding,
+    writer: &mut impl Write,
+) -> Result<Decoded, Error> {
+    let mut declared = None;
+    let mut head = Vec::with_capacity(MAGIC_SIZE);
+    let mut pending = Vec::new();
+    let mut size = 0;
+    let mut first = true;
+    loop {
+        let mut chunk = Vec::with_capacity(DECODE_CHUNK);
+        reader
+            .by_ref()
+            .take(DECODE_CHUNK as u64)
+            .read_to_end(&mut chunk)?;
+        let last = chunk.len() < DECODE_CHUNK;
+        if first {
+            let (mime, start) = data_uri(&chunk)?;
+            declared = mime;
+            chunk.drain(..start);
+            first = false;
+        }
+        pending.extend(chunk.into_iter().filter(|b| !b.is_ascii_whitespace()));
+
+        // 中间的分块只解码完整的 4 个字符一组的部分，剩余的留到下一块
+        let length = match last {
+            true => pending.len(),
+            false => pending.len() / 4 * 4,
+        };
+        let text = std::str::from_utf8(&pending[..length]).map_err(|_| Error::NotText)?;
+        let bytes = mode.decode(text)?;
+        if head.len() < MAGIC_SIZE {
+            let take = (MAGIC_SIZE - head.len()).min(bytes.len());
+            head.extend_from_slice(&bytes[..take]);
+        }
+        size += bytes.len() as u64;
+        writer.write_all(&bytes)?;
+        pending.drain(..length);
+        if last {
+            break;
+        }
+    }
+    Ok(Decoded {
+        kind: identify(&head),
+        declared,
+        size,
+    })
+}
+
+///
+/// 解析开头的 `data:[<mime>][;param];base64,`，返回声明的 MIME 和数据开始的位置
+///
+fn data_uri(chunk: &[u8]) -> Result<(Option<String>, usize), Error> {
+    let start = chunk
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(chunk.len());
+    if !chunk[start..].starts_with(b"data:") {
+        return Ok((None, 0));
+    }
+    let comma = chunk[start..]
+        .iter()
+        .position(|&b| b == b',')
+        .ok_or(Error::DataUri)?;
+    let header =
+        std::str::from_utf8(&chunk[start + 5..start + comma]).map_err(|_| Error::DataUri)?;
+    let mut params = header.split(';');
+    let mime = params.next().unwrap_or_default().trim();
+    if !params.any(|param| param.trim().eq_ignore_ascii_case("base64")) {
+        return Err(Error::DataUri);
+    }
+    let mime = (!mime.is_empty()).then(|| mime.to_string());
+    Ok((mime, start + comma + 1))
+}
+
+command_error! {
+    (Io, "io error: {0}", #[from] std::io::Error),
+    (Encoding, "encoding error: {0}", #[from] EncodingError),
+    (Unsupported, "{0:?} is not a base64 encoding", Encoding),
+    (NotText, "base64 input contains non-ascii characters"),
+    (DataUri, "invalid data uri, only base64 data uri is supported"),
+}
@@ -1,23 +1,33 @@
-use std::string::FromUtf8Error;
-
+use crate::charset::{Charset, CharsetError};
 use crate::command_error;
-
-use super::base64_helper::Base64Mode;
+use crate::encoding::{Encoding, EncodingError};
 
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn encode_text_base64(text: &str, mode: Base64Mode) -> Result<String, Error> {
-    Ok(mode.encode(text.as_bytes()))
+pub fn encode_text_base64(
+    text: &str,
+    mode: Encoding,
+    charset: Option<Charset>,
+) -> Result<String, Error> {
+    let bytes = charset.unwrap_or(Charset::Utf8).encode(text)?;
+    mode.encode(&bytes).map_err(Into::into)
 }
 
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn decode_text_base64(base64: &str, mode: Base64Mode) -> Result<String, Error> {
+pub fn decode_text_base64(
+    base64: &str,
+    mode: Encoding,
+    charset: Option<Charset>,
+) -> Result<String, Error> {
     let bytes = mode.decode(base64)?;
-    String::from_utf8(bytes).map_err(Into::into)
+    charset
+        .unwrap_or(Charset::Utf8)
+        .decode(&bytes)
+        .map_err(Into::into)
 }
 
 command_error! {
-    (Utf8, "utf-8 error: {0}", #[from] FromUtf8Error),
-    (DecodeBase64, "decode base64 error: {0}", #[from] base64::DecodeError),
+    (Charset, "charset error: {0}", #[from] CharsetError),
+    (Encoding, "encoding error: {0}", #[from] EncodingError),
 }
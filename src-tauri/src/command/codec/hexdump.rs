@@ -0,0 +1,676 @@
+use crate::charset::{Charset, CharsetError};
+use crate::command_error;
+use crate::encoding::{EncodingError, EncodingText};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+/// 默认每页的字节数
+const PAGE_SIZE: usize = 4096;
+
+/// 每页最多返回的字节数
+const MAX_PAGE_SIZE: usize = 1024 * 1024;
+
+/// 搜索时每次读取的字节数
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 默认最多返回的匹配数
+const MAX_MATCHES: usize = 1000;
+
+/// 识别文件类型时读取的字节数，tar 的标记位于 257 字节处
+pub(crate) const MAGIC_SIZE: usize = 512;
+
+///
+/// 待查看的数据，文件按需分段读取
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Source {
+    Bytes(EncodingText),
+    File(String),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+///
+/// 搜索的内容
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Pattern {
+    /// 十六进制字节，忽略空白，`??` 匹配任意字节
+    Hex(String),
+    /// 按字符集编码后的字符串，默认 UTF-8
+    Text {
+        text: String,
+        charset: Option<Charset>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct Dump {
+    /// 数据的总字节数
+    size: u64,
+    offset: u64,
+    lines: Vec<Line>,
+    /// 与 `xxd` 相同格式的文本
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Line {
+    offset: u64,
+    hex: String,
+    ascii: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Matches {
+    offsets: Vec<u64>,
+    /// 匹配的字节数
+    length: usize,
+    /// 达到数量上限后停止了搜索
+    truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileType {
+    pub extension: &'static str,
+    pub mime: &'static str,
+    pub description: &'static str,
+}
+
+///
+/// 指定偏移处按不同类型解读的数值，字节不足时为空
+///
+#[derive(Debug, Serialize)]
+pub struct Values {
+    offset: u64,
+    binary: Option<String>,
+    int8: Option<String>,
+    uint8: Option<String>,
+    int16: Option<String>,
+    uint16: Option<String>,
+    int32: Option<String>,
+    uint32: Option<String>,
+    int64: Option<String>,
+    uint64: Option<String>,
+    float32: Option<String>,
+    float64: Option<String>,
+    /// 无符号 LEB128 变长整数，与字节序无关
+    leb128: Option<String>,
+}
+
+///
+/// 分页输出 `xxd` 风格的十六进制视图
+///
+/// `width` 为每行的字节数，`group` 为每组的字节数，为 0 时不分组
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn dump_hex(
+    source: Source,
+    offset: Option<u64>,
+    length: Option<usize>,
+    width: Option<usize>,
+    group: Option<usize>,
+) -> Result<Dump, Error> {
+    let width = width.unwrap_or(16);
+    let group = group.unwrap_or(2);
+    if !(1..=64).contains(&width) {
+        return Err(Error::Width(width));
+    }
+    let offset = offset.unwrap_or(0);
+    let length = length.unwrap_or(PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+    let mut data = Data::open(source)?;
+    let bytes = data.read(offset, length)?;
+
+    // 最后一行不满时也按整行对齐 ASCII 列
+    let columns = hex(&vec![0; width], group).len();
+    let mut lines = Vec::with_capacity(bytes.len().div_ceil(width));
+    let mut text = String::new();
+    for (index, chunk) in bytes.chunks(width).enumerate() {
+        let line = Line {
+            offset: offset + (index * width) as u64,
+            hex: hex(chunk, group),
+            ascii: chunk.iter().map(|&b| ascii(b)).collect(),
+        };
+        text.push_str(&format!(
+            "{:08x}: {:<columns$}  {}\n",
+            line.offset, line.hex, line.ascii
+        ));
+        lines.push(line);
+    }
+    Ok(Dump {
+        size: data.size(),
+        offset,
+        lines,
+        text,
+    })
+}
+
+///
+/// 从 `start` 开始搜索字节序列，返回所有匹配的偏移量，匹配之间可以重叠
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn search_hex(
+    source: Source,
+    pattern: Pattern,
+    start: Option<u64>,
+    limit: Option<usize>,
+) -> Result<Matches, Error> {
+    let needle = pattern.to_bytes()?;
+    let limit = limit.unwrap_or(MAX_MATCHES);
+    let mut data = Data::open(source)?;
+    let size = data.size();
+
+    let mut offsets = Vec::new();
+    let mut position = start.unwrap_or(0);
+    while position < size {
+        // 多读出 needle.len() - 1 个字节，避免漏掉跨块的匹配
+        let chunk = data.read(position, CHUNK_SIZE + needle.len() - 1)?;
+        for (index, window) in chunk.windows(needle.len()).enumerate() {
+            let matched = window
+                .iter()
+                .zip(&needle)
+                .all(|(byte, expected)| expected.map_or(true, |expected| *byte == expected));
+            if matched {
+                if offsets.len() == limit {
+                    return Ok(Matches {
+                        offsets,
+                        length: needle.len(),
+                        truncated: true,
+                    });
+                }
+                offsets.push(position + index as u64);
+            }
+        }
+        position += CHUNK_SIZE as u64;
+    }
+    Ok(Matches {
+        offsets,
+        length: needle.len(),
+        truncated: false,
+    })
+}
+
+///
+/// 根据开头的魔数识别文件类型，无法识别的文本按纯文本处理
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn identify_file(source: Source) -> Result<Option<FileType>, Error> {
+    let head = Data::open(source)?.read(0, MAGIC_SIZE)?;
+    Ok(identify(&head))
+}
+
+///
+/// 根据开头最多 [`MAGIC_SIZE`] 个字节识别文件类型
+///
+pub(crate) fn identify(head: &[u8]) -> Option<FileType> {
+    let matched = MAGICS.iter().find(|magic| {
+        let signed = magic.signature.iter().all(|(offset, signature)| {
+            head.get(*offset..*offset + signature.len()) == Some(*signature)
+        });
+        signed
+            && match magic.verify {
+                Some(verify) => verify(head),
+                None => true,
+            }
+    });
+    if let Some(magic) = matched {
+        return Some(FileType {
+            extension: magic.extension,
+            mime: magic.mime,
+            description: magic.description,
+        });
+    }
+
+    is_text(head).then_some(FileType {
+        extension: "txt",
+        mime: "text/plain",
+        description: "纯文本",
+    })
+}
+
+fn is_text(head: &[u8]) -> bool {
+    // 采样可能截断在多字节字符中间
+    let text = match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(error) => error.error_len().is_none(),
+    };
+    let binary = head
+        .iter()
+        .any(|&b| b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r' | 0x0c));
+    !head.is_empty() && text && !binary
+}
+
+fn read_u32_le(head: &[u8], offset: usize) -> Option<u32> {
+    let bytes = head.get(offset..offset + 4)?.try_into().ok()?;
+    Some(u32::from_le_bytes(bytes))
+}
+
+/// 文件头之后是 DIB 头，它的长度只有几种固定的取值
+fn bmp_header(head: &[u8]) -> bool {
+    matches!(
+        read_u32_le(head, 14),
+        Some(12 | 16 | 40 | 52 | 56 | 64 | 108 | 124)
+    )
+}
+
+/// 0x3C 处是 PE 头的偏移，偏移超出采样范围时只要求内容不是文本
+fn pe_header(head: &[u8]) -> bool {
+    let offset = read_u32_le(head, 0x3c).map(|offset| offset as usize);
+    match offset.and_then(|offset| head.get(offset..offset + 4)) {
+        Some(signature) => signature == b"PE\0\0",
+        None => !is_text(head),
+    }
+}
+
+///
+/// 按指定字节序解读偏移处的整数和浮点数
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn inspect_hex(source: Source, offset: u64, endian: Endian) -> Result<Values, Error> {
+    let bytes = Data::open(source)?.read(offset, 16)?;
+
+    macro_rules! read {
+        ($ty:ty) => {
+            bytes.get(..std::mem::size_of::<$ty>()).map(|slice| {
+                let array = slice.try_into().expect("slice length checked");
+                let value = match endian {
+                    Endian::Little => <$ty>::from_le_bytes(array),
+                    Endian::Big => <$ty>::from_be_bytes(array),
+                };
+                // Debug 格式在数值过大或过小时使用科学计数法
+                format!("{value:?}")
+            })
+        };
+    }
+
+    Ok(Values {
+        offset,
+        binary: bytes.first().map(|b| format!("{b:08b}")),
+        int8: read!(i8),
+        uint8: read!(u8),
+        int16: read!(i16),
+        uint16: read!(u16),
+        int32: read!(i32),
+        uint32: read!(u32),
+        int64: read!(i64),
+        uint64: read!(u64),
+        float32: read!(f32),
+        float64: read!(f64),
+        leb128: leb128(&bytes).map(|value| value.to_string()),
+    })
+}
+
+///
+/// 字节数据，文本输入一次性解码，文件按需读取
+///
+enum Data {
+    Bytes(Vec<u8>),
+    File(File, u64),
+}
+
+impl Data {
+    fn open(source: Source) -> Result<Self, Error> {
+        match source {
+            Source::Bytes(text) => Ok(Data::Bytes(text.to_bytes()?)),
+            Source::File(path) => {
+                let file = File::open(path)?;
+                let size = file.metadata()?.len();
+                Ok(Data::File(file, size))
+            }
+        }
+    }
+
+    fn size(&self) -> u64 {
+        match self {
+            Data::Bytes(bytes) => bytes.len() as u64,
+            Data::File(_, size) => *size,
+        }
+    }
+
+    fn read(&mut self, offset: u64, length: usize) -> Result<Vec<u8>, Error> {
+        match self {
+            Data::Bytes(bytes) => {
+                let start = offset.min(bytes.len() as u64) as usize;
+                let end = start.saturating_add(length).min(bytes.len());
+                Ok(bytes[start..end].to_vec())
+            }
+            Data::File(file, _) => {
+                let mut buffer = Vec::with_capacity(length.min(MAX_PAGE_SIZE));
+                file.seek(SeekFrom::Start(offset))?;
+                file.take(length as u64).read_to_end(&mut buffer)?;
+                Ok(buffer)
+            }
+        }
+    }
+}
+
+impl Pattern {
+    ///
+    /// `None` 表示通配的字节
+    ///
+    fn to_bytes(&self) -> Result<Vec<Option<u8>>, Error> {
+        let needle = match self {
+            Pattern::Hex(hex) => {
+                let digits: Vec<char> = hex.chars().filter(|c| !c.is_whitespace()).collect();
+                if digits.len() % 2 != 0 {
+                    return Err(Error::Pattern(hex.clone()));
+                }
+                digits
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        ['?', '?'] => Ok(None),
+                        [high, low] => match (high.to_digit(16), low.to_digit(16)) {
+                            (Some(high), Some(low)) => Ok(Some((high << 4 | low) as u8)),
+                            _ => Err(Error::Pattern(hex.clone())),
+                        },
+                        _ => unreachable!("chunks of two"),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            Pattern::Text { text, charset } => charset
+                .unwrap_or(Charset::Utf8)
+                .encode(text)?
+                .into_iter()
+                .map(Some)
+                .collect(),
+        };
+        match needle.is_empty() {
+            true => Err(Error::EmptyPattern),
+            false => Ok(needle),
+        }
+    }
+}
+
+fn hex(bytes: &[u8], group: usize) -> String {
+    let group = if group == 0 {
+        bytes.len().max(1)
+    } else {
+        group
+    };
+    bytes
+        .chunks(group)
+        .map(hex::encode)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn ascii(byte: u8) -> char {
+    match byte {
+        0x20..=0x7e => byte as char,
+        _ => '.',
+    }
+}
+
+fn leb128(bytes: &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for (index, byte) in bytes.iter().take(10).enumerate() {
+        let bits = (*byte & 0x7f) as u64;
+        // 第 10 个字节只剩最低位可用
+        if index == 9 && bits > 1 {
+            return None;
+        }
+        value |= bits << (index * 7);
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+struct Magic {
+    /// 偏移量与对应的字节，全部匹配才算命中
+    signature: &'static [(usize, &'static [u8])],
+    /// 签名过短容易误判时，进一步检查文件头
+    verify: Option<fn(&[u8]) -> bool>,
+    extension: &'static str,
+    mime: &'static str,
+    description: &'static str,
+}
+
+const fn magic(
+    signature: &'static [(usize, &'static [u8])],
+    extension: &'static str,
+    mime: &'static str,
+    description: &'static str,
+) -> Magic {
+    Magic {
+        signature,
+        verify: None,
+        extension,
+        mime,
+        description,
+    }
+}
+
+impl Magic {
+    const fn verify(self, verify: fn(&[u8]) -> bool) -> Magic {
+        Magic {
+            verify: Some(verify),
+            ..self
+        }
+    }
+}
+
+/// 更具体的签名需要排在前面，比如 RIFF 的各种子格式
+const MAGICS: &[Magic] = &[
+    magic(&[(0, b"\x89PNG\r\n\x1a\n")], "png", "image/png", "PNG 图像"),
+    magic(&[(0, b"\xff\xd8\xff")], "jpg", "image/jpeg", "JPEG 图像"),
+    magic(&[(0, b"GIF87a")], "gif", "image/gif", "GIF 图像"),
+    magic(&[(0, b"GIF89a")], "gif", "image/gif", "GIF 图像"),
+    magic(
+        &[(0, b"RIFF"), (8, b"WEBP")],
+        "webp",
+        "image/webp",
+        "WebP 图像",
+    ),
+    magic(
+        &[(0, b"RIFF"), (8, b"WAVE")],
+        "wav",
+        "audio/wav",
+        "WAV 音频",
+    ),
+    magic(
+        &[(0, b"RIFF"), (8, b"AVI ")],
+        "avi",
+        "video/x-msvideo",
+        "AVI 视频",
+    ),
+    magic(&[(0, b"BM")], "bmp", "image/bmp", "BMP 图像").verify(bmp_header),
+    magic(
+        &[(0, b"\x00\x00\x01\x00")],
+        "ico",
+        "image/x-icon",
+        "ICO 图标",
+    ),
+    magic(&[(0, b"II*\x00")], "tif", "image/tiff", "TIFF 图像"),
+    magic(&[(0, b"MM\x00*")], "tif", "image/tiff", "TIFF 图像"),
+    magic(
+        &[(0, b"8BPS")],
+        "psd",
+        "image/vnd.adobe.photoshop",
+        "Photoshop 文档",
+    ),
+    magic(&[(4, b"ftypavif")], "avif", "image/avif", "AVIF 图像"),
+    magic(&[(4, b"ftypheic")], "heic", "image/heic", "HEIC 图像"),
+    magic(
+        &[(4, b"ftypqt")],
+        "mov",
+        "video/quicktime",
+        "QuickTime 视频",
+    ),
+    magic(&[(4, b"ftyp")], "mp4", "video/mp4", "MP4 视频"),
+    magic(
+        &[(0, b"\x1a\x45\xdf\xa3")],
+        "mkv",
+        "video/x-matroska",
+        "Matroska 视频",
+    ),
+    magic(&[(0, b"ID3")], "mp3", "audio/mpeg", "MP3 音频"),
+    magic(&[(0, b"OggS")], "ogg", "audio/ogg", "Ogg 音频"),
+    magic(&[(0, b"fLaC")], "flac", "audio/flac", "FLAC 音频"),
+    magic(&[(0, b"%PDF-")], "pdf", "application/pdf", "PDF 文档"),
+    magic(&[(0, b"{\\rtf")], "rtf", "application/rtf", "RTF 文档"),
+    magic(
+        &[(0, b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1")],
+        "doc",
+        "application/x-ole-storage",
+        "OLE 复合文档（doc/xls/ppt/msi）",
+    ),
+    magic(
+        &[(0, b"PK\x03\x04")],
+        "zip",
+        "application/zip",
+        "ZIP 压缩包（也可能是 docx/xlsx/jar/apk）",
+    ),
+    magic(
+        &[(0, b"PK\x05\x06")],
+        "zip",
+        "application/zip",
+        "空 ZIP 压缩包",
+    ),
+    magic(
+        &[(0, b"\x1f\x8b")],
+        "gz",
+        "application/gzip",
+        "Gzip 压缩数据",
+    ),
+    magic(
+        &[(0, b"BZh")],
+        "bz2",
+        "application/x-bzip2",
+        "Bzip2 压缩数据",
+    ),
+    magic(
+        &[(0, b"\xfd7zXZ\x00")],
+        "xz",
+        "application/x-xz",
+        "XZ 压缩数据",
+    ),
+    magic(
+        &[(0, b"\x28\xb5\x2f\xfd")],
+        "zst",
+        "application/zstd",
+        "Zstandard 压缩数据",
+    ),
+    magic(
+        &[(0, b"\x04\x22\x4d\x18")],
+        "lz4",
+        "application/x-lz4",
+        "LZ4 压缩数据",
+    ),
+    magic(
+        &[(0, b"7z\xbc\xaf\x27\x1c")],
+        "7z",
+        "application/x-7z-compressed",
+        "7-Zip 压缩包",
+    ),
+    magic(
+        &[(0, b"Rar!\x1a\x07")],
+        "rar",
+        "application/vnd.rar",
+        "RAR 压缩包",
+    ),
+    magic(&[(257, b"ustar")], "tar", "application/x-tar", "tar 归档"),
+    magic(
+        &[(0, b"\x7fELF")],
+        "elf",
+        "application/x-elf",
+        "ELF 可执行文件",
+    ),
+    magic(
+        &[(0, b"MZ")],
+        "exe",
+        "application/vnd.microsoft.portable-executable",
+        "Windows 可执行文件",
+    )
+    .verify(pe_header),
+    magic(
+        &[(0, b"\xcf\xfa\xed\xfe")],
+        "macho",
+        "application/x-mach-binary",
+        "Mach-O 可执行文件",
+    ),
+    magic(
+        &[(0, b"\xce\xfa\xed\xfe")],
+        "macho",
+        "application/x-mach-binary",
+        "Mach-O 可执行文件",
+    ),
+    magic(
+        &[(0, b"\xca\xfe\xba\xbe")],
+        "class",
+        "application/java-vm",
+        "Java 字节码或 Mach-O 通用二进制",
+    ),
+    magic(
+        &[(0, b"dex\n")],
+        "dex",
+        "application/vnd.android.dex",
+        "Android DEX 字节码",
+    ),
+    magic(
+        &[(0, b"\x00asm")],
+        "wasm",
+        "application/wasm",
+        "WebAssembly 模块",
+    ),
+    magic(
+        &[(0, b"SQLite format 3\x00")],
+        "sqlite",
+        "application/vnd.sqlite3",
+        "SQLite 数据库",
+    ),
+    magic(
+        &[(0, b"PAR1")],
+        "parquet",
+        "application/vnd.apache.parquet",
+        "Parquet 数据文件",
+    ),
+    magic(&[(0, b"wOFF")], "woff", "font/woff", "WOFF 字体"),
+    magic(&[(0, b"wOF2")], "woff2", "font/woff2", "WOFF2 字体"),
+    magic(&[(0, b"OTTO")], "otf", "font/otf", "OpenType 字体"),
+    magic(
+        &[(0, b"\x00\x01\x00\x00\x00")],
+        "ttf",
+        "font/ttf",
+        "TrueType 字体",
+    ),
+    magic(&[(0, b"<?xml")], "xml", "application/xml", "XML 文档"),
+    magic(
+        &[(0, b"\xef\xbb\xbf")],
+        "txt",
+        "text/plain",
+        "带 BOM 的 UTF-8 文本",
+    ),
+    magic(
+        &[(0, b"\xff\xfe")],
+        "txt",
+        "text/plain",
+        "带 BOM 的 UTF-16LE 文本",
+    ),
+    magic(
+        &[(0, b"\xfe\xff")],
+        "txt",
+        "text/plain",
+        "带 BOM 的 UTF-16BE 文本",
+    ),
+];
+
+command_error! {
+    (Io, "io error: {0}", #[from] std::io::Error),
+    (Encoding, "encoding error: {0}", #[from] EncodingError),
+    (Charset, "charset error: {0}", #[from] CharsetError),
+    (Width, "bytes per line must be between 1 and 64, got {0}", usize),
+    (Pattern, "invalid hex pattern `{0}`", String),
+    (EmptyPattern, "search pattern is empty"),
+}
@@ -1,7 +1,8 @@
-pub mod base64_helper;
+pub mod base64_file;
 pub mod base64_image;
 pub mod base64_text;
 pub mod gzip;
+pub mod hexdump;
 pub mod jwt;
 pub mod qrcode;
 pub mod url;
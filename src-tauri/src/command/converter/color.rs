@@ -0,0 +1,984 @@
+use crate::command_error;
+use image::ImageError;
+use serde::{Deserialize, Serialize};
+
+/// 提取主色调之前先把图片缩小到该尺寸以内
+const THUMBNAIL_SIZE: u32 = 128;
+
+/// 提取主色调时忽略透明度低于该值的像素
+const MIN_ALPHA: u8 = 128;
+
+/// 色板和主色调最多的颜色数量
+const MAX_COUNT: usize = 32;
+
+/// CSS 命名颜色
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+/// D50 白点，CSS 的 lab() 使用 D50
+const D50: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+const LINEAR_SRGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+const XYZ_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [
+        0.05563007969699366,
+        -0.20397695888897652,
+        1.0569715142428786,
+    ],
+];
+
+/// Bradford 色适应，D65 到 D50
+const D65_TO_D50: [[f64; 3]; 3] = [
+    [
+        1.0479298208405488,
+        0.022946793341019088,
+        -0.05019222954313557,
+    ],
+    [
+        0.029627815688159344,
+        0.990434484573249,
+        -0.01707382502938514,
+    ],
+    [
+        -0.009243058152591178,
+        0.015055144896577895,
+        0.7518742899580008,
+    ],
+];
+
+const D50_TO_D65: [[f64; 3]; 3] = [
+    [
+        0.9554734527042182,
+        -0.023098536874261423,
+        0.0632593086610217,
+    ],
+    [
+        -0.028369706963208136,
+        1.0099954580058226,
+        0.021041398966943008,
+    ],
+    [
+        0.012314001688319899,
+        -0.020507696433477912,
+        1.3303659366080753,
+    ],
+];
+
+const LINEAR_SRGB_TO_LMS: [[f64; 3]; 3] = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+const LMS_TO_OKLAB: [[f64; 3]; 3] = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+const OKLAB_TO_LMS: [[f64; 3]; 3] = [
+    [1.0, 0.3963377774, 0.2158037573],
+    [1.0, -0.1055613458, -0.0638541728],
+    [1.0, -0.0894841775, -1.2914855480],
+];
+
+const LMS_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+///
+/// 色板的类型
+///
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Palette {
+    /// 逐步混入白色
+    Tints,
+    /// 逐步混入黑色
+    Shades,
+    /// 逐步混入灰色
+    Tones,
+    /// 互补色
+    Complementary,
+    /// 分裂互补色
+    SplitComplementary,
+    /// 相邻的 ±30°
+    Analogous,
+    Triadic,
+    Tetradic,
+}
+
+///
+/// 同一个颜色的各种写法，超出 sRGB 色域的颜色会被截断
+///
+#[derive(Debug, Serialize)]
+pub struct Color {
+    hex: String,
+    rgb: String,
+    hsl: String,
+    hsv: String,
+    hwb: String,
+    cmyk: String,
+    lab: String,
+    oklch: String,
+    /// 完全相同的 CSS 命名颜色
+    name: Option<String>,
+    /// 0 - 255 的 RGBA 分量
+    rgba: [u8; 4],
+}
+
+///
+/// WCAG 2 的对比度，前景色的透明度按叠加在背景色上计算
+///
+#[derive(Debug, Serialize)]
+pub struct Contrast {
+    ratio: f64,
+    /// 普通文字 4.5:1
+    aa: bool,
+    /// 大号文字 3:1
+    aa_large: bool,
+    /// 普通文字 7:1
+    aaa: bool,
+    /// 大号文字 4.5:1
+    aaa_large: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Swatch {
+    color: Color,
+    /// 在图片中所占的比例
+    ratio: f64,
+}
+
+///
+/// 0 - 1 的 sRGB 分量
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Rgba {
+    red: f64,
+    green: f64,
+    blue: f64,
+    alpha: f64,
+}
+
+///
+/// 函数写法中的一个分量
+///
+#[derive(Debug, Copy, Clone)]
+enum Value {
+    Number(f64),
+    Percent(f64),
+}
+
+///
+/// 转换颜色，支持 HEX、rgb()、hsl()、hsv()、hwb()、cmyk()、lab()、oklch() 和 CSS 命名颜色
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_color(input: &str) -> Result<Color, Error> {
+    Ok(parse(input)?.into())
+}
+
+///
+/// 计算前景色和背景色的 WCAG 对比度，背景色的透明度按叠加在白色上计算
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn contrast_color(foreground: &str, background: &str) -> Result<Contrast, Error> {
+    let background = parse(background)?.over(Rgba::WHITE);
+    let foreground = parse(foreground)?.over(background);
+    let (lighter, darker) = {
+        let (first, second) = (foreground.luminance(), background.luminance());
+        (first.max(second), first.min(second))
+    };
+    let ratio = (lighter + 0.05) / (darker + 0.05);
+    Ok(Contrast {
+        ratio,
+        aa: ratio >= 4.5,
+        aa_large: ratio >= 3.0,
+        aaa: ratio >= 7.0,
+        aaa_large: ratio >= 4.5,
+    })
+}
+
+///
+/// 生成色板，第一个总是原来的颜色
+///
+/// `count` 只对 `Tints`、`Shades`、`Tones` 有效，为混合的步数，其他类型按色相旋转
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn generate_palette(input: &str, palette: Palette, count: usize) -> Result<Vec<Color>, Error> {
+    let color = parse(input)?;
+    let mix = |target: Rgba| -> Result<Vec<Rgba>, Error> {
+        if count == 0 || count > MAX_COUNT {
+            return Err(Error::Count(count, MAX_COUNT));
+        }
+        // 不包括纯白、纯黑或纯灰
+        Ok((0..=count)
+            .map(|step| color.mix(target, step as f64 / (count + 1) as f64))
+            .collect())
+    };
+    let rotate = |angles: &[f64]| -> Vec<Rgba> {
+        let (hue, saturation, lightness) = color.hsl();
+        angles
+            .iter()
+            .map(|angle| Rgba::from_hsl(hue + angle, saturation, lightness, color.alpha))
+            .collect()
+    };
+    let colors = match palette {
+        Palette::Tints => mix(Rgba::WHITE)?,
+        Palette::Shades => mix(Rgba::BLACK)?,
+        Palette::Tones => mix(Rgba::GRAY)?,
+        Palette::Complementary => rotate(&[0.0, 180.0]),
+        Palette::SplitComplementary => rotate(&[0.0, 150.0, 210.0]),
+        Palette::Analogous => rotate(&[0.0, -30.0, 30.0]),
+        Palette::Triadic => rotate(&[0.0, 120.0, 240.0]),
+        Palette::Tetradic => rotate(&[0.0, 90.0, 180.0, 270.0]),
+    };
+    Ok(colors.into_iter().map(Color::from).collect())
+}
+
+///
+/// 使用中位切分提取图片的主色调，按所占比例从大到小排列，忽略接近透明的像素
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn extract_colors(image: &str, count: usize) -> Result<Vec<Swatch>, Error> {
+    if count == 0 || count > MAX_COUNT {
+        return Err(Error::Count(count, MAX_COUNT));
+    }
+    let pixels: Vec<[u8; 3]> = image::open(image)?
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .to_rgba8()
+        .pixels()
+        .filter(|pixel| pixel[3] >= MIN_ALPHA)
+        .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect();
+    let total = pixels.len() as f64;
+    let mut boxes = median_cut(pixels, count);
+    boxes.sort_by_key(|pixels| std::cmp::Reverse(pixels.len()));
+    Ok(boxes
+        .into_iter()
+        .map(|pixels| {
+            let mut sum = [0u64; 3];
+            for pixel in &pixels {
+                for (sum, value) in sum.iter_mut().zip(pixel) {
+                    *sum += *value as u64;
+                }
+            }
+            let average = |index: usize| sum[index] as f64 / pixels.len() as f64 / 255.0;
+            Swatch {
+                color: Rgba::new(average(0), average(1), average(2), 1.0).into(),
+                ratio: pixels.len() as f64 / total,
+            }
+        })
+        .collect())
+}
+
+///
+/// 每次选择跨度最大的分量所在的盒子，按该分量的中位数一分为二
+///
+fn median_cut(pixels: Vec<[u8; 3]>, count: usize) -> Vec<Vec<[u8; 3]>> {
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+    let range = |pixels: &[[u8; 3]]| -> (u8, usize) {
+        (0..3)
+            .map(|channel| {
+                let (min, max) = pixels.iter().fold((u8::MAX, u8::MIN), |(min, max), pixel| {
+                    (min.min(pixel[channel]), max.max(pixel[channel]))
+                });
+                (max - min, channel)
+            })
+            .max()
+            .unwrap_or_default()
+    };
+    let mut boxes = vec![pixels];
+    while boxes.len() < count {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, pixels)| pixels.len() > 1)
+            .map(|(index, pixels)| (range(pixels), index))
+            .max();
+        let Some(((width, channel), index)) = widest else {
+            break;
+        };
+        // 所有盒子都只剩一种颜色
+        if width == 0 {
+            break;
+        }
+        let mut pixels = boxes.swap_remove(index);
+        pixels.sort_unstable_by_key(|pixel| pixel[channel]);
+        // 相同的值分在同一个盒子里，避免同一种颜色出现多次
+        let median = pixels[pixels.len() / 2][channel];
+        let at = match pixels.partition_point(|pixel| pixel[channel] < median) {
+            0 => pixels.partition_point(|pixel| pixel[channel] <= median),
+            at => at,
+        };
+        let upper = pixels.split_off(at);
+        boxes.push(pixels);
+        boxes.push(upper);
+    }
+    boxes
+}
+
+///
+/// 解析颜色，忽略大小写和首尾空白
+///
+fn parse(input: &str) -> Result<Rgba, Error> {
+    let text = input.trim().to_ascii_lowercase();
+    let invalid = || Error::Parse(input.trim().to_string());
+    if text == "transparent" {
+        return Ok(Rgba::new(0.0, 0.0, 0.0, 0.0));
+    }
+    if let Some((_, value)) = NAMED_COLORS.iter().find(|(name, _)| *name == text) {
+        return Ok(Rgba::from_u32(*value));
+    }
+    let Some((name, args)) = text.strip_suffix(')').and_then(|text| text.split_once('(')) else {
+        return parse_hex(text.strip_prefix('#').unwrap_or(&text)).ok_or_else(invalid);
+    };
+    let args: Vec<&str> = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect();
+    let size = if name.trim() == "cmyk" { 4 } else { 3 };
+    if args.len() != size && args.len() != size + 1 {
+        return Err(invalid());
+    }
+    let alpha = match args.get(size) {
+        Some(arg) => match parse_value(arg).ok_or_else(invalid)? {
+            Value::Number(value) => value,
+            Value::Percent(value) => value / 100.0,
+        },
+        None => 1.0,
+    };
+    let value = |index: usize| parse_value(args[index]).ok_or_else(invalid);
+    let hue = |index: usize| parse_angle(args[index]).ok_or_else(invalid);
+    // 饱和度等分量不带 % 时也按百分比处理
+    let percent = |index: usize| -> Result<f64, Error> {
+        Ok(match value(index)? {
+            Value::Number(value) | Value::Percent(value) => value / 100.0,
+        })
+    };
+    let color = match name.trim() {
+        "rgb" | "rgba" => {
+            let channel = |index: usize| -> Result<f64, Error> {
+                Ok(match value(index)? {
+                    Value::Number(value) => value / 255.0,
+                    Value::Percent(value) => value / 100.0,
+                })
+            };
+            Rgba::new(channel(0)?, channel(1)?, channel(2)?, alpha)
+        }
+        "hsl" | "hsla" => Rgba::from_hsl(hue(0)?, percent(1)?, percent(2)?, alpha),
+        "hsv" | "hsva" | "hsb" | "hsba" => Rgba::from_hsv(hue(0)?, percent(1)?, percent(2)?, alpha),
+        "hwb" => Rgba::from_hwb(hue(0)?, percent(1)?, percent(2)?, alpha),
+        "cmyk" => Rgba::from_cmyk(percent(0)?, percent(1)?, percent(2)?, percent(3)?, alpha),
+        "lab" => {
+            let lightness = match value(0)? {
+                Value::Number(value) | Value::Percent(value) => value,
+            };
+            let axis = |index: usize| -> Result<f64, Error> {
+                Ok(match value(index)? {
+                    Value::Number(value) => value,
+                    Value::Percent(value) => value * 1.25,
+                })
+            };
+            Rgba::from_lab(lightness, axis(1)?, axis(2)?, alpha)
+        }
+        "oklch" => {
+            let lightness = match value(0)? {
+                Value::Number(value) => value,
+                Value::Percent(value) => value / 100.0,
+            };
+            let chroma = match value(1)? {
+                Value::Number(value) => value,
+                Value::Percent(value) => value * 0.004,
+            };
+            Rgba::from_oklch(lightness, chroma, hue(2)?, alpha)
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(color.clamp())
+}
+
+///
+/// 解析 `rgb`、`rgba`、`rrggbb`、`rrggbbaa` 四种长度的十六进制
+///
+fn parse_hex(text: &str) -> Option<Rgba> {
+    if !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits: Vec<u8> = match text.len() {
+        3 | 4 => text.bytes().flat_map(|b| [b, b]).collect(),
+        6 | 8 => text.bytes().collect(),
+        _ => return None,
+    };
+    let channels: Vec<f64> = digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap_or_default(), 16))
+        .map(|value| value.map(|value| value as f64 / 255.0))
+        .collect::<Result<_, _>>()
+        .ok()?;
+    Some(Rgba::new(
+        channels[0],
+        channels[1],
+        channels[2],
+        channels.get(3).copied().unwrap_or(1.0),
+    ))
+}
+
+fn parse_value(text: &str) -> Option<Value> {
+    if text == "none" {
+        return Some(Value::Number(0.0));
+    }
+    match text.strip_suffix('%') {
+        Some(value) => value.parse().ok().map(Value::Percent),
+        None => text.parse().ok().map(Value::Number),
+    }
+}
+
+///
+/// 解析色相，不带单位时为角度
+///
+fn parse_angle(text: &str) -> Option<f64> {
+    let units = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f64::consts::PI),
+        ("turn", 360.0),
+    ];
+    if text == "none" {
+        return Some(0.0);
+    }
+    for (unit, scale) in units {
+        if let Some(value) = text.strip_suffix(unit) {
+            return value.parse::<f64>().ok().map(|value| value * scale);
+        }
+    }
+    text.parse().ok()
+}
+
+impl Rgba {
+    const WHITE: Rgba = Rgba::new(1.0, 1.0, 1.0, 1.0);
+    const BLACK: Rgba = Rgba::new(0.0, 0.0, 0.0, 1.0);
+    const GRAY: Rgba = Rgba::new(0.5, 0.5, 0.5, 1.0);
+
+    const fn new(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    fn from_u32(value: u32) -> Self {
+        let channel = |shift: u32| ((value >> shift) & 0xff) as f64 / 255.0;
+        Rgba::new(channel(16), channel(8), channel(0), 1.0)
+    }
+
+    fn from_hsl(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        Rgba::from_chroma(hue, chroma, lightness - chroma / 2.0, alpha)
+    }
+
+    fn from_hsv(hue: f64, saturation: f64, value: f64, alpha: f64) -> Self {
+        let (saturation, value) = (saturation.clamp(0.0, 1.0), value.clamp(0.0, 1.0));
+        let chroma = value * saturation;
+        Rgba::from_chroma(hue, chroma, value - chroma, alpha)
+    }
+
+    fn from_hwb(hue: f64, white: f64, black: f64, alpha: f64) -> Self {
+        let (white, black) = (white.clamp(0.0, 1.0), black.clamp(0.0, 1.0));
+        if white + black >= 1.0 {
+            let gray = white / (white + black);
+            return Rgba::new(gray, gray, gray, alpha);
+        }
+        let value = 1.0 - black;
+        Rgba::from_hsv(hue, 1.0 - white / value, value, alpha)
+    }
+
+    fn from_cmyk(cyan: f64, magenta: f64, yellow: f64, black: f64, alpha: f64) -> Self {
+        let channel = |value: f64| (1.0 - value.clamp(0.0, 1.0)) * (1.0 - black.clamp(0.0, 1.0));
+        Rgba::new(channel(cyan), channel(magenta), channel(yellow), alpha)
+    }
+
+    fn from_lab(lightness: f64, a: f64, b: f64, alpha: f64) -> Self {
+        const EPSILON: f64 = 216.0 / 24389.0;
+        const KAPPA: f64 = 24389.0 / 27.0;
+        let fy = (lightness + 16.0) / 116.0;
+        let fx = a / 500.0 + fy;
+        let fz = fy - b / 200.0;
+        let inverse = |f: f64| match f.powi(3) {
+            cube if cube > EPSILON => cube,
+            _ => (116.0 * f - 16.0) / KAPPA,
+        };
+        let y = match lightness > KAPPA * EPSILON {
+            true => fy.powi(3),
+            false => lightness / KAPPA,
+        };
+        let xyz = [inverse(fx) * D50[0], y * D50[1], inverse(fz) * D50[2]];
+        let linear = multiply(XYZ_TO_LINEAR_SRGB, multiply(D50_TO_D65, xyz));
+        Rgba::from_linear(linear, alpha)
+    }
+
+    fn from_oklch(lightness: f64, chroma: f64, hue: f64, alpha: f64) -> Self {
+        let (sin, cos) = hue.to_radians().sin_cos();
+        let lms =
+            multiply(OKLAB_TO_LMS, [lightness, chroma * cos, chroma * sin]).map(|v| v.powi(3));
+        Rgba::from_linear(multiply(LMS_TO_LINEAR_SRGB, lms), alpha)
+    }
+
+    ///
+    /// HSL 和 HSV 共用的部分，`min` 为最小的分量
+    ///
+    fn from_chroma(hue: f64, chroma: f64, min: f64, alpha: f64) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (red, green, blue) = match hue as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        Rgba::new(red + min, green + min, blue + min, alpha)
+    }
+
+    fn from_linear(linear: [f64; 3], alpha: f64) -> Self {
+        let encode = |value: f64| match value.abs() {
+            abs if abs > 0.0031308 => value.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055),
+            _ => 12.92 * value,
+        };
+        Rgba::new(
+            encode(linear[0]),
+            encode(linear[1]),
+            encode(linear[2]),
+            alpha,
+        )
+    }
+
+    fn linear(self) -> [f64; 3] {
+        let decode = |value: f64| match value.abs() {
+            abs if abs > 0.04045 => value.signum() * ((abs + 0.055) / 1.055).powf(2.4),
+            _ => value / 12.92,
+        };
+        [decode(self.red), decode(self.green), decode(self.blue)]
+    }
+
+    fn clamp(self) -> Self {
+        let clamp = |value: f64| match value.is_nan() {
+            true => 0.0,
+            false => value.clamp(0.0, 1.0),
+        };
+        Rgba::new(
+            clamp(self.red),
+            clamp(self.green),
+            clamp(self.blue),
+            clamp(self.alpha),
+        )
+    }
+
+    fn to_u8(self) -> [u8; 4] {
+        [self.red, self.green, self.blue, self.alpha].map(|value| (value * 255.0).round() as u8)
+    }
+
+    ///
+    /// 返回 0 - 360 的色相，0 - 1 的饱和度和亮度
+    ///
+    fn hsl(self) -> (f64, f64, f64) {
+        let (hue, max, min) = self.hue();
+        let lightness = (max + min) / 2.0;
+        let saturation = match lightness {
+            l if l <= 0.0 || l >= 1.0 => 0.0,
+            l => (max - lightness) / l.min(1.0 - l),
+        };
+        (hue, saturation, lightness)
+    }
+
+    fn hsv(self) -> (f64, f64, f64) {
+        let (hue, max, min) = self.hue();
+        let saturation = match max > 0.0 {
+            true => (max - min) / max,
+            false => 0.0,
+        };
+        (hue, saturation, max)
+    }
+
+    ///
+    /// 返回色相以及最大、最小的分量，灰色的色相为 0
+    ///
+    fn hue(self) -> (f64, f64, f64) {
+        let Rgba {
+            red, green, blue, ..
+        } = self;
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let delta = max - min;
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == red {
+            60.0 * ((green - blue) / delta).rem_euclid(6.0)
+        } else if max == green {
+            60.0 * ((blue - red) / delta + 2.0)
+        } else {
+            60.0 * ((red - green) / delta + 4.0)
+        };
+        (hue, max, min)
+    }
+
+    fn lab(self) -> (f64, f64, f64) {
+        const EPSILON: f64 = 216.0 / 24389.0;
+        const KAPPA: f64 = 24389.0 / 27.0;
+        let xyz = multiply(D65_TO_D50, multiply(LINEAR_SRGB_TO_XYZ, self.linear()));
+        let [fx, fy, fz] = [0, 1, 2].map(|index| match xyz[index] / D50[index] {
+            value if value > EPSILON => value.cbrt(),
+            value => (KAPPA * value + 16.0) / 116.0,
+        });
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    fn oklch(self) -> (f64, f64, f64) {
+        let lms = multiply(LINEAR_SRGB_TO_LMS, self.linear()).map(f64::cbrt);
+        let [lightness, a, b] = multiply(LMS_TO_OKLAB, lms);
+        let chroma = (a * a + b * b).sqrt();
+        // 接近灰色时色相没有意义
+        let hue = match chroma < 1e-4 {
+            true => 0.0,
+            false => b.atan2(a).to_degrees().rem_euclid(360.0),
+        };
+        (lightness, chroma, hue)
+    }
+
+    ///
+    /// WCAG 2 的相对亮度
+    ///
+    fn luminance(self) -> f64 {
+        let linear = |value: f64| match value <= 0.03928 {
+            true => value / 12.92,
+            false => ((value + 0.055) / 1.055).powf(2.4),
+        };
+        0.2126 * linear(self.red) + 0.7152 * linear(self.green) + 0.0722 * linear(self.blue)
+    }
+
+    ///
+    /// 叠加在不透明的背景色上
+    ///
+    fn over(self, background: Rgba) -> Rgba {
+        let blend = |top: f64, bottom: f64| top * self.alpha + bottom * (1.0 - self.alpha);
+        Rgba::new(
+            blend(self.red, background.red),
+            blend(self.green, background.green),
+            blend(self.blue, background.blue),
+            1.0,
+        )
+    }
+
+    ///
+    /// 在 sRGB 中按比例混合，保留原来的透明度
+    ///
+    fn mix(self, other: Rgba, weight: f64) -> Rgba {
+        let mix = |from: f64, to: f64| from + (to - from) * weight;
+        Rgba::new(
+            mix(self.red, other.red),
+            mix(self.green, other.green),
+            mix(self.blue, other.blue),
+            self.alpha,
+        )
+    }
+}
+
+impl From<Rgba> for Color {
+    fn from(value: Rgba) -> Self {
+        let value = value.clamp();
+        let rgba = value.to_u8();
+        let opaque = rgba[3] == 255;
+        let alpha = number(value.alpha, 3);
+        // 现代写法的透明度后缀
+        let slash = match opaque {
+            true => String::new(),
+            false => format!(" / {alpha}"),
+        };
+
+        let hex = match opaque {
+            true => format!("#{:02x}{:02x}{:02x}", rgba[0], rgba[1], rgba[2]),
+            false => format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                rgba[0], rgba[1], rgba[2], rgba[3]
+            ),
+        };
+        let rgb = match opaque {
+            true => format!("rgb({}, {}, {})", rgba[0], rgba[1], rgba[2]),
+            false => format!("rgba({}, {}, {}, {alpha})", rgba[0], rgba[1], rgba[2]),
+        };
+        let (hue, saturation, lightness) = value.hsl();
+        let (hue, saturation, lightness) = (
+            angle(hue, 1),
+            number(saturation * 100.0, 1),
+            number(lightness * 100.0, 1),
+        );
+        let hsl = match opaque {
+            true => format!("hsl({hue}, {saturation}%, {lightness}%)"),
+            false => format!("hsla({hue}, {saturation}%, {lightness}%, {alpha})"),
+        };
+        let (hue, saturation, brightness) = value.hsv();
+        let hsv = format!(
+            "hsv({}, {}%, {}%)",
+            angle(hue, 1),
+            number(saturation * 100.0, 1),
+            number(brightness * 100.0, 1)
+        );
+        let (hue, max, min) = value.hue();
+        let hwb = format!(
+            "hwb({} {}% {}%{slash})",
+            angle(hue, 1),
+            number(min * 100.0, 1),
+            number((1.0 - max) * 100.0, 1)
+        );
+        let black = 1.0 - max;
+        let ink = |channel: f64| match max > 0.0 {
+            true => (max - channel) / max * 100.0,
+            false => 0.0,
+        };
+        let cmyk = format!(
+            "cmyk({}%, {}%, {}%, {}%)",
+            number(ink(value.red), 1),
+            number(ink(value.green), 1),
+            number(ink(value.blue), 1),
+            number(black * 100.0, 1)
+        );
+        let (lightness, a, b) = value.lab();
+        let lab = format!(
+            "lab({}% {} {}{slash})",
+            number(lightness, 2),
+            number(a, 2),
+            number(b, 2)
+        );
+        let (lightness, chroma, hue) = value.oklch();
+        let oklch = format!(
+            "oklch({}% {} {}{slash})",
+            number(lightness * 100.0, 2),
+            number(chroma, 4),
+            angle(hue, 2)
+        );
+        let name = match opaque {
+            true => {
+                let code = u32::from_be_bytes([0, rgba[0], rgba[1], rgba[2]]);
+                NAMED_COLORS
+                    .iter()
+                    .find(|(_, value)| *value == code)
+                    .map(|(name, _)| name.to_string())
+            }
+            false if rgba[3] == 0 => Some("transparent".to_string()),
+            false => None,
+        };
+        Color {
+            hex,
+            rgb,
+            hsl,
+            hsv,
+            hwb,
+            cmyk,
+            lab,
+            oklch,
+            name,
+            rgba,
+        }
+    }
+}
+
+///
+/// 格式化色相，四舍五入后为 360 时输出 0
+///
+fn angle(value: f64, digits: usize) -> String {
+    match number(value, digits).as_str() {
+        "360" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+fn multiply(matrix: [[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+///
+/// 保留指定的小数位数，去掉末尾的 0
+///
+fn number(value: f64, digits: usize) -> String {
+    let text = format!("{value:.digits$}");
+    let text = match text.contains('.') {
+        true => text.trim_end_matches('0').trim_end_matches('.'),
+        false => &text,
+    };
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+command_error! {
+    (Image, "image error: {0}", #[from] ImageError),
+    (Parse, "invalid color: {0}", String),
+    (Count, "count must be between 1 and {1}, got {0}", usize, usize),
+}
@@ -1,22 +1,670 @@
+use super::crontab::{
+    self, Bounds, Day, Expression, Part, Unsupported, Weekday, HOUR, MINUTE, SECOND, YEAR,
+};
 use crate::command_error;
-use chrono::{format::StrftimeItems, Local};
-use cron::Schedule;
-use std::str::FromStr;
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local, LocalResult, NaiveDateTime, TimeDelta, TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const WEEKDAY_NAMES_ZH: [&str; 7] = ["周日", "周一", "周二", "周三", "周四", "周五", "周六"];
+
+const ORDINALS: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+
+/// 开始时间支持的格式，也可以使用 RFC 3339
+const START_PATTERNS: &[&str] = &["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"];
+
+///
+/// Cron 表达式的写法
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Dialect {
+    /// `分 时 日 月 周`，星期 0 和 7 都是星期日，支持 `@daily` 等宏
+    Unix,
+    /// `秒 分 时 日 月 周 [年]`，星期 1 是星期日，日期和星期必须有一个是 `?`
+    Quartz,
+    /// `秒 分 时 日 月 周`，星期 0 和 7 都是星期日，支持 `@daily` 等宏
+    Spring,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Description {
+    english: String,
+    chinese: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Plan {
+    /// 实际使用的方言，未指定时根据表达式识别
+    dialect: Dialect,
+    description: Description,
+    times: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Conversion {
+    dialect: Dialect,
+    /// 无法表达时为空
+    expression: Option<String>,
+    /// 表达式的含义，与目标方言无关
+    description: Description,
+    /// 无法表达的原因
+    unsupported: Vec<Description>,
+}
+
+///
+/// 解析 Cron 表达式，返回说明和从 `start` 开始的 `size` 个执行时间
+///
+/// `timezone` 为 IANA 时区名，为空时使用本地时区；`start` 为空时从当前时间之后开始
+///
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn parse_cron(cron: &str, pattern: &str, size: usize) -> Result<Vec<String>, Error> {
+pub fn parse_cron(
+    cron: &str,
+    dialect: Option<Dialect>,
+    pattern: &str,
+    size: usize,
+    timezone: Option<String>,
+    start: Option<String>,
+) -> Result<Plan, Error> {
     let items = StrftimeItems::new(pattern).parse()?;
-    let schedule = Schedule::from_str(cron)?;
-    let times = schedule
-        .after(&Local::now())
-        .take(size)
-        .map(|time| time.format_with_items(items.iter()).to_string())
-        .collect::<Vec<_>>();
+    let dialect = dialect.unwrap_or_else(|| crontab::detect(cron));
+    let expression = Expression::parse(cron, dialect)?;
+    let start = start.as_deref().filter(|start| !start.trim().is_empty());
+    let times = match timezone.as_deref().filter(|name| !name.is_empty()) {
+        Some(name) => {
+            let zone = name
+                .parse::<Tz>()
+                .map_err(|_| Error::TimeZone(name.to_string()))?;
+            schedule(&expression, &zone, start, &items, size)?
+        }
+        None => schedule(&expression, &Local, start, &items, size)?,
+    };
+    Ok(Plan {
+        dialect,
+        description: Description {
+            english: describe(&expression, &ENGLISH),
+            chinese: spacing(&describe(&expression, &CHINESE)),
+        },
+        times,
+    })
+}
+
+///
+/// 由各字段的取值生成 `dialect` 的表达式，日期和星期要同时满足，星期按 Unix 编号：0 和 7 是星期日
+///
+/// 字段为空时不限制，`seconds` 为空时秒为 0，`years` 只有 Quartz 支持
+///
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn build_cron(
+    dialect: Dialect,
+    seconds: Option<Vec<Part>>,
+    minutes: Vec<Part>,
+    hours: Vec<Part>,
+    days: Vec<Day>,
+    months: Vec<Part>,
+    weekdays: Vec<Weekday>,
+    years: Option<Vec<Part>>,
+) -> Result<Conversion, Error> {
+    let expression = Expression::build(
+        seconds.as_deref(),
+        &minutes,
+        &hours,
+        &days,
+        &months,
+        &weekdays,
+        years.as_deref(),
+    )?;
+    convert(&expression, dialect)
+}
+
+///
+/// 把 Cron 表达式转换为另一种方言，`from` 为空时根据表达式识别
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_cron(cron: &str, from: Option<Dialect>, to: Dialect) -> Result<Conversion, Error> {
+    let from = from.unwrap_or_else(|| crontab::detect(cron));
+    let expression = Expression::parse(cron, from)?;
+    convert(&expression, to)
+}
+
+fn convert(expression: &Expression, dialect: Dialect) -> Result<Conversion, Error> {
+    let description = Description {
+        english: describe(expression, &ENGLISH),
+        chinese: spacing(&describe(expression, &CHINESE)),
+    };
+    let (expression, unsupported) = match expression.convert(dialect) {
+        Ok(text) => {
+            // 生成的表达式必须能被目标方言解析
+            Expression::parse(&text, dialect)?;
+            (Some(text), Vec::new())
+        }
+        Err(unsupported) => {
+            let reasons = unsupported
+                .iter()
+                .map(|item| explain(item, dialect))
+                .collect();
+            (None, reasons)
+        }
+    };
+    Ok(Conversion {
+        dialect,
+        expression,
+        description,
+        unsupported,
+    })
+}
+
+/// 说明转换时无法表达的原因
+fn explain(unsupported: &Unsupported, to: Dialect) -> Description {
+    let (english, chinese) = match unsupported {
+        Unsupported::Seconds => (
+            "Unix cron has no seconds field, the second must be 0".to_string(),
+            "Unix 没有秒字段，秒只能是 0".to_string(),
+        ),
+        Unsupported::Years => (
+            format!("{to:?} cron has no year field"),
+            format!("{to:?} 没有年份字段"),
+        ),
+        Unsupported::Day(text) => (
+            format!("Unix cron does not support '{text}' in day-of-month"),
+            format!("Unix 的日期字段不支持 {text}"),
+        ),
+        Unsupported::Weekday(text) => (
+            format!("Unix cron does not support '{text}' in day-of-week"),
+            format!("Unix 的星期字段不支持 {text}"),
+        ),
+        Unsupported::BothDays => (
+            "Quartz cannot restrict both day-of-month and day-of-week".to_string(),
+            "Quartz 不能同时限制日期和星期".to_string(),
+        ),
+        Unsupported::EitherDay => (
+            format!(
+                "The expression runs when either day-of-month or day-of-week matches, \
+                 but {to:?} cron requires both"
+            ),
+            format!("原表达式在日期或星期满足其一时执行，但 {to:?} 要求两者同时满足"),
+        ),
+        Unsupported::AllDays => (
+            "The expression requires both day-of-month and day-of-week to match, \
+             but Unix cron runs when either matches"
+                .to_string(),
+            "原表达式要求日期和星期同时满足，但 Unix 在两者满足其一时就会执行".to_string(),
+        ),
+    };
+    Description {
+        english,
+        chinese: spacing(&chinese),
+    }
+}
+
+fn schedule<Z: TimeZone>(
+    expression: &Expression,
+    zone: &Z,
+    start: Option<&str>,
+    items: &[Item],
+    size: usize,
+) -> Result<Vec<String>, Error>
+where
+    Z::Offset: Display,
+{
+    // 指定的开始时间本身也算在内
+    let mut after = match start {
+        Some(start) => parse_start(start, zone)? - TimeDelta::seconds(1),
+        None => Utc::now().with_timezone(zone).naive_local(),
+    };
+    let mut times = Vec::with_capacity(size.min(1024));
+    while times.len() < size {
+        let Some(next) = expression.next(after) else {
+            break;
+        };
+        after = next;
+        // 夏令时跳过的时间不执行，重复的时间只执行一次
+        match zone.from_local_datetime(&next) {
+            LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => {
+                times.push(time.format_with_items(items.iter()).to_string())
+            }
+            LocalResult::None => {}
+        }
+    }
     Ok(times)
 }
 
+fn parse_start<Z: TimeZone>(start: &str, zone: &Z) -> Result<NaiveDateTime, Error> {
+    let start = start.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(start) {
+        return Ok(time
+            .with_timezone(zone)
+            .naive_local()
+            .with_nanosecond(0)
+            .unwrap_or_default());
+    }
+    START_PATTERNS
+        .iter()
+        .find_map(|pattern| NaiveDateTime::parse_from_str(start, pattern).ok())
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(start, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| Error::Start(start.to_string()))
+}
+
+///
+/// 生成说明所需的词语，英文从小单位写到大单位，中文相反
+///
+struct Language {
+    english: bool,
+    /// 单数、复数和中文的量词
+    units: [(&'static str, &'static str); 3],
+    every: fn(&str) -> String,
+    every_n: fn(u32, &str) -> String,
+    list: fn(&[String]) -> String,
+    range: fn(String, String) -> String,
+}
+
+const ENGLISH: Language = Language {
+    english: true,
+    units: [
+        ("second", "seconds"),
+        ("minute", "minutes"),
+        ("hour", "hours"),
+    ],
+    every: |unit| format!("every {unit}"),
+    every_n: |n, units| format!("every {n} {units}"),
+    list: |items| match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} and {last}", init.join(", ")),
+    },
+    range: |start, end| format!("{start} through {end}"),
+};
+
+const CHINESE: Language = Language {
+    english: false,
+    units: [("秒", "秒"), ("分钟", "分钟"), ("小时", "小时")],
+    every: |unit| format!("每{unit}"),
+    every_n: |n, unit| format!("每{n}{unit}"),
+    list: |items| items.join("、"),
+    range: |start, end| format!("{start}到{end}"),
+};
+
+///
+/// 生成表达式的说明，例如 `At 09:30, Monday through Friday` 或 `周一到周五的 09:30`
+///
+fn describe(expression: &Expression, language: &Language) -> String {
+    let seconds = expression.dialect != Dialect::Unix;
+    let mut dates = Vec::new();
+    if let Some(years) = describe_years(&expression.years, language) {
+        dates.push(years);
+    }
+    if let Some(months) = describe_months(&expression.months, language) {
+        dates.push(months);
+    }
+    if let Some(days) = describe_days(expression, language) {
+        dates.push(days);
+    }
+    let (time, fixed) = match fixed_times(expression, seconds) {
+        Some(times) => {
+            let times = (language.list)(&times);
+            match language.english {
+                true => (format!("at {times}"), true),
+                false => (times, true),
+            }
+        }
+        None => (describe_time(expression, seconds, language), false),
+    };
+    if language.english {
+        let mut text = time;
+        for date in dates {
+            text.push_str(", ");
+            text.push_str(&date);
+        }
+        capitalize(&text)
+    } else if dates.is_empty() {
+        match fixed {
+            true => format!("每天{time}"),
+            false => time,
+        }
+    } else {
+        format!("{}的{time}", dates.join("，"))
+    }
+}
+
+/// 在汉字和英文、数字之间加空格
+fn spacing(text: &str) -> String {
+    let han = |c: char| ('\u{4e00}'..='\u{9fff}').contains(&c);
+    let mut output = String::with_capacity(text.len() + 8);
+    let mut previous: Option<char> = None;
+    for c in text.chars() {
+        if let Some(p) = previous {
+            if (han(p) && c.is_ascii_alphanumeric()) || (p.is_ascii_alphanumeric() && han(c)) {
+                output.push(' ');
+            }
+        }
+        output.push(c);
+        previous = Some(c);
+    }
+    output
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+///
+/// 时、分、秒都是固定值且组合不多时，直接列出时间
+///
+fn fixed_times(expression: &Expression, with_seconds: bool) -> Option<Vec<String>> {
+    let fixed = |parts: &[Part]| {
+        parts
+            .iter()
+            .map(|part| match part {
+                Part::Value(value) => Some(*value),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+    let hours = fixed(&expression.hours)?;
+    let minutes = fixed(&expression.minutes)?;
+    let seconds = fixed(&expression.seconds)?;
+    if hours.len() * minutes.len() * seconds.len() > 6 {
+        return None;
+    }
+    let mut times = Vec::new();
+    for hour in &hours {
+        for minute in &minutes {
+            for second in &seconds {
+                times.push(match with_seconds && *second != 0 {
+                    true => format!("{hour:02}:{minute:02}:{second:02}"),
+                    false => format!("{hour:02}:{minute:02}"),
+                });
+            }
+        }
+    }
+    times.sort();
+    Some(times)
+}
+
+fn describe_time(expression: &Expression, seconds: bool, language: &Language) -> String {
+    let fields = [
+        (&expression.seconds, SECOND),
+        (&expression.minutes, MINUTE),
+        (&expression.hours, HOUR),
+    ];
+    // 每一项是说明和它是否为“每…”的形式
+    let mut phrases: Vec<(String, bool)> = Vec::new();
+    for (index, (parts, bounds)) in fields.into_iter().enumerate() {
+        let (unit, units) = language.units[index];
+        if index == 0 && (!seconds || parts[..] == [Part::Value(0)]) {
+            continue;
+        }
+        if parts[..] == [Part::Any] {
+            if !phrases.last().is_some_and(|(_, every)| *every) {
+                phrases.push(((language.every)(unit), true));
+            }
+            continue;
+        }
+        if let [Part::Step(base, step)] = &parts[..] {
+            let every = (language.every_n)(*step, units);
+            let phrase = match (&**base, language.english) {
+                (Part::Any, _) => every,
+                (Part::Value(start), true) => format!("{every} starting at {unit} {start}"),
+                (Part::Value(start), false) => format!("从{}开始{every}", value_zh(*start, index)),
+                (Part::Range(start, end), true) => {
+                    format!("{every}, {units} {start} through {end}")
+                }
+                (Part::Range(start, end), false) => format!(
+                    "{}到{}{every}",
+                    value_zh(*start, index),
+                    value_zh(*end, index)
+                ),
+                _ => every,
+            };
+            phrases.push((phrase, true));
+            continue;
+        }
+        let items = parts
+            .iter()
+            .map(|part| {
+                describe_part(part, bounds, language, &|value| match language.english {
+                    true => value.to_string(),
+                    false => value_zh(value, index),
+                })
+            })
+            .collect::<Vec<_>>();
+        let phrase = match language.english {
+            true => match &parts[..] {
+                [Part::Value(_)] => format!("at {unit} {}", items[0]),
+                _ => format!("at {units} {}", (language.list)(&items)),
+            },
+            false => (language.list)(&items),
+        };
+        phrases.push((phrase, false));
+    }
+    if language.english {
+        let mut text = String::new();
+        for (index, (phrase, every)) in phrases.iter().enumerate() {
+            if index > 0 {
+                // 列出的时间后面跟着“每小时”等，写成 `at minute 0 of every hour`
+                text.push_str(match *every && !phrases[index - 1].1 {
+                    true => " of ",
+                    false => ", ",
+                });
+            }
+            text.push_str(phrase);
+        }
+        text
+    } else {
+        phrases
+            .iter()
+            .rev()
+            .map(|(phrase, _)| phrase.as_str())
+            .collect::<Vec<_>>()
+            .join("的")
+    }
+}
+
+/// 中文的秒、分、时的写法
+fn value_zh(value: u32, index: usize) -> String {
+    match index {
+        0 => format!("第 {value} 秒"),
+        1 => format!("第 {value} 分钟"),
+        _ => format!("{value} 点"),
+    }
+}
+
+/// 列表中的一项，步长展开为具体的取值
+fn describe_part(
+    part: &Part,
+    bounds: Bounds,
+    language: &Language,
+    name: &dyn Fn(u32) -> String,
+) -> String {
+    match part {
+        Part::Value(value) => name(*value),
+        Part::Range(start, end) => (language.range)(name(*start), name(*end)),
+        Part::Any | Part::Step(_, _) => {
+            let values = part.values(bounds).into_iter().map(name);
+            (language.list)(&values.collect::<Vec<_>>())
+        }
+    }
+}
+
+fn describe_months(months: &[Part], language: &Language) -> Option<String> {
+    if months == [Part::Any] {
+        return None;
+    }
+    if let [Part::Step(base, step)] = months {
+        if **base == Part::Any {
+            return Some(match language.english {
+                true => format!("every {step} months"),
+                false => format!("每 {step} 个月"),
+            });
+        }
+    }
+    let name = |month: u32| match language.english {
+        true => MONTH_NAMES[month as usize - 1].to_string(),
+        false => format!("{month} 月"),
+    };
+    let items = months
+        .iter()
+        .map(|part| describe_part(part, crontab::MONTH, language, &name))
+        .collect::<Vec<_>>();
+    Some(match language.english {
+        true => format!("only in {}", (language.list)(&items)),
+        false => (language.list)(&items),
+    })
+}
+
+fn describe_years(years: &[Part], language: &Language) -> Option<String> {
+    if years == [Part::Any] {
+        return None;
+    }
+    let name = |year: u32| match language.english {
+        true => year.to_string(),
+        false => format!("{year} 年"),
+    };
+    let items = years
+        .iter()
+        .map(|part| describe_part(part, YEAR, language, &name))
+        .collect::<Vec<_>>();
+    Some(match language.english {
+        true => format!("in {}", (language.list)(&items)),
+        false => (language.list)(&items),
+    })
+}
+
+///
+/// 日期和星期的说明，两者都有限制时按方言的规则用“或”或者“且”连接
+///
+fn describe_days(expression: &Expression, language: &Language) -> Option<String> {
+    let days = match &expression.days[..] {
+        [] | [Day::Part(Part::Any)] => None,
+        days => Some(describe_day_items(days, language)),
+    };
+    let weekdays = match &expression.weekdays[..] {
+        [] | [Weekday::Part(Part::Any)] => None,
+        items => Some(describe_weekday_items(items, expression.dialect, language)),
+    };
+    match (days, weekdays) {
+        (Some(days), Some(weekdays)) => Some(match (language.english, expression.either_day()) {
+            (true, true) => format!("{days}, or {weekdays}"),
+            (true, false) => format!("{days}, if it is {weekdays}"),
+            (false, true) => format!("{days}或{weekdays}"),
+            (false, false) => format!("{days}且为{weekdays}"),
+        }),
+        (days, weekdays) => days.or(weekdays),
+    }
+}
+
+fn describe_day_items(days: &[Day], language: &Language) -> String {
+    if let [Day::Part(Part::Step(base, step))] = days {
+        if **base == Part::Any {
+            return match language.english {
+                true => format!("every {step} days"),
+                false => format!("每 {step} 天"),
+            };
+        }
+    }
+    let name = |day: u32| match language.english {
+        true => day.to_string(),
+        false => format!("{day} 号"),
+    };
+    let items = days
+        .iter()
+        .map(|day| match (day, language.english) {
+            (Day::Part(part), _) => describe_part(part, crontab::DAY, language, &name),
+            (Day::Last(0), true) => "the last day".to_string(),
+            (Day::Last(0), false) => "最后一天".to_string(),
+            (Day::Last(offset), true) => format!("the day {offset} days before the last day"),
+            (Day::Last(offset), false) => format!("最后一天的前 {offset} 天"),
+            (Day::Nearest(day), true) => format!("the weekday nearest day {day}"),
+            (Day::Nearest(day), false) => format!("离 {day} 号最近的工作日"),
+            (Day::LastWeekday, true) => "the last weekday".to_string(),
+            (Day::LastWeekday, false) => "最后一个工作日".to_string(),
+        })
+        .collect::<Vec<_>>();
+    let list = (language.list)(&items);
+    match language.english {
+        true => match days {
+            [Day::Part(Part::Value(_))] => format!("on day {list} of the month"),
+            _ if days.iter().all(|day| matches!(day, Day::Part(_))) => {
+                format!("on days {list} of the month")
+            }
+            _ => format!("on {list} of the month"),
+        },
+        false => format!("每月{list}"),
+    }
+}
+
+fn describe_weekday_items(weekdays: &[Weekday], dialect: Dialect, language: &Language) -> String {
+    let bounds = crontab::weekday_bounds(dialect);
+    let name = |weekday: u32| match language.english {
+        true => WEEKDAY_NAMES[weekday as usize % 7].to_string(),
+        false => WEEKDAY_NAMES_ZH[weekday as usize % 7].to_string(),
+    };
+    let items = weekdays
+        .iter()
+        .map(|weekday| match (weekday, language.english) {
+            (Weekday::Part(part), _) => describe_part(part, bounds, language, &name),
+            (Weekday::Last(weekday), true) => format!("the last {} of the month", name(*weekday)),
+            (Weekday::Last(weekday), false) => format!("每月最后一个{}", name(*weekday)),
+            (Weekday::Nth(weekday, nth), true) => format!(
+                "the {} {} of the month",
+                ORDINALS[*nth as usize - 1],
+                name(*weekday)
+            ),
+            (Weekday::Nth(weekday, nth), false) => {
+                format!("每月第 {nth} 个{}", name(*weekday))
+            }
+        })
+        .collect::<Vec<_>>();
+    let list = (language.list)(&items);
+    match language.english {
+        true => format!("on {list}"),
+        false => list,
+    }
+}
+
 command_error! {
-    (Cron, "cron error: {0}", #[from] cron::error::Error),
+    (Cron, "cron error: {0}", #[from] crontab::Error),
     (Pattern, "pattern error: {0}", #[from] chrono::format::ParseError),
+    (TimeZone, "unknown time zone: {0}", String),
+    (Start, "invalid start time: {0}", String),
 }
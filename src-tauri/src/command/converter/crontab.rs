@@ -0,0 +1,816 @@
+//!
+//! Cron 表达式的解析和匹配，支持 Unix crontab、Quartz 和 Spring 三种写法。
+//!
+//! 星期统一按 Unix 的编号保存：0 到 7，0 和 7 都表示星期日，Quartz 的 1 到 7 读取时减一。
+//!
+use super::cron::Dialect;
+use crate::command_error;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::Deserialize;
+
+/// 没有限制年份时最多向后查找的年数
+const SEARCH_YEARS: i32 = 100;
+
+pub const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+pub const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Unix 和 Spring 支持的宏及其对应的 5 段表达式
+const MACROS: &[(&str, &str)] = &[
+    ("@yearly", "0 0 1 1 *"),
+    ("@annually", "0 0 1 1 *"),
+    ("@monthly", "0 0 1 * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@daily", "0 0 * * *"),
+    ("@midnight", "0 0 * * *"),
+    ("@hourly", "0 * * * *"),
+];
+
+///
+/// 字段的名称和取值范围
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub name: &'static str,
+    pub min: u32,
+    pub max: u32,
+}
+
+pub const SECOND: Bounds = Bounds {
+    name: "second",
+    min: 0,
+    max: 59,
+};
+pub const MINUTE: Bounds = Bounds {
+    name: "minute",
+    min: 0,
+    max: 59,
+};
+pub const HOUR: Bounds = Bounds {
+    name: "hour",
+    min: 0,
+    max: 23,
+};
+pub const DAY: Bounds = Bounds {
+    name: "day-of-month",
+    min: 1,
+    max: 31,
+};
+pub const MONTH: Bounds = Bounds {
+    name: "month",
+    min: 1,
+    max: 12,
+};
+pub const YEAR: Bounds = Bounds {
+    name: "year",
+    min: 1970,
+    max: 2099,
+};
+
+///
+/// 星期字段按 Unix 编号后的取值范围，Quartz 没有 7
+///
+pub fn weekday_bounds(dialect: Dialect) -> Bounds {
+    Bounds {
+        name: "day-of-week",
+        min: 0,
+        max: match dialect {
+            Dialect::Quartz => 6,
+            Dialect::Unix | Dialect::Spring => 7,
+        },
+    }
+}
+
+///
+/// 字段中以 `,` 分隔的一项
+///
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum Part {
+    /// `*`
+    Any,
+    Value(u32),
+    Range(u32, u32),
+    /// `*/n`、`a/n` 或 `a-b/n`，起点是 `Any`、`Value` 或 `Range`
+    Step(Box<Part>, u32),
+}
+
+impl Part {
+    pub fn contains(&self, value: u32, bounds: Bounds) -> bool {
+        match self {
+            Part::Any => true,
+            Part::Value(v) => *v == value,
+            Part::Range(start, end) => (*start..=*end).contains(&value),
+            Part::Step(base, step) => {
+                let (start, end) = match **base {
+                    Part::Value(start) => (start, bounds.max),
+                    Part::Range(start, end) => (start, end),
+                    _ => (bounds.min, bounds.max),
+                };
+                (start..=end).contains(&value) && (value - start) % step == 0
+            }
+        }
+    }
+
+    /// 所有匹配的取值，按从小到大排列
+    pub fn values(&self, bounds: Bounds) -> Vec<u32> {
+        (bounds.min..=bounds.max)
+            .filter(|value| self.contains(*value, bounds))
+            .collect()
+    }
+
+    /// 写在表达式中的数字
+    fn literals(&self) -> Vec<u32> {
+        match self {
+            Part::Any => Vec::new(),
+            Part::Value(value) => vec![*value],
+            Part::Range(start, end) => vec![*start, *end],
+            Part::Step(base, _) => base.literals(),
+        }
+    }
+
+    fn map(self, f: impl Fn(u32) -> u32 + Copy) -> Part {
+        match self {
+            Part::Any => Part::Any,
+            Part::Value(value) => Part::Value(f(value)),
+            Part::Range(start, end) => Part::Range(f(start), f(end)),
+            Part::Step(base, step) => Part::Step(Box::new(base.map(f)), step),
+        }
+    }
+}
+
+///
+/// 日期字段中的一项
+///
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum Day {
+    Part(Part),
+    /// `L` 或 `L-n`，当月最后一天往前 n 天
+    Last(u32),
+    /// `nW`，离 n 号最近的工作日，不跨月
+    Nearest(u32),
+    /// `LW`，当月最后一个工作日
+    LastWeekday,
+}
+
+///
+/// 星期字段中的一项
+///
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum Weekday {
+    Part(Part),
+    /// `nL`，当月最后一个星期 n
+    Last(u32),
+    /// `n#k`，当月第 k 个星期 n
+    Nth(u32, u32),
+}
+
+///
+/// 解析后的表达式，没有秒字段的方言秒固定为 0
+///
+#[derive(Debug, Clone)]
+pub struct Expression {
+    pub dialect: Dialect,
+    pub seconds: Vec<Part>,
+    pub minutes: Vec<Part>,
+    pub hours: Vec<Part>,
+    /// 为空表示 `?`
+    pub days: Vec<Day>,
+    pub months: Vec<Part>,
+    /// 为空表示 `?`
+    pub weekdays: Vec<Weekday>,
+    /// 没有年份字段时为 `*`
+    pub years: Vec<Part>,
+}
+
+///
+/// 根据字段数和写法猜测方言：5 段是 Unix，7 段是 Quartz，6 段中有 `?` 的是 Quartz，否则是 Spring
+///
+pub fn detect(input: &str) -> Dialect {
+    let input = input.trim();
+    let fields = input.split_whitespace().count();
+    match fields {
+        _ if input.starts_with('@') => Dialect::Unix,
+        5 => Dialect::Unix,
+        6 if input.contains('?') => Dialect::Quartz,
+        6 => Dialect::Spring,
+        7 => Dialect::Quartz,
+        _ => Dialect::Unix,
+    }
+}
+
+impl Expression {
+    pub fn parse(input: &str, dialect: Dialect) -> Result<Self, Error> {
+        let input = input.trim();
+        let expanded;
+        let input = match input.starts_with('@') {
+            true => {
+                let unix = MACROS
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(input))
+                    .map(|(_, unix)| *unix)
+                    .ok_or_else(|| Error::Macro(input.to_string()))?;
+                expanded = match dialect {
+                    Dialect::Unix => unix.to_string(),
+                    Dialect::Spring => format!("0 {unix}"),
+                    Dialect::Quartz => return Err(Error::Macro(input.to_string())),
+                };
+                expanded.as_str()
+            }
+            false => input,
+        };
+
+        let fields = input.split_whitespace().collect::<Vec<_>>();
+        let (seconds, fields) = match (dialect, fields.len()) {
+            (Dialect::Unix, 5) => ("0", &fields[..]),
+            (Dialect::Quartz, 6 | 7) | (Dialect::Spring, 6) => (fields[0], &fields[1..]),
+            (Dialect::Unix, count) => return Err(Error::Fields(dialect, "5", count)),
+            (Dialect::Quartz, count) => return Err(Error::Fields(dialect, "6 or 7", count)),
+            (Dialect::Spring, count) => return Err(Error::Fields(dialect, "6", count)),
+        };
+        let expression = Expression {
+            dialect,
+            seconds: parse_parts(seconds, SECOND, &[])?,
+            minutes: parse_parts(fields[0], MINUTE, &[])?,
+            hours: parse_parts(fields[1], HOUR, &[])?,
+            days: parse_days(fields[2], dialect)?,
+            months: parse_parts(fields[3], MONTH, &MONTHS)?,
+            weekdays: parse_weekdays(fields[4], dialect)?,
+            years: match fields.get(5) {
+                Some(years) => parse_parts(years, YEAR, &[])?,
+                None => vec![Part::Any],
+            },
+        };
+        if dialect == Dialect::Quartz
+            && expression.days.is_empty() == expression.weekdays.is_empty()
+        {
+            return Err(Error::Quartz);
+        }
+        Ok(expression)
+    }
+
+    ///
+    /// `after` 之后（不含）第一个匹配的时间，找不到时返回 `None`
+    ///
+    pub fn next(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let hours = values(&self.hours, HOUR);
+        let minutes = values(&self.minutes, MINUTE);
+        let seconds = values(&self.seconds, SECOND);
+        let limit = after.year() + SEARCH_YEARS;
+        let mut date = after.date();
+        let mut floor = Some(after.time());
+        while date.year() <= limit {
+            if !self.matches_month(date) {
+                date = first_of_next_month(date)?;
+                floor = None;
+                continue;
+            }
+            if self.matches_day(date) {
+                if let Some(time) = first_time(&hours, &minutes, &seconds, floor) {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = date.succ_opt()?;
+            floor = None;
+        }
+        None
+    }
+
+    fn matches_month(&self, date: NaiveDate) -> bool {
+        let year = date.year() as u32;
+        self.months
+            .iter()
+            .any(|part| part.contains(date.month(), MONTH))
+            && self.years.iter().any(|part| part.contains(year, YEAR))
+    }
+
+    ///
+    /// 日期和星期是否满足其一即可：Unix 中两者都有限制时是“或”，其余情况要同时满足
+    ///
+    pub fn either_day(&self) -> bool {
+        let star_day = matches!(self.days.first(), Some(Day::Part(part)) if is_star(part));
+        let star_weekday =
+            matches!(self.weekdays.first(), Some(Weekday::Part(part)) if is_star(part));
+        self.dialect == Dialect::Unix && !star_day && !star_weekday
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = self.days.is_empty() || self.days.iter().any(|day| day.matches(date));
+        let bounds = weekday_bounds(self.dialect);
+        let weekday = self.weekdays.is_empty()
+            || self
+                .weekdays
+                .iter()
+                .any(|weekday| weekday.matches(date, bounds));
+        if self.either_day() {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+///
+/// 转换为其他方言时无法表达的内容，日期和星期的写法按原方言给出
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unsupported {
+    /// Unix 没有秒字段，秒必须是 0
+    Seconds,
+    /// 只有 Quartz 有年份字段
+    Years,
+    /// Unix 不支持 `L`、`W` 等日期写法
+    Day(String),
+    /// Unix 不支持 `L`、`#` 等星期写法
+    Weekday(String),
+    /// Quartz 不能同时限制日期和星期
+    BothDays,
+    /// 日期和星期满足其一即可，目标方言会要求同时满足
+    EitherDay,
+    /// 日期和星期要同时满足，Unix 会按满足其一处理
+    AllDays,
+}
+
+impl Expression {
+    ///
+    /// 由各字段的取值组成表达式，日期和星期要同时满足，星期使用 Unix 的编号
+    ///
+    /// 日期或星期为空时不限制，其他字段为空时相当于 `*`，没有秒时为 0
+    ///
+    pub fn build(
+        seconds: Option<&[Part]>,
+        minutes: &[Part],
+        hours: &[Part],
+        days: &[Day],
+        months: &[Part],
+        weekdays: &[Weekday],
+        years: Option<&[Part]>,
+    ) -> Result<Self, Error> {
+        let all = |parts: &[Part]| match parts {
+            [] => "*".to_string(),
+            parts => format_parts(parts, 0),
+        };
+        let days = match days {
+            [] => "?".to_string(),
+            days => format_days(days),
+        };
+        let weekdays = match weekdays {
+            [] => "?".to_string(),
+            weekdays => format_weekdays(weekdays, 0),
+        };
+        // 按 Spring 的写法重新解析一遍，顺便检查取值范围
+        let text = [
+            seconds.map_or("0".to_string(), all),
+            all(minutes),
+            all(hours),
+            days,
+            all(months),
+            weekdays,
+        ]
+        .join(" ");
+        let mut expression = Expression::parse(&text, Dialect::Spring)?;
+        if let Some(years) = years {
+            expression.years = parse_parts(&all(years), YEAR, &[])?;
+        }
+        Ok(expression)
+    }
+
+    ///
+    /// 转换为 `dialect` 的写法，有无法表达的内容时返回全部原因
+    ///
+    pub fn convert(&self, dialect: Dialect) -> Result<String, Vec<Unsupported>> {
+        let mut unsupported = Vec::new();
+        let mut fields = Vec::new();
+        match dialect {
+            Dialect::Unix if self.seconds != [Part::Value(0)] => {
+                unsupported.push(Unsupported::Seconds)
+            }
+            Dialect::Unix => {}
+            Dialect::Quartz | Dialect::Spring => fields.push(format_parts(&self.seconds, 0)),
+        }
+        fields.push(format_parts(&self.minutes, 0));
+        fields.push(format_parts(&self.hours, 0));
+        let (days, weekdays) = self.convert_days(dialect, &mut unsupported);
+        fields.push(days);
+        fields.push(format_parts(&self.months, 0));
+        fields.push(weekdays);
+        if self.years != [Part::Any] {
+            match dialect {
+                Dialect::Quartz => fields.push(format_parts(&self.years, 0)),
+                Dialect::Unix | Dialect::Spring => unsupported.push(Unsupported::Years),
+            }
+        }
+        match unsupported.is_empty() {
+            true => Ok(fields.join(" ")),
+            false => Err(unsupported),
+        }
+    }
+
+    /// 转换日期和星期字段
+    fn convert_days(
+        &self,
+        dialect: Dialect,
+        unsupported: &mut Vec<Unsupported>,
+    ) -> (String, String) {
+        let offset = match dialect {
+            Dialect::Quartz => 1,
+            Dialect::Unix | Dialect::Spring => 0,
+        };
+        if dialect == Dialect::Unix {
+            for day in self.days.iter().filter(|day| !matches!(day, Day::Part(_))) {
+                unsupported.push(Unsupported::Day(format_days(std::slice::from_ref(day))));
+            }
+            let source = self.weekday_offset();
+            for weekday in self
+                .weekdays
+                .iter()
+                .filter(|weekday| !matches!(weekday, Weekday::Part(_)))
+            {
+                let text = format_weekdays(std::slice::from_ref(weekday), source);
+                unsupported.push(Unsupported::Weekday(text));
+            }
+        }
+        let weekdays = self
+            .weekdays
+            .iter()
+            .flat_map(|weekday| match weekday {
+                Weekday::Part(part) => self
+                    .convert_weekday(part, dialect)
+                    .into_iter()
+                    .map(Weekday::Part)
+                    .collect(),
+                Weekday::Last(value) => vec![Weekday::Last(value % 7)],
+                Weekday::Nth(value, nth) => vec![Weekday::Nth(value % 7, *nth)],
+            })
+            .collect::<Vec<_>>();
+
+        let any_day = matches!(&self.days[..], [] | [Day::Part(Part::Any)]);
+        let any_weekday = matches!(&self.weekdays[..], [] | [Weekday::Part(Part::Any)]);
+        let days_text = match any_day {
+            true => "*".to_string(),
+            false => format_days(&self.days),
+        };
+        let weekdays_text = match any_weekday {
+            true => "*".to_string(),
+            false => format_weekdays(&weekdays, offset),
+        };
+        if dialect == Dialect::Quartz {
+            return match (any_day, any_weekday) {
+                (false, false) => {
+                    unsupported.push(Unsupported::BothDays);
+                    (days_text, weekdays_text)
+                }
+                (true, false) => ("?".to_string(), weekdays_text),
+                (_, true) => (days_text, "?".to_string()),
+            };
+        }
+        if !any_day && !any_weekday {
+            let either = dialect == Dialect::Unix
+                && !days_text.starts_with('*')
+                && !weekdays_text.starts_with('*');
+            match (self.either_day(), either) {
+                (true, false) => unsupported.push(Unsupported::EitherDay),
+                (false, true) => unsupported.push(Unsupported::AllDays),
+                _ => {}
+            }
+        }
+        (days_text, weekdays_text)
+    }
+
+    ///
+    /// 转换星期的一项，两种方言的取值范围不同，结果不一致时展开为具体的星期
+    ///
+    fn convert_weekday(&self, part: &Part, dialect: Dialect) -> Vec<Part> {
+        let weekdays = |part: &Part, dialect: Dialect| {
+            let mut values = part
+                .values(weekday_bounds(dialect))
+                .into_iter()
+                .map(|value| value % 7)
+                .collect::<Vec<_>>();
+            values.sort_unstable();
+            values.dedup();
+            values
+        };
+        let source = weekdays(part, self.dialect);
+        let max = weekday_bounds(dialect).max;
+        if part.literals().iter().all(|value| *value <= max) && weekdays(part, dialect) == source {
+            return vec![part.clone()];
+        }
+        // 连续三天以上写成范围
+        let mut parts = Vec::new();
+        let mut index = 0;
+        while index < source.len() {
+            let start = source[index];
+            let mut end = index;
+            while end + 1 < source.len() && source[end + 1] == source[end] + 1 {
+                end += 1;
+            }
+            match end - index {
+                0 => parts.push(Part::Value(start)),
+                1 => parts.extend([Part::Value(start), Part::Value(source[end])]),
+                _ => parts.push(Part::Range(start, source[end])),
+            }
+            index = end + 1;
+        }
+        parts
+    }
+
+    /// 原方言中星期的编号比 Unix 大多少
+    fn weekday_offset(&self) -> u32 {
+        match self.dialect {
+            Dialect::Quartz => 1,
+            Dialect::Unix | Dialect::Spring => 0,
+        }
+    }
+}
+
+/// 用 `,` 连接各项，取值都加上 `offset`
+fn format_parts(parts: &[Part], offset: u32) -> String {
+    parts
+        .iter()
+        .map(|part| format_part(part, offset))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn format_part(part: &Part, offset: u32) -> String {
+    match part {
+        Part::Any => "*".to_string(),
+        Part::Value(value) => (value + offset).to_string(),
+        Part::Range(start, end) => format!("{}-{}", start + offset, end + offset),
+        Part::Step(base, step) => format!("{}/{step}", format_part(base, offset)),
+    }
+}
+
+fn format_days(days: &[Day]) -> String {
+    days.iter()
+        .map(|day| match day {
+            Day::Part(part) => format_part(part, 0),
+            Day::Last(0) => "L".to_string(),
+            Day::Last(offset) => format!("L-{offset}"),
+            Day::Nearest(day) => format!("{day}W"),
+            Day::LastWeekday => "LW".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn format_weekdays(weekdays: &[Weekday], offset: u32) -> String {
+    weekdays
+        .iter()
+        .map(|weekday| match weekday {
+            Weekday::Part(part) => format_part(part, offset),
+            Weekday::Last(value) => format!("{}L", value + offset),
+            Weekday::Nth(value, nth) => format!("{}#{nth}", value + offset),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// crontab 中以 `*` 开头的日期或星期字段不参与“或”的判断
+fn is_star(part: &Part) -> bool {
+    match part {
+        Part::Any => true,
+        Part::Step(base, _) => **base == Part::Any,
+        _ => false,
+    }
+}
+
+impl Day {
+    fn matches(&self, date: NaiveDate) -> bool {
+        let last = last_day(date);
+        let day = date.day();
+        match self {
+            Day::Part(part) => part.contains(day, DAY),
+            Day::Last(offset) => last.checked_sub(*offset) == Some(day),
+            Day::Nearest(target) => *target <= last && nearest_weekday(date, *target) == day,
+            Day::LastWeekday => nearest_weekday(date, last) == day,
+        }
+    }
+}
+
+impl Weekday {
+    fn matches(&self, date: NaiveDate, bounds: Bounds) -> bool {
+        let weekday = date.weekday().num_days_from_sunday();
+        match self {
+            Weekday::Part(part) => part.values(bounds).iter().any(|value| value % 7 == weekday),
+            Weekday::Last(value) => value % 7 == weekday && date.day() + 7 > last_day(date),
+            Weekday::Nth(value, nth) => value % 7 == weekday && (date.day() - 1) / 7 + 1 == *nth,
+        }
+    }
+}
+
+fn values(parts: &[Part], bounds: Bounds) -> Vec<u32> {
+    (bounds.min..=bounds.max)
+        .filter(|value| parts.iter().any(|part| part.contains(*value, bounds)))
+        .collect()
+}
+
+fn first_time(
+    hours: &[u32],
+    minutes: &[u32],
+    seconds: &[u32],
+    floor: Option<NaiveTime>,
+) -> Option<NaiveTime> {
+    for &hour in hours {
+        if floor.is_some_and(|floor| hour < floor.hour()) {
+            continue;
+        }
+        for &minute in minutes {
+            if floor.is_some_and(|floor| hour == floor.hour() && minute < floor.minute()) {
+                continue;
+            }
+            for &second in seconds {
+                let time = NaiveTime::from_hms_opt(hour, minute, second)?;
+                if floor.map_or(true, |floor| time > floor) {
+                    return Some(time);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    match date.month() {
+        12 => NaiveDate::from_ymd_opt(date.year() + 1, 1, 1),
+        month => NaiveDate::from_ymd_opt(date.year(), month + 1, 1),
+    }
+}
+
+fn last_day(date: NaiveDate) -> u32 {
+    first_of_next_month(date)
+        .and_then(|date| date.pred_opt())
+        .map_or(31, |date| date.day())
+}
+
+///
+/// 离当月 `target` 号最近的工作日，周六提前到周五，周日推后到周一，但不跨月
+///
+fn nearest_weekday(date: NaiveDate, target: u32) -> u32 {
+    let last = last_day(date);
+    let Some(day) = date.with_day(target) else {
+        return 0;
+    };
+    match day.weekday().num_days_from_sunday() {
+        6 if target == 1 => 3,
+        6 => target - 1,
+        0 if target == last => target - 2,
+        0 => target + 1,
+        _ => target,
+    }
+}
+
+fn parse_parts(text: &str, bounds: Bounds, names: &[&str]) -> Result<Vec<Part>, Error> {
+    text.split(',')
+        .map(|item| parse_part(item, bounds, names, 0))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|message| Error::Field(bounds.name, text.to_string(), message))
+}
+
+///
+/// 解析一项，`names` 中第 i 个名称对应 `i + offset`
+///
+fn parse_part(text: &str, bounds: Bounds, names: &[&str], offset: u32) -> Result<Part, String> {
+    if let Some((base, step)) = text.split_once('/') {
+        let step = step
+            .parse::<u32>()
+            .map_err(|_| format!("invalid step '{step}'"))?;
+        if step == 0 {
+            return Err("step must be greater than 0".to_string());
+        }
+        let base = match base {
+            "*" => Part::Any,
+            base => parse_range(base, bounds, names, offset)?,
+        };
+        return Ok(Part::Step(Box::new(base), step));
+    }
+    match text {
+        "*" => Ok(Part::Any),
+        text => parse_range(text, bounds, names, offset),
+    }
+}
+
+fn parse_range(text: &str, bounds: Bounds, names: &[&str], offset: u32) -> Result<Part, String> {
+    match text.split_once('-') {
+        Some((start, end)) => {
+            let start = parse_value(start, bounds, names, offset)?;
+            let end = parse_value(end, bounds, names, offset)?;
+            if start > end {
+                return Err(format!("range start {start} is greater than end {end}"));
+            }
+            Ok(Part::Range(start, end))
+        }
+        None => parse_value(text, bounds, names, offset).map(Part::Value),
+    }
+}
+
+fn parse_value(text: &str, bounds: Bounds, names: &[&str], offset: u32) -> Result<u32, String> {
+    let value = match names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(text))
+    {
+        Some(index) => index as u32 + offset,
+        None => text
+            .parse::<u32>()
+            .map_err(|_| format!("invalid value '{text}'"))?,
+    };
+    match (bounds.min..=bounds.max).contains(&value) {
+        true => Ok(value),
+        false => Err(format!(
+            "{value} is out of range {}-{}",
+            bounds.min, bounds.max
+        )),
+    }
+}
+
+fn parse_days(text: &str, dialect: Dialect) -> Result<Vec<Day>, Error> {
+    let error = |message: String| Error::Field(DAY.name, text.to_string(), message);
+    if text == "?" && dialect != Dialect::Unix {
+        return Ok(Vec::new());
+    }
+    let mut days = Vec::new();
+    for item in text.split(',') {
+        let extended = dialect != Dialect::Unix;
+        let day = match item.to_ascii_uppercase().as_str() {
+            "L" if extended => Day::Last(0),
+            "LW" if extended => Day::LastWeekday,
+            item if extended && item.starts_with("L-") => {
+                let bounds = Bounds {
+                    min: 0,
+                    max: 30,
+                    ..DAY
+                };
+                Day::Last(parse_value(&item[2..], bounds, &[], 0).map_err(error)?)
+            }
+            item if extended && item.ends_with('W') => {
+                let day = parse_value(&item[..item.len() - 1], DAY, &[], 0).map_err(error)?;
+                Day::Nearest(day)
+            }
+            _ => Day::Part(parse_part(item, DAY, &[], 0).map_err(error)?),
+        };
+        days.push(day);
+    }
+    Ok(days)
+}
+
+fn parse_weekdays(text: &str, dialect: Dialect) -> Result<Vec<Weekday>, Error> {
+    let error = |message: String| Error::Field("day-of-week", text.to_string(), message);
+    if text == "?" && dialect != Dialect::Unix {
+        return Ok(Vec::new());
+    }
+    // Quartz 的星期从 1 开始，读取后统一减一
+    let (bounds, offset) = match dialect {
+        Dialect::Quartz => (
+            Bounds {
+                min: 1,
+                ..weekday_bounds(dialect)
+            },
+            1,
+        ),
+        Dialect::Unix | Dialect::Spring => (weekday_bounds(dialect), 0),
+    };
+    let bounds = Bounds {
+        max: bounds.max + offset,
+        ..bounds
+    };
+    let normalize = move |value: u32| value - offset;
+    let extended = dialect != Dialect::Unix;
+    let mut weekdays = Vec::new();
+    for item in text.split(',') {
+        let value = |text: &str| {
+            parse_value(text, bounds, &WEEKDAYS, offset)
+                .map(normalize)
+                .map_err(error)
+        };
+        let weekday = match item.to_ascii_uppercase() {
+            // 单独的 `L` 表示一周的最后一天，即星期六
+            item if extended && item == "L" => Weekday::Part(Part::Value(6)),
+            item if extended && item.ends_with('L') => {
+                Weekday::Last(value(&item[..item.len() - 1])?)
+            }
+            item if extended && item.contains('#') => {
+                let (day, nth) = item.split_once('#').unwrap_or_default();
+                match nth.parse::<u32>() {
+                    Ok(nth @ 1..=5) => Weekday::Nth(value(day)?, nth),
+                    _ => return Err(error(format!("invalid occurrence '{nth}', expected 1-5"))),
+                }
+            }
+            _ => Weekday::Part(
+                parse_part(item, bounds, &WEEKDAYS, offset)
+                    .map_err(error)?
+                    .map(normalize),
+            ),
+        };
+        weekdays.push(weekday);
+    }
+    Ok(weekdays)
+}
+
+command_error! {
+    (Fields, "{0:?} cron expects {1} fields, found {2}", Dialect, &'static str, usize),
+    (Field, "invalid {0} field '{1}': {2}", &'static str, String, String),
+    (Macro, "unsupported macro {0}", String),
+    (Quartz, "Quartz requires '?' in exactly one of day-of-month and day-of-week"),
+}
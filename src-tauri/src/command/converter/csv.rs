@@ -0,0 +1,1191 @@
+use crate::charset::Charset;
+use crate::command_error;
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use sqlparser::ast::{
+    BinaryOperator, Distinct, Expr, FunctionArg, FunctionArgExpr, FunctionArguments, GroupByExpr,
+    Query, SelectItem, SetExpr, Statement, UnaryOperator, Value,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Read, Write};
+
+/// 用于探测编码、分隔符和引号的采样字节数
+const SAMPLE_SIZE: usize = 64 * 1024;
+
+/// 用于探测分隔符的采样行数
+const SAMPLE_ROWS: usize = 20;
+
+/// 候选分隔符
+const DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+
+///
+/// 数据来源，大文件通过路径流式读取
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Source {
+    Text(String),
+    File(String),
+}
+
+///
+/// CSV 可以相互转换的格式
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Format {
+    /// 对象数组，没有表头时也可以是二维数组
+    Json,
+    Markdown,
+    /// `INSERT` 语句
+    Sql,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Table {
+    charset: Charset,
+    delimiter: char,
+    quote: char,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    /// 全部数据行数，`rows` 只包含其中的前若干行
+    total: usize,
+}
+
+///
+/// 解析 CSV/TSV，`limit` 限制返回的行数，其余行只计数
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn parse_csv(
+    source: Source,
+    charset: Option<Charset>,
+    delimiter: Option<char>,
+    quote: Option<char>,
+    header: bool,
+    limit: Option<usize>,
+) -> Result<Table, Error> {
+    let reader = Reader::open(source, charset, delimiter, quote, header)?;
+    let (charset, delimiter, quote) = (reader.charset, reader.delimiter, reader.quote);
+    let headers = reader.headers.clone();
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut rows = Vec::new();
+    let mut total = 0;
+    for row in reader {
+        let row = row?;
+        if rows.len() < limit {
+            rows.push(row);
+        }
+        total += 1;
+    }
+    Ok(Table {
+        charset,
+        delimiter,
+        quote,
+        headers,
+        rows,
+        total,
+    })
+}
+
+///
+/// 将 CSV 转换为其他格式，指定 `output` 时逐行写入文件并返回文件路径
+///
+#[allow(clippy::too_many_arguments)]
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn convert_csv(
+    source: Source,
+    charset: Option<Charset>,
+    delimiter: Option<char>,
+    quote: Option<char>,
+    header: bool,
+    format: Format,
+    table: Option<String>,
+    output: Option<String>,
+) -> Result<String, Error> {
+    let reader = Reader::open(source, charset, delimiter, quote, header)?;
+    let table = table.unwrap_or_else(|| "data".to_string());
+    match output {
+        Some(output) => {
+            let mut writer = BufWriter::new(File::create(&output)?);
+            write_rows(reader, format, &table, &mut writer)?;
+            writer.flush()?;
+            Ok(output)
+        }
+        None => {
+            let mut buffer = Vec::new();
+            write_rows(reader, format, &table, &mut buffer)?;
+            Ok(String::from_utf8(buffer)?)
+        }
+    }
+}
+
+///
+/// 将 JSON 数组、Markdown 表格或 `INSERT` 语句转换为 CSV
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_to_csv(
+    input: &str,
+    format: Format,
+    delimiter: Option<char>,
+) -> Result<String, Error> {
+    let (headers, rows) = match format {
+        Format::Json => from_json(input)?,
+        Format::Markdown => from_markdown(input),
+        Format::Sql => from_sql(input)?,
+    };
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(ascii(delimiter.unwrap_or(','))?)
+        .flexible(true)
+        .from_writer(Vec::new());
+    if !headers.is_empty() {
+        writer.write_record(&headers)?;
+    }
+    for row in rows {
+        writer.write_record(&row)?;
+    }
+    let buffer = writer.into_inner().map_err(|error| error.into_error())?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+///
+/// 使用简单的 SQL 查询 CSV，表名任意，支持列选择、`WHERE`、`GROUP BY`、
+/// `ORDER BY`、`LIMIT` 以及 `COUNT`/`SUM`/`AVG`/`MIN`/`MAX` 聚合
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn query_csv(
+    source: Source,
+    charset: Option<Charset>,
+    delimiter: Option<char>,
+    quote: Option<char>,
+    header: bool,
+    query: &str,
+) -> Result<Table, Error> {
+    let reader = Reader::open(source, charset, delimiter, quote, header)?;
+    let (charset, delimiter, quote) = (reader.charset, reader.delimiter, reader.quote);
+    let plan = Plan::parse(query, &reader.headers)?;
+    let (headers, rows) = plan.execute(reader)?;
+    Ok(Table {
+        charset,
+        delimiter,
+        quote,
+        headers,
+        total: rows.len(),
+        rows,
+    })
+}
+
+///
+/// 流式读取 CSV 记录，表头已经预先读出
+///
+struct Reader {
+    charset: Charset,
+    delimiter: char,
+    quote: char,
+    headers: Vec<String>,
+    /// 没有表头时，第一行在生成列名时已经读出
+    first: Option<Vec<String>>,
+    records: csv::StringRecordsIntoIter<Box<dyn Read>>,
+}
+
+impl Reader {
+    fn open(
+        source: Source,
+        charset: Option<Charset>,
+        delimiter: Option<char>,
+        quote: Option<char>,
+        header: bool,
+    ) -> Result<Self, Error> {
+        let (input, charset, sample): (Box<dyn Read>, _, _) = match source {
+            Source::Text(text) => {
+                let text = text.strip_prefix('\u{feff}').unwrap_or(&text).to_string();
+                let mut end = text.len().min(SAMPLE_SIZE);
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                let sample = text[..end].to_string();
+                (Box::new(Cursor::new(text)), Charset::Utf8, sample)
+            }
+            Source::File(path) => {
+                let mut head = Vec::with_capacity(SAMPLE_SIZE);
+                File::open(&path)?
+                    .take(SAMPLE_SIZE as u64)
+                    .read_to_end(&mut head)?;
+                let charset = charset.unwrap_or_else(|| Charset::detect(&head).charset);
+                let (sample, _, _) = charset.encoding().decode(&head);
+                let input = DecodeReaderBytesBuilder::new()
+                    .encoding(Some(charset.encoding()))
+                    .build(File::open(&path)?);
+                (Box::new(input), charset, sample.into_owned())
+            }
+        };
+        let quote = quote.unwrap_or_else(|| detect_quote(&sample));
+        let delimiter = delimiter.unwrap_or_else(|| detect_delimiter(&sample, quote));
+        let mut records = csv::ReaderBuilder::new()
+            .delimiter(ascii(delimiter)?)
+            .quote(ascii(quote)?)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(input)
+            .into_records();
+        let first = records
+            .next()
+            .transpose()?
+            .map(|record| record.iter().map(str::to_string).collect::<Vec<_>>());
+        let (headers, first) = match first {
+            Some(first) if header => (first, None),
+            Some(first) => ((1..=first.len()).map(column_name).collect(), Some(first)),
+            None => (Vec::new(), None),
+        };
+        Ok(Reader {
+            charset,
+            delimiter,
+            quote,
+            headers,
+            first,
+            records,
+        })
+    }
+}
+
+impl Iterator for Reader {
+    type Item = Result<Vec<String>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.first.take() {
+            return Some(Ok(first));
+        }
+        self.records.next().map(|record| {
+            record
+                .map(|record| record.iter().map(str::to_string).collect())
+                .map_err(Into::into)
+        })
+    }
+}
+
+/// 没有表头时生成的列名，从 1 开始
+fn column_name(index: usize) -> String {
+    format!("c{index}")
+}
+
+fn ascii(value: char) -> Result<u8, Error> {
+    u8::try_from(value)
+        .ok()
+        .filter(u8::is_ascii)
+        .ok_or(Error::NonAscii(value))
+}
+
+///
+/// 统计出现在字段开头的双引号和单引号，单引号明显更多时才使用单引号
+///
+fn detect_quote(sample: &str) -> char {
+    let (mut double, mut single) = (0, 0);
+    let mut previous = '\n';
+    for c in sample.chars() {
+        if previous == '\n' || DELIMITERS.contains(&previous) {
+            match c {
+                '"' => double += 1,
+                '\'' => single += 1,
+                _ => {}
+            }
+        }
+        previous = c;
+    }
+    if single > double {
+        '\''
+    } else {
+        '"'
+    }
+}
+
+///
+/// 选择在采样行中字段数最稳定的分隔符，稳定程度相同时选择字段更多的
+///
+fn detect_delimiter(sample: &str, quote: char) -> char {
+    DELIMITERS
+        .into_iter()
+        .filter_map(|delimiter| {
+            let counts = count_fields(sample, delimiter, quote);
+            let first = *counts.first()?;
+            if first < 2 {
+                return None;
+            }
+            let stable = counts.iter().filter(|count| **count == first).count();
+            Some(((stable, first), delimiter))
+        })
+        .max_by_key(|(score, _)| *score)
+        .map_or(',', |(_, delimiter)| delimiter)
+}
+
+/// 统计采样中每行的字段数，引号内的分隔符和换行不计，最后一行可能被截断所以忽略
+fn count_fields(sample: &str, delimiter: char, quote: char) -> Vec<usize> {
+    let mut counts = Vec::new();
+    let mut fields = 1;
+    let mut quoted = false;
+    for c in sample.chars() {
+        if c == quote {
+            quoted = !quoted;
+        } else if !quoted && c == delimiter {
+            fields += 1;
+        } else if !quoted && c == '\n' {
+            counts.push(fields);
+            fields = 1;
+            if counts.len() == SAMPLE_ROWS {
+                break;
+            }
+        }
+    }
+    if counts.is_empty() {
+        counts.push(fields);
+    }
+    counts
+}
+
+fn write_rows(
+    reader: Reader,
+    format: Format,
+    table: &str,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let headers = reader.headers.clone();
+    let name = |index: usize| {
+        headers
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| column_name(index + 1))
+    };
+    match format {
+        Format::Json => {
+            writer.write_all(b"[")?;
+            for (index, row) in reader.enumerate() {
+                let object = row?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| (name(index), JsonValue::String(value)))
+                    .collect::<Map<_, _>>();
+                writer.write_all(if index == 0 { b"\n  " } else { b",\n  " })?;
+                serde_json::to_writer(&mut *writer, &object)?;
+            }
+            writer.write_all(b"\n]\n")?;
+        }
+        Format::Markdown => {
+            let cells = |row: &[String]| {
+                let cells = row.iter().map(|cell| escape_markdown(cell));
+                format!("| {} |\n", cells.collect::<Vec<_>>().join(" | "))
+            };
+            writer.write_all(cells(&headers).as_bytes())?;
+            writer.write_all(format!("|{}\n", " --- |".repeat(headers.len())).as_bytes())?;
+            for row in reader {
+                let mut row = row?;
+                row.resize(headers.len().max(row.len()), String::new());
+                writer.write_all(cells(&row).as_bytes())?;
+            }
+        }
+        Format::Sql => {
+            let table = quote_identifier(table);
+            for row in reader {
+                let row = row?;
+                let columns = (0..row.len())
+                    .map(|index| quote_identifier(&name(index)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let values = row
+                    .iter()
+                    .map(|value| sql_literal(value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(writer, "INSERT INTO {table} ({columns}) VALUES ({values});")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn escape_markdown(cell: &str) -> String {
+    cell.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// 仅在包含特殊字符时用双引号包裹标识符
+fn quote_identifier(name: &str) -> String {
+    let plain = name
+        .chars()
+        .enumerate()
+        .all(|(index, c)| c == '_' || c.is_alphabetic() || (index > 0 && c.is_ascii_digit()));
+    if plain && !name.is_empty() {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// 空值写作 `NULL`，不带前导零的数字保持原样，其余作为字符串
+fn sql_literal(value: &str) -> String {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let number = value.parse::<f64>().is_ok_and(f64::is_finite)
+        && digits.starts_with(|c: char| c.is_ascii_digit())
+        && (!digits.starts_with('0') || digits.len() == 1 || digits.starts_with("0."));
+    if value.is_empty() {
+        "NULL".to_string()
+    } else if number {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+type Rows = (Vec<String>, Vec<Vec<String>>);
+
+///
+/// 对象数组按键首次出现的顺序生成表头，二维数组直接作为行
+///
+fn from_json(input: &str) -> Result<Rows, Error> {
+    let JsonValue::Array(items) = serde_json::from_str(input)? else {
+        return Err(Error::NotArray);
+    };
+    let cell = |value: &JsonValue| match value {
+        JsonValue::Null => String::new(),
+        JsonValue::String(value) => value.clone(),
+        value => value.to_string(),
+    };
+    let mut headers = Vec::<String>::new();
+    for item in &items {
+        if let JsonValue::Object(object) = item {
+            for key in object.keys() {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+        }
+    }
+    let rows = items
+        .iter()
+        .map(|item| match item {
+            JsonValue::Object(object) => headers
+                .iter()
+                .map(|key| object.get(key).map(cell).unwrap_or_default())
+                .collect(),
+            JsonValue::Array(values) => values.iter().map(cell).collect(),
+            value => vec![cell(value)],
+        })
+        .collect();
+    Ok((headers, rows))
+}
+
+///
+/// 读取 Markdown 表格，忽略分隔行和表格外的文本
+///
+fn from_markdown(input: &str) -> Rows {
+    let mut rows = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('|'))
+        .map(split_markdown_row)
+        .filter(|cells| {
+            !cells.iter().all(|cell| {
+                let cell = cell.trim_matches(':');
+                !cell.is_empty() && cell.chars().all(|c| c == '-')
+            })
+        });
+    let headers = rows.next().unwrap_or_default();
+    (headers, rows.collect())
+}
+
+fn split_markdown_row(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line,
+    };
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => cell.push(chars.next().unwrap_or('|')),
+            '|' => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+        .into_iter()
+        .map(|cell| cell.trim().replace("<br>", "\n"))
+        .collect()
+}
+
+///
+/// 读取 `INSERT ... VALUES` 语句，以第一条语句的列名作为表头
+///
+fn from_sql(input: &str) -> Result<Rows, Error> {
+    let mut headers = Vec::new();
+    let mut rows = Vec::new();
+    for statement in Parser::parse_sql(&GenericDialect {}, input)? {
+        let Statement::Insert(insert) = statement else {
+            continue;
+        };
+        if headers.is_empty() {
+            headers = insert
+                .columns
+                .iter()
+                .map(|ident| ident.value.clone())
+                .collect();
+        }
+        let Some(SetExpr::Values(values)) = insert.source.as_deref().map(|query| &*query.body)
+        else {
+            continue;
+        };
+        for row in &values.rows {
+            rows.push(
+                row.iter()
+                    .map(|expr| match expr {
+                        Expr::Value(Value::Null) => String::new(),
+                        Expr::Value(Value::Number(number, _)) => number.to_string(),
+                        Expr::Value(
+                            Value::SingleQuotedString(value) | Value::DoubleQuotedString(value),
+                        ) => value.clone(),
+                        expr => expr.to_string(),
+                    })
+                    .collect(),
+            );
+        }
+    }
+    if headers.is_empty() && rows.is_empty() {
+        return Err(Error::NoInsert);
+    }
+    Ok((headers, rows))
+}
+
+///
+/// 查询中的值，CSV 中的空字段视为 `NULL`
+///
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl Cell {
+    fn from_field(field: &str) -> Cell {
+        if field.is_empty() {
+            Cell::Null
+        } else {
+            Cell::Text(field.to_string())
+        }
+    }
+
+    fn number(&self) -> Option<f64> {
+        match self {
+            Cell::Number(number) => Some(*number),
+            Cell::Text(text) => text.trim().parse().ok(),
+            Cell::Bool(_) | Cell::Null => None,
+        }
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            Cell::Bool(value) => *value,
+            Cell::Number(number) => *number != 0.0,
+            Cell::Text(text) => !text.is_empty(),
+            Cell::Null => false,
+        }
+    }
+
+    /// 两边都能解析为数字时按数值比较，否则按文本比较，`NULL` 最小
+    fn compare(&self, other: &Cell) -> Ordering {
+        match (self, other) {
+            (Cell::Null, Cell::Null) => Ordering::Equal,
+            (Cell::Null, _) => Ordering::Less,
+            (_, Cell::Null) => Ordering::Greater,
+            _ => match (self.number(), other.number()) {
+                (Some(left), Some(right)) => left.total_cmp(&right),
+                _ => self.to_string().cmp(&other.to_string()),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Null => Ok(()),
+            Cell::Bool(value) => write!(f, "{value}"),
+            Cell::Number(number) => write!(f, "{number}"),
+            Cell::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Aggregate {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+///
+/// 聚合函数调用，参数为 `None` 表示 `COUNT(*)`
+///
+#[derive(Debug)]
+struct Call {
+    aggregate: Aggregate,
+    argument: Option<Expr>,
+    key: String,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Accumulator {
+    count: usize,
+    sum: f64,
+    min: Option<Cell>,
+    max: Option<Cell>,
+}
+
+impl Accumulator {
+    fn update(&mut self, aggregate: Aggregate, value: Cell) {
+        match aggregate {
+            Aggregate::Count => self.count += 1,
+            Aggregate::Sum | Aggregate::Avg => {
+                if let Some(number) = value.number() {
+                    self.sum += number;
+                    self.count += 1;
+                }
+            }
+            Aggregate::Min => {
+                if self
+                    .min
+                    .as_ref()
+                    .map_or(true, |min| value.compare(min).is_lt())
+                {
+                    self.min = Some(value);
+                }
+            }
+            Aggregate::Max => {
+                if self
+                    .max
+                    .as_ref()
+                    .map_or(true, |max| value.compare(max).is_gt())
+                {
+                    self.max = Some(value);
+                }
+            }
+        }
+    }
+
+    fn finish(&self, aggregate: Aggregate) -> Cell {
+        match aggregate {
+            Aggregate::Count => Cell::Number(self.count as f64),
+            Aggregate::Sum if self.count > 0 => Cell::Number(self.sum),
+            Aggregate::Avg if self.count > 0 => Cell::Number(self.sum / self.count as f64),
+            Aggregate::Sum | Aggregate::Avg => Cell::Null,
+            Aggregate::Min => self.min.clone().unwrap_or(Cell::Null),
+            Aggregate::Max => self.max.clone().unwrap_or(Cell::Null),
+        }
+    }
+}
+
+///
+/// 表达式求值的上下文，`output` 用于在 `ORDER BY` 中引用别名
+///
+struct Scope<'a> {
+    headers: &'a [String],
+    row: &'a [String],
+    output: Option<(&'a [String], &'a [Cell])>,
+    aggregates: Option<&'a HashMap<String, Cell>>,
+}
+
+impl Scope<'_> {
+    fn column(&self, name: &str) -> Result<Cell, Error> {
+        if let Some((headers, values)) = self.output {
+            if let Some(index) = position(headers, name) {
+                return Ok(values[index].clone());
+            }
+        }
+        position(self.headers, name)
+            .map(|index| Cell::from_field(self.row.get(index).map_or("", String::as_str)))
+            .ok_or_else(|| Error::UnknownColumn(name.to_string()))
+    }
+
+    fn eval(&self, expr: &Expr) -> Result<Cell, Error> {
+        Ok(match expr {
+            Expr::Identifier(ident) => self.column(&ident.value)?,
+            Expr::CompoundIdentifier(idents) => match idents.last() {
+                Some(ident) => self.column(&ident.value)?,
+                None => Cell::Null,
+            },
+            Expr::Value(value) => literal(value)?,
+            Expr::Nested(expr) => self.eval(expr)?,
+            Expr::IsNull(expr) => Cell::Bool(self.eval(expr)? == Cell::Null),
+            Expr::IsNotNull(expr) => Cell::Bool(self.eval(expr)? != Cell::Null),
+            Expr::UnaryOp { op, expr } => {
+                let value = self.eval(expr)?;
+                match op {
+                    UnaryOperator::Not => Cell::Bool(!value.truthy()),
+                    UnaryOperator::Minus => value.number().map_or(Cell::Null, |n| Cell::Number(-n)),
+                    UnaryOperator::Plus => value,
+                    op => return Err(Error::Unsupported(op.to_string())),
+                }
+            }
+            Expr::BinaryOp { left, op, right } => binary(self.eval(left)?, op, self.eval(right)?)?,
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => {
+                let value = self.eval(expr)?;
+                let between = value.compare(&self.eval(low)?).is_ge()
+                    && value.compare(&self.eval(high)?).is_le();
+                Cell::Bool(between != *negated)
+            }
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let value = self.eval(expr)?;
+                let mut found = false;
+                for item in list {
+                    found |= value.compare(&self.eval(item)?).is_eq();
+                }
+                Cell::Bool(found != *negated)
+            }
+            Expr::Like {
+                negated,
+                expr,
+                pattern,
+                ..
+            } => self.like(expr, pattern, *negated, false)?,
+            Expr::ILike {
+                negated,
+                expr,
+                pattern,
+                ..
+            } => self.like(expr, pattern, *negated, true)?,
+            Expr::Function(_) => match self.aggregates {
+                Some(aggregates) => aggregates
+                    .get(&expr.to_string())
+                    .cloned()
+                    .ok_or_else(|| Error::Unsupported(expr.to_string()))?,
+                None => return Err(Error::Aggregate(expr.to_string())),
+            },
+            expr => return Err(Error::Unsupported(expr.to_string())),
+        })
+    }
+
+    fn like(
+        &self,
+        expr: &Expr,
+        pattern: &Expr,
+        negated: bool,
+        ignore_case: bool,
+    ) -> Result<Cell, Error> {
+        let (value, pattern) = (self.eval(expr)?, self.eval(pattern)?);
+        if value == Cell::Null || pattern == Cell::Null {
+            return Ok(Cell::Bool(false));
+        }
+        let (value, pattern) = if ignore_case {
+            (
+                value.to_string().to_lowercase(),
+                pattern.to_string().to_lowercase(),
+            )
+        } else {
+            (value.to_string(), pattern.to_string())
+        };
+        let value = value.chars().collect::<Vec<_>>();
+        let pattern = pattern.chars().collect::<Vec<_>>();
+        Ok(Cell::Bool(like(&value, &pattern) != negated))
+    }
+}
+
+/// 列名优先精确匹配，其次忽略大小写匹配
+fn position(headers: &[String], name: &str) -> Option<usize> {
+    headers
+        .iter()
+        .position(|header| header == name)
+        .or_else(|| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(name))
+        })
+}
+
+/// `%` 匹配任意个字符，`_` 匹配单个字符
+fn like(value: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some(('%', rest)) => (0..=value.len()).any(|skip| like(&value[skip..], rest)),
+        Some(('_', rest)) => !value.is_empty() && like(&value[1..], rest),
+        Some((c, rest)) => value.first() == Some(c) && like(&value[1..], rest),
+    }
+}
+
+fn literal(value: &Value) -> Result<Cell, Error> {
+    Ok(match value {
+        Value::Null => Cell::Null,
+        Value::Boolean(value) => Cell::Bool(*value),
+        Value::Number(number, _) => Cell::Number(
+            number
+                .to_string()
+                .parse()
+                .map_err(|_| Error::Unsupported(number.to_string()))?,
+        ),
+        Value::SingleQuotedString(text) | Value::DoubleQuotedString(text) => {
+            Cell::Text(text.clone())
+        }
+        value => return Err(Error::Unsupported(value.to_string())),
+    })
+}
+
+fn binary(left: Cell, op: &BinaryOperator, right: Cell) -> Result<Cell, Error> {
+    let arithmetic = |f: fn(f64, f64) -> f64| match (left.number(), right.number()) {
+        (Some(left), Some(right)) => Cell::Number(f(left, right)),
+        _ => Cell::Null,
+    };
+    let comparison = |f: fn(Ordering) -> bool| {
+        if left == Cell::Null || right == Cell::Null {
+            Cell::Bool(false)
+        } else {
+            Cell::Bool(f(left.compare(&right)))
+        }
+    };
+    Ok(match op {
+        BinaryOperator::And => Cell::Bool(left.truthy() && right.truthy()),
+        BinaryOperator::Or => Cell::Bool(left.truthy() || right.truthy()),
+        BinaryOperator::Eq => comparison(Ordering::is_eq),
+        BinaryOperator::NotEq => comparison(Ordering::is_ne),
+        BinaryOperator::Lt => comparison(Ordering::is_lt),
+        BinaryOperator::LtEq => comparison(Ordering::is_le),
+        BinaryOperator::Gt => comparison(Ordering::is_gt),
+        BinaryOperator::GtEq => comparison(Ordering::is_ge),
+        BinaryOperator::Plus => arithmetic(|left, right| left + right),
+        BinaryOperator::Minus => arithmetic(|left, right| left - right),
+        BinaryOperator::Multiply => arithmetic(|left, right| left * right),
+        BinaryOperator::Divide => arithmetic(|left, right| left / right),
+        BinaryOperator::Modulo => arithmetic(|left, right| left % right),
+        BinaryOperator::StringConcat => Cell::Text(format!("{left}{right}")),
+        op => return Err(Error::Unsupported(op.to_string())),
+    })
+}
+
+///
+/// 解析后的查询
+///
+struct Plan {
+    /// 输出列，`None` 表示展开全部列
+    projection: Vec<(String, Option<Expr>)>,
+    selection: Option<Expr>,
+    group_by: Vec<Expr>,
+    having: Option<Expr>,
+    /// 排序表达式和是否降序
+    order_by: Vec<(Expr, bool)>,
+    calls: Vec<Call>,
+    distinct: bool,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+impl Plan {
+    fn parse(query: &str, headers: &[String]) -> Result<Plan, Error> {
+        let mut statements = Parser::parse_sql(&GenericDialect {}, query)?;
+        let Some(Statement::Query(query)) = statements.pop().filter(|_| statements.is_empty())
+        else {
+            return Err(Error::NotSelect);
+        };
+        let Query {
+            body,
+            order_by,
+            limit,
+            offset,
+            ..
+        } = *query;
+        let SetExpr::Select(select) = *body else {
+            return Err(Error::NotSelect);
+        };
+        let mut projection = Vec::new();
+        for item in select.projection {
+            match item {
+                SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..) => {
+                    projection.extend(headers.iter().map(|header| {
+                        (
+                            header.clone(),
+                            Some(Expr::Identifier(header.as_str().into())),
+                        )
+                    }))
+                }
+                SelectItem::UnnamedExpr(expr) => {
+                    let name = match &expr {
+                        Expr::Identifier(ident) => ident.value.clone(),
+                        Expr::CompoundIdentifier(idents) => idents
+                            .last()
+                            .map_or_else(|| expr.to_string(), |ident| ident.value.clone()),
+                        expr => expr.to_string(),
+                    };
+                    projection.push((name, Some(expr)));
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    projection.push((alias.value, Some(expr)))
+                }
+            }
+        }
+        let group_by = match select.group_by {
+            GroupByExpr::Expressions(exprs, _) => exprs,
+            GroupByExpr::All(_) => return Err(Error::Unsupported("GROUP BY ALL".to_string())),
+        };
+        let order_by = order_by
+            .map(|order_by| order_by.exprs)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|expr| (expr.expr, expr.asc == Some(false)))
+            .collect::<Vec<_>>();
+        let mut calls = Vec::new();
+        let exprs = projection
+            .iter()
+            .filter_map(|(_, expr)| expr.as_ref())
+            .chain(select.having.as_ref())
+            .chain(order_by.iter().map(|(expr, _)| expr));
+        for expr in exprs {
+            collect_calls(expr, &mut calls)?;
+        }
+        let number = |expr: Expr| match expr {
+            Expr::Value(Value::Number(number, _)) => number
+                .to_string()
+                .parse::<usize>()
+                .map_err(|_| Error::Unsupported(number.to_string())),
+            expr => Err(Error::Unsupported(expr.to_string())),
+        };
+        Ok(Plan {
+            projection,
+            selection: select.selection,
+            group_by,
+            having: select.having,
+            order_by,
+            calls,
+            distinct: matches!(select.distinct, Some(Distinct::Distinct)),
+            limit: limit.map(number).transpose()?,
+            offset: offset
+                .map(|offset| number(offset.value))
+                .transpose()?
+                .unwrap_or(0),
+        })
+    }
+
+    fn headers(&self) -> Vec<String> {
+        self.projection
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    fn execute(&self, reader: Reader) -> Result<Rows, Error> {
+        let headers = reader.headers.clone();
+        let output = self.headers();
+        // 每行的排序键和输出值
+        let mut rows: Vec<(Vec<Cell>, Vec<Cell>)> = Vec::new();
+        let mut seen = HashSet::new();
+        let grouped = !self.group_by.is_empty() || !self.calls.is_empty();
+        // 分组键、代表行和各聚合函数的累加器，按分组首次出现的顺序保存
+        let mut groups: Vec<(Vec<String>, Accumulators)> = Vec::new();
+        let mut index = HashMap::new();
+        // 没有排序和聚合时，读够行数即可停止
+        let enough = match self.limit {
+            Some(limit) if !grouped && self.order_by.is_empty() => limit + self.offset,
+            _ => usize::MAX,
+        };
+        for row in reader {
+            if rows.len() >= enough {
+                break;
+            }
+            let row = row?;
+            let scope = Scope {
+                headers: &headers,
+                row: &row,
+                output: None,
+                aggregates: None,
+            };
+            if let Some(selection) = &self.selection {
+                if !scope.eval(selection)?.truthy() {
+                    continue;
+                }
+            }
+            if grouped {
+                let key = self
+                    .group_by
+                    .iter()
+                    .map(|expr| scope.eval(expr).map(|cell| cell.to_string()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let group = *index.entry(key).or_insert_with(|| {
+                    groups.push((row.clone(), vec![Accumulator::default(); self.calls.len()]));
+                    groups.len() - 1
+                });
+                for (call, accumulator) in self.calls.iter().zip(groups[group].1.iter_mut()) {
+                    let value = match &call.argument {
+                        Some(argument) => scope.eval(argument)?,
+                        None => Cell::Bool(true),
+                    };
+                    if value != Cell::Null {
+                        accumulator.update(call.aggregate, value);
+                    }
+                }
+                continue;
+            }
+            let row = self.project(&scope, &output)?;
+            if !self.distinct || seen.insert(row.1.iter().map(Cell::to_string).collect::<Vec<_>>())
+            {
+                rows.push(row);
+            }
+        }
+        // 没有 `GROUP BY` 的聚合查询总是返回一行
+        if grouped && self.group_by.is_empty() && groups.is_empty() {
+            groups.push((Vec::new(), vec![Accumulator::default(); self.calls.len()]));
+        }
+        for (row, accumulators) in &groups {
+            let aggregates = self
+                .calls
+                .iter()
+                .zip(accumulators)
+                .map(|(call, accumulator)| (call.key.clone(), accumulator.finish(call.aggregate)))
+                .collect::<HashMap<_, _>>();
+            let scope = Scope {
+                headers: &headers,
+                row,
+                output: None,
+                aggregates: Some(&aggregates),
+            };
+            if let Some(having) = &self.having {
+                if !scope.eval(having)?.truthy() {
+                    continue;
+                }
+            }
+            let row = self.project(&scope, &output)?;
+            if !self.distinct || seen.insert(row.1.iter().map(Cell::to_string).collect::<Vec<_>>())
+            {
+                rows.push(row);
+            }
+        }
+        if !self.order_by.is_empty() {
+            rows.sort_by(|(left, _), (right, _)| {
+                left.iter()
+                    .zip(right)
+                    .zip(&self.order_by)
+                    .map(|((left, right), (_, descending))| {
+                        let ordering = left.compare(right);
+                        if *descending {
+                            ordering.reverse()
+                        } else {
+                            ordering
+                        }
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        let rows = rows
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|(_, values)| values.iter().map(Cell::to_string).collect())
+            .collect();
+        Ok((output, rows))
+    }
+
+    ///
+    /// 计算输出值和排序键，排序键可以引用输出列的别名或序号
+    ///
+    fn project(&self, scope: &Scope, output: &[String]) -> Result<(Vec<Cell>, Vec<Cell>), Error> {
+        let values = self
+            .projection
+            .iter()
+            .map(|(_, expr)| {
+                expr.as_ref()
+                    .map_or(Ok(Cell::Null), |expr| scope.eval(expr))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let scope = Scope {
+            output: Some((output, &values)),
+            ..*scope
+        };
+        let mut keys = Vec::with_capacity(self.order_by.len());
+        for (expr, _) in &self.order_by {
+            keys.push(match expr {
+                Expr::Value(Value::Number(number, _)) => number
+                    .to_string()
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| values.get(index.checked_sub(1)?).cloned())
+                    .ok_or_else(|| Error::Unsupported(expr.to_string()))?,
+                expr => scope.eval(expr)?,
+            });
+        }
+        Ok((keys, values))
+    }
+}
+
+type Accumulators = Vec<Accumulator>;
+
+///
+/// 收集表达式中的聚合函数调用，相同的调用只计算一次
+///
+fn collect_calls(expr: &Expr, calls: &mut Vec<Call>) -> Result<(), Error> {
+    match expr {
+        Expr::Function(function) => {
+            let aggregate = match function.name.to_string().to_uppercase().as_str() {
+                "COUNT" => Aggregate::Count,
+                "SUM" => Aggregate::Sum,
+                "AVG" => Aggregate::Avg,
+                "MIN" => Aggregate::Min,
+                "MAX" => Aggregate::Max,
+                _ => return Err(Error::Unsupported(expr.to_string())),
+            };
+            let FunctionArguments::List(list) = &function.args else {
+                return Err(Error::Unsupported(expr.to_string()));
+            };
+            let argument = match list.args.as_slice() {
+                [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)] => None,
+                [FunctionArg::Unnamed(FunctionArgExpr::Expr(argument))] => Some(argument.clone()),
+                _ => return Err(Error::Unsupported(expr.to_string())),
+            };
+            let key = expr.to_string();
+            if !calls.iter().any(|call| call.key == key) {
+                calls.push(Call {
+                    aggregate,
+                    argument,
+                    key,
+                });
+            }
+        }
+        Expr::Nested(expr) | Expr::UnaryOp { expr, .. } => collect_calls(expr, calls)?,
+        Expr::BinaryOp { left, right, .. } => {
+            collect_calls(left, calls)?;
+            collect_calls(right, calls)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+command_error! {
+    (Io, "io error: {0}", #[from] std::io::Error),
+    (Csv, "csv error: {0}", #[from] csv::Error),
+    (Json, "json error: {0}", #[from] serde_json::Error),
+    (Sql, "sql error: {0}", #[from] sqlparser::parser::ParserError),
+    (Utf8, "utf8 error: {0}", #[from] std::string::FromUtf8Error),
+    (NonAscii, "delimiter and quote must be ascii: {0}", char),
+    (NotArray, "json must be an array"),
+    (NoInsert, "no insert statement found"),
+    (NotSelect, "query must be a single select statement"),
+    (UnknownColumn, "unknown column: {0}", String),
+    (Aggregate, "aggregate function is not allowed here: {0}", String),
+    (Unsupported, "unsupported expression: {0}", String),
+}
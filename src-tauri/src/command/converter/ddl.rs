@@ -0,0 +1,738 @@
+use crate::command_error;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sqlparser::ast::{
+    ColumnOption, CommentDef, CommentObject, CreateTable, ObjectName, Statement, TableConstraint,
+};
+use sqlparser::dialect::{MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+use std::collections::BTreeSet;
+
+/// Rust 关键字，作为字段名时需要写成 `r#name`
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// 不能写成 `r#name` 的关键字，作为字段名时在末尾加下划线
+const RESERVED_KEYWORDS: &[&str] = &["crate", "self", "super"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Dialect {
+    MySql,
+    PostgreSql,
+    SQLite,
+}
+
+///
+/// 生成的代码类型
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Target {
+    /// 带 serde 和 sqlx 派生的结构体
+    Rust,
+    /// JPA 实体类
+    Java,
+    /// TypeScript 接口
+    TypeScript,
+    JsonSchema,
+}
+
+///
+/// 根据 `CREATE TABLE` 语句生成实体代码，PostgreSQL 的 `COMMENT ON` 语句会作为注释
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_ddl(ddl: &str, dialect: Dialect, target: Target) -> Result<String, Error> {
+    let tables = parse(ddl, dialect)?;
+    if tables.is_empty() {
+        return Err(Error::NoTable);
+    }
+    Ok(match target {
+        Target::Rust => to_rust(&tables),
+        Target::Java => to_java(&tables),
+        Target::TypeScript => to_typescript(&tables),
+        Target::JsonSchema => serde_json::to_string_pretty(&to_json_schema(&tables))?,
+    })
+}
+
+/// 与目标语言无关的列类型
+#[derive(Debug, Clone)]
+enum Kind {
+    Bool,
+    Integer {
+        bits: u8,
+        unsigned: bool,
+    },
+    Float,
+    Double,
+    Decimal,
+    String,
+    Uuid,
+    Date,
+    Time,
+    DateTime,
+    /// 带时区的时间戳
+    DateTimeTz,
+    Json,
+    Bytes,
+    Array(Box<Kind>),
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    kind: Kind,
+    /// 字符串类型的长度
+    length: Option<u64>,
+    nullable: bool,
+    primary_key: bool,
+    auto_increment: bool,
+    comment: Option<String>,
+}
+
+#[derive(Debug)]
+struct Table {
+    name: String,
+    comment: Option<String>,
+    columns: Vec<Column>,
+}
+
+fn parse(ddl: &str, dialect: Dialect) -> Result<Vec<Table>, Error> {
+    let statements = match dialect {
+        Dialect::MySql => Parser::parse_sql(&MySqlDialect {}, ddl)?,
+        Dialect::PostgreSql => Parser::parse_sql(&PostgreSqlDialect {}, ddl)?,
+        Dialect::SQLite => Parser::parse_sql(&SQLiteDialect {}, ddl)?,
+    };
+    let mut tables = Vec::new();
+    for statement in statements {
+        match statement {
+            Statement::CreateTable(create) => tables.push(table(create, dialect)),
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                ..
+            } => apply_comment(&mut tables, object_type, &object_name, comment),
+            _ => {}
+        }
+    }
+    Ok(tables)
+}
+
+/// 名称的最后一段，去除 schema 前缀
+fn last_name(name: &ObjectName) -> String {
+    name.0
+        .last()
+        .map(|ident| ident.value.clone())
+        .unwrap_or_default()
+}
+
+fn table(create: CreateTable, dialect: Dialect) -> Table {
+    let primary_keys = create
+        .constraints
+        .iter()
+        .flat_map(|constraint| match constraint {
+            TableConstraint::PrimaryKey { columns, .. } => columns.clone(),
+            _ => Vec::new(),
+        })
+        .map(|ident| ident.value.to_lowercase())
+        .collect::<BTreeSet<_>>();
+
+    let columns = create
+        .columns
+        .into_iter()
+        .map(|definition| {
+            let name = definition.name.value;
+            let type_name = definition.data_type.to_string();
+            let mut column = Column {
+                kind: kind(&type_name, dialect),
+                length: length(&type_name),
+                nullable: true,
+                primary_key: primary_keys.contains(&name.to_lowercase()),
+                // PostgreSQL 的 serial 类型自增
+                auto_increment: type_name.to_lowercase().ends_with("serial"),
+                comment: None,
+                name,
+            };
+            for option in definition.options {
+                match option.option {
+                    ColumnOption::NotNull => column.nullable = false,
+                    ColumnOption::Unique {
+                        is_primary: true, ..
+                    } => column.primary_key = true,
+                    ColumnOption::Comment(comment) => column.comment = Some(comment),
+                    ColumnOption::DialectSpecific(tokens) => {
+                        let keyword = tokens
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<String>()
+                            .to_uppercase();
+                        if keyword == "AUTO_INCREMENT" || keyword == "AUTOINCREMENT" {
+                            column.auto_increment = true;
+                        }
+                    }
+                    // 没有表达式的 GENERATED 为标识列
+                    ColumnOption::Generated {
+                        generation_expr: None,
+                        ..
+                    }
+                    | ColumnOption::Identity(_) => column.auto_increment = true,
+                    _ => {}
+                }
+            }
+            if column.primary_key || column.auto_increment {
+                column.nullable = false;
+            }
+            column
+        })
+        .collect();
+
+    Table {
+        name: last_name(&create.name),
+        comment: create.comment.map(|comment| match comment {
+            CommentDef::WithEq(comment)
+            | CommentDef::WithoutEq(comment)
+            | CommentDef::AfterColumnDefsWithoutEq(comment) => comment,
+        }),
+        columns,
+    }
+}
+
+/// `COMMENT ON TABLE t IS '...'` 和 `COMMENT ON COLUMN t.c IS '...'`
+fn apply_comment(
+    tables: &mut [Table],
+    object_type: CommentObject,
+    object_name: &ObjectName,
+    comment: Option<String>,
+) {
+    let names = object_name
+        .0
+        .iter()
+        .map(|ident| ident.value.as_str())
+        .collect::<Vec<_>>();
+    let (table, column) = match (object_type, names.as_slice()) {
+        (CommentObject::Table, [.., table]) => (*table, None),
+        (CommentObject::Column, [.., table, column]) => (*table, Some(*column)),
+        _ => return,
+    };
+    let Some(table) = tables
+        .iter_mut()
+        .rev()
+        .find(|t| t.name.eq_ignore_ascii_case(table))
+    else {
+        return;
+    };
+    match column {
+        None => table.comment = comment,
+        Some(column) => {
+            if let Some(column) = table
+                .columns
+                .iter_mut()
+                .find(|c| c.name.eq_ignore_ascii_case(column))
+            {
+                column.comment = comment;
+            }
+        }
+    }
+}
+
+///
+/// 根据类型名称推断列类型，未知的类型按字符串处理
+///
+fn kind(type_name: &str, dialect: Dialect) -> Kind {
+    let lower = type_name.to_lowercase();
+    if let Some(element) = lower.strip_suffix("[]") {
+        return Kind::Array(Box::new(kind(element, dialect)));
+    }
+    let unsigned = lower.contains("unsigned");
+    // 只去掉括号中的参数，`timestamp(6) with time zone` 的后半部分仍然有效
+    let base = match lower.split_once('(') {
+        Some((head, rest)) => match rest.split_once(')') {
+            Some((_, tail)) => format!("{} {}", head, tail),
+            None => head.to_string(),
+        },
+        None => lower.clone(),
+    };
+    let base = base
+        .split_whitespace()
+        .filter(|word| *word != "unsigned")
+        .collect::<Vec<_>>()
+        .join(" ");
+    let integer = |bits: u8| Kind::Integer { bits, unsigned };
+    match base.as_str() {
+        "bool" | "boolean" => Kind::Bool,
+        // MySQL 习惯用 tinyint(1) 和 bit(1) 表示布尔值
+        "tinyint" | "bit" if length(type_name) == Some(1) => Kind::Bool,
+        "bit" => Kind::Bytes,
+        "tinyint" | "int1" => integer(8),
+        "smallint" | "int2" | "smallserial" => integer(16),
+        // SQLite 的 integer 为 64 位
+        "integer" if matches!(dialect, Dialect::SQLite) => integer(64),
+        "mediumint" | "int" | "integer" | "int4" | "serial" => integer(32),
+        "bigint" | "int8" | "bigserial" => integer(64),
+        "real" if matches!(dialect, Dialect::SQLite) => Kind::Double,
+        "real" | "float4" => Kind::Float,
+        "float" if matches!(dialect, Dialect::MySql) => Kind::Float,
+        "float" | "float8" | "double" | "double precision" => Kind::Double,
+        "decimal" | "numeric" | "dec" | "money" => Kind::Decimal,
+        "uuid" => Kind::Uuid,
+        "date" => Kind::Date,
+        "timestamptz" | "timestamp with time zone" => Kind::DateTimeTz,
+        "datetime" | "timestamp" | "timestamp without time zone" => Kind::DateTime,
+        "time" | "timetz" | "time with time zone" | "time without time zone" => Kind::Time,
+        "json" | "jsonb" => Kind::Json,
+        "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" | "bytea" => {
+            Kind::Bytes
+        }
+        _ => Kind::String,
+    }
+}
+
+/// 类型参数中的第一个数字，如 `varchar(64)` 中的 64
+fn length(type_name: &str) -> Option<u64> {
+    let (_, rest) = type_name.split_once('(')?;
+    rest.split([',', ')']).next()?.trim().parse().ok()
+}
+
+/// 将名称拆分为小写的单词，支持下划线、连字符和驼峰形式
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    let name = words(name)
+        .iter()
+        .map(|word| capitalize(word))
+        .collect::<String>();
+    match name.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("T{}", name),
+        false => name,
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let name = words(name).join("_");
+    match name.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("_{}", name),
+        false => name,
+    }
+}
+
+/// 注释的每一行，去除首尾空行
+fn comment_lines(comment: &Option<String>) -> Vec<&str> {
+    comment
+        .as_deref()
+        .map(|comment| comment.trim().lines().map(str::trim_end).collect())
+        .unwrap_or_default()
+}
+
+fn rust_type(kind: &Kind) -> String {
+    match kind {
+        Kind::Bool => "bool".to_string(),
+        Kind::Integer { bits, unsigned } => {
+            format!("{}{}", if *unsigned { "u" } else { "i" }, bits)
+        }
+        Kind::Float => "f32".to_string(),
+        Kind::Double => "f64".to_string(),
+        Kind::Decimal => "rust_decimal::Decimal".to_string(),
+        Kind::String => "String".to_string(),
+        Kind::Uuid => "uuid::Uuid".to_string(),
+        Kind::Date => "chrono::NaiveDate".to_string(),
+        Kind::Time => "chrono::NaiveTime".to_string(),
+        Kind::DateTime => "chrono::NaiveDateTime".to_string(),
+        Kind::DateTimeTz => "chrono::DateTime<chrono::Utc>".to_string(),
+        Kind::Json => "serde_json::Value".to_string(),
+        Kind::Bytes => "Vec<u8>".to_string(),
+        Kind::Array(element) => format!("Vec<{}>", rust_type(element)),
+    }
+}
+
+fn to_rust(tables: &[Table]) -> String {
+    let mut output = String::from("use serde::{Deserialize, Serialize};\n");
+    for table in tables {
+        output.push('\n');
+        for line in comment_lines(&table.comment) {
+            match line.is_empty() {
+                true => output.push_str("///\n"),
+                false => output.push_str(&format!("/// {}\n", line)),
+            }
+        }
+        output.push_str("#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]\n");
+        output.push_str(&format!("pub struct {} {{\n", pascal_case(&table.name)));
+        for column in &table.columns {
+            let mut doc = comment_lines(&column.comment);
+            if column.primary_key {
+                if !doc.is_empty() {
+                    doc.push("");
+                }
+                doc.push("Primary key");
+            }
+            for line in doc {
+                match line.is_empty() {
+                    true => output.push_str("    ///\n"),
+                    false => output.push_str(&format!("    /// {}\n", line)),
+                }
+            }
+            let mut field = snake_case(&column.name);
+            if RESERVED_KEYWORDS.contains(&field.as_str()) {
+                field.push('_');
+            }
+            if field != column.name {
+                output.push_str(&format!("    #[serde(rename = \"{}\")]\n", column.name));
+                output.push_str(&format!("    #[sqlx(rename = \"{}\")]\n", column.name));
+            }
+            let field = match RUST_KEYWORDS.contains(&field.as_str()) {
+                true => format!("r#{}", field),
+                false => field,
+            };
+            let kind = match column.nullable {
+                true => format!("Option<{}>", rust_type(&column.kind)),
+                false => rust_type(&column.kind),
+            };
+            output.push_str(&format!("    pub {}: {},\n", field, kind));
+        }
+        output.push_str("}\n");
+    }
+    output
+}
+
+/// Java 类型和需要导入的类
+fn java_type(kind: &Kind) -> (String, Option<&'static str>) {
+    let simple = |name: &str| (name.to_string(), None);
+    match kind {
+        Kind::Bool => simple("Boolean"),
+        Kind::Integer { bits: 8, .. } => simple("Short"),
+        // 无符号类型需要更宽的类型才能容纳
+        Kind::Integer { bits: 16, unsigned } => simple(if *unsigned { "Integer" } else { "Short" }),
+        Kind::Integer { bits: 32, unsigned } => simple(if *unsigned { "Long" } else { "Integer" }),
+        Kind::Integer { unsigned: true, .. } => {
+            ("BigInteger".to_string(), Some("java.math.BigInteger"))
+        }
+        Kind::Integer { .. } => simple("Long"),
+        Kind::Float => simple("Float"),
+        Kind::Double => simple("Double"),
+        Kind::Decimal => ("BigDecimal".to_string(), Some("java.math.BigDecimal")),
+        Kind::String | Kind::Json => simple("String"),
+        Kind::Uuid => ("UUID".to_string(), Some("java.util.UUID")),
+        Kind::Date => ("LocalDate".to_string(), Some("java.time.LocalDate")),
+        Kind::Time => ("LocalTime".to_string(), Some("java.time.LocalTime")),
+        Kind::DateTime => ("LocalDateTime".to_string(), Some("java.time.LocalDateTime")),
+        Kind::DateTimeTz => (
+            "OffsetDateTime".to_string(),
+            Some("java.time.OffsetDateTime"),
+        ),
+        Kind::Bytes => simple("byte[]"),
+        Kind::Array(element) => (
+            format!("List<{}>", java_type(element).0),
+            Some("java.util.List"),
+        ),
+    }
+}
+
+/// 数组元素类型也需要导入
+fn java_imports(kind: &Kind, imports: &mut BTreeSet<&'static str>) {
+    if let Some(import) = java_type(kind).1 {
+        imports.insert(import);
+    }
+    if let Kind::Array(element) = kind {
+        java_imports(element, imports);
+    }
+}
+
+/// Javadoc 注释，缩进为 `indent`
+fn javadoc(lines: &[&str], indent: &str) -> String {
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut doc = format!("{}/**\n", indent);
+    for line in lines {
+        let line = line.replace("*/", "*&#47;");
+        match line.is_empty() {
+            true => doc.push_str(&format!("{} *\n", indent)),
+            false => doc.push_str(&format!("{} * {}\n", indent, line)),
+        }
+    }
+    doc.push_str(&format!("{} */\n", indent));
+    doc
+}
+
+fn to_java(tables: &[Table]) -> String {
+    let mut imports = BTreeSet::from([
+        "jakarta.persistence.Column",
+        "jakarta.persistence.Entity",
+        "jakarta.persistence.Table",
+    ]);
+    for column in tables.iter().flat_map(|table| &table.columns) {
+        java_imports(&column.kind, &mut imports);
+        if column.primary_key {
+            imports.insert("jakarta.persistence.Id");
+        }
+        if column.auto_increment {
+            imports.insert("jakarta.persistence.GeneratedValue");
+            imports.insert("jakarta.persistence.GenerationType");
+        }
+    }
+    let mut output = imports
+        .iter()
+        .map(|import| format!("import {};\n", import))
+        .collect::<String>();
+
+    for table in tables {
+        output.push('\n');
+        output.push_str(&javadoc(&comment_lines(&table.comment), ""));
+        output.push_str("@Entity\n");
+        output.push_str(&format!("@Table(name = \"{}\")\n", table.name));
+        output.push_str(&format!("public class {} {{\n", pascal_case(&table.name)));
+
+        let mut accessors = String::new();
+        for column in &table.columns {
+            let field = camel_case(&column.name);
+            let (kind, _) = java_type(&column.kind);
+            output.push('\n');
+            output.push_str(&javadoc(&comment_lines(&column.comment), "    "));
+            if column.primary_key {
+                output.push_str("    @Id\n");
+            }
+            if column.auto_increment {
+                output.push_str("    @GeneratedValue(strategy = GenerationType.IDENTITY)\n");
+            }
+            let mut attributes = vec![format!("name = \"{}\"", column.name)];
+            if !column.nullable {
+                attributes.push("nullable = false".to_string());
+            }
+            if let (Kind::String, Some(length)) = (&column.kind, column.length) {
+                attributes.push(format!("length = {}", length));
+            }
+            output.push_str(&format!("    @Column({})\n", attributes.join(", ")));
+            output.push_str(&format!("    private {} {};\n", kind, field));
+
+            let accessor = capitalize(&field);
+            accessors.push_str(&format!(
+                "\n    public {kind} get{accessor}() {{\n        return {field};\n    }}\n"
+            ));
+            accessors.push_str(&format!(
+                "\n    public void set{accessor}({kind} {field}) {{\n        this.{field} = {field};\n    }}\n"
+            ));
+        }
+        output.push_str(&accessors);
+        output.push_str("}\n");
+    }
+    output
+}
+
+fn typescript_type(kind: &Kind) -> String {
+    match kind {
+        Kind::Bool => "boolean".to_string(),
+        Kind::Integer { .. } | Kind::Float | Kind::Double => "number".to_string(),
+        // 小数和日期时间在 JSON 中通常为字符串
+        Kind::Decimal
+        | Kind::String
+        | Kind::Uuid
+        | Kind::Date
+        | Kind::Time
+        | Kind::DateTime
+        | Kind::DateTimeTz
+        | Kind::Bytes => "string".to_string(),
+        Kind::Json => "unknown".to_string(),
+        Kind::Array(element) => match element.as_ref() {
+            Kind::Array(_) => format!("Array<{}>", typescript_type(element)),
+            element => format!("{}[]", typescript_type(element)),
+        },
+    }
+}
+
+fn to_typescript(tables: &[Table]) -> String {
+    let mut output = String::new();
+    for table in tables {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&javadoc(&comment_lines(&table.comment), ""));
+        output.push_str(&format!(
+            "export interface {} {{\n",
+            pascal_case(&table.name)
+        ));
+        for column in &table.columns {
+            let mut doc = comment_lines(&column.comment);
+            if column.primary_key {
+                doc.push("@primaryKey");
+            }
+            output.push_str(&javadoc(&doc, "  "));
+            let identifier = column
+                .name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+                && column
+                    .name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+            let name = match identifier {
+                true => column.name.clone(),
+                false => format!("\"{}\"", column.name.replace('"', "\\\"")),
+            };
+            let kind = match column.nullable {
+                true => format!("{} | null", typescript_type(&column.kind)),
+                false => typescript_type(&column.kind),
+            };
+            output.push_str(&format!("  {}: {};\n", name, kind));
+        }
+        output.push_str("}\n");
+    }
+    output
+}
+
+fn json_schema_type(kind: &Kind) -> Map<String, Value> {
+    let mut schema = Map::new();
+    let (kind_name, format) = match kind {
+        Kind::Bool => ("boolean", None),
+        Kind::Integer { .. } => ("integer", None),
+        Kind::Float | Kind::Double | Kind::Decimal => ("number", None),
+        Kind::String => ("string", None),
+        Kind::Uuid => ("string", Some("uuid")),
+        Kind::Date => ("string", Some("date")),
+        Kind::Time => ("string", Some("time")),
+        Kind::DateTime | Kind::DateTimeTz => ("string", Some("date-time")),
+        Kind::Bytes => {
+            schema.insert("type".to_string(), json!("string"));
+            schema.insert("contentEncoding".to_string(), json!("base64"));
+            return schema;
+        }
+        // 任意 JSON 值
+        Kind::Json => return schema,
+        Kind::Array(element) => {
+            schema.insert("type".to_string(), json!("array"));
+            schema.insert(
+                "items".to_string(),
+                Value::Object(json_schema_type(element)),
+            );
+            return schema;
+        }
+    };
+    schema.insert("type".to_string(), json!(kind_name));
+    if let Some(format) = format {
+        schema.insert("format".to_string(), json!(format));
+    }
+    if let Kind::Integer { unsigned: true, .. } = kind {
+        schema.insert("minimum".to_string(), json!(0));
+    }
+    schema
+}
+
+fn table_schema(table: &Table) -> Value {
+    let mut properties = Map::new();
+    for column in &table.columns {
+        let mut schema = json_schema_type(&column.kind);
+        if column.nullable {
+            if let Some(kind) = schema.get_mut("type") {
+                *kind = json!([kind.take(), "null"]);
+            }
+        }
+        if let (Kind::String, Some(length)) = (&column.kind, column.length) {
+            schema.insert("maxLength".to_string(), json!(length));
+        }
+        if let Some(comment) = &column.comment {
+            schema.insert("description".to_string(), json!(comment));
+        }
+        properties.insert(column.name.clone(), Value::Object(schema));
+    }
+
+    let mut schema = Map::new();
+    schema.insert("title".to_string(), json!(table.name));
+    if let Some(comment) = &table.comment {
+        schema.insert("description".to_string(), json!(comment));
+    }
+    schema.insert("type".to_string(), json!("object"));
+    schema.insert("properties".to_string(), Value::Object(properties));
+    let required = table
+        .columns
+        .iter()
+        .filter(|column| !column.nullable)
+        .map(|column| json!(column.name))
+        .collect::<Vec<_>>();
+    if !required.is_empty() {
+        schema.insert("required".to_string(), Value::Array(required));
+    }
+    // JSON Schema 没有主键的概念，使用扩展关键字记录
+    let primary_key = table
+        .columns
+        .iter()
+        .filter(|column| column.primary_key)
+        .map(|column| json!(column.name))
+        .collect::<Vec<_>>();
+    if !primary_key.is_empty() {
+        schema.insert("x-primary-key".to_string(), Value::Array(primary_key));
+    }
+    Value::Object(schema)
+}
+
+/// 多个表时放在 `$defs` 中
+fn to_json_schema(tables: &[Table]) -> Value {
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    match tables {
+        [table] => {
+            if let Value::Object(table) = table_schema(table) {
+                schema.extend(table);
+            }
+        }
+        tables => {
+            let definitions = tables
+                .iter()
+                .map(|table| (pascal_case(&table.name), table_schema(table)))
+                .collect();
+            schema.insert("$defs".to_string(), Value::Object(definitions));
+        }
+    }
+    Value::Object(schema)
+}
+
+command_error! {
+    (Parse, "sql error: {0}", #[from] sqlparser::parser::ParserError),
+    (Json, "json error: {0}", #[from] serde_json::Error),
+    (NoTable, "no CREATE TABLE statement found"),
+}
@@ -0,0 +1,450 @@
+use crate::command_error;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageEncoder, ImageError, ImageFormat, ImageReader};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+
+/// 默认的 JPEG 质量
+const JPEG_QUALITY: u8 = 80;
+
+/// 需要随输出图片更新的 EXIF 标签
+const TAG_IMAGE_WIDTH: u16 = 0x0100;
+const TAG_IMAGE_LENGTH: u16 = 0x0101;
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_PIXEL_X_DIMENSION: u16 = 0xA002;
+const TAG_PIXEL_Y_DIMENSION: u16 = 0xA003;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Format {
+    Png,
+    Jpeg,
+    WebP,
+    Gif,
+    Bmp,
+    Ico,
+    Tiff,
+}
+
+///
+/// 同时指定宽高时的缩放方式
+///
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Fit {
+    /// 保持比例缩放到宽高以内
+    Contain,
+    /// 保持比例填满宽高，超出的部分居中裁掉
+    Cover,
+    /// 拉伸到指定的宽高
+    Stretch,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Filter {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    Lanczos3,
+}
+
+///
+/// 缩放参数，只指定一边时按原图比例计算另一边
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resize {
+    width: Option<u32>,
+    height: Option<u32>,
+    fit: Fit,
+    filter: Option<Filter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Crop {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageInfo {
+    format: Option<Format>,
+    width: u32,
+    height: u32,
+    /// 解码前的颜色类型，例如 Rgba8、L16
+    color: String,
+    bits: u16,
+    alpha: bool,
+    /// 文件的字节数
+    size: u64,
+    orientation: String,
+    exif: Vec<ExifField>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExifField {
+    /// 所在的 IFD，主图为 primary，缩略图为 thumbnail
+    ifd: String,
+    tag: String,
+    value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Converted {
+    base64: String,
+    extension: String,
+    width: u32,
+    height: u32,
+    /// 转换后的字节数
+    size: usize,
+}
+
+impl Format {
+    fn image_format(self) -> ImageFormat {
+        match self {
+            Format::Png => ImageFormat::Png,
+            Format::Jpeg => ImageFormat::Jpeg,
+            Format::WebP => ImageFormat::WebP,
+            Format::Gif => ImageFormat::Gif,
+            Format::Bmp => ImageFormat::Bmp,
+            Format::Ico => ImageFormat::Ico,
+            Format::Tiff => ImageFormat::Tiff,
+        }
+    }
+
+    fn from_image_format(format: ImageFormat) -> Option<Format> {
+        [
+            Format::Png,
+            Format::Jpeg,
+            Format::WebP,
+            Format::Gif,
+            Format::Bmp,
+            Format::Ico,
+            Format::Tiff,
+        ]
+        .into_iter()
+        .find(|value| value.image_format() == format)
+    }
+}
+
+impl From<Filter> for FilterType {
+    fn from(value: Filter) -> Self {
+        match value {
+            Filter::Nearest => FilterType::Nearest,
+            Filter::Triangle => FilterType::Triangle,
+            Filter::CatmullRom => FilterType::CatmullRom,
+            Filter::Gaussian => FilterType::Gaussian,
+            Filter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+///
+/// 查看图片的尺寸、格式、颜色类型和 EXIF 信息
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn inspect_image(image: &str) -> Result<ImageInfo, Error> {
+    let size = std::fs::metadata(image)?.len();
+    let reader = ImageReader::open(image)?.with_guessed_format()?;
+    let format = reader.format().and_then(Format::from_image_format);
+    let mut decoder = reader.into_decoder()?;
+    let (width, height) = decoder.dimensions();
+    let color = decoder.original_color_type();
+    let alpha = decoder.color_type().has_alpha();
+    let orientation = decoder.orientation()?;
+    let exif = decoder.exif_metadata()?.map(parse_exif).unwrap_or_default();
+    Ok(ImageInfo {
+        format,
+        width,
+        height,
+        color: format!("{color:?}"),
+        bits: color.bits_per_pixel(),
+        alpha,
+        size,
+        orientation: format!("{orientation:?}"),
+        exif,
+    })
+}
+
+///
+/// 转换图片格式，依次裁剪、缩放后按目标格式编码，返回 Base64 预览
+///
+/// `quality` 只对 JPEG 有效，WebP 使用无损编码；图片总是先按 EXIF 的方向旋转，
+/// 保留元数据时 EXIF 会写入 PNG、JPEG 和 WebP，其中的方向和像素尺寸会改为输出图片的值，
+/// 指定 `output` 时同时写入该文件
+///
+#[tauri::command(async)]
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn convert_image(
+    image: &str,
+    format: Format,
+    crop: Option<Crop>,
+    resize: Option<Resize>,
+    quality: Option<u8>,
+    strip: bool,
+    output: Option<String>,
+) -> Result<Converted, Error> {
+    let mut decoder = ImageReader::open(image)?
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let exif = decoder.exif_metadata()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    // 裁剪区域按旋转后的图片计算
+    image.apply_orientation(orientation);
+
+    let keep = !strip && matches!(format, Format::Png | Format::Jpeg | Format::WebP);
+    let exif = exif.filter(|_| keep);
+
+    if let Some(crop) = crop {
+        if crop.width == 0
+            || crop.height == 0
+            || crop.x.saturating_add(crop.width) > image.width()
+            || crop.y.saturating_add(crop.height) > image.height()
+        {
+            return Err(Error::Crop(image.width(), image.height()));
+        }
+        image = image.crop_imm(crop.x, crop.y, crop.width, crop.height);
+    }
+    if let Some(resize) = resize {
+        image = scale(image, resize)?;
+    }
+
+    let exif = exif.and_then(|exif| patch_exif(exif, image.width(), image.height()));
+    let bytes = encode(&image, format, quality, exif)?;
+    if let Some(output) = output {
+        std::fs::write(output, &bytes)?;
+    }
+    Ok(Converted {
+        base64: BASE64_STANDARD.encode(&bytes),
+        extension: format.image_format().extensions_str()[0].to_string(),
+        width: image.width(),
+        height: image.height(),
+        size: bytes.len(),
+    })
+}
+
+fn scale(image: DynamicImage, resize: Resize) -> Result<DynamicImage, Error> {
+    let filter = resize.filter.unwrap_or(Filter::Lanczos3).into();
+    let ratio = image.width() as f64 / image.height() as f64;
+    let (width, height) = match (resize.width, resize.height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, (width as f64 / ratio).round() as u32),
+        (None, Some(height)) => ((height as f64 * ratio).round() as u32, height),
+        (None, None) => return Ok(image),
+    };
+    if width == 0 || height == 0 {
+        return Err(Error::Size(width, height));
+    }
+    let image = match (resize.width.and(resize.height), resize.fit) {
+        // 只指定一边时已经按比例算出另一边
+        (None, _) | (_, Fit::Stretch) => image.resize_exact(width, height, filter),
+        (_, Fit::Contain) => image.resize(width, height, filter),
+        (_, Fit::Cover) => image.resize_to_fill(width, height, filter),
+    };
+    Ok(image)
+}
+
+fn encode(
+    image: &DynamicImage,
+    format: Format,
+    quality: Option<u8>,
+    exif: Option<Vec<u8>>,
+) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    match format {
+        Format::Png => {
+            let mut encoder = PngEncoder::new(&mut bytes);
+            if let Some(exif) = exif {
+                encoder.set_exif_metadata(exif)?;
+            }
+            image.write_with_encoder(encoder)?;
+        }
+        Format::Jpeg => {
+            let quality = quality.unwrap_or(JPEG_QUALITY).clamp(1, 100);
+            let mut encoder = JpegEncoder::new_with_quality(&mut bytes, quality);
+            if let Some(exif) = exif {
+                encoder.set_exif_metadata(exif)?;
+            }
+            // JPEG 不支持透明通道
+            DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)?;
+        }
+        Format::WebP => {
+            let mut encoder = WebPEncoder::new_lossless(&mut bytes);
+            if let Some(exif) = exif {
+                encoder.set_exif_metadata(exif)?;
+            }
+            image.write_with_encoder(encoder)?;
+        }
+        Format::Ico if image.width() > 256 || image.height() > 256 => {
+            return Err(Error::Icon(image.width(), image.height()));
+        }
+        format => image.write_to(&mut Cursor::new(&mut bytes), format.image_format())?,
+    }
+    Ok(bytes)
+}
+
+///
+/// 方向已经应用到像素上，将 EXIF 中的方向改为 1，像素尺寸改为输出图片的尺寸，
+/// 无法解析时丢弃 EXIF，避免图片被再次旋转
+///
+fn patch_exif(mut raw: Vec<u8>, width: u32, height: u32) -> Option<Vec<u8>> {
+    // WebP 中的 EXIF 有时带有 JPEG APP1 段的前缀
+    let start = match raw.starts_with(b"Exif\0\0") {
+        true => 6,
+        false => 0,
+    };
+    let patched = Tiff::new(&mut raw[start..]).and_then(|mut tiff| {
+        let ifd = tiff.u32(4)? as usize;
+        let mut exif_ifd = None;
+        for entry in tiff.entries(ifd)? {
+            match tiff.u16(entry)? {
+                TAG_ORIENTATION => tiff.set_short(entry, 1)?,
+                TAG_IMAGE_WIDTH => tiff.set_long(entry, width)?,
+                TAG_IMAGE_LENGTH => tiff.set_long(entry, height)?,
+                TAG_EXIF_IFD => exif_ifd = Some(tiff.u32(entry + 8)? as usize),
+                _ => {}
+            }
+        }
+        if let Some(ifd) = exif_ifd {
+            for entry in tiff.entries(ifd)? {
+                match tiff.u16(entry)? {
+                    TAG_PIXEL_X_DIMENSION => tiff.set_long(entry, width)?,
+                    TAG_PIXEL_Y_DIMENSION => tiff.set_long(entry, height)?,
+                    _ => {}
+                }
+            }
+        }
+        Some(())
+    });
+    match patched {
+        Some(()) => Some(raw),
+        None => {
+            tracing::warn!("patch exif error: invalid tiff structure");
+            None
+        }
+    }
+}
+
+///
+/// EXIF 的 TIFF 结构，只支持原地修改单个值的条目
+///
+struct Tiff<'a> {
+    data: &'a mut [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a mut [u8]) -> Option<Self> {
+        let little_endian = match data.get(..4)? {
+            b"II*\0" => true,
+            b"MM\0*" => false,
+            _ => return None,
+        };
+        Some(Self {
+            data,
+            little_endian,
+        })
+    }
+
+    fn u16(&self, pos: usize) -> Option<u16> {
+        let bytes = self.data.get(pos..pos + 2)?.try_into().ok()?;
+        Some(match self.little_endian {
+            true => u16::from_le_bytes(bytes),
+            false => u16::from_be_bytes(bytes),
+        })
+    }
+
+    fn u32(&self, pos: usize) -> Option<u32> {
+        let bytes = self.data.get(pos..pos + 4)?.try_into().ok()?;
+        Some(match self.little_endian {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn write(&mut self, pos: usize, bytes: &[u8]) -> Option<()> {
+        self.data
+            .get_mut(pos..pos + bytes.len())?
+            .copy_from_slice(bytes);
+        Some(())
+    }
+
+    fn write_u16(&mut self, pos: usize, value: u16) -> Option<()> {
+        match self.little_endian {
+            true => self.write(pos, &value.to_le_bytes()),
+            false => self.write(pos, &value.to_be_bytes()),
+        }
+    }
+
+    fn write_u32(&mut self, pos: usize, value: u32) -> Option<()> {
+        match self.little_endian {
+            true => self.write(pos, &value.to_le_bytes()),
+            false => self.write(pos, &value.to_be_bytes()),
+        }
+    }
+
+    /// IFD 中每个 12 字节条目的位置
+    fn entries(&self, ifd: usize) -> Option<Vec<usize>> {
+        let count = self.u16(ifd)? as usize;
+        let end = ifd + 2 + count * 12;
+        self.data.get(ifd..end)?;
+        Some((0..count).map(|index| ifd + 2 + index * 12).collect())
+    }
+
+    /// 将条目改为一个 SHORT 值
+    fn set_short(&mut self, entry: usize, value: u16) -> Option<()> {
+        self.write_u16(entry + 2, 3)?;
+        self.write_u32(entry + 4, 1)?;
+        self.write_u16(entry + 8, value)?;
+        self.write_u16(entry + 10, 0)
+    }
+
+    /// 将条目改为一个 LONG 值，原来的 SHORT 可能放不下新的尺寸
+    fn set_long(&mut self, entry: usize, value: u32) -> Option<()> {
+        self.write_u16(entry + 2, 4)?;
+        self.write_u32(entry + 4, 1)?;
+        self.write_u32(entry + 8, value)
+    }
+}
+
+///
+/// 解析 EXIF，无法解析时只记录日志，不影响查看其他信息
+///
+fn parse_exif(raw: Vec<u8>) -> Vec<ExifField> {
+    // WebP 中的 EXIF 有时带有 JPEG APP1 段的前缀
+    let raw = match raw.strip_prefix(b"Exif\0\0") {
+        Some(raw) => raw.to_vec(),
+        None => raw,
+    };
+    let exif = match exif::Reader::new().read_raw(raw) {
+        Ok(exif) => exif,
+        Err(error) => {
+            tracing::warn!("parse exif error: {error}");
+            return Vec::new();
+        }
+    };
+    exif.fields()
+        .map(|field| ExifField {
+            ifd: field.ifd_num.to_string(),
+            tag: field.tag.to_string(),
+            value: field.display_value().with_unit(&exif).to_string(),
+        })
+        .collect()
+}
+
+command_error! {
+    (Io, "io error: {0}", #[from] std::io::Error),
+    (Image, "image error: {0}", #[from] ImageError),
+    (Unsupported, "unsupported metadata: {0}", #[from] image::error::UnsupportedError),
+    (Crop, "crop area is outside the image of {0}x{1}", u32, u32),
+    (Size, "invalid target size {0}x{1}", u32, u32),
+    (Icon, "ico images can not be larger than 256x256, got {0}x{1}", u32, u32),
+}
@@ -1,9 +1,18 @@
+use super::yaml;
 use crate::command_error;
 
+///
+/// JSON 转 YAML，`tags` 为 `true` 时把 `{"!Tag": value}` 形式的映射还原为标签
+///
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn convert_json_to_yaml(json: &str) -> Result<String, Error> {
+pub fn convert_json_to_yaml(json: &str, tags: bool) -> Result<String, Error> {
     let value = serde_json::from_str::<serde_json::Value>(json)?;
+    let value = serde_yaml::to_value(value)?;
+    let value = match tags {
+        true => yaml::restore_tags(value),
+        false => value,
+    };
     serde_yaml::to_string(&value).map_err(Into::into)
 }
 
@@ -11,10 +20,21 @@ pub fn convert_json_to_yaml(json: &str) -> Result<String, Error> {
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
 pub fn convert_yaml_to_json(yaml: &str) -> Result<String, Error> {
     let value = serde_yaml::from_str::<serde_yaml::Value>(yaml)?;
+    let value = yaml::resolve(value)?;
     serde_json::to_string_pretty(&value).map_err(Into::into)
 }
 
+///
+/// 重新格式化 YAML，保留注释
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_yaml_to_yaml(yaml: &str) -> Result<String, Error> {
+    yaml::reformat(yaml).map_err(Into::into)
+}
+
 command_error! {
     (Json, "json error: {0}", #[from] serde_json::Error),
     (Yaml, "yaml error: {0}", #[from] serde_yaml::Error),
+    (Convert, "{0}", #[from] yaml::Error),
 }
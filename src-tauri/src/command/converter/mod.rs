@@ -1,3 +1,14 @@
+mod crontab;
+mod properties;
+mod yaml;
+
+pub mod color;
 pub mod cron;
+pub mod csv;
+pub mod ddl;
+pub mod image;
 pub mod json_yaml;
+pub mod number;
+pub mod time;
+pub mod xml_json;
 pub mod yaml_properties;
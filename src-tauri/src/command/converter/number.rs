@@ -0,0 +1,447 @@
+use crate::command_error;
+use crate::encoding::{Encoding, EncodingError};
+use num_bigint::BigInt;
+use num_traits::{Num, One, Signed};
+use serde::Serialize;
+
+/// 没有指定时输出的进制
+const DEFAULT_RADIXES: [u32; 4] = [2, 8, 10, 16];
+
+/// 补码视图的位宽
+const WIDTHS: [usize; 5] = [8, 16, 32, 64, 128];
+
+#[derive(Debug, Serialize)]
+pub struct Number {
+    /// 十进制的值
+    value: String,
+    radixes: Vec<Radix>,
+    widths: Vec<Width>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Radix {
+    radix: u32,
+    text: String,
+}
+
+///
+/// 按固定位宽截断后的补码视图
+///
+#[derive(Debug, Serialize)]
+pub struct Width {
+    bits: usize,
+    /// 值超出有符号和无符号的范围，下面的结果都是截断后的
+    overflow: bool,
+    signed: String,
+    unsigned: String,
+    binary: String,
+    hex: String,
+    big_endian: String,
+    little_endian: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Floats {
+    single: Ieee,
+    double: Ieee,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Category {
+    Zero,
+    Subnormal,
+    Normal,
+    Infinite,
+    Nan,
+}
+
+///
+/// IEEE-754 浮点数的各个部分
+///
+#[derive(Debug, Serialize)]
+pub struct Ieee {
+    value: String,
+    category: Category,
+    /// 十六进制的位模式
+    bits: String,
+    /// 符号、指数、尾数之间用空格分隔
+    binary: String,
+    sign: u8,
+    /// 指数字段的原始值
+    exponent: u32,
+    /// 减去偏移量后的指数，零、无穷和 NaN 没有指数
+    unbiased: Option<i32>,
+    /// 十六进制的尾数字段
+    mantissa: String,
+    big_endian: String,
+    little_endian: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Permission {
+    /// 例如 `0755`
+    octal: String,
+    /// 例如 `rwxr-xr-x`
+    symbolic: String,
+    /// `chmod` 的符号写法，例如 `u=rwx,g=rx,o=rx`
+    chmod: String,
+    owner: Access,
+    group: Access,
+    others: Access,
+    setuid: bool,
+    setgid: bool,
+    sticky: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Access {
+    read: bool,
+    write: bool,
+    execute: bool,
+}
+
+///
+/// 转换任意精度的整数，`radix` 为空时根据 `0x`、`0o`、`0b` 前缀识别，默认十进制
+///
+/// 结果按 `radixes` 中的每个进制输出，为空时输出二、八、十、十六进制；字节序列按 `encoding` 输出
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_number(
+    input: &str,
+    radix: Option<u32>,
+    radixes: Vec<u32>,
+    encoding: Encoding,
+) -> Result<Number, Error> {
+    let value = parse_integer(input, radix)?;
+    let radixes = match radixes.is_empty() {
+        true => DEFAULT_RADIXES.to_vec(),
+        false => radixes,
+    };
+    let radixes = radixes
+        .into_iter()
+        .map(|radix| {
+            check_radix(radix)?;
+            Ok(Radix {
+                radix,
+                text: value.to_str_radix(radix),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let widths = WIDTHS
+        .iter()
+        .map(|bits| width(&value, *bits, encoding))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Number {
+        value: value.to_string(),
+        radixes,
+        widths,
+    })
+}
+
+///
+/// 分解单精度和双精度浮点数，输入为十进制小数，或者 `0x` 开头的位模式：8 位以内按单精度，否则按双精度
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_float(input: &str, encoding: Encoding) -> Result<Floats, Error> {
+    let text = input.trim().replace('_', "");
+    let (single, double) = match strip_prefix(&text, 16) {
+        Some(hex) => {
+            let bits = u64::from_str_radix(hex, 16).map_err(|_| Error::Float(input.to_string()))?;
+            match hex.len() {
+                0 => return Err(Error::Float(input.to_string())),
+                1..=8 => {
+                    let single = f32::from_bits(bits as u32);
+                    (single, single as f64)
+                }
+                _ => {
+                    let double = f64::from_bits(bits);
+                    (double as f32, double)
+                }
+            }
+        }
+        None => {
+            let single = text.parse::<f32>();
+            let double = text.parse::<f64>();
+            match (single, double) {
+                (Ok(single), Ok(double)) => (single, double),
+                _ => return Err(Error::Float(input.to_string())),
+            }
+        }
+    };
+    Ok(Floats {
+        single: decompose(
+            single.to_bits() as u64,
+            8,
+            23,
+            single.to_string(),
+            &single.to_be_bytes(),
+            encoding,
+        )?,
+        double: decompose(
+            double.to_bits(),
+            11,
+            52,
+            double.to_string(),
+            &double.to_be_bytes(),
+            encoding,
+        )?,
+    })
+}
+
+///
+/// 转换 Unix 权限，输入为八进制（`755`、`0755`、`4755`）或符号形式（`rwxr-xr-x`、`-rwsr-xr-t`）
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_permission(input: &str) -> Result<Permission, Error> {
+    let text = input.trim();
+    let mode = match text.chars().count() {
+        9 | 10 if !text.chars().all(|c| c.is_ascii_digit()) => parse_symbolic(text),
+        _ => {
+            let digits = strip_prefix(text, 8).unwrap_or(text);
+            match digits.len() {
+                1..=4 => u32::from_str_radix(digits, 8).ok(),
+                _ => None,
+            }
+        }
+    }
+    .ok_or_else(|| Error::Permission(input.to_string()))?;
+    Ok(permission(mode))
+}
+
+fn check_radix(radix: u32) -> Result<(), Error> {
+    match radix {
+        2..=36 => Ok(()),
+        radix => Err(Error::Radix(radix)),
+    }
+}
+
+/// 去掉进制对应的前缀
+fn strip_prefix(text: &str, radix: u32) -> Option<&str> {
+    let prefix = match radix {
+        2 => "0b",
+        8 => "0o",
+        16 => "0x",
+        _ => return None,
+    };
+    match text.get(..2) {
+        Some(head) if head.eq_ignore_ascii_case(prefix) => Some(&text[2..]),
+        _ => None,
+    }
+}
+
+fn parse_integer(input: &str, radix: Option<u32>) -> Result<BigInt, Error> {
+    let text = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_' && *c != ',')
+        .collect::<String>();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(&text)),
+    };
+    let (radix, digits) = match radix {
+        Some(radix) => {
+            check_radix(radix)?;
+            (radix, strip_prefix(text, radix).unwrap_or(text))
+        }
+        None => [16, 8, 2]
+            .into_iter()
+            .find_map(|radix| strip_prefix(text, radix).map(|digits| (radix, digits)))
+            .unwrap_or((10, text)),
+    };
+    if digits.is_empty() {
+        return Err(Error::Input(input.to_string()));
+    }
+    let value = BigInt::from_str_radix(digits, radix)
+        .map_err(|_| Error::Digits(input.to_string(), radix))?;
+    Ok(if negative { -value } else { value })
+}
+
+fn width(value: &BigInt, bits: usize, encoding: Encoding) -> Result<Width, Error> {
+    let modulus = BigInt::one() << bits;
+    let half = BigInt::one() << (bits - 1);
+    let overflow = *value < -&half || *value >= modulus;
+    // 截断到 bits 位，负数取补码
+    let mut unsigned = value % &modulus;
+    if unsigned.is_negative() {
+        unsigned += &modulus;
+    }
+    let signed = match unsigned >= half {
+        true => &unsigned - &modulus,
+        false => unsigned.clone(),
+    };
+    let (_, magnitude) = unsigned.to_bytes_be();
+    let mut bytes = vec![0u8; bits / 8 - magnitude.len()];
+    bytes.extend(magnitude);
+    let mut little = bytes.clone();
+    little.reverse();
+    Ok(Width {
+        bits,
+        overflow,
+        signed: signed.to_string(),
+        unsigned: unsigned.to_string(),
+        binary: format!("{:0>bits$}", unsigned.to_str_radix(2)),
+        hex: format!("{:0>width$}", unsigned.to_str_radix(16), width = bits / 4),
+        big_endian: encoding.encode(&bytes)?,
+        little_endian: encoding.encode(&little)?,
+    })
+}
+
+fn decompose(
+    bits: u64,
+    exponent_bits: u32,
+    mantissa_bits: u32,
+    value: String,
+    bytes: &[u8],
+    encoding: Encoding,
+) -> Result<Ieee, Error> {
+    let mantissa = bits & ((1 << mantissa_bits) - 1);
+    let exponent = ((bits >> mantissa_bits) & ((1 << exponent_bits) - 1)) as u32;
+    let sign = (bits >> (mantissa_bits + exponent_bits)) as u8;
+    let max = (1 << exponent_bits) - 1;
+    let bias = (1 << (exponent_bits - 1)) - 1;
+    let category = match (exponent, mantissa) {
+        (0, 0) => Category::Zero,
+        (0, _) => Category::Subnormal,
+        (e, 0) if e == max => Category::Infinite,
+        (e, _) if e == max => Category::Nan,
+        _ => Category::Normal,
+    };
+    let unbiased = match category {
+        Category::Normal => Some(exponent as i32 - bias),
+        Category::Subnormal => Some(1 - bias),
+        Category::Zero | Category::Infinite | Category::Nan => None,
+    };
+    let mut little = bytes.to_vec();
+    little.reverse();
+    let total = (1 + exponent_bits + mantissa_bits) as usize;
+    Ok(Ieee {
+        value,
+        category,
+        bits: format!("{bits:0width$x}", width = total / 4),
+        binary: format!(
+            "{sign} {exponent:0e$b} {mantissa:0m$b}",
+            e = exponent_bits as usize,
+            m = mantissa_bits as usize
+        ),
+        sign,
+        exponent,
+        unbiased,
+        mantissa: format!("{mantissa:x}"),
+        big_endian: encoding.encode(bytes)?,
+        little_endian: encoding.encode(&little)?,
+    })
+}
+
+/// 解析 `rwxr-xr-x`，可以带文件类型前缀
+fn parse_symbolic(text: &str) -> Option<u32> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let chars = match chars.len() {
+        10 => &chars[1..],
+        _ => &chars[..],
+    };
+    if chars.len() != 9 {
+        return None;
+    }
+    let mut mode = 0;
+    for (class, triple) in chars.chunks(3).enumerate() {
+        let [read, write, execute] = *triple else {
+            return None;
+        };
+        let shift = 6 - class as u32 * 3;
+        let (special, upper, lower) = match class {
+            0 => (0o4000, 'S', 's'),
+            1 => (0o2000, 'S', 's'),
+            _ => (0o1000, 'T', 't'),
+        };
+        match read {
+            'r' => mode |= 0o4 << shift,
+            '-' => {}
+            _ => return None,
+        }
+        match write {
+            'w' => mode |= 0o2 << shift,
+            '-' => {}
+            _ => return None,
+        }
+        match execute {
+            'x' => mode |= 0o1 << shift,
+            '-' => {}
+            c if c == upper => mode |= special,
+            c if c == lower => mode |= special | (0o1 << shift),
+            _ => return None,
+        }
+    }
+    Some(mode)
+}
+
+fn permission(mode: u32) -> Permission {
+    let access = |shift: u32| Access {
+        read: mode & (0o4 << shift) != 0,
+        write: mode & (0o2 << shift) != 0,
+        execute: mode & (0o1 << shift) != 0,
+    };
+    let (owner, group, others) = (access(6), access(3), access(0));
+    let (setuid, setgid, sticky) = (mode & 0o4000 != 0, mode & 0o2000 != 0, mode & 0o1000 != 0);
+    let symbol = |access: Access, special: bool, lower: char, upper: char| {
+        let execute = match (access.execute, special) {
+            (true, true) => lower,
+            (false, true) => upper,
+            (true, false) => 'x',
+            (false, false) => '-',
+        };
+        format!(
+            "{}{}{execute}",
+            if access.read { 'r' } else { '-' },
+            if access.write { 'w' } else { '-' },
+        )
+    };
+    let symbolic = [
+        symbol(owner, setuid, 's', 'S'),
+        symbol(group, setgid, 's', 'S'),
+        symbol(others, sticky, 't', 'T'),
+    ]
+    .concat();
+    let clause = |name: char, access: Access, special: Option<char>| {
+        let mut text = format!("{name}=");
+        for (enabled, flag) in [
+            (access.read, 'r'),
+            (access.write, 'w'),
+            (access.execute, 'x'),
+        ] {
+            if enabled {
+                text.push(flag);
+            }
+        }
+        text.extend(special);
+        text
+    };
+    let chmod = [
+        clause('u', owner, setuid.then_some('s')),
+        clause('g', group, setgid.then_some('s')),
+        clause('o', others, sticky.then_some('t')),
+    ]
+    .join(",");
+    Permission {
+        octal: format!("{mode:04o}"),
+        symbolic,
+        chmod,
+        owner,
+        group,
+        others,
+        setuid,
+        setgid,
+        sticky,
+    }
+}
+
+command_error! {
+    (Input, "missing digits: {0}", String),
+    (Radix, "radix must be between 2 and 36, found {0}", u32),
+    (Digits, "invalid digits in '{0}' for radix {1}", String, u32),
+    (Float, "invalid floating-point number: {0}", String),
+    (Permission, "invalid permission: {0}", String),
+    (Encoding, "encoding error: {0}", #[from] EncodingError),
+}
@@ -0,0 +1,265 @@
+//!
+//! Java `.properties` 文件的读写实现，遵循 `java.util.Properties#load` 的语法规则。
+//!
+//! 读取时保留注释、空行以及键值对的原始顺序，写出时按照规范转义特殊字符。
+//!
+use crate::command_error;
+
+/// 文件中的一个逻辑行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    /// 注释行，包含 `#` 或 `!` 前缀
+    Comment(String),
+    /// 空行
+    Blank,
+    /// 键值对
+    Entry(String, String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Properties {
+    items: Vec<Item>,
+}
+
+impl Properties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    pub fn push_comment(&mut self, comment: impl Into<String>) {
+        self.items.push(Item::Comment(comment.into()));
+    }
+
+    pub fn push_blank(&mut self) {
+        self.items.push(Item::Blank);
+    }
+
+    pub fn push_entry(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.items.push(Item::Entry(key.into(), value.into()));
+    }
+
+    ///
+    /// 设置键的值，已存在的键保持原来的位置
+    ///
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        let value = value.into();
+        let entry = self.items.iter_mut().find_map(|item| match item {
+            Item::Entry(k, v) if *k == key => Some(v),
+            _ => None,
+        });
+        match entry {
+            Some(entry) => *entry = value,
+            None => self.items.push(Item::Entry(key, value)),
+        }
+    }
+
+    pub fn retain_entries(&mut self, mut f: impl FnMut(&str, &str) -> bool) {
+        self.items.retain(|item| match item {
+            Item::Entry(key, value) => f(key, value),
+            _ => true,
+        });
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.items.iter().filter_map(|item| match item {
+            Item::Entry(key, value) => Some((key.as_str(), value.as_str())),
+            _ => None,
+        })
+    }
+
+    ///
+    /// 解析 properties 文本
+    ///
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut properties = Properties::new();
+        let mut lines = natural_lines(input).enumerate();
+        while let Some((index, line)) = lines.next() {
+            let line = trim_start(line);
+            if line.is_empty() {
+                properties.push_blank();
+                continue;
+            }
+            // 注释行不支持续行
+            if line.starts_with(['#', '!']) {
+                properties.push_comment(line);
+                continue;
+            }
+
+            // 以奇数个反斜杠结尾的行与下一行拼接，下一行的前导空白会被忽略
+            let mut logical = line.to_string();
+            while ends_with_continuation(&logical) {
+                logical.pop();
+                match lines.next() {
+                    Some((_, next)) => logical.push_str(trim_start(next)),
+                    None => break,
+                }
+            }
+
+            let (key, value) = split_entry(&logical);
+            let key = unescape(key).ok_or(Error::MalformedUnicodeEscape(index + 1))?;
+            let value = unescape(value).ok_or(Error::MalformedUnicodeEscape(index + 1))?;
+            properties.push_entry(key, value);
+        }
+        Ok(properties)
+    }
+
+    ///
+    /// 序列化为 properties 文本
+    ///
+    pub fn write(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| match item {
+                Item::Comment(comment) => comment.clone(),
+                Item::Blank => String::new(),
+                Item::Entry(key, value) => {
+                    format!("{}={}", escape(key, true), escape(value, false))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 按照 `\n`、`\r` 或 `\r\n` 拆分自然行
+fn natural_lines(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(input);
+    std::iter::from_fn(move || {
+        let current = rest?;
+        match current.find(['\r', '\n']) {
+            Some(pos) => {
+                let next = match current[pos..].starts_with("\r\n") {
+                    true => pos + 2,
+                    false => pos + 1,
+                };
+                rest = Some(&current[next..]);
+                Some(&current[..pos])
+            }
+            None => {
+                rest = None;
+                (!current.is_empty()).then_some(current)
+            }
+        }
+    })
+}
+
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\x0c')
+}
+
+fn trim_start(line: &str) -> &str {
+    line.trim_start_matches(is_whitespace)
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+/// 键以第一个未转义的 `=`、`:` 或空白字符结束，分隔符前后的空白会被忽略
+fn split_entry(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    let mut key_end = line.len();
+    for (pos, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '=' || c == ':' || is_whitespace(c) {
+            key_end = pos;
+            break;
+        }
+    }
+    let key = &line[..key_end];
+    let mut rest = trim_start(&line[key_end..]);
+    if let Some(stripped) = rest.strip_prefix(['=', ':']) {
+        rest = trim_start(stripped);
+    }
+    (key, rest)
+}
+
+/// 处理转义字符，`\uXXXX` 格式错误时返回 `None`
+fn unescape(value: &str) -> Option<String> {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => output.push('\t'),
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some('f') => output.push('\x0c'),
+            Some('u') => {
+                let hex = chars.by_ref().take(4).collect::<String>();
+                let code = parse_hex(&hex)?;
+                // 代理对需要与后一个 `\uXXXX` 组合
+                if (0xD800..0xDC00).contains(&code) {
+                    let rest = chars.as_str();
+                    let low = rest
+                        .strip_prefix("\\u")
+                        .and_then(|rest| rest.get(..4))
+                        .and_then(parse_hex)
+                        .filter(|low| (0xDC00..0xE000).contains(low));
+                    match low {
+                        Some(low) => {
+                            let code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                            output.push(char::from_u32(code)?);
+                            chars = rest[6..].chars();
+                        }
+                        None => output.push(char::REPLACEMENT_CHARACTER),
+                    }
+                } else {
+                    output.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+            }
+            Some(other) => output.push(other),
+            None => {}
+        }
+    }
+    Some(output)
+}
+
+/// `\uXXXX` 中的四位十六进制数字，`from_str_radix` 会接受 `+` 号，这里需要先检查
+fn parse_hex(hex: &str) -> Option<u32> {
+    match hex.len() == 4 && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        true => u32::from_str_radix(hex, 16).ok(),
+        false => None,
+    }
+}
+
+/// 转义特殊字符，键中的空白字符和值的前导空格需要转义
+fn escape(value: &str, is_key: bool) -> String {
+    let mut output = String::with_capacity(value.len());
+    for (pos, c) in value.chars().enumerate() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '\t' => output.push_str("\\t"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\x0c' => output.push_str("\\f"),
+            ' ' if is_key || pos == 0 => output.push_str("\\ "),
+            '=' | ':' if is_key => {
+                output.push('\\');
+                output.push(c);
+            }
+            '#' | '!' if is_key && pos == 0 => {
+                output.push('\\');
+                output.push(c);
+            }
+            c if c.is_control() => output.push_str(&format!("\\u{:04X}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+command_error! {
+    (MalformedUnicodeEscape, "malformed \\uxxxx encoding at line {0}", usize),
+}
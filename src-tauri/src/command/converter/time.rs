@@ -0,0 +1,640 @@
+use crate::command_error;
+use chrono::{
+    format::StrftimeItems, DateTime, Datelike, Days, FixedOffset, Local, LocalResult, Months,
+    NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// 没有指定输出格式时使用的格式
+const DEFAULT_PATTERN: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 不带时区的时间格式，按输入时区解释
+const NAIVE_PATTERNS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%Y/%m/%d %H:%M",
+];
+
+const DATE_PATTERNS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d"];
+
+/// 纯数字的日期和时间，优先于时间戳识别
+const COMPACT_PATTERNS: &[(usize, &str)] = &[(8, "%Y%m%d"), (14, "%Y%m%d%H%M%S")];
+
+///
+/// 识别出的输入格式
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Source {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+    Rfc3339,
+    Rfc2822,
+    /// ISO 周日期，例如 `2025-W05-4`
+    IsoWeek,
+    /// 使用传入的 strftime 格式
+    Pattern,
+    /// 不带时区的常见格式，按输入时区解释
+    Local,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Time {
+    source: Source,
+    /// 各单位的时间戳，使用字符串避免 JavaScript 丢失精度
+    seconds: String,
+    milliseconds: String,
+    microseconds: String,
+    nanoseconds: String,
+    /// UTC 时间
+    rfc3339: String,
+    /// RFC 2822 只能表示 0 - 9999 年
+    rfc2822: Option<String>,
+    /// 输入时区下的 ISO 周日期
+    iso_week: String,
+    zones: Vec<Zoned>,
+    relative: Phrase,
+}
+
+///
+/// 某个时区下的时间
+///
+#[derive(Debug, Serialize)]
+pub struct Zoned {
+    zone: String,
+    time: String,
+    /// 例如 `+08:00`
+    offset: String,
+    /// 时区缩写，没有缩写的时区为偏移量
+    abbreviation: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Phrase {
+    english: String,
+    chinese: String,
+}
+
+///
+/// 两个时间之间的间隔
+///
+#[derive(Debug, Serialize)]
+pub struct Span {
+    /// 结束时间早于开始时间
+    negative: bool,
+    milliseconds: i64,
+    /// ISO 8601 表示，例如 `P1DT2H30M`，天数按 24 小时计算
+    iso: String,
+    /// 输入时区下按日历计算的年、月、日、时、分、秒
+    years: i64,
+    months: i64,
+    days: i64,
+    hours: i64,
+    minutes: i64,
+    seconds: i64,
+    /// 不含结束日期的工作日（周一到周五）天数
+    business_days: i64,
+    description: Phrase,
+}
+
+///
+/// 时间计算的单位
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Unit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    /// 只计算周一到周五
+    BusinessDays,
+    Weeks,
+    /// 目标月份没有这一天时取月末
+    Months,
+    Years,
+}
+
+///
+/// 解析时间，时间戳的单位根据位数识别
+///
+/// `pattern` 为自定义的 strftime 格式，`timezone` 用于解释不带时区的时间，为空时使用本地时区；
+/// 结果按 `zones` 中的每个时区用 `output` 格式输出，`zones` 为空时输出输入时区和 UTC
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn parse_time(
+    input: &str,
+    pattern: Option<String>,
+    timezone: Option<String>,
+    zones: Vec<String>,
+    output: Option<String>,
+) -> Result<Time, Error> {
+    let zone = Zone::parse(timezone.as_deref())?;
+    let (time, source) = parse(input, pattern.as_deref(), &zone)?;
+    render(time, source, &zone, &zones, output.as_deref())
+}
+
+///
+/// 计算两个时间之间的间隔，两个时间的解析规则和 [`parse_time`] 相同
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn time_between(
+    start: &str,
+    end: &str,
+    pattern: Option<String>,
+    timezone: Option<String>,
+) -> Result<Span, Error> {
+    let zone = Zone::parse(timezone.as_deref())?;
+    let (start, _) = parse(start, pattern.as_deref(), &zone)?;
+    let (end, _) = parse(end, pattern.as_deref(), &zone)?;
+    let negative = end < start;
+    let (from, to) = match negative {
+        true => (end, start),
+        false => (start, end),
+    };
+    let delta = to - from;
+    // 日历上的差值在输入时区下计算
+    let (local_from, local_to) = (zone.local(from), zone.local(to));
+    let mut months = (local_to.year() - local_from.year()) as i64 * 12 + local_to.month() as i64
+        - local_from.month() as i64;
+    let shifted = |months: i64| add_months(local_from, months).unwrap_or(local_from);
+    if months > 0 && shifted(months) > local_to {
+        months -= 1;
+    }
+    let rest = local_to - shifted(months);
+    let units = [
+        months / 12,
+        months % 12,
+        rest.num_days(),
+        rest.num_hours() % 24,
+        rest.num_minutes() % 60,
+        rest.num_seconds() % 60,
+    ];
+    let [years, months, days, hours, minutes, seconds] = units;
+    Ok(Span {
+        negative,
+        milliseconds: match negative {
+            true => -delta.num_milliseconds(),
+            false => delta.num_milliseconds(),
+        },
+        iso: iso_duration(delta, negative),
+        years,
+        months,
+        days,
+        hours,
+        minutes,
+        seconds,
+        business_days: business_days(local_from.date(), local_to.date()),
+        description: describe_span(units),
+    })
+}
+
+///
+/// 在时间上加减 `amount` 个单位，按天及以上的单位在输入时区下计算
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn shift_time(
+    input: &str,
+    pattern: Option<String>,
+    timezone: Option<String>,
+    amount: i64,
+    unit: Unit,
+    zones: Vec<String>,
+    output: Option<String>,
+) -> Result<Time, Error> {
+    let zone = Zone::parse(timezone.as_deref())?;
+    let (time, source) = parse(input, pattern.as_deref(), &zone)?;
+    let seconds = |seconds: i64| {
+        TimeDelta::try_seconds(seconds)
+            .and_then(|delta| time.checked_add_signed(delta))
+            .ok_or(Error::Range)
+    };
+    let local = zone.local(time);
+    let shifted = match unit {
+        Unit::Seconds => seconds(amount)?,
+        Unit::Minutes => seconds(amount.checked_mul(60).ok_or(Error::Range)?)?,
+        Unit::Hours => seconds(amount.checked_mul(3600).ok_or(Error::Range)?)?,
+        Unit::Days => zone.resolve(add_days(local, amount).ok_or(Error::Range)?)?,
+        Unit::Weeks => {
+            let days = amount.checked_mul(7).ok_or(Error::Range)?;
+            zone.resolve(add_days(local, days).ok_or(Error::Range)?)?
+        }
+        Unit::BusinessDays => {
+            zone.resolve(add_business_days(local, amount).ok_or(Error::Range)?)?
+        }
+        Unit::Months => zone.resolve(add_months(local, amount).ok_or(Error::Range)?)?,
+        Unit::Years => {
+            let months = amount.checked_mul(12).ok_or(Error::Range)?;
+            zone.resolve(add_months(local, months).ok_or(Error::Range)?)?
+        }
+    };
+    render(shifted, source, &zone, &zones, output.as_deref())
+}
+
+///
+/// 时区，为空时使用本地时区
+///
+enum Zone {
+    Local,
+    Named(Tz),
+}
+
+impl Zone {
+    fn parse(name: Option<&str>) -> Result<Self, Error> {
+        match name.map(str::trim) {
+            None | Some("") => Ok(Zone::Local),
+            Some(name) if name.eq_ignore_ascii_case("local") => Ok(Zone::Local),
+            Some(name) => name
+                .parse::<Tz>()
+                .map(Zone::Named)
+                .map_err(|_| Error::TimeZone(name.to_string())),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Zone::Local => "Local".to_string(),
+            Zone::Named(tz) => tz.name().to_string(),
+        }
+    }
+
+    /// 当地时间对应的时刻，重复的时间取较早的一个
+    fn resolve(&self, time: NaiveDateTime) -> Result<DateTime<Utc>, Error> {
+        let result = match self {
+            Zone::Local => Local.from_local_datetime(&time).map(|t| t.to_utc()),
+            Zone::Named(tz) => tz.from_local_datetime(&time).map(|t| t.to_utc()),
+        };
+        match result {
+            LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => Ok(time),
+            LocalResult::None => Err(Error::Nonexistent(time.to_string(), self.name())),
+        }
+    }
+
+    fn local(&self, time: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Local => time.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => time.with_timezone(tz).naive_local(),
+        }
+    }
+
+    fn zoned(&self, time: DateTime<Utc>, pattern: &str) -> Zoned {
+        let render = |time: DateTime<FixedOffset>, abbreviation: String| Zoned {
+            zone: self.name(),
+            time: time.format(pattern).to_string(),
+            offset: time.format("%:z").to_string(),
+            abbreviation,
+        };
+        match self {
+            Zone::Local => {
+                let time = time.with_timezone(&Local).fixed_offset();
+                render(time, time.format("%:z").to_string())
+            }
+            Zone::Named(tz) => {
+                let time = time.with_timezone(tz);
+                render(time.fixed_offset(), time.format("%Z").to_string())
+            }
+        }
+    }
+}
+
+///
+/// 依次尝试自定义格式、纯数字的日期、时间戳、RFC 3339、RFC 2822、ISO 周日期和常见格式
+///
+fn parse(
+    input: &str,
+    pattern: Option<&str>,
+    zone: &Zone,
+) -> Result<(DateTime<Utc>, Source), Error> {
+    let input = input.trim();
+    if let Some(pattern) = pattern.filter(|pattern| !pattern.is_empty()) {
+        if let Ok(time) = DateTime::parse_from_str(input, pattern) {
+            return Ok((time.to_utc(), Source::Pattern));
+        }
+        let naive = NaiveDateTime::parse_from_str(input, pattern).or_else(|_| {
+            NaiveDate::parse_from_str(input, pattern).map(|date| date.and_time(NaiveTime::MIN))
+        });
+        return match naive {
+            Ok(time) => Ok((zone.resolve(time)?, Source::Pattern)),
+            Err(error) => Err(Error::Pattern(
+                input.to_string(),
+                pattern.to_string(),
+                error,
+            )),
+        };
+    }
+    let compact = COMPACT_PATTERNS
+        .iter()
+        .filter(|(len, _)| input.len() == *len)
+        .find_map(|(_, pattern)| {
+            NaiveDateTime::parse_from_str(input, pattern)
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(input, pattern)
+                        .map(|date| date.and_time(NaiveTime::MIN))
+                })
+                .ok()
+        });
+    if let Some(time) = compact {
+        return Ok((zone.resolve(time)?, Source::Local));
+    }
+    if let Some(result) = parse_timestamp(input) {
+        return result;
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok((time.to_utc(), Source::Rfc3339));
+    }
+    if let Ok(time) = DateTime::parse_from_rfc2822(input) {
+        return Ok((time.to_utc(), Source::Rfc2822));
+    }
+    if let Some(time) = parse_iso_week(input) {
+        return Ok((zone.resolve(time)?, Source::IsoWeek));
+    }
+    let naive = NAIVE_PATTERNS
+        .iter()
+        .find_map(|pattern| NaiveDateTime::parse_from_str(input, pattern).ok())
+        .or_else(|| {
+            DATE_PATTERNS
+                .iter()
+                .find_map(|pattern| NaiveDate::parse_from_str(input, pattern).ok())
+                .map(|date| date.and_time(NaiveTime::MIN))
+        });
+    match naive {
+        Some(time) => Ok((zone.resolve(time)?, Source::Local)),
+        None => Err(Error::Input(input.to_string())),
+    }
+}
+
+///
+/// 整数时间戳按位数识别单位：11 位以内是秒，14 位以内是毫秒，17 位以内是微秒，否则是纳秒；带小数的按秒处理
+///
+fn parse_timestamp(input: &str) -> Option<Result<(DateTime<Utc>, Source), Error>> {
+    let digits = input.strip_prefix('-').unwrap_or(input);
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if integer.is_empty() || !integer.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let negative = input.starts_with('-');
+    let value = match integer.parse::<i64>() {
+        Ok(value) if negative => -value,
+        Ok(value) => value,
+        Err(_) => return Some(Err(Error::Range)),
+    };
+    let (source, time) = match (integer.len(), fraction.is_empty()) {
+        (_, false) => {
+            // 小数部分最多保留到纳秒
+            let nanos = format!("{fraction:0<9}")[..9].parse::<i64>().unwrap_or(0);
+            let nanos = if negative { -nanos } else { nanos };
+            (
+                Source::Seconds,
+                DateTime::from_timestamp(value, 0)
+                    .and_then(|time| time.checked_add_signed(TimeDelta::nanoseconds(nanos))),
+            )
+        }
+        (0..=11, true) => (Source::Seconds, DateTime::from_timestamp(value, 0)),
+        (12..=14, true) => (Source::Milliseconds, DateTime::from_timestamp_millis(value)),
+        (15..=17, true) => (Source::Microseconds, DateTime::from_timestamp_micros(value)),
+        _ => (
+            Source::Nanoseconds,
+            Some(DateTime::from_timestamp_nanos(value)),
+        ),
+    };
+    Some(time.map(|time| (time, source)).ok_or(Error::Range))
+}
+
+///
+/// ISO 周日期：`2025-W05`、`2025-W05-4`、`2025W054`，可以带 `T` 开头的时间
+///
+fn parse_iso_week(input: &str) -> Option<NaiveDateTime> {
+    let (date, time) = match input.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (input, None),
+    };
+    let (year, rest) = date.split_once(['W', 'w'])?;
+    let year = year.strip_suffix('-').unwrap_or(year).parse::<i32>().ok()?;
+    let rest = rest.replace('-', "");
+    let (week, day) = match rest.len() {
+        2 => (rest.parse::<u32>().ok()?, 1),
+        3 => (
+            rest[..2].parse::<u32>().ok()?,
+            rest[2..].parse::<u32>().ok()?,
+        ),
+        _ => return None,
+    };
+    let weekday = match day {
+        1..=7 => Weekday::try_from(day as u8 - 1).ok()?,
+        _ => return None,
+    };
+    let date = NaiveDate::from_isoywd_opt(year, week, weekday)?;
+    let time = match time {
+        Some(time) => ["%H:%M:%S%.f", "%H:%M"]
+            .iter()
+            .find_map(|pattern| NaiveTime::parse_from_str(time, pattern).ok())?,
+        None => NaiveTime::MIN,
+    };
+    Some(date.and_time(time))
+}
+
+fn render(
+    time: DateTime<Utc>,
+    source: Source,
+    zone: &Zone,
+    zones: &[String],
+    output: Option<&str>,
+) -> Result<Time, Error> {
+    let pattern = output
+        .filter(|pattern| !pattern.is_empty())
+        .unwrap_or(DEFAULT_PATTERN);
+    StrftimeItems::new(pattern).parse()?;
+    let zones = match zones {
+        [] => vec![
+            zone.zoned(time, pattern),
+            Zone::Named(Tz::UTC).zoned(time, pattern),
+        ],
+        zones => zones
+            .iter()
+            .map(|name| Zone::parse(Some(name)).map(|zone| zone.zoned(time, pattern)))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    let nanos = time.timestamp() as i128 * 1_000_000_000 + time.timestamp_subsec_nanos() as i128;
+    Ok(Time {
+        source,
+        seconds: time.timestamp().to_string(),
+        milliseconds: time.timestamp_millis().to_string(),
+        microseconds: time.timestamp_micros().to_string(),
+        nanoseconds: nanos.to_string(),
+        rfc3339: time.to_rfc3339(),
+        rfc2822: (0..=9999).contains(&time.year()).then(|| time.to_rfc2822()),
+        iso_week: zone.local(time).format("%G-W%V-%u").to_string(),
+        zones,
+        relative: relative(time - Utc::now()),
+    })
+}
+
+///
+/// 相对于现在的说明，例如 `3 days ago` 或 `3 天后`
+///
+fn relative(delta: TimeDelta) -> Phrase {
+    let seconds = delta.num_seconds();
+    let abs = seconds.unsigned_abs();
+    if abs < 10 {
+        return Phrase {
+            english: "just now".to_string(),
+            chinese: "刚刚".to_string(),
+        };
+    }
+    let (value, unit, unit_zh) = match abs {
+        0..=59 => (abs, "second", "秒"),
+        60..=3599 => (abs / 60, "minute", "分钟"),
+        3600..=86399 => (abs / 3600, "hour", "小时"),
+        86400..=2_591_999 => (abs / 86400, "day", "天"),
+        2_592_000..=31_535_999 => (abs / 2_592_000, "month", "个月"),
+        _ => (abs / 31_536_000, "year", "年"),
+    };
+    let plural = if value == 1 { "" } else { "s" };
+    match seconds < 0 {
+        true => Phrase {
+            english: format!("{value} {unit}{plural} ago"),
+            chinese: format!("{value} {unit_zh}前"),
+        },
+        false => Phrase {
+            english: format!("in {value} {unit}{plural}"),
+            chinese: format!("{value} {unit_zh}后"),
+        },
+    }
+}
+
+fn add_days(time: NaiveDateTime, days: i64) -> Option<NaiveDateTime> {
+    match days >= 0 {
+        true => time.checked_add_days(Days::new(days as u64)),
+        false => time.checked_sub_days(Days::new(days.unsigned_abs())),
+    }
+}
+
+/// 加减月份，目标月份没有这一天时取月末
+fn add_months(time: NaiveDateTime, months: i64) -> Option<NaiveDateTime> {
+    let count = u32::try_from(months.unsigned_abs()).ok()?;
+    match months >= 0 {
+        true => time.checked_add_months(Months::new(count)),
+        false => time.checked_sub_months(Months::new(count)),
+    }
+}
+
+///
+/// 加减工作日，从周末开始时第一步先移到相邻的工作日
+///
+fn add_business_days(time: NaiveDateTime, days: i64) -> Option<NaiveDateTime> {
+    let step = if days >= 0 { 1 } else { -1 };
+    let mut time = time;
+    let mut remaining = days.unsigned_abs();
+    while remaining > 0 {
+        time = add_days(time, step)?;
+        if !is_weekend(time.date()) {
+            remaining -= 1;
+        }
+    }
+    Some(time)
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// `[from, to)` 之间的工作日天数
+fn business_days(from: NaiveDate, to: NaiveDate) -> i64 {
+    let days = (to - from).num_days();
+    let weeks = days / 7;
+    let extra = (0..days % 7)
+        .filter_map(|offset| from.checked_add_days(Days::new((weeks * 7 + offset) as u64)))
+        .filter(|date| !is_weekend(*date))
+        .count() as i64;
+    weeks * 5 + extra
+}
+
+fn iso_duration(delta: TimeDelta, negative: bool) -> String {
+    let total = delta.num_seconds();
+    let (days, hours, minutes) = (total / 86400, total % 86400 / 3600, total % 3600 / 60);
+    let nanos = delta.subsec_nanos();
+    let mut text = String::from(if negative { "-P" } else { "P" });
+    if days > 0 {
+        text.push_str(&format!("{days}D"));
+    }
+    let seconds = match nanos {
+        0 => format!("{}", total % 60),
+        nanos => {
+            let fraction = format!("{nanos:09}");
+            format!("{}.{}", total % 60, fraction.trim_end_matches('0'))
+        }
+    };
+    if hours > 0 || minutes > 0 || seconds != "0" || days == 0 {
+        text.push('T');
+        if hours > 0 {
+            text.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            text.push_str(&format!("{minutes}M"));
+        }
+        if seconds != "0" || (hours == 0 && minutes == 0) {
+            text.push_str(&format!("{seconds}S"));
+        }
+    }
+    text
+}
+
+/// 年、月、日、时、分、秒的说明，例如 `1 year, 2 months and 3 days` 或 `1 年 2 个月 3 天`
+fn describe_span(units: [i64; 6]) -> Phrase {
+    let names = [
+        ("year", "年"),
+        ("month", "个月"),
+        ("day", "天"),
+        ("hour", "小时"),
+        ("minute", "分钟"),
+        ("second", "秒"),
+    ];
+    let parts = units
+        .into_iter()
+        .zip(names)
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, (unit, unit_zh))| (value, unit, unit_zh))
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        return Phrase {
+            english: "0 seconds".to_string(),
+            chinese: "0 秒".to_string(),
+        };
+    }
+    let english = parts
+        .iter()
+        .map(|(value, unit, _)| match value {
+            1 => format!("1 {unit}"),
+            value => format!("{value} {unit}s"),
+        })
+        .collect::<Vec<_>>();
+    let english = match &english[..] {
+        [init @ .., last] if !init.is_empty() => format!("{} and {last}", init.join(", ")),
+        _ => english.join(""),
+    };
+    let chinese = parts
+        .iter()
+        .map(|(value, _, unit)| format!("{value} {unit}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Phrase { english, chinese }
+}
+
+command_error! {
+    (Input, "unrecognized time: {0}", String),
+    (Pattern, "'{0}' does not match pattern '{1}': {2}", String, String, chrono::format::ParseError),
+    (Output, "invalid output pattern: {0}", #[from] chrono::format::ParseError),
+    (TimeZone, "unknown time zone: {0}", String),
+    (Nonexistent, "{0} does not exist in time zone {1}", String, String),
+    (Range, "time is out of range"),
+}
@@ -0,0 +1,424 @@
+use crate::command::formatter::indent::Indent;
+use crate::command_error;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::io::Cursor;
+
+/// JSON 转 XML 时，无法确定根元素名称时使用的名称
+const DEFAULT_ROOT: &str = "root";
+
+/// JSON 顶层为数组时，数组元素使用的名称
+const DEFAULT_ITEM: &str = "item";
+
+///
+/// XML 和 JSON 之间的映射约定
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Convention {
+    /// 属性为 `@name`，文本为 `$`，命名空间声明在 `@xmlns` 中
+    BadgerFish,
+    /// 忽略属性和根元素名称，只有文本的元素直接转换为值
+    Parker,
+    /// 属性为 `@name`，文本为 `#text`，没有属性的文本元素直接转换为值
+    Attribute,
+}
+
+///
+/// 带命名空间的名称的处理方式
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Namespace {
+    /// 保留原文中的前缀和命名空间声明
+    Preserve,
+    /// 只保留本地名称，去除命名空间声明
+    Strip,
+    /// 使用 `{uri}local` 形式的名称，去除命名空间声明
+    Expand,
+}
+
+///
+/// 子元素转换为数组的规则
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Arrays {
+    /// 只有重复出现的元素转换为数组
+    Repeated,
+    /// 所有子元素都转换为数组
+    Always,
+    /// 指定名称的元素总是转换为数组，其余元素重复出现时才转换
+    Elements(Vec<String>),
+}
+
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_xml_to_json(
+    xml: &str,
+    convention: Convention,
+    namespace: Namespace,
+    arrays: Arrays,
+    coerce_types: bool,
+) -> Result<String, Error> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let document = roxmltree::Document::parse_with_options(xml, options)?;
+    let reader = XmlReader {
+        input: xml,
+        convention,
+        namespace,
+        arrays,
+        coerce_types,
+    };
+    let root = document.root_element();
+    let value = match convention {
+        Convention::Parker => reader.element(root),
+        Convention::BadgerFish | Convention::Attribute => {
+            let mut object = Map::new();
+            object.insert(reader.name(root), reader.element(root));
+            Value::Object(object)
+        }
+    };
+    serde_json::to_string_pretty(&value).map_err(Into::into)
+}
+
+///
+/// JSON 转 XML，`root` 为空时，只有一个键的对象使用该键作为根元素，否则使用 `root`
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn convert_json_to_xml(
+    json: &str,
+    convention: Convention,
+    indent: Indent,
+    root: Option<String>,
+) -> Result<String, Error> {
+    let value = serde_json::from_str::<Value>(json)?;
+    let cursor = Cursor::new(Vec::with_capacity(json.len()));
+    let writer = match indent {
+        Indent::TwoSpace => Writer::new_with_indent(cursor, b' ', 2),
+        Indent::FourSpace => Writer::new_with_indent(cursor, b' ', 4),
+        Indent::Tab => Writer::new_with_indent(cursor, b'\t', 1),
+        Indent::None | Indent::Minify => Writer::new(cursor),
+    };
+    let mut writer = XmlWriter { writer, convention };
+    writer
+        .writer
+        .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    // 顶层数组放在根元素下，避免产生多个根元素
+    let value = match value {
+        Value::Array(values) => Value::Object(Map::from_iter([(
+            DEFAULT_ITEM.to_string(),
+            Value::Array(values),
+        )])),
+        value => value,
+    };
+    let root = root.filter(|root| !root.trim().is_empty());
+    match (root, &value, convention) {
+        (Some(root), value, _) => writer.element(root.trim(), value)?,
+        (None, Value::Object(object), Convention::BadgerFish | Convention::Attribute)
+            if object.len() == 1 && !object.values().all(Value::is_array) =>
+        {
+            let (name, value) = object.iter().next().unwrap();
+            writer.element(name, value)?;
+        }
+        (None, value, _) => writer.element(DEFAULT_ROOT, value)?,
+    }
+    let bytes = writer.writer.into_inner().into_inner();
+    String::from_utf8(bytes).map_err(Into::into)
+}
+
+struct XmlReader<'a> {
+    input: &'a str,
+    convention: Convention,
+    namespace: Namespace,
+    arrays: Arrays,
+    coerce_types: bool,
+}
+
+impl XmlReader<'_> {
+    fn name(&self, node: roxmltree::Node) -> String {
+        let tag = node.tag_name();
+        match (self.namespace, tag.namespace()) {
+            (Namespace::Preserve, _) => {
+                // 原文中的限定名，紧跟在 `<` 之后
+                let source = &self.input[node.range().start + 1..];
+                let end = source
+                    .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                    .unwrap_or(source.len());
+                source[..end].to_string()
+            }
+            (Namespace::Expand, Some(uri)) => format!("{{{}}}{}", uri, tag.name()),
+            _ => tag.name().to_string(),
+        }
+    }
+
+    fn attribute_name(&self, attribute: &roxmltree::Attribute) -> String {
+        match (self.namespace, attribute.namespace()) {
+            (Namespace::Preserve, _) => self.input[attribute.range_qname()].to_string(),
+            (Namespace::Expand, Some(uri)) => format!("{{{}}}{}", uri, attribute.name()),
+            _ => attribute.name().to_string(),
+        }
+    }
+
+    /// 在当前元素上声明的命名空间
+    fn declarations<'a>(&self, node: roxmltree::Node<'a, '_>) -> Vec<(Option<&'a str>, &'a str)> {
+        if !matches!(self.namespace, Namespace::Preserve) {
+            return Vec::new();
+        }
+        let inherited = node
+            .parent_element()
+            .map(|parent| {
+                parent
+                    .namespaces()
+                    .map(|ns| (ns.name(), ns.uri()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        node.namespaces()
+            .map(|ns| (ns.name(), ns.uri()))
+            .filter(|ns| !inherited.contains(ns))
+            .collect()
+    }
+
+    fn scalar(&self, text: &str) -> Value {
+        if !self.coerce_types {
+            return Value::String(text.to_string());
+        }
+        match text.trim() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            number if is_number(number) => {
+                serde_json::from_str(number).unwrap_or_else(|_| Value::String(text.to_string()))
+            }
+            _ => Value::String(text.to_string()),
+        }
+    }
+
+    /// 指定的名称可以是转换后的名称，也可以是本地名称
+    fn force_array(&self, node: roxmltree::Node, name: &str) -> bool {
+        match &self.arrays {
+            Arrays::Repeated => false,
+            Arrays::Always => true,
+            Arrays::Elements(names) => names
+                .iter()
+                .any(|n| n == name || n == node.tag_name().name()),
+        }
+    }
+
+    fn element(&self, node: roxmltree::Node) -> Value {
+        let children = node
+            .children()
+            .filter(|child| child.is_element())
+            .collect::<Vec<_>>();
+        // 空白文本只用于排版，混合内容中的各段文本去除首尾空白后以空格连接
+        let texts = node
+            .children()
+            .filter(|child| child.is_text())
+            .filter_map(|child| child.text())
+            .collect::<Vec<_>>();
+        let text = match children.is_empty() {
+            true => Some(texts.concat()),
+            false => Some(
+                texts
+                    .iter()
+                    .map(|text| text.trim())
+                    .filter(|text| !text.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+        }
+        .filter(|text| !text.trim().is_empty());
+
+        let mut object = Map::new();
+        if !matches!(self.convention, Convention::Parker) {
+            let declarations = self.declarations(node);
+            match self.convention {
+                Convention::BadgerFish if !declarations.is_empty() => {
+                    let namespaces = declarations
+                        .into_iter()
+                        .map(|(prefix, uri)| (prefix.unwrap_or("$").to_string(), Value::from(uri)))
+                        .collect();
+                    object.insert("@xmlns".to_string(), Value::Object(namespaces));
+                }
+                _ => {
+                    for (prefix, uri) in declarations {
+                        let name = match prefix {
+                            Some(prefix) => format!("@xmlns:{}", prefix),
+                            None => "@xmlns".to_string(),
+                        };
+                        object.insert(name, Value::from(uri));
+                    }
+                }
+            }
+            for attribute in node.attributes() {
+                object.insert(
+                    format!("@{}", self.attribute_name(&attribute)),
+                    self.scalar(attribute.value()),
+                );
+            }
+        }
+
+        match (self.convention, text) {
+            (Convention::BadgerFish, Some(text)) => {
+                object.insert("$".to_string(), self.scalar(&text));
+            }
+            (Convention::Parker | Convention::Attribute, Some(text))
+                if object.is_empty() && children.is_empty() =>
+            {
+                return self.scalar(&text);
+            }
+            (Convention::Attribute, Some(text)) => {
+                object.insert("#text".to_string(), self.scalar(&text));
+            }
+            (Convention::Parker | Convention::Attribute, None)
+                if object.is_empty() && children.is_empty() =>
+            {
+                return Value::Null;
+            }
+            _ => {}
+        }
+
+        for child in children {
+            let name = self.name(child);
+            let value = self.element(child);
+            match object.get_mut(&name) {
+                Some(Value::Array(values)) => values.push(value),
+                Some(previous) => *previous = Value::Array(vec![previous.take(), value]),
+                None if self.force_array(child, &name) => {
+                    object.insert(name, Value::Array(vec![value]));
+                }
+                None => {
+                    object.insert(name, value);
+                }
+            }
+        }
+        Value::Object(object)
+    }
+}
+
+/// 是否为 JSON 数字，不转换 `007` 这样带前导零的文本
+fn is_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let integer = digits.split(['.', 'e', 'E']).next().unwrap_or_default();
+    !integer.is_empty()
+        && (integer == "0" || !integer.starts_with('0'))
+        && serde_json::from_str::<serde_json::Number>(text).is_ok()
+}
+
+/// 是否为合法的 XML 元素或属性名
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '.' | '-'))
+}
+
+struct XmlWriter {
+    writer: Writer<Cursor<Vec<u8>>>,
+    convention: Convention,
+}
+
+impl XmlWriter {
+    fn text_key(&self) -> Option<&'static str> {
+        match self.convention {
+            Convention::BadgerFish => Some("$"),
+            Convention::Attribute => Some("#text"),
+            Convention::Parker => None,
+        }
+    }
+
+    fn element(&mut self, name: &str, value: &Value) -> Result<(), Error> {
+        if !is_name(name) {
+            return Err(Error::Name(name.to_string()));
+        }
+        let object = match value {
+            // 数组转换为重复的元素
+            Value::Array(values) => {
+                for value in values {
+                    self.element(name, value)?;
+                }
+                return Ok(());
+            }
+            Value::Object(object) => object,
+            Value::Null => {
+                self.writer
+                    .write_event(Event::Empty(BytesStart::new(name)))?;
+                return Ok(());
+            }
+            scalar => {
+                self.writer
+                    .write_event(Event::Start(BytesStart::new(name)))?;
+                self.writer
+                    .write_event(Event::Text(BytesText::new(&scalar_text(scalar))))?;
+                self.writer.write_event(Event::End(BytesEnd::new(name)))?;
+                return Ok(());
+            }
+        };
+
+        let mut start = BytesStart::new(name);
+        let mut text = None;
+        let mut children = Vec::new();
+        for (key, value) in object {
+            let attribute = key
+                .strip_prefix('@')
+                .filter(|_| !matches!(self.convention, Convention::Parker));
+            match (attribute, value) {
+                (Some("xmlns"), Value::Object(namespaces)) => {
+                    for (prefix, uri) in namespaces {
+                        let name = match prefix.as_str() {
+                            "$" => "xmlns".to_string(),
+                            prefix => format!("xmlns:{}", prefix),
+                        };
+                        start.push_attribute((name.as_str(), scalar_text(uri).as_str()));
+                    }
+                }
+                (Some(attribute), value) => {
+                    if !is_name(attribute) {
+                        return Err(Error::Name(attribute.to_string()));
+                    }
+                    start.push_attribute((attribute, scalar_text(value).as_str()));
+                }
+                (None, value) if Some(key.as_str()) == self.text_key() => {
+                    text = Some(scalar_text(value))
+                }
+                (None, value) => children.push((key, value)),
+            }
+        }
+
+        if text.is_none() && children.is_empty() {
+            self.writer.write_event(Event::Empty(start))?;
+            return Ok(());
+        }
+        self.writer.write_event(Event::Start(start))?;
+        if let Some(text) = text {
+            self.writer
+                .write_event(Event::Text(BytesText::new(&text)))?;
+        }
+        for (key, value) in children {
+            self.element(key, value)?;
+        }
+        self.writer.write_event(Event::End(BytesEnd::new(name)))?;
+        Ok(())
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+command_error! {
+    (Json, "json error: {0}", #[from] serde_json::Error),
+    (Xml, "xml error: {0}", #[from] roxmltree::Error),
+    (Io, "io error: {0}", #[from] std::io::Error),
+    (Utf8, "utf8 error: {0}", #[from] std::string::FromUtf8Error),
+    (Name, "\"{0}\" is not a valid xml name", String),
+}
@@ -0,0 +1,638 @@
+//!
+//! YAML 转换的公共实现。
+//!
+//! - 锚点和别名在解析时由 `serde_yaml` 展开，合并键 `<<` 由 [`resolve`] 显式处理，
+//!   显式声明的键优先，合并进来的键放在 `<<` 所在的位置。
+//! - 自定义标签 `!Tag value` 表示为只有一个键的映射 `{"!Tag": value}`，
+//!   [`restore_tags`] 和 [`Node::restore_tags`] 可以将这种映射还原为标签。
+//! - `serde_yaml::Value` 无法保存注释，[`Node`] 在值的基础上记录注释，
+//!   [`reformat`] 通过扫描源文本把注释挂到对应的节点上，从而在重新格式化时保留注释。
+//!
+use crate::command_error;
+use serde::Deserialize;
+use serde_yaml::{
+    value::{Tag, TaggedValue},
+    Mapping, Value,
+};
+use std::collections::HashSet;
+
+/// 合并键
+const MERGE_KEY: &str = "<<";
+
+/// 缩进宽度
+const INDENT: usize = 2;
+
+///
+/// 展开合并键，并将自定义标签转换为 `{"!Tag": value}` 形式的映射
+///
+pub fn resolve(value: Value) -> Result<Value, Error> {
+    match value {
+        Value::Sequence(values) => values
+            .into_iter()
+            .map(resolve)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Sequence),
+        Value::Mapping(mapping) => {
+            let explicit = mapping
+                .keys()
+                .filter(|key| key.as_str() != Some(MERGE_KEY))
+                .cloned()
+                .collect::<HashSet<_>>();
+            let mut resolved = Mapping::with_capacity(mapping.len());
+            for (key, value) in mapping {
+                if key.as_str() != Some(MERGE_KEY) {
+                    resolved.insert(key, resolve(value)?);
+                    continue;
+                }
+                // `<<: *a` 或 `<<: [*a, *b]`，前面的来源优先
+                let sources = match resolve(value)? {
+                    Value::Mapping(source) => vec![source],
+                    Value::Sequence(sources) => sources
+                        .into_iter()
+                        .map(|source| match source {
+                            Value::Mapping(source) => Ok(source),
+                            _ => Err(Error::InvalidMerge),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => return Err(Error::InvalidMerge),
+                };
+                for (key, value) in sources.into_iter().flatten() {
+                    if !explicit.contains(&key) && !resolved.contains_key(&key) {
+                        resolved.insert(key, value);
+                    }
+                }
+            }
+            Ok(Value::Mapping(resolved))
+        }
+        Value::Tagged(tagged) => {
+            let mut mapping = Mapping::with_capacity(1);
+            mapping.insert(
+                Value::String(tagged.tag.to_string()),
+                resolve(tagged.value)?,
+            );
+            Ok(Value::Mapping(mapping))
+        }
+        value => Ok(value),
+    }
+}
+
+///
+/// 将 `{"!Tag": value}` 形式的映射还原为标签
+///
+pub fn restore_tags(value: Value) -> Value {
+    match value {
+        Value::Sequence(values) => Value::Sequence(values.into_iter().map(restore_tags).collect()),
+        Value::Mapping(mapping) => {
+            let mapping = mapping
+                .into_iter()
+                .map(|(key, value)| (key, restore_tags(value)))
+                .collect::<Mapping>();
+            match single_tag(&mapping) {
+                Some(tag) => {
+                    let value = mapping.into_values().next().unwrap_or_default();
+                    Value::Tagged(Box::new(TaggedValue {
+                        tag: Tag::new(tag),
+                        value,
+                    }))
+                }
+                None => Value::Mapping(mapping),
+            }
+        }
+        value => value,
+    }
+}
+
+fn single_tag(mapping: &Mapping) -> Option<String> {
+    match mapping.len() {
+        1 => mapping
+            .keys()
+            .next()
+            .and_then(as_tag)
+            .map(ToString::to_string),
+        _ => None,
+    }
+}
+
+/// 以 `!` 开头的键表示自定义标签，`!!` 开头的核心标签由 `serde_yaml` 处理
+fn as_tag(key: &Value) -> Option<&str> {
+    key.as_str()
+        .filter(|key| key.len() > 1 && key.starts_with('!') && !key.starts_with("!!"))
+}
+
+///
+/// 重新格式化 YAML，统一缩进和引号，保留注释、标签和合并键。
+///
+/// 别名会被展开为对应锚点的值。
+///
+pub fn reformat(yaml: &str) -> Result<String, Error> {
+    Ok(documents(yaml, Ok)?
+        .iter()
+        .map(Node::to_yaml)
+        .collect::<Result<Vec<_>, _>>()?
+        .join("---\n"))
+}
+
+///
+/// 解析 YAML 中的每个文档并保留注释，合并键和自定义标签按 [`resolve`] 的方式处理
+///
+pub fn parse(yaml: &str) -> Result<Vec<Node>, Error> {
+    documents(yaml, resolve)
+}
+
+fn documents(
+    yaml: &str,
+    convert: impl Fn(Value) -> Result<Value, Error>,
+) -> Result<Vec<Node>, Error> {
+    let mut comments = scan_comments(yaml).into_iter();
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        let value = Value::deserialize(document)?;
+        // 末尾的 `---` 会产生一个空文档
+        if value.is_null() && !documents.is_empty() {
+            continue;
+        }
+        let mut node = Node::from_value(convert(value)?);
+        if let Some(comments) = comments.next() {
+            node.attach(comments);
+        }
+        documents.push(node);
+    }
+    Ok(documents)
+}
+
+///
+/// 带注释的 YAML 节点
+///
+#[derive(Debug, Default)]
+pub struct Node {
+    /// 节点之前的注释行
+    pub comments: Vec<String>,
+    /// 与节点写在同一行的注释
+    pub inline: Option<String>,
+    pub tag: Option<String>,
+    pub value: NodeValue,
+    /// 文档末尾的注释，只对根节点有效
+    pub trailing: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum NodeValue {
+    Scalar(Value),
+    Mapping(Vec<(Value, Node)>),
+    Sequence(Vec<Node>),
+}
+
+impl Default for NodeValue {
+    fn default() -> Self {
+        NodeValue::Scalar(Value::Null)
+    }
+}
+
+impl Node {
+    pub fn mapping() -> Self {
+        Self {
+            value: NodeValue::Mapping(Vec::new()),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_value(value: Value) -> Self {
+        match value {
+            Value::Mapping(mapping) => Node {
+                value: NodeValue::Mapping(
+                    mapping
+                        .into_iter()
+                        .map(|(key, value)| (key, Node::from_value(value)))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            Value::Sequence(values) => Node {
+                value: NodeValue::Sequence(values.into_iter().map(Node::from_value).collect()),
+                ..Default::default()
+            },
+            Value::Tagged(tagged) => Node {
+                tag: Some(tagged.tag.to_string()),
+                ..Node::from_value(tagged.value)
+            },
+            value => Node {
+                value: NodeValue::Scalar(value),
+                ..Default::default()
+            },
+        }
+    }
+
+    ///
+    /// 将 `{"!Tag": value}` 形式的映射还原为标签
+    ///
+    pub fn restore_tags(&mut self) {
+        match &mut self.value {
+            NodeValue::Mapping(entries) => {
+                entries.iter_mut().for_each(|(_, node)| node.restore_tags());
+                let tag = match entries.as_slice() {
+                    [(key, node)] if node.tag.is_none() => as_tag(key).map(ToString::to_string),
+                    _ => None,
+                };
+                if let Some(tag) = tag {
+                    let (_, node) = entries.pop().unwrap();
+                    self.tag = Some(tag);
+                    self.comments.extend(node.comments);
+                    self.value = node.value;
+                }
+            }
+            NodeValue::Sequence(items) => items.iter_mut().for_each(Node::restore_tags),
+            NodeValue::Scalar(_) => {}
+        }
+    }
+
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        let mut output = String::new();
+        write_comments(&self.comments, 0, &mut output);
+        if let Some(tag) = &self.tag {
+            output.push_str(tag);
+            output.push('\n');
+        }
+        match &self.value {
+            NodeValue::Mapping(entries) if !entries.is_empty() => {
+                write_mapping(entries, 0, &mut output)?
+            }
+            NodeValue::Sequence(items) if !items.is_empty() => {
+                write_sequence(items, 0, &mut output)?
+            }
+            NodeValue::Scalar(value) => {
+                output.push_str(&scalar(value)?);
+                output.push('\n');
+            }
+            NodeValue::Mapping(_) => output.push_str("{}\n"),
+            NodeValue::Sequence(_) => output.push_str("[]\n"),
+        }
+        write_comments(&self.trailing, 0, &mut output);
+        Ok(output)
+    }
+
+    fn attach(&mut self, comments: DocumentComments) {
+        for (path, before, inline) in comments.nodes {
+            match self.find_mut(&path) {
+                Some(node) => {
+                    node.comments.extend(before);
+                    node.inline = node.inline.take().or(inline);
+                }
+                // 找不到对应节点的注释放到文档末尾，避免丢失
+                None => self.trailing.extend(before.into_iter().chain(inline)),
+            }
+        }
+        self.trailing.extend(comments.trailing);
+    }
+
+    fn find_mut(&mut self, path: &[Segment]) -> Option<&mut Node> {
+        let Some((segment, rest)) = path.split_first() else {
+            return Some(self);
+        };
+        let child = match (&mut self.value, segment) {
+            (NodeValue::Mapping(entries), Segment::Key(text)) => entries
+                .iter_mut()
+                .find(|(key, _)| key_matches(key, text))
+                .map(|(_, node)| node),
+            (NodeValue::Sequence(items), Segment::Index(index)) => items.get_mut(*index),
+            _ => None,
+        }?;
+        child.find_mut(rest)
+    }
+}
+
+fn key_matches(key: &Value, text: &str) -> bool {
+    match key.as_str() {
+        Some(key) => key == text,
+        None => scalar(key).is_ok_and(|key| key == text),
+    }
+}
+
+fn write_comments(comments: &[String], indent: usize, output: &mut String) {
+    for comment in comments {
+        // properties 的注释可能以 `!` 开头，YAML 中统一使用 `#`
+        output.push_str(&" ".repeat(indent));
+        output.push('#');
+        output.push_str(&comment[1..]);
+        output.push('\n');
+    }
+}
+
+fn write_mapping(
+    entries: &[(Value, Node)],
+    indent: usize,
+    output: &mut String,
+) -> Result<(), Error> {
+    for (key, node) in entries {
+        write_comments(&node.comments, indent, output);
+        output.push_str(&" ".repeat(indent));
+        output.push_str(&scalar(key)?);
+        output.push(':');
+        write_child(node, indent, output)?;
+    }
+    Ok(())
+}
+
+fn write_sequence(items: &[Node], indent: usize, output: &mut String) -> Result<(), Error> {
+    for item in items {
+        write_comments(&item.comments, indent, output);
+        output.push_str(&" ".repeat(indent));
+        output.push('-');
+        // 嵌套集合的第一行与 `-` 写在同一行
+        let mut nested = String::new();
+        match &item.value {
+            _ if item.tag.is_some() || item.inline.is_some() => {
+                write_child(item, indent, output)?;
+                continue;
+            }
+            NodeValue::Mapping(entries) if !entries.is_empty() => {
+                write_mapping(entries, indent + INDENT, &mut nested)?
+            }
+            NodeValue::Sequence(items) if !items.is_empty() => {
+                write_sequence(items, indent + INDENT, &mut nested)?
+            }
+            _ => {
+                write_child(item, indent, output)?;
+                continue;
+            }
+        }
+        output.push(' ');
+        output.push_str(&nested[indent + INDENT..]);
+    }
+    Ok(())
+}
+
+fn write_child(node: &Node, indent: usize, output: &mut String) -> Result<(), Error> {
+    if let Some(tag) = &node.tag {
+        output.push(' ');
+        output.push_str(tag);
+    }
+    let nested = match &node.value {
+        NodeValue::Scalar(value) => {
+            output.push(' ');
+            output.push_str(&scalar(value)?);
+            None
+        }
+        NodeValue::Mapping(entries) if entries.is_empty() => {
+            output.push_str(" {}");
+            None
+        }
+        NodeValue::Sequence(items) if items.is_empty() => {
+            output.push_str(" []");
+            None
+        }
+        value => Some(value),
+    };
+    if let Some(inline) = &node.inline {
+        output.push(' ');
+        output.push_str(inline);
+    }
+    output.push('\n');
+    match nested {
+        Some(NodeValue::Mapping(entries)) => write_mapping(entries, indent + INDENT, output),
+        Some(NodeValue::Sequence(items)) => write_sequence(items, indent + INDENT, output),
+        _ => Ok(()),
+    }
+}
+
+/// 序列化单个标量，多行字符串使用双引号形式，避免块标量破坏缩进
+fn scalar(value: &Value) -> Result<String, Error> {
+    let yaml = serde_yaml::to_string(value)?;
+    let yaml = yaml.strip_suffix('\n').unwrap_or(&yaml);
+    match yaml.contains('\n') {
+        true => serde_json::to_string(value).map_err(Into::into),
+        false => Ok(yaml.to_string()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// 一个文档中的注释，按节点路径记录
+#[derive(Debug, Default)]
+struct DocumentComments {
+    nodes: Vec<(Vec<Segment>, Vec<String>, Option<String>)>,
+    trailing: Vec<String>,
+}
+
+struct Frame {
+    indent: isize,
+    segment: Option<Segment>,
+    /// 是否为序列的元素
+    item: bool,
+    /// 下一个序列元素的下标
+    items: usize,
+}
+
+impl Frame {
+    fn new(indent: isize, segment: Segment, item: bool) -> Self {
+        Self {
+            indent,
+            segment: Some(segment),
+            item,
+            items: 0,
+        }
+    }
+}
+
+///
+/// 逐行扫描块风格的 YAML，根据缩进推断每行注释所属节点的路径
+///
+fn scan_comments(yaml: &str) -> Vec<DocumentComments> {
+    fn path(stack: &[Frame]) -> Vec<Segment> {
+        stack
+            .iter()
+            .filter_map(|frame| frame.segment.clone())
+            .collect()
+    }
+
+    let root = || Frame {
+        indent: -1,
+        segment: None,
+        item: false,
+        items: 0,
+    };
+
+    let mut documents = Vec::new();
+    let mut document = DocumentComments::default();
+    let mut has_content = false;
+    let mut stack = vec![root()];
+    let mut pending = Vec::new();
+    // 块标量所属节点的缩进，块标量中的内容不需要扫描
+    let mut block_scalar: Option<isize> = None;
+
+    for line in yaml.lines() {
+        let trimmed = line.trim_start();
+        let indent = (line.len() - trimmed.len()) as isize;
+        if let Some(owner) = block_scalar {
+            if trimmed.is_empty() || indent > owner {
+                continue;
+            }
+            block_scalar = None;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('%') || line.starts_with("...") {
+            continue;
+        }
+        if trimmed.starts_with('#') {
+            pending.push(trimmed.to_string());
+            continue;
+        }
+        if line == "---" || line.starts_with("--- ") || line.starts_with("---\t") {
+            if has_content {
+                document.trailing.append(&mut pending);
+                documents.push(std::mem::take(&mut document));
+                has_content = false;
+            }
+            stack.truncate(1);
+            stack[0].items = 0;
+            continue;
+        }
+        has_content = true;
+
+        let (mut rest, inline) = split_comment(trimmed);
+        let mut col = indent;
+        let mut current = None;
+
+        // 序列元素，可能有多个 `- - value`
+        while rest == "-" || rest.starts_with("- ") {
+            while stack.len() > 1 {
+                let top = stack.last().unwrap();
+                if top.indent > col || (top.indent == col && top.item) {
+                    stack.pop();
+                } else {
+                    break;
+                }
+            }
+            let parent = stack.last_mut().unwrap();
+            let index = parent.items;
+            parent.items += 1;
+            stack.push(Frame::new(col, Segment::Index(index), true));
+            if current.is_none() {
+                document
+                    .nodes
+                    .push((path(&stack), std::mem::take(&mut pending), None));
+            }
+            current = Some(path(&stack));
+
+            let after = rest[1..].trim_start();
+            col += (rest.len() - after.len()) as isize;
+            rest = after;
+        }
+
+        if let Some((key, value)) = split_key(rest) {
+            while stack.len() > 1 && stack.last().unwrap().indent >= col {
+                stack.pop();
+            }
+            stack.push(Frame::new(col, Segment::Key(key), false));
+            if current.is_none() {
+                document
+                    .nodes
+                    .push((path(&stack), std::mem::take(&mut pending), None));
+            }
+            current = Some(path(&stack));
+            if is_block_scalar(value) {
+                block_scalar = Some(col);
+            }
+        } else if current.is_some() && is_block_scalar(rest) {
+            block_scalar = stack.last().map(|frame| frame.indent);
+        }
+
+        if let (Some(path), Some(inline)) = (current, inline) {
+            document.nodes.push((path, Vec::new(), Some(inline)));
+        }
+    }
+    document.trailing.append(&mut pending);
+    documents.push(document);
+    documents
+}
+
+/// 拆分行尾注释，`#` 之前必须是空白字符且不在引号中
+fn split_comment(text: &str) -> (&str, Option<String>) {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut previous = ' ';
+    for (pos, c) in text.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '#' && previous.is_whitespace() => {
+                return (text[..pos].trim_end(), Some(text[pos..].to_string()));
+            }
+            // 引号只有出现在标量开头时才有意义
+            None if (c == '"' || c == '\'')
+                && (previous.is_whitespace() || "-:[{,".contains(previous)) =>
+            {
+                quote = Some(c)
+            }
+            None => {}
+        }
+        previous = c;
+    }
+    (text, None)
+}
+
+/// 拆分 `key: value`，返回去掉引号的键和值的部分
+fn split_key(text: &str) -> Option<(String, &str)> {
+    let is_separator =
+        |rest: &str| rest == ":" || rest.starts_with(": ") || rest.starts_with(":\t");
+    match text.chars().next()? {
+        '"' => {
+            let mut escaped = false;
+            let end = text.char_indices().skip(1).find_map(|(pos, c)| match c {
+                _ if escaped => {
+                    escaped = false;
+                    None
+                }
+                '\\' => {
+                    escaped = true;
+                    None
+                }
+                '"' => Some(pos),
+                _ => None,
+            })?;
+            let rest = text[end + 1..].trim_start();
+            let key = serde_yaml::from_str::<String>(&text[..=end]).ok()?;
+            is_separator(rest).then(|| (key, &rest[1..]))
+        }
+        '\'' => {
+            let mut end = 1;
+            loop {
+                end += text[end..].find('\'')?;
+                if text[end + 1..].starts_with('\'') {
+                    end += 2;
+                } else {
+                    break;
+                }
+            }
+            let rest = text[end + 1..].trim_start();
+            let key = text[1..end].replace("''", "'");
+            is_separator(rest).then(|| (key, &rest[1..]))
+        }
+        '[' | '{' | '?' | '|' | '>' => None,
+        _ => {
+            let pos = text
+                .match_indices(':')
+                .map(|(pos, _)| pos)
+                .find(|pos| is_separator(&text[*pos..]))?;
+            Some((text[..pos].trim_end().to_string(), &text[pos + 1..]))
+        }
+    }
+}
+
+/// 值是否为 `|` 或 `>` 开头的块标量，可能带有锚点和标签
+fn is_block_scalar(value: &str) -> bool {
+    value
+        .split_whitespace()
+        .find(|token| !token.starts_with('&') && !token.starts_with('!'))
+        .is_some_and(|token| token.starts_with('|') || token.starts_with('>'))
+}
+
+command_error! {
+    (Yaml, "yaml error: {0}", #[from] serde_yaml::Error),
+    (Json, "json error: {0}", #[from] serde_json::Error),
+    (InvalidMerge, "invalid merge key, expected a mapping or a sequence of mappings"),
+}
@@ -1,64 +1,249 @@
+use super::properties::{self, Item, Properties};
+use super::yaml::{self, Node, NodeValue};
 use crate::command_error;
 use regex::Regex;
-use serde_yaml::{Mapping, Value};
-use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
 
-type Properties = BTreeMap<String, String>;
+/// Spring Boot 用于指定文档所属 profile 的配置项，`spring.profiles` 为旧版本写法
+const PROFILE_KEYS: [&str; 2] = ["spring.config.activate.on-profile", "spring.profiles"];
+
+/// Spring Boot 多文档 properties 文件的分隔符
+const DOCUMENT_SEPARATOR: &str = "#---";
+
+///
+/// properties 转 YAML 时值的处理方式
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ValueMode {
+    /// 所有值都保持为字符串
+    String,
+    /// 推断布尔值和数字，带前导零的数字(如 `00123`)仍然保持为字符串
+    Infer,
+}
 
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn convert_yaml_to_properties(yaml: &str) -> Result<String, Error> {
-    yaml_to_properties(yaml).map(|properties| serialize_properties(&properties))
+pub fn convert_yaml_to_properties(yaml: &str, profile: Option<String>) -> Result<String, Error> {
+    let documents = yaml_to_properties(yaml)?;
+    let documents = match profile.filter(|profile| !profile.is_empty()) {
+        Some(profile) => vec![merge_profile(documents, &profile)?],
+        None => documents,
+    };
+    Ok(documents
+        .iter()
+        .map(Properties::write)
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}\n", DOCUMENT_SEPARATOR)))
 }
 
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn convert_properties_to_yaml(properties: &str) -> Result<String, Error> {
-    let properties = deserialize_properties(properties);
-    let value = properties_to_yaml(properties)?;
-    serde_yaml::to_string(&value).map_err(Into::into)
+pub fn convert_properties_to_yaml(properties: &str, mode: ValueMode) -> Result<String, Error> {
+    let properties = Properties::parse(properties)?;
+    properties
+        .items()
+        .split(|item| matches!(item, Item::Comment(comment) if comment[1..] == *"---"))
+        .map(|items| Ok(properties_to_yaml(items, mode)?.to_yaml()?))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|documents| documents.join("---\n"))
 }
 
-fn yaml_to_properties(yaml: &str) -> Result<Properties, Error> {
-    fn insert(key: String, value: Value, properties: &mut Properties) -> Result<(), Error> {
-        match value {
-            Value::Null => {
-                properties.insert(key, "".to_string());
+///
+/// YAML 中的每个文档转换为一组 properties，保持键在文档中的顺序，并保留注释。
+///
+/// 自定义标签在 [`yaml::parse`] 中已经转换为一级键，与转换为 JSON 时的表示方式一致。
+///
+fn yaml_to_properties(yaml: &str) -> Result<Vec<Properties>, Error> {
+    fn insert(key: String, node: Node, properties: &mut Properties) -> Result<(), Error> {
+        // properties 不支持行尾注释，放到键值对的上一行
+        for comment in node.comments.into_iter().chain(node.inline) {
+            properties.push_comment(comment);
+        }
+        match node.value {
+            NodeValue::Scalar(Value::Null) => {
+                properties.push_entry(key, "");
+            }
+            NodeValue::Scalar(Value::Bool(value)) => {
+                properties.push_entry(key, format!("{}", value));
             }
-            Value::Bool(value) => {
-                properties.insert(key, format!("{}", value));
+            NodeValue::Scalar(Value::Number(value)) => {
+                properties.push_entry(key, format!("{}", value));
             }
-            Value::Number(value) => {
-                properties.insert(key, format!("{}", value));
+            NodeValue::Scalar(Value::String(value)) => {
+                properties.push_entry(key, value);
             }
-            Value::String(value) => {
-                properties.insert(key, value);
+            NodeValue::Scalar(value) => {
+                insert(key, Node::from_value(value), properties)?;
             }
-            Value::Sequence(values) => {
-                for (index, value) in values.into_iter().enumerate() {
-                    insert(format!("{}[{}]", key, index), value, properties)?;
+            NodeValue::Sequence(items) => {
+                for (index, item) in items.into_iter().enumerate() {
+                    insert(format!("{}[{}]", key, index), item, properties)?;
                 }
             }
-            Value::Mapping(mapping) => {
-                for (suffix, value) in mapping.into_iter() {
-                    if let Some(suffix) = suffix.as_str() {
-                        insert(format!("{}.{}", key, suffix), value, properties)?;
-                    } else {
-                        return Err(Error::KeyNotString(format!("{}.{:?}", key, suffix)));
+            NodeValue::Mapping(entries) => {
+                for (suffix, node) in entries {
+                    match suffix.as_str() {
+                        Some(suffix) => insert(format!("{}.{}", key, suffix), node, properties)?,
+                        None => return Err(Error::KeyNotString(format!("{}.{:?}", key, suffix))),
                     }
                 }
             }
-            Value::Tagged(_) => return Err(Error::ContainsTaggedValue(key)),
         }
         Ok(())
     }
 
-    let mut properties = Properties::new();
-    let value: HashMap<String, Value> = serde_yaml::from_str(yaml)?;
-    value
+    let mut documents = Vec::new();
+    for document in yaml::parse(yaml)? {
+        let entries = match document.value {
+            // 空文档，只有注释时仍然保留注释
+            NodeValue::Scalar(Value::Null) => Vec::new(),
+            NodeValue::Mapping(entries) => entries,
+            _ => return Err(Error::ExpectedMap),
+        };
+        let mut properties = Properties::new();
+        document
+            .comments
+            .into_iter()
+            .for_each(|comment| properties.push_comment(comment));
+        for (key, node) in entries {
+            match key.as_str() {
+                Some(key) => insert(key.to_string(), node, &mut properties)?,
+                None => return Err(Error::KeyNotString(format!("{:?}", key))),
+            }
+        }
+        document
+            .trailing
+            .into_iter()
+            .for_each(|comment| properties.push_comment(comment));
+        if !properties.items().is_empty() {
+            documents.push(properties);
+        }
+    }
+    Ok(documents)
+}
+
+///
+/// 将指定 profile 激活的文档依次合并到默认配置上，多个 profile 用逗号分隔。
+///
+/// 与 Spring Boot 的行为一致，后出现的值覆盖先出现的值，列表整体替换而不是按下标合并。
+///
+fn merge_profile(documents: Vec<Properties>, profile: &str) -> Result<Properties, Error> {
+    let profiles = profile
+        .split(',')
+        .map(str::trim)
+        .filter(|profile| !profile.is_empty())
+        .collect::<Vec<_>>();
+    let mut merged = Properties::new();
+    for document in &documents {
+        if !is_active(document, &profiles)? {
+            continue;
+        }
+        let lists = document
+            .entries()
+            .flat_map(|(key, _)| key.match_indices('[').map(|(pos, _)| &key[..=pos]))
+            .collect::<Vec<_>>();
+        merged.retain_entries(|key, _| !lists.iter().any(|list| key.starts_with(list)));
+        document
+            .entries()
+            .filter(|(key, _)| profile_key(key).is_none())
+            .for_each(|(key, value)| merged.insert(key, value));
+    }
+    Ok(merged)
+}
+
+/// 没有指定 profile 的文档为默认配置，总是生效，逗号分隔的表达式满足一个即可
+fn is_active(document: &Properties, profiles: &[&str]) -> Result<bool, Error> {
+    let mut expressions = document
+        .entries()
+        .filter(|(key, _)| profile_key(key).is_some())
+        .map(|(_, value)| value)
+        .peekable();
+    if expressions.peek().is_none() {
+        return Ok(true);
+    }
+    for expression in expressions.flat_map(|expression| expression.split(',')) {
+        if !expression.trim().is_empty() && evaluate(expression, profiles)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+type Tokens<'a> = std::iter::Peekable<std::vec::IntoIter<&'a str>>;
+
+///
+/// 按照 Spring 的规则计算 profile 表达式，支持 `!`、`&`、`|` 和括号，
+/// `&` 和 `|` 混用时必须加括号
+///
+fn evaluate(expression: &str, profiles: &[&str]) -> Result<bool, Error> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (pos, c) in expression.char_indices() {
+        let operator = "()!&|".contains(c);
+        if operator || c.is_whitespace() {
+            if let Some(start) = start.take() {
+                tokens.push(&expression[start..pos]);
+            }
+            if operator {
+                tokens.push(&expression[pos..pos + 1]);
+            }
+        } else if start.is_none() {
+            start = Some(pos);
+        }
+    }
+    if let Some(start) = start {
+        tokens.push(&expression[start..]);
+    }
+
+    let mut tokens = tokens.into_iter().peekable();
+    match profile_expression(&mut tokens, profiles) {
+        Some(value) if tokens.next().is_none() => Ok(value),
+        _ => Err(Error::Profile(expression.trim().to_string())),
+    }
+}
+
+fn profile_expression(tokens: &mut Tokens, profiles: &[&str]) -> Option<bool> {
+    let mut value = profile_operand(tokens, profiles)?;
+    let mut operator = None;
+    while let Some(&next) = tokens.peek() {
+        if next != "&" && next != "|" {
+            break;
+        }
+        if operator.is_some_and(|operator| operator != next) {
+            return None;
+        }
+        operator = Some(next);
+        tokens.next();
+        let operand = profile_operand(tokens, profiles)?;
+        value = match next {
+            "&" => value && operand,
+            _ => value || operand,
+        };
+    }
+    Some(value)
+}
+
+fn profile_operand(tokens: &mut Tokens, profiles: &[&str]) -> Option<bool> {
+    match tokens.next()? {
+        "!" => profile_operand(tokens, profiles).map(|value| !value),
+        "(" => {
+            let value = profile_expression(tokens, profiles)?;
+            (tokens.next()? == ")").then_some(value)
+        }
+        ")" | "&" | "|" => None,
+        name => Some(profiles.contains(&name)),
+    }
+}
+
+/// 判断是否为 profile 配置项，列表形式的配置项会展开为 `key[0]`、`key[1]`
+fn profile_key(key: &str) -> Option<&'static str> {
+    PROFILE_KEYS
         .into_iter()
-        .try_for_each(|(key, value)| insert(key, value, &mut properties))?;
-    Ok(properties)
+        .find(|profile| match key.strip_prefix(profile) {
+            Some(rest) => rest.is_empty() || rest.starts_with('['),
+            None => false,
+        })
 }
 
 fn parse_key(key: &str) -> Result<Vec<String>, Error> {
@@ -77,42 +262,73 @@ fn parse_key(key: &str) -> Result<Vec<String>, Error> {
     }
 }
 
-fn parse_value(s: &str) -> Value {
+fn parse_value(s: &str, mode: ValueMode) -> Value {
+    if let ValueMode::String = mode {
+        return Value::String(s.to_string());
+    }
     if s.eq_ignore_ascii_case("true") {
         return Value::Bool(true);
     }
     if s.eq_ignore_ascii_case("false") {
         return Value::Bool(false);
     }
-    if let Ok(i) = s.parse::<i64>() {
-        return Value::Number(i.into());
-    }
-    if let Ok(f) = s.parse::<f64>() {
-        if f.is_finite() {
-            return Value::Number(serde_yaml::Number::from(f));
-        }
+    match parse_number(s) {
+        Some(number) => Value::Number(number),
+        None => Value::String(s.to_string()),
     }
-    Value::String(s.to_string())
 }
 
-pub fn properties_to_yaml(properties: Properties) -> Result<Value, Error> {
-    let mut root = Value::Mapping(Mapping::new());
-
-    let mut entries: Vec<(Vec<String>, Value)> = Vec::new();
-    for (key, val) in properties {
-        entries.push((parse_key(&key)?, parse_value(&val)));
+/// 只识别没有前导零的十进制数字，超出整数范围的整数保持为字符串以免丢失精度
+fn parse_number(s: &str) -> Option<serde_yaml::Number> {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let integer = unsigned.split(['.', 'e', 'E']).next().unwrap_or_default();
+    if integer.is_empty()
+        || !integer.bytes().all(|b| b.is_ascii_digit())
+        || (integer.len() > 1 && integer.starts_with('0'))
+    {
+        return None;
+    }
+    if integer.len() == unsigned.len() {
+        return match s.parse::<i64>() {
+            Ok(i) => Some(i.into()),
+            Err(_) => s.parse::<u64>().ok().map(Into::into),
+        };
     }
-    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    s.parse::<f64>()
+        .ok()
+        .filter(|f| f.is_finite())
+        .map(Into::into)
+}
+
+fn properties_to_yaml(items: &[Item], mode: ValueMode) -> Result<Node, Error> {
+    let mut root = Node::mapping();
 
-    for (parts, value) in entries {
-        insert_value(&mut root, &parts, value)?;
+    // 注释挂在紧随其后的键上
+    let mut comments = Vec::new();
+    for item in items {
+        match item {
+            Item::Comment(comment) => comments.push(comment.clone()),
+            Item::Blank => {}
+            Item::Entry(key, value) => {
+                let parts = parse_key(key)?;
+                let node = Node {
+                    comments: std::mem::take(&mut comments),
+                    value: NodeValue::Scalar(parse_value(value, mode)),
+                    ..Default::default()
+                };
+                insert_value(&mut root, &parts, node)?;
+            }
+        }
     }
+    root.trailing = comments;
+    root.restore_tags();
 
     Ok(root)
 }
 
-fn insert_value(root: &mut Value, path: &[String], value: Value) -> Result<(), Error> {
+fn insert_value(root: &mut Node, path: &[String], value: Node) -> Result<(), Error> {
     let mut current = root;
+    let mut value = Some(value);
 
     for (i, part) in path.iter().enumerate() {
         let is_last = i == path.len() - 1;
@@ -124,76 +340,66 @@ fn insert_value(root: &mut Value, path: &[String], value: Value) -> Result<(), E
                 .map_err(|_| Error::InvalidArrayIndex(idx_str.to_string()))?;
 
             // 如果 current 不是序列，则创建一个新的序列
-            if !current.is_sequence() {
-                *current = Value::Sequence(Vec::new());
+            if !matches!(current.value, NodeValue::Sequence(_)) {
+                current.value = NodeValue::Sequence(Vec::new());
             }
-
-            // 获取序列的可变引用
-            let seq = current.as_sequence_mut().ok_or(Error::ExpectedSequence)?;
+            let NodeValue::Sequence(seq) = &mut current.value else {
+                unreachable!()
+            };
 
             // 扩展数组以确保索引位置存在
             while seq.len() <= idx {
-                if is_last {
-                    seq.push(Value::Null);
-                } else {
-                    seq.push(Value::Mapping(Mapping::new()));
-                }
+                seq.push(Node::default());
             }
 
             if is_last {
-                seq[idx] = value;
+                merge(&mut seq[idx], value.take().unwrap());
                 return Ok(());
             } else {
                 current = &mut seq[idx];
             }
         } else {
             // 确保 current 是 Mapping 类型
-            if !current.is_mapping() {
-                *current = Value::Mapping(Mapping::new());
+            if !matches!(current.value, NodeValue::Mapping(_)) {
+                current.value = NodeValue::Mapping(Vec::new());
             }
+            let NodeValue::Mapping(map) = &mut current.value else {
+                unreachable!()
+            };
 
-            let map = current.as_mapping_mut().ok_or(Error::ExpectedMap)?;
+            // 保持键第一次出现的顺序
+            let pos = match map.iter().position(|(key, _)| key.as_str() == Some(part)) {
+                Some(pos) => pos,
+                None => {
+                    map.push((Value::String(part.clone()), Node::default()));
+                    map.len() - 1
+                }
+            };
 
             if is_last {
-                map.insert(Value::String(part.clone()), value);
+                merge(&mut map[pos].1, value.take().unwrap());
                 return Ok(());
             } else {
-                // 如果键不存在，插入一个新的 Mapping
-                if !map.contains_key(&Value::String(part.clone())) {
-                    map.insert(Value::String(part.clone()), Value::Mapping(Mapping::new()));
-                }
-                // 更新 current 为该键对应的值
-                current = map.get_mut(&Value::String(part.clone())).unwrap();
+                current = &mut map[pos].1;
             }
         }
     }
     Ok(())
 }
 
-fn serialize_properties(properties: &Properties) -> String {
-    properties
-        .iter()
-        .map(|(key, value)| format!("{}={}", key, value))
-        .collect::<Vec<_>>()
-        .join("\n")
-}
-
-fn deserialize_properties(value: &str) -> Properties {
-    value
-        .lines()
-        .filter(|line| !line.is_empty())
-        .filter(|line| !line.starts_with("#"))
-        .flat_map(|line| line.split_once("="))
-        .map(|(key, value)| (key.to_string(), value.to_string()))
-        .collect::<Properties>()
+/// 覆盖节点的值，同时保留已有的注释
+fn merge(node: &mut Node, value: Node) {
+    node.comments.extend(value.comments);
+    node.value = value.value;
 }
 
 command_error! {
     (Yaml, "yaml error: {0}", #[from]  serde_yaml::Error),
-    (ContainsTaggedValue, "unsupported yaml tagged value: {0}", String),
+    (Convert, "{0}", #[from] yaml::Error),
+    (Properties, "properties error: {0}", #[from] properties::Error),
     (KeyNotString, "unsupported yaml key is not string: {0}", String),
     (InvalidArrayIndex, "invalid array index: {0}", String),
-    (ExpectedSequence, "expected a sequence (array)"),
     (ExpectedMap, "expected a map (object)"),
     (EmptyKey, "empty key"),
+    (Profile, "unsupported profile expression: {0}", String),
 }
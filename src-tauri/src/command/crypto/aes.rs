@@ -2,6 +2,7 @@ use super::block_mode::BlockMode;
 use super::padding::Padding;
 use crate::command::crypto::symmetric::Error;
 use crate::{
+    charset::Charset,
     decrypt_symmetric,
     encoding::{Encoding, EncodingText},
     encrypt_symmetric, generate_iv, generate_key,
@@ -65,6 +66,7 @@ pub fn encrypt_aes(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
 pub fn decrypt_aes(
@@ -75,16 +77,17 @@ pub fn decrypt_aes(
     block_mode: BlockMode,
     padding: Padding,
     encoding: Encoding,
+    charset: Option<Charset>,
 ) -> Result<String, Error> {
     match bit_size {
         BitSize::Bits128 => {
-            decrypt_symmetric!(Aes128, input, key, iv, block_mode, padding, encoding, 128)
+            decrypt_symmetric!(Aes128, input, key, iv, block_mode, padding, encoding, charset, 128)
         }
         BitSize::Bits192 => {
-            decrypt_symmetric!(Aes192, input, key, iv, block_mode, padding, encoding, 192)
+            decrypt_symmetric!(Aes192, input, key, iv, block_mode, padding, encoding, charset, 192)
         }
         BitSize::Bits256 => {
-            decrypt_symmetric!(Aes256, input, key, iv, block_mode, padding, encoding, 256)
+            decrypt_symmetric!(Aes256, input, key, iv, block_mode, padding, encoding, charset, 256)
         }
     }
 }
@@ -2,6 +2,7 @@ use super::block_mode::BlockMode;
 use super::padding::Padding;
 use crate::command::crypto::symmetric::Error;
 use crate::{
+    charset::Charset,
     decrypt_symmetric,
     encoding::{Encoding, EncodingText},
     encrypt_symmetric, generate_iv, generate_key,
@@ -62,6 +63,7 @@ pub fn encrypt_des(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
 pub fn decrypt_des(
@@ -72,13 +74,16 @@ pub fn decrypt_des(
     block_mode: BlockMode,
     padding: Padding,
     encoding: Encoding,
+    charset: Option<Charset>,
 ) -> Result<String, Error> {
     match bit_size {
         BitSize::Bits64 => {
-            decrypt_symmetric!(Des, input, key, iv, block_mode, padding, encoding, 64)
+            decrypt_symmetric!(Des, input, key, iv, block_mode, padding, encoding, charset, 64)
         }
         BitSize::Bits192 => {
-            decrypt_symmetric!(TdesEde3, input, key, iv, block_mode, padding, encoding, 192)
+            decrypt_symmetric!(
+                TdesEde3, input, key, iv, block_mode, padding, encoding, charset, 192
+            )
         }
     }
 }
@@ -5,6 +5,7 @@ use rand::thread_rng;
 use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
 use serde::{Deserialize, Serialize};
 
+use crate::charset::{Charset, CharsetError};
 use crate::command_error;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,14 +77,22 @@ pub fn encrypt_rsa(key_format: KeyFormat, public_key: &str, text: &str) -> Resul
 
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn decrypt_rsa(key_format: KeyFormat, private_key: &str, text: &str) -> Result<String, Error> {
+pub fn decrypt_rsa(
+    key_format: KeyFormat,
+    private_key: &str,
+    text: &str,
+    charset: Option<Charset>,
+) -> Result<String, Error> {
     let private_key = match key_format {
         KeyFormat::Pkcs1 => RsaPrivateKey::from_pkcs1_pem(private_key)?,
         KeyFormat::Pkcs8 => RsaPrivateKey::from_pkcs8_pem(private_key)?,
     };
     let bytes = BASE64_STANDARD.decode(text)?;
     let bytes = private_key.decrypt(Pkcs1v15Encrypt, &bytes)?;
-    String::from_utf8(bytes).map_err(Into::into)
+    charset
+        .unwrap_or(Charset::Utf8)
+        .decode(&bytes)
+        .map_err(Into::into)
 }
 
 command_error! {
@@ -92,5 +101,5 @@ command_error! {
    (Pkcs8, "pkcs8 error: {0}", #[from] pkcs8::Error),
    (Spki, "spki error: {0}", String),
    (Base64Decode,"decode base64 error: {0}", #[from] base64::DecodeError),
-   (Utf8, "utf8 error: {0}", #[from] std::string::FromUtf8Error),
+   (Charset, "charset error: {0}", #[from] CharsetError),
 }
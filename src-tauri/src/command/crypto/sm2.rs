@@ -8,6 +8,7 @@ use sm2::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
 use sm2::pke::{DecryptingKey, EncryptingKey, Mode};
 use sm2::{EncodedPoint, PublicKey, SecretKey};
 
+use crate::charset::{Charset, CharsetError};
 use crate::command_error;
 
 /// SM2 密钥格式
@@ -111,7 +112,12 @@ pub fn encrypt_sm2(key_format: KeyFormat, public_key: &str, text: &str) -> Resul
 
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn decrypt_sm2(key_format: KeyFormat, private_key: &str, text: &str) -> Result<String, Error> {
+pub fn decrypt_sm2(
+    key_format: KeyFormat,
+    private_key: &str,
+    text: &str,
+    charset: Option<Charset>,
+) -> Result<String, Error> {
     let secret_key = match key_format {
         KeyFormat::Sec1 => {
             SecretKey::from_sec1_pem(private_key).map_err(|e| Error::Pem(e.to_string()))?
@@ -133,7 +139,10 @@ pub fn decrypt_sm2(key_format: KeyFormat, private_key: &str, text: &str) -> Resu
         .decrypt(&ciphertext)
         .map_err(|e| Error::Decryption(e.to_string()))?;
 
-    String::from_utf8(plaintext).map_err(Into::into)
+    charset
+        .unwrap_or(Charset::Utf8)
+        .decode(&plaintext)
+        .map_err(Into::into)
 }
 
 command_error! {
@@ -143,5 +152,5 @@ command_error! {
     (Decryption, "decryption error: {0}", String),
     (Base64Decode, "decode base64 error: {0}", #[from] base64::DecodeError),
     (HexDecode, "decode hex error: {0}", #[from] hex::FromHexError),
-    (Utf8, "utf8 error: {0}", #[from] std::string::FromUtf8Error),
+    (Charset, "charset error: {0}", #[from] CharsetError),
 }
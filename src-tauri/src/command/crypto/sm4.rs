@@ -1,4 +1,5 @@
 use super::symmetric::Error;
+use crate::charset::Charset;
 use crate::command::crypto::block_mode::BlockMode;
 use crate::command::crypto::padding::Padding;
 use crate::encoding::{Encoding, EncodingText};
@@ -40,6 +41,7 @@ pub fn decrypt_sm4(
     block_mode: BlockMode,
     padding: Padding,
     encoding: Encoding,
+    charset: Option<Charset>,
 ) -> Result<String, Error> {
-    decrypt_symmetric!(Sm4, input, key, iv, block_mode, padding, encoding, 128)
+    decrypt_symmetric!(Sm4, input, key, iv, block_mode, padding, encoding, charset, 128)
 }
@@ -1,3 +1,4 @@
+use crate::charset::{Charset, CharsetError};
 use crate::command::crypto::padding::Padding;
 use crate::command_error;
 use crate::encoding::Encoding;
@@ -101,7 +102,7 @@ macro_rules! encrypt_symmetric {
 
 #[macro_export]
 macro_rules! decrypt_symmetric {
-    ($ty:ty, $input:expr, $key:expr, $iv:expr, $block_mode:expr, $padding:expr, $encoding:expr, $bit_size:literal) => {{
+    ($ty:ty, $input:expr, $key:expr, $iv:expr, $block_mode:expr, $padding:expr, $encoding:expr, $charset:expr, $bit_size:literal) => {{
         let key = $key.to_bytes()?;
         // 除了CBC模式，其他模式都需要iv。
         if BlockMode::Ecb != $block_mode && $iv.is_none() {
@@ -118,21 +119,21 @@ macro_rules! decrypt_symmetric {
                 let decryptor = cbc::Decryptor::<$ty>::new_from_slices(&key, &iv)
                     .map_err(|_| Error::InvalidLength($bit_size))?;
                 $crate::command::crypto::symmetric::decrypt_block(
-                    decryptor, &mut input, $padding, $encoding,
+                    decryptor, &mut input, $padding, $encoding, $charset,
                 )
             }
             BlockMode::Cfb => {
                 let decryptor = cfb_mode::Decryptor::<$ty>::new_from_slices(&key, &iv)
                     .map_err(|_| Error::InvalidLength($bit_size))?;
                 $crate::command::crypto::symmetric::decrypt_block(
-                    decryptor, &mut input, $padding, $encoding,
+                    decryptor, &mut input, $padding, $encoding, $charset,
                 )
             }
             BlockMode::Ecb => {
                 let decryptor = ecb::Decryptor::<$ty>::new_from_slice(&key)
                     .map_err(|_| Error::InvalidLength($bit_size))?;
                 $crate::command::crypto::symmetric::decrypt_block(
-                    decryptor, &mut input, $padding, $encoding,
+                    decryptor, &mut input, $padding, $encoding, $charset,
                 )
             }
 
@@ -140,12 +141,16 @@ macro_rules! decrypt_symmetric {
             BlockMode::Ofb => {
                 let decryptor = ofb::Ofb::<$ty>::new_from_slices(&key, &iv)
                     .map_err(|_| Error::InvalidLength($bit_size))?;
-                $crate::command::crypto::symmetric::decrypt_stream(decryptor, &mut input, $encoding)
+                $crate::command::crypto::symmetric::decrypt_stream(
+                    decryptor, &mut input, $encoding, $charset,
+                )
             }
             BlockMode::Ctr => {
                 let decryptor = ctr::Ctr32BE::<$ty>::new_from_slices(&key, &iv)
                     .map_err(|_| Error::InvalidLength($bit_size))?;
-                $crate::command::crypto::symmetric::decrypt_stream(decryptor, &mut input, $encoding)
+                $crate::command::crypto::symmetric::decrypt_stream(
+                    decryptor, &mut input, $encoding, $charset,
+                )
             }
         }
     }};
@@ -157,6 +162,7 @@ pub fn decrypt_block<C>(
     input: &mut [u8],
     padding: Padding,
     encoding: Encoding,
+    charset: Option<Charset>,
 ) -> Result<String, Error>
 where
     C: BlockDecryptMut,
@@ -169,7 +175,7 @@ where
         Padding::Zero => decryptor.decrypt_padded_mut::<ZeroPadding>(input),
         Padding::None => decryptor.decrypt_padded_mut::<NoPadding>(input),
     }?;
-    encoding.encode(output).map_err(Into::into)
+    render(output, encoding, charset)
 }
 
 #[doc(hidden)]
@@ -177,12 +183,23 @@ pub fn decrypt_stream<C>(
     mut decryptor: C,
     input: &mut [u8],
     encoding: Encoding,
+    charset: Option<Charset>,
 ) -> Result<String, Error>
 where
     C: StreamCipher,
 {
     decryptor.apply_keystream(input);
-    encoding.encode(&input).map_err(Into::into)
+    render(input, encoding, charset)
+}
+
+///
+/// 以 UTF-8 输出明文时，可以改用指定的字符集解码
+///
+fn render(output: &[u8], encoding: Encoding, charset: Option<Charset>) -> Result<String, Error> {
+    match (encoding, charset) {
+        (Encoding::Utf8, Some(charset)) => charset.decode(output).map_err(Into::into),
+        _ => encoding.encode(output).map_err(Into::into),
+    }
 }
 
 command_error! {
@@ -190,7 +207,8 @@ command_error! {
     (Pad, "Pad error", #[from] inout::PadError),
     (Unpad, "UnPad error", #[from] cipher::block_padding::UnpadError),
     (RequiredIv, "block mode `{0:?}` required iv", crate::command::crypto::block_mode::BlockMode),
-    (Encoding, "encoding error", #[from] crate::encoding::EncodingError)
+    (Encoding, "encoding error: {0}", #[from] crate::encoding::EncodingError),
+    (Charset, "charset error: {0}", #[from] CharsetError),
 }
 
 #[macro_export]
@@ -0,0 +1,313 @@
+//!
+//! 各语言格式化共用的字符扫描、括号匹配和缩进输出
+//!
+
+use super::indent::Indent;
+
+///
+/// 带位置的语法错误，行号和列号从 1 开始
+///
+#[derive(Debug, thiserror::Error)]
+#[error("line {line}, column {column}: {message}")]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn error(self, message: impl Into<String>) -> SyntaxError {
+        SyntaxError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+}
+
+///
+/// 逐字符读取输入并记录当前位置
+///
+pub struct Scanner<'a> {
+    input: &'a str,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Scanner {
+            input,
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.offset >= self.input.len()
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.offset..]
+    }
+
+    /// 从 `start` 到当前位置的原文
+    pub fn slice(&self, start: usize) -> &'a str {
+        &self.input[start..self.offset]
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.rest().starts_with(prefix)
+    }
+
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn eat_str(&mut self, prefix: &str) -> bool {
+        if self.starts_with(prefix) {
+            prefix.chars().for_each(|_| {
+                self.bump();
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn eat_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.offset;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        self.slice(start)
+    }
+
+    /// 跳过空白，返回其中的换行数
+    pub fn skip_whitespace(&mut self) -> usize {
+        self.eat_while(char::is_whitespace).matches('\n').count()
+    }
+
+    /// 读取到 `end` 为止（包含 `end`），找不到时返回 `false` 并停在末尾
+    pub fn skip_until(&mut self, end: &str) -> bool {
+        while !self.is_eof() {
+            if self.eat_str(end) {
+                return true;
+            }
+            self.bump();
+        }
+        false
+    }
+
+    ///
+    /// 读取以当前引号开始的字符串，支持反斜杠转义，`multiline` 为 `false` 时字符串不能跨行
+    ///
+    pub fn string(&mut self, multiline: bool) -> Result<&'a str, SyntaxError> {
+        let start = (self.offset, self.position());
+        let quote = self.bump().unwrap_or('"');
+        loop {
+            match self.bump() {
+                Some('\\') => {
+                    self.bump();
+                }
+                Some(c) if c == quote => return Ok(self.slice(start.0)),
+                Some('\n') if !multiline => break,
+                Some(_) => {}
+                None => break,
+            }
+        }
+        Err(start.1.error("unterminated string"))
+    }
+}
+
+///
+/// 检查括号是否配对
+///
+#[derive(Default)]
+pub struct Brackets {
+    stack: Vec<(char, Position)>,
+}
+
+impl Brackets {
+    pub fn open(&mut self, bracket: char, position: Position) {
+        self.stack.push((bracket, position));
+    }
+
+    pub fn close(&mut self, bracket: char, position: Position) -> Result<(), SyntaxError> {
+        match self.stack.pop() {
+            Some((open, _)) if closing(open) == bracket => Ok(()),
+            Some((open, at)) => Err(position.error(format!(
+                "expected '{}' to close '{open}' at line {}, column {}, found '{bracket}'",
+                closing(open),
+                at.line,
+                at.column
+            ))),
+            None => Err(position.error(format!("unexpected '{bracket}'"))),
+        }
+    }
+
+    pub fn finish(self) -> Result<(), SyntaxError> {
+        match self.stack.last() {
+            Some((open, at)) => Err(at.error(format!("unclosed '{open}'"))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn closing(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        c => c,
+    }
+}
+
+///
+/// 按层级缩进输出，压缩模式下不输出缩进
+///
+pub struct Printer {
+    output: String,
+    unit: Option<&'static str>,
+    depth: usize,
+    space: bool,
+}
+
+impl Printer {
+    pub fn new(indent: Indent) -> Self {
+        Printer {
+            output: String::new(),
+            unit: indent.unit(),
+            depth: 0,
+            space: false,
+        }
+    }
+
+    pub fn minify(&self) -> bool {
+        self.unit.is_none()
+    }
+
+    pub fn indent(&mut self) {
+        self.depth += 1;
+    }
+
+    pub fn dedent(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn is_line_start(&self) -> bool {
+        self.output.is_empty() || self.output.ends_with('\n')
+    }
+
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        self.output.ends_with(suffix)
+    }
+
+    /// 删除末尾的一个字符，压缩时用于去掉多余的分隔符
+    pub fn pop(&mut self) -> Option<char> {
+        self.output.pop()
+    }
+
+    /// 在行首写入时先输出缩进，否则按需补一个空格
+    pub fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.is_line_start() {
+            if let Some(unit) = self.unit {
+                self.output.push_str(&unit.repeat(self.depth));
+            }
+        } else if self.space {
+            self.output.push(' ');
+        }
+        self.space = false;
+        self.output.push_str(text);
+    }
+
+    /// 下一次写入前补一个空格，行首不补
+    pub fn space(&mut self) {
+        self.space = !self.is_line_start();
+    }
+
+    /// 压缩模式下不换行，需要保留的换行由调用方通过 [`Printer::line_break`] 输出
+    pub fn newline(&mut self) {
+        self.space = false;
+        if !self.minify() && !self.is_line_start() {
+            self.output.push('\n');
+        }
+    }
+
+    /// 保证当前位置之前有且只有一个空行
+    pub fn blank_line(&mut self) {
+        self.newline();
+        if !self.minify() && !self.output.is_empty() && !self.output.ends_with("\n\n") {
+            self.output.push('\n');
+        }
+    }
+
+    /// 无论是否压缩都输出换行
+    pub fn line_break(&mut self) {
+        self.space = false;
+        if !self.is_line_start() {
+            self.output.push('\n');
+        }
+    }
+
+    pub fn finish(mut self) -> String {
+        let length = self.output.trim_end().len();
+        self.output.truncate(length);
+        if !self.minify() && !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        self.output
+    }
+}
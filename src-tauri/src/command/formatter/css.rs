@@ -0,0 +1,302 @@
+use super::code::{Brackets, Position, Printer, Scanner, SyntaxError};
+use super::indent::{Indent, CODE_INDENTS};
+use super::registry::{self, Capabilities, Descriptor, Formatter, Options};
+use crate::command_error;
+
+///
+/// 格式化 CSS，兼容 SCSS 的 `//` 注释、嵌套规则和 `#{}` 插值
+///
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn format_css(input: &str, indent: Indent) -> Result<String, Error> {
+    let items = parse(input)?;
+    let mut printer = Printer::new(indent);
+    write(&mut printer, &items);
+    Ok(printer.finish())
+}
+
+#[derive(Debug)]
+enum Item<'a> {
+    /// 选择器或 at 规则，后面跟着 `{`
+    Open(String),
+    Close,
+    /// 声明或不带块的 at 规则，不含结尾的 `;`
+    Statement(String),
+    Comment(&'a str),
+}
+
+///
+/// 将样式表拆分为块、声明和注释，空白压缩为一个空格
+///
+fn parse(input: &str) -> Result<Vec<Item<'_>>, SyntaxError> {
+    let mut scanner = Scanner::new(input);
+    let mut brackets = Brackets::default();
+    let mut items = Vec::new();
+    let mut chunk = String::new();
+    let mut start: Option<Position> = None;
+    let mut parentheses = 0;
+    while let Some(c) = scanner.peek() {
+        let position = scanner.position();
+        if c.is_whitespace() {
+            scanner.bump();
+            if !chunk.is_empty() && !chunk.ends_with(' ') {
+                chunk.push(' ');
+            }
+            continue;
+        }
+        let line_comment = scanner.starts_with("//")
+            && parentheses == 0
+            && (chunk.is_empty() || chunk.ends_with(' '));
+        if scanner.starts_with("/*") || line_comment {
+            let offset = scanner.offset();
+            if line_comment {
+                scanner.eat_while(|c| c != '\n');
+            } else if !scanner.skip_until("*/") {
+                return Err(position.error("unterminated comment"));
+            }
+            let comment = scanner.slice(offset);
+            if chunk.trim().is_empty() {
+                items.push(Item::Comment(comment));
+            } else if !line_comment {
+                chunk.push_str(comment);
+            }
+            continue;
+        }
+        start.get_or_insert(position);
+        match c {
+            '"' | '\'' => chunk.push_str(scanner.string(false)?),
+            '#' if scanner.peek_nth(1) == Some('{') => {
+                let offset = scanner.offset();
+                let mut depth = 0;
+                while let Some(c) = scanner.bump() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' if depth == 1 => break,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                }
+                if !scanner.slice(offset).ends_with('}') {
+                    return Err(position.error("unterminated interpolation"));
+                }
+                chunk.push_str(scanner.slice(offset));
+            }
+            '(' | '[' => {
+                scanner.bump();
+                brackets.open(c, position);
+                parentheses += 1;
+                chunk.push(c);
+            }
+            ')' | ']' => {
+                scanner.bump();
+                brackets.close(c, position)?;
+                parentheses -= 1;
+                chunk.push(c);
+            }
+            '{' => {
+                scanner.bump();
+                brackets.open(c, position);
+                items.push(Item::Open(take(&mut chunk)));
+                start = None;
+            }
+            '}' => {
+                scanner.bump();
+                brackets.close(c, position)?;
+                if !chunk.trim().is_empty() {
+                    items.push(Item::Statement(take(&mut chunk)));
+                }
+                chunk.clear();
+                items.push(Item::Close);
+                start = None;
+            }
+            ';' if parentheses == 0 => {
+                scanner.bump();
+                if !chunk.trim().is_empty() {
+                    items.push(Item::Statement(take(&mut chunk)));
+                }
+                chunk.clear();
+                start = None;
+            }
+            c => {
+                scanner.bump();
+                chunk.push(c);
+            }
+        }
+    }
+    brackets.finish()?;
+    match start {
+        Some(position) if !chunk.trim().is_empty() => {
+            Err(position.error("expected '{' or ';' after this"))
+        }
+        _ => Ok(items),
+    }
+}
+
+fn take(chunk: &mut String) -> String {
+    let text = chunk.trim().to_string();
+    chunk.clear();
+    text
+}
+
+fn write(printer: &mut Printer, items: &[Item]) {
+    let minify = printer.minify();
+    let mut previous: Option<&Item> = None;
+    for item in items {
+        match item {
+            Item::Open(prelude) => {
+                if printer.depth() == 0
+                    && matches!(previous, Some(Item::Close | Item::Statement(_)))
+                {
+                    printer.blank_line();
+                }
+                if prelude.starts_with('@') {
+                    printer.write(prelude);
+                } else if minify {
+                    printer.write(&minify_selector(prelude));
+                } else {
+                    let selectors = split(prelude, ',');
+                    for (index, selector) in selectors.iter().enumerate() {
+                        if index > 0 {
+                            printer.write(",");
+                            printer.newline();
+                        }
+                        printer.write(selector.trim());
+                    }
+                }
+                if !minify {
+                    printer.space();
+                }
+                printer.write("{");
+                printer.newline();
+                printer.indent();
+            }
+            Item::Close => {
+                if minify && printer.ends_with(";") {
+                    printer.pop();
+                }
+                printer.dedent();
+                printer.newline();
+                printer.write("}");
+                printer.newline();
+            }
+            Item::Statement(statement) => {
+                printer.write(&declaration(statement, minify));
+                printer.write(";");
+                printer.newline();
+            }
+            Item::Comment(comment) => {
+                if minify && !comment.starts_with("/*!") {
+                    continue;
+                }
+                printer.newline();
+                printer.write(comment);
+                printer.newline();
+            }
+        }
+        previous = Some(item);
+    }
+    if minify && printer.ends_with(";") && previous.is_some_and(|item| !matches!(item, Item::Close))
+    {
+        printer.pop();
+    }
+}
+
+///
+/// 声明统一写成 `name: value`，at 规则保持原样
+///
+fn declaration(statement: &str, minify: bool) -> String {
+    if statement.starts_with('@') {
+        return statement.to_string();
+    }
+    let parts = split(statement, ':');
+    let (Some(name), Some(_)) = (parts.first(), parts.get(1)) else {
+        return statement.to_string();
+    };
+    let value = statement[name.len() + 1..].trim();
+    if minify {
+        let value = split(value, ',')
+            .iter()
+            .map(|part| part.trim())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{}:{}",
+            name.trim(),
+            value.replace(" !important", "!important")
+        )
+    } else {
+        format!("{}: {}", name.trim(), value)
+    }
+}
+
+/// 去掉组合符和逗号两侧的空格
+fn minify_selector(selector: &str) -> String {
+    let mut output = String::with_capacity(selector.len());
+    let mut chars = selector.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' if output.ends_with([',', '>', '+', '~'])
+                || chars
+                    .peek()
+                    .is_some_and(|c| matches!(c, ',' | '>' | '+' | '~')) => {}
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+///
+/// 按不在括号和字符串内的分隔符拆分
+///
+fn split(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match (quote, c) {
+            _ if escaped => escaped = false,
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, c) if c == separator && depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+pub struct CssFormatter;
+
+impl Formatter for CssFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "css",
+            label: "CSS",
+            extensions: &["css", "scss"],
+            capabilities: Capabilities::new(CODE_INDENTS, false),
+            options: Vec::new(),
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        _: bool,
+        _: &Options,
+    ) -> Result<String, registry::Error> {
+        format_css(input, indent).map_err(registry::Error::format)
+    }
+}
+
+command_error! {
+    (Syntax, "syntax error: {0}", #[from] SyntaxError),
+}
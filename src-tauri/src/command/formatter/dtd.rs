@@ -0,0 +1,723 @@
+//!
+//! DTD 声明的解析和校验。
+//!
+//! 支持 `<!ELEMENT>`、`<!ATTLIST>` 以及内部参数实体，外部实体不会被加载。
+//!
+use super::xml_schema::{Mismatch, Particle, Term, Violation};
+use crate::command_error;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+enum Content {
+    Empty,
+    Any,
+    /// `(#PCDATA | a | b)*`
+    Mixed(Vec<String>),
+    Children(Model),
+}
+
+/// 解析后的内容模型，校验时转换为 [`Particle`]
+#[derive(Debug, Clone)]
+enum Model {
+    Name(String, char),
+    Sequence(Vec<Model>, char),
+    Choice(Vec<Model>, char),
+}
+
+#[derive(Debug)]
+enum AttributeType {
+    CData,
+    Id,
+    IdRef,
+    IdRefs,
+    Entity,
+    Entities,
+    NmToken,
+    NmTokens,
+    /// 枚举值，包括 `NOTATION (a|b)`
+    Enumeration(Vec<String>),
+}
+
+#[derive(Debug)]
+enum DefaultValue {
+    Required,
+    Implied,
+    Fixed(String),
+    /// 有默认值，缺省时由解析器补充，不需要检查
+    Value,
+}
+
+#[derive(Debug)]
+struct AttributeDecl {
+    name: String,
+    kind: AttributeType,
+    default: DefaultValue,
+}
+
+#[derive(Debug, Default)]
+struct Dtd {
+    /// DOCTYPE 中声明的根元素名
+    root: Option<String>,
+    elements: HashMap<String, Content>,
+    attributes: HashMap<String, Vec<AttributeDecl>>,
+    /// 内部参数实体
+    entities: HashMap<String, String>,
+}
+
+pub fn validate(
+    document: &roxmltree::Document,
+    externals: &[String],
+) -> Result<Vec<Violation>, Error> {
+    let input = document.input_text();
+    let (root, subset) = doctype(input);
+    if root.is_none() && externals.iter().all(|dtd| dtd.trim().is_empty()) {
+        return Err(Error::NoDoctype);
+    }
+
+    // 内部子集优先，先出现的声明生效
+    let mut dtd = Dtd {
+        root,
+        ..Default::default()
+    };
+    if let Some(subset) = subset {
+        dtd.parse(subset)?;
+    }
+    for external in externals {
+        dtd.parse(external)?;
+    }
+
+    let mut validator = Validator {
+        dtd: &dtd,
+        ids: HashSet::new(),
+        references: Vec::new(),
+        violations: Vec::new(),
+    };
+    let root = document.root_element();
+    if let Some(expected) = &dtd.root {
+        if qname(root) != expected {
+            validator.violations.push(Violation::element(
+                root,
+                format!(
+                    "root element must be <{}>, found <{}>",
+                    expected,
+                    qname(root)
+                ),
+            ));
+        }
+    }
+    validator.validate(root);
+
+    // IDREF 需要在所有 ID 收集完之后检查
+    let Validator {
+        ids,
+        references,
+        mut violations,
+        ..
+    } = validator;
+    for (node, attribute, id) in references {
+        if !ids.contains(&id) {
+            let attribute = node
+                .attributes()
+                .find(|a| a.range() == attribute)
+                .expect("attribute exists");
+            violations.push(Violation::attribute(
+                node,
+                &attribute,
+                format!("IDREF \"{}\" does not match any ID", id),
+            ));
+        }
+    }
+    violations.sort_by_key(Violation::position);
+    Ok(violations)
+}
+
+/// 元素在原文中的限定名
+fn qname<'a>(node: roxmltree::Node<'a, '_>) -> &'a str {
+    let input = node.document().input_text();
+    let tag = &input[node.range().start + 1..];
+    let end = tag
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+fn attribute_qname<'a>(node: roxmltree::Node<'a, '_>, attribute: &roxmltree::Attribute) -> &'a str {
+    &node.document().input_text()[attribute.range_qname()]
+}
+
+///
+/// 找到 DOCTYPE 声明的根元素名和内部子集
+///
+fn doctype(input: &str) -> (Option<String>, Option<&str>) {
+    let Some(start) = input.find("<!DOCTYPE") else {
+        return (None, None);
+    };
+    let rest = &input[start + "<!DOCTYPE".len()..];
+    let name = rest
+        .split(|c: char| c.is_whitespace() || c == '[' || c == '>')
+        .find(|name| !name.is_empty())
+        .map(str::to_string);
+
+    let mut quote = None;
+    let mut subset_start = None;
+    for (pos, c) in rest.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') if subset_start.is_none() => quote = Some(c),
+            (None, '[') if subset_start.is_none() => subset_start = Some(pos + 1),
+            (None, '>') if subset_start.is_none() => return (name, None),
+            (None, ']') if subset_start.is_some() => {
+                let start = subset_start.unwrap();
+                // 子集中可能包含 `]`，例如注释或字面量，以 `]` 后紧跟 `>` 为结束
+                if rest[pos + 1..].trim_start().starts_with('>') {
+                    return (name, Some(&rest[start..pos]));
+                }
+            }
+            _ => {}
+        }
+    }
+    (name, None)
+}
+
+impl Dtd {
+    fn parse(&mut self, text: &str) -> Result<(), Error> {
+        let mut input = text.to_string();
+        let mut pos = 0;
+        while pos < input.len() {
+            let rest = &input[pos..];
+            let trimmed = rest.trim_start();
+            pos += rest.len() - trimmed.len();
+            if trimmed.is_empty() {
+                break;
+            }
+            if trimmed.starts_with("<!--") {
+                let end = trimmed.find("-->").ok_or(Error::Unterminated("comment"))?;
+                pos += end + 3;
+            } else if trimmed.starts_with("<?") {
+                let end = trimmed
+                    .find("?>")
+                    .ok_or(Error::Unterminated("processing instruction"))?;
+                pos += end + 2;
+            } else if trimmed.starts_with("<![") {
+                return Err(Error::Unsupported("conditional sections".to_string()));
+            } else if let Some(reference) = trimmed.strip_prefix('%') {
+                // 声明之间的参数实体引用直接展开
+                let end = reference
+                    .find(';')
+                    .ok_or(Error::Unterminated("entity reference"))?;
+                let value = self
+                    .entities
+                    .get(&reference[..end])
+                    .cloned()
+                    .unwrap_or_default();
+                input.replace_range(pos..pos + end + 2, &value);
+            } else if trimmed.starts_with("<!") {
+                let end = declaration_end(trimmed).ok_or(Error::Unterminated("declaration"))?;
+                let declaration = self.expand(&trimmed[2..end]);
+                pos += end + 1;
+                self.declare(&declaration)?;
+            } else {
+                let snippet = trimmed.chars().take(20).collect::<String>();
+                return Err(Error::Syntax(snippet));
+            }
+        }
+        Ok(())
+    }
+
+    /// 展开声明中的参数实体引用，未定义的实体(如外部实体)展开为空
+    fn expand(&self, declaration: &str) -> String {
+        let mut output = declaration.to_string();
+        let mut from = 0;
+        // 限制展开次数，避免实体互相引用
+        let mut remaining = 64;
+        while let Some(offset) = output[from..].find('%') {
+            let start = from + offset;
+            let name_end = output[start + 1..]
+                .find(|c: char| !is_name_char(c))
+                .map(|end| start + 1 + end);
+            match name_end {
+                Some(end) if end > start + 1 && output[end..].starts_with(';') && remaining > 0 => {
+                    let value = self
+                        .entities
+                        .get(&output[start + 1..end])
+                        .cloned()
+                        .unwrap_or_default();
+                    output.replace_range(start..end + 1, &format!(" {} ", value));
+                    remaining -= 1;
+                }
+                _ => from = start + 1,
+            }
+        }
+        output
+    }
+
+    fn declare(&mut self, declaration: &str) -> Result<(), Error> {
+        let mut tokens = Tokens::new(declaration);
+        match tokens.word().as_deref() {
+            Some("ELEMENT") => {
+                let name = tokens
+                    .word()
+                    .ok_or(Error::Syntax(declaration.to_string()))?;
+                let content = tokens.content()?;
+                self.elements.entry(name).or_insert(content);
+            }
+            Some("ATTLIST") => {
+                let element = tokens
+                    .word()
+                    .ok_or(Error::Syntax(declaration.to_string()))?;
+                while let Some(name) = tokens.word() {
+                    let kind = tokens.attribute_type()?;
+                    let default = tokens.default_value()?;
+                    let decls = self.attributes.entry(element.clone()).or_default();
+                    if decls.iter().all(|decl| decl.name != name) {
+                        decls.push(AttributeDecl {
+                            name,
+                            kind,
+                            default,
+                        });
+                    }
+                }
+            }
+            Some("ENTITY") => {
+                // 只记录内部参数实体，通用实体由 XML 解析器处理
+                if tokens.symbol('%') {
+                    let name = tokens
+                        .word()
+                        .ok_or(Error::Syntax(declaration.to_string()))?;
+                    if let Some(value) = tokens.literal() {
+                        self.entities.entry(name).or_insert(value);
+                    }
+                }
+            }
+            Some("NOTATION") => {}
+            _ => return Err(Error::Syntax(declaration.to_string())),
+        }
+        Ok(())
+    }
+}
+
+/// 找到声明结束的 `>`，跳过字面量中的字符
+fn declaration_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (pos, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(pos),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.' | '#')
+}
+
+/// 声明内容的词法分析
+struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { rest: text }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn symbol(&mut self, symbol: char) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(symbol) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn word(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let end = self
+            .rest
+            .find(|c: char| !is_name_char(c))
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return None;
+        }
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(word.to_string())
+    }
+
+    fn literal(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let quote = self
+            .rest
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '"' | '\''))?;
+        let end = self.rest[1..].find(quote)?;
+        let literal = self.rest[1..end + 1].to_string();
+        self.rest = &self.rest[end + 2..];
+        Some(literal)
+    }
+
+    fn occurrence(&mut self) -> char {
+        match self.rest.chars().next() {
+            Some(c @ ('?' | '*' | '+')) => {
+                self.rest = &self.rest[1..];
+                c
+            }
+            _ => '1',
+        }
+    }
+
+    fn content(&mut self) -> Result<Content, Error> {
+        self.skip_whitespace();
+        if self.rest.starts_with("EMPTY") {
+            return Ok(Content::Empty);
+        }
+        if self.rest.starts_with("ANY") {
+            return Ok(Content::Any);
+        }
+        let error = |rest: &str| Error::Syntax(rest.to_string());
+        if !self.symbol('(') {
+            return Err(error(self.rest));
+        }
+        if self.rest.trim_start().starts_with("#PCDATA") {
+            self.word();
+            let mut names = Vec::new();
+            while self.symbol('|') {
+                names.push(self.word().ok_or_else(|| error(self.rest))?);
+            }
+            if !self.symbol(')') {
+                return Err(error(self.rest));
+            }
+            self.occurrence();
+            return Ok(Content::Mixed(names));
+        }
+        self.model_group().map(Content::Children)
+    }
+
+    /// 解析 `(` 之后的内容模型
+    fn model_group(&mut self) -> Result<Model, Error> {
+        let error = |rest: &str| Error::Syntax(rest.to_string());
+        let mut items = vec![self.model_item()?];
+        let mut separator = None;
+        loop {
+            self.skip_whitespace();
+            match self.rest.chars().next() {
+                Some(')') => {
+                    self.rest = &self.rest[1..];
+                    break;
+                }
+                Some(c @ (',' | '|')) if separator.is_none() || separator == Some(c) => {
+                    separator = Some(c);
+                    self.rest = &self.rest[1..];
+                    items.push(self.model_item()?);
+                }
+                _ => return Err(error(self.rest)),
+            }
+        }
+        let occurrence = self.occurrence();
+        Ok(match separator {
+            Some('|') => Model::Choice(items, occurrence),
+            _ => Model::Sequence(items, occurrence),
+        })
+    }
+
+    fn model_item(&mut self) -> Result<Model, Error> {
+        if self.symbol('(') {
+            return self.model_group();
+        }
+        let name = self.word().ok_or(Error::Syntax(self.rest.to_string()))?;
+        Ok(Model::Name(name, self.occurrence()))
+    }
+
+    fn attribute_type(&mut self) -> Result<AttributeType, Error> {
+        let enumeration = |tokens: &mut Self| {
+            if !tokens.symbol('(') {
+                return Err(Error::Syntax(tokens.rest.to_string()));
+            }
+            let mut values = Vec::new();
+            loop {
+                values.push(
+                    tokens
+                        .word()
+                        .ok_or(Error::Syntax(tokens.rest.to_string()))?,
+                );
+                if tokens.symbol(')') {
+                    break Ok(AttributeType::Enumeration(values));
+                }
+                if !tokens.symbol('|') {
+                    break Err(Error::Syntax(tokens.rest.to_string()));
+                }
+            }
+        };
+        self.skip_whitespace();
+        if self.rest.starts_with('(') {
+            return enumeration(self);
+        }
+        Ok(match self.word().as_deref() {
+            Some("CDATA") => AttributeType::CData,
+            Some("ID") => AttributeType::Id,
+            Some("IDREF") => AttributeType::IdRef,
+            Some("IDREFS") => AttributeType::IdRefs,
+            Some("ENTITY") => AttributeType::Entity,
+            Some("ENTITIES") => AttributeType::Entities,
+            Some("NMTOKEN") => AttributeType::NmToken,
+            Some("NMTOKENS") => AttributeType::NmTokens,
+            Some("NOTATION") => return enumeration(self),
+            _ => return Err(Error::Syntax(self.rest.to_string())),
+        })
+    }
+
+    fn default_value(&mut self) -> Result<DefaultValue, Error> {
+        self.skip_whitespace();
+        let error = |rest: &str| Error::Syntax(rest.to_string());
+        if self.symbol('#') {
+            return match self.word().as_deref() {
+                Some("REQUIRED") => Ok(DefaultValue::Required),
+                Some("IMPLIED") => Ok(DefaultValue::Implied),
+                Some("FIXED") => self
+                    .literal()
+                    .map(DefaultValue::Fixed)
+                    .ok_or_else(|| error(self.rest)),
+                _ => Err(error(self.rest)),
+            };
+        }
+        self.literal()
+            .map(|_| DefaultValue::Value)
+            .ok_or_else(|| error(self.rest))
+    }
+}
+
+impl Model {
+    fn to_particle(&self) -> Particle {
+        let occurs = |occurrence: char| match occurrence {
+            '?' => (0, Some(1)),
+            '*' => (0, None),
+            '+' => (1, None),
+            _ => (1, Some(1)),
+        };
+        let (term, occurrence) = match self {
+            Model::Name(name, occurrence) => (Term::Element(name.clone()), *occurrence),
+            Model::Sequence(items, occurrence) => (
+                Term::Sequence(items.iter().map(Model::to_particle).collect()),
+                *occurrence,
+            ),
+            Model::Choice(items, occurrence) => (
+                Term::Choice(items.iter().map(Model::to_particle).collect()),
+                *occurrence,
+            ),
+        };
+        let (min, max) = occurs(occurrence);
+        Particle::new(term, min, max)
+    }
+}
+
+struct Validator<'d, 'a, 'input> {
+    dtd: &'d Dtd,
+    ids: HashSet<String>,
+    /// 等待检查的 IDREF：元素、属性在原文中的范围、引用的 ID
+    references: Vec<(roxmltree::Node<'a, 'input>, std::ops::Range<usize>, String)>,
+    violations: Vec<Violation>,
+}
+
+impl<'a, 'input> Validator<'_, 'a, 'input> {
+    fn validate(&mut self, node: roxmltree::Node<'a, 'input>) {
+        let name = qname(node);
+        self.validate_attributes(node, name);
+
+        let children = node
+            .children()
+            .filter(|child| child.is_element())
+            .collect::<Vec<_>>();
+        let has_text = node
+            .children()
+            .any(|child| child.is_text() && !child.text().unwrap_or_default().trim().is_empty());
+        match self.dtd.elements.get(name) {
+            None => self.violations.push(Violation::element(
+                node,
+                format!("element <{}> is not declared", name),
+            )),
+            Some(Content::Any) => {}
+            Some(Content::Empty) => {
+                if node.has_children() {
+                    self.violations.push(Violation::element(
+                        node,
+                        format!("element <{}> is declared EMPTY but has content", name),
+                    ));
+                }
+            }
+            Some(Content::Mixed(names)) => {
+                for child in &children {
+                    if !names.iter().any(|allowed| allowed == qname(*child)) {
+                        self.violations.push(Violation::element(
+                            *child,
+                            format!("element <{}> is not allowed in <{}>", qname(*child), name),
+                        ));
+                    }
+                }
+            }
+            Some(Content::Children(model)) => {
+                if has_text {
+                    self.violations.push(Violation::element(
+                        node,
+                        format!("element <{}> cannot contain text", name),
+                    ));
+                }
+                let keys = children
+                    .iter()
+                    .map(|child| qname(*child).to_string())
+                    .collect::<Vec<_>>();
+                match model.to_particle().check(&keys) {
+                    Ok(()) => {}
+                    Err(Mismatch::Unexpected { index, expected }) => {
+                        self.violations.push(Violation::element(
+                            children[index],
+                            format!(
+                                "unexpected element <{}> in <{}>, {}",
+                                keys[index],
+                                name,
+                                Mismatch::describe(&expected)
+                            ),
+                        ))
+                    }
+                    Err(Mismatch::Incomplete { expected }) => {
+                        self.violations.push(Violation::element(
+                            node,
+                            format!(
+                                "content of <{}> is incomplete, {}",
+                                name,
+                                Mismatch::describe(&expected)
+                            ),
+                        ))
+                    }
+                }
+            }
+        }
+
+        for child in children {
+            self.validate(child);
+        }
+    }
+
+    fn validate_attributes(&mut self, node: roxmltree::Node<'a, 'input>, name: &str) {
+        let decls = self
+            .dtd
+            .attributes
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for attribute in node.attributes() {
+            let attribute_name = attribute_qname(node, &attribute);
+            let Some(decl) = decls.iter().find(|decl| decl.name == attribute_name) else {
+                self.violations.push(Violation::attribute(
+                    node,
+                    &attribute,
+                    format!(
+                        "attribute \"{}\" is not declared for <{}>",
+                        attribute_name, name
+                    ),
+                ));
+                continue;
+            };
+            let value = match decl.kind {
+                AttributeType::CData => attribute.value().to_string(),
+                _ => attribute
+                    .value()
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            };
+            if let Some(message) = self.check_value(node, &attribute, decl, &value) {
+                self.violations
+                    .push(Violation::attribute(node, &attribute, message));
+            }
+        }
+        for decl in decls {
+            if let DefaultValue::Required = decl.default {
+                if !node
+                    .attributes()
+                    .any(|attribute| attribute_qname(node, &attribute) == decl.name)
+                {
+                    self.violations.push(Violation::element(
+                        node,
+                        format!("required attribute \"{}\" is missing", decl.name),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_value(
+        &mut self,
+        node: roxmltree::Node<'a, 'input>,
+        attribute: &roxmltree::Attribute,
+        decl: &AttributeDecl,
+        value: &str,
+    ) -> Option<String> {
+        if let DefaultValue::Fixed(fixed) = &decl.default {
+            if value != fixed {
+                return Some(format!(
+                    "attribute \"{}\" must have the fixed value \"{}\"",
+                    decl.name, fixed
+                ));
+            }
+        }
+        let is_name = |value: &str| {
+            value
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_' || c == ':')
+                && value.chars().all(is_name_char)
+        };
+        let is_token = |value: &str| !value.is_empty() && value.chars().all(is_name_char);
+        match &decl.kind {
+            AttributeType::CData => None,
+            AttributeType::Id => {
+                if !is_name(value) {
+                    Some(format!("\"{}\" is not a valid ID", value))
+                } else if !self.ids.insert(value.to_string()) {
+                    Some(format!("duplicate ID \"{}\"", value))
+                } else {
+                    None
+                }
+            }
+            AttributeType::IdRef | AttributeType::IdRefs => {
+                for id in value.split(' ') {
+                    if !is_name(id) {
+                        return Some(format!("\"{}\" is not a valid IDREF", id));
+                    }
+                    self.references
+                        .push((node, attribute.range(), id.to_string()));
+                }
+                None
+            }
+            AttributeType::Entity | AttributeType::Entities => value
+                .split(' ')
+                .find(|entity| !is_name(entity))
+                .map(|entity| format!("\"{}\" is not a valid entity name", entity)),
+            AttributeType::NmToken | AttributeType::NmTokens => value
+                .split(' ')
+                .find(|token| !is_token(token))
+                .map(|token| format!("\"{}\" is not a valid NMTOKEN", token)),
+            AttributeType::Enumeration(values) => (!values.iter().any(|v| v == value))
+                .then(|| format!("\"{}\" is not one of: {}", value, values.join(", "))),
+        }
+    }
+}
+
+command_error! {
+    (NoDoctype, "document has no DOCTYPE declaration and no external DTD is given"),
+    (Unterminated, "dtd error: unterminated {0}", &'static str),
+    (Unsupported, "dtd error: {0} are not supported", String),
+    (Syntax, "dtd error: invalid declaration near `{0}`", String),
+}
@@ -0,0 +1,337 @@
+use super::code::{Brackets, Printer, Scanner, SyntaxError};
+use super::indent::{Indent, CODE_INDENTS};
+use super::registry::{self, Capabilities, Descriptor, Formatter, Options};
+use crate::command_error;
+
+/// 开始一个顶层定义的关键字
+const DEFINITIONS: &[&str] = &[
+    "query",
+    "mutation",
+    "subscription",
+    "fragment",
+    "schema",
+    "scalar",
+    "type",
+    "interface",
+    "union",
+    "enum",
+    "input",
+    "directive",
+    "extend",
+];
+
+///
+/// 格式化 GraphQL 查询和 SDL，逗号按需要重新生成
+///
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn format_graphql(input: &str, indent: Indent) -> Result<String, Error> {
+    let tokens = tokenize(input)?;
+    let mut printer = Printer::new(indent);
+    if printer.minify() {
+        minify(&mut printer, &tokens);
+    } else {
+        beautify(&mut printer, &tokens);
+    }
+    Ok(printer.finish())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Name,
+    /// `$name`
+    Variable,
+    /// `@name`
+    Directive,
+    Number,
+    String,
+    Punct,
+    Comment,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    newlines: usize,
+}
+
+impl Token<'_> {
+    fn is(&self, text: &str) -> bool {
+        matches!(self.kind, Kind::Punct | Kind::Name) && self.text == text
+    }
+
+    /// 可以作为一个字段、参数或值的结尾
+    fn ends_item(&self) -> bool {
+        match self.kind {
+            Kind::Name | Kind::Variable | Kind::Directive | Kind::Number | Kind::String => true,
+            Kind::Punct => matches!(self.text, ")" | "]" | "}" | "!"),
+            Kind::Comment => false,
+        }
+    }
+
+    /// 可以作为一个字段、参数或值的开头
+    fn starts_item(&self) -> bool {
+        match self.kind {
+            Kind::Name | Kind::Variable | Kind::Number | Kind::String => true,
+            Kind::Punct => matches!(self.text, "[" | "{" | "..."),
+            Kind::Directive | Kind::Comment => false,
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>, SyntaxError> {
+    let mut scanner = Scanner::new(input);
+    let mut brackets = Brackets::default();
+    let mut tokens = Vec::new();
+    let name = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    loop {
+        // 逗号和空白一样没有意义
+        let mut newlines = 0;
+        while scanner
+            .peek()
+            .is_some_and(|c| c.is_whitespace() || c == ',' || c == '\u{feff}')
+        {
+            if scanner.bump() == Some('\n') {
+                newlines += 1;
+            }
+        }
+        let Some(c) = scanner.peek() else {
+            break;
+        };
+        let start = scanner.offset();
+        let position = scanner.position();
+        let kind = match c {
+            '#' => {
+                scanner.eat_while(|c| c != '\n' && c != '\r');
+                Kind::Comment
+            }
+            '"' if scanner.starts_with("\"\"\"") => {
+                scanner.eat_str("\"\"\"");
+                loop {
+                    if scanner.eat_str("\\\"\"\"") {
+                        continue;
+                    }
+                    if scanner.eat_str("\"\"\"") {
+                        break;
+                    }
+                    if scanner.bump().is_none() {
+                        return Err(position.error("unterminated block string"));
+                    }
+                }
+                Kind::String
+            }
+            '"' => {
+                scanner.string(false)?;
+                Kind::String
+            }
+            '$' | '@' => {
+                scanner.bump();
+                if scanner.eat_while(name).is_empty() {
+                    return Err(position.error(format!("expected name after '{c}'")));
+                }
+                if c == '$' {
+                    Kind::Variable
+                } else {
+                    Kind::Directive
+                }
+            }
+            '-' | '0'..='9' => {
+                scanner.bump();
+                scanner.eat_while(|c| c.is_ascii_digit() || c == '.');
+                if scanner.eat('e') || scanner.eat('E') {
+                    let _ = scanner.eat('+') || scanner.eat('-');
+                    scanner.eat_while(|c| c.is_ascii_digit());
+                }
+                Kind::Number
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                scanner.eat_while(name);
+                Kind::Name
+            }
+            '.' => {
+                if !scanner.eat_str("...") {
+                    return Err(position.error("expected '...'"));
+                }
+                Kind::Punct
+            }
+            '{' | '(' | '[' => {
+                scanner.bump();
+                brackets.open(c, position);
+                Kind::Punct
+            }
+            '}' | ')' | ']' => {
+                scanner.bump();
+                brackets.close(c, position)?;
+                Kind::Punct
+            }
+            '!' | ':' | '=' | '|' | '&' => {
+                scanner.bump();
+                Kind::Punct
+            }
+            c => return Err(position.error(format!("unexpected character '{c}'"))),
+        };
+        tokens.push(Token {
+            kind,
+            text: scanner.slice(start),
+            newlines,
+        });
+    }
+    brackets.finish()?;
+    Ok(tokens)
+}
+
+fn minify(printer: &mut Printer, tokens: &[Token]) {
+    let mut previous: Option<&Token> = None;
+    for token in tokens.iter().filter(|token| token.kind != Kind::Comment) {
+        let word = |token: &Token| {
+            matches!(
+                token.kind,
+                Kind::Name | Kind::Number | Kind::Variable | Kind::Directive
+            )
+        };
+        if previous.is_some_and(|previous| {
+            word(previous) && matches!(token.kind, Kind::Name | Kind::Number)
+        }) {
+            printer.space();
+        }
+        printer.write(token.text);
+        previous = Some(token);
+    }
+}
+
+///
+/// 括号的上下文，圆括号、方括号和其中的对象值写在一行
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Context {
+    /// 选择集或类型定义的字段列表
+    Block,
+    Inline,
+}
+
+fn beautify(printer: &mut Printer, tokens: &[Token]) {
+    let mut stack: Vec<Context> = Vec::new();
+    let mut previous: Option<&Token> = None;
+    let mut before: Option<&Token> = None;
+    for token in tokens {
+        if token.kind == Kind::Comment {
+            if token.newlines == 0 && previous.is_some() {
+                if printer.ends_with("\n") {
+                    printer.pop();
+                }
+                printer.space();
+            } else {
+                printer.newline();
+            }
+            printer.write(token.text);
+            printer.newline();
+            continue;
+        }
+        let context = stack.last().copied();
+        if let Some(previous) = previous {
+            let after_spread = previous.is("on") && before.is_some_and(|before| before.is("..."));
+            let new_item = previous.ends_item()
+                && token.starts_item()
+                && !after_spread
+                && !token.is("{")
+                && !previous.is("...");
+            match context {
+                None if token.kind == Kind::String
+                    || (token.kind == Kind::Name
+                        && DEFINITIONS.contains(&token.text)
+                        && !previous.is("extend")
+                        && previous.kind != Kind::String)
+                    || (token.is("{") && previous.is("}")) =>
+                {
+                    printer.blank_line();
+                }
+                None if previous.kind == Kind::String => printer.newline(),
+                Some(Context::Block) if new_item => printer.newline(),
+                Some(Context::Inline) if new_item => {
+                    printer.write(",");
+                    printer.space();
+                }
+                _ => {
+                    if needs_space(previous, token) {
+                        printer.space();
+                    }
+                }
+            }
+        }
+        match token.text {
+            "{" if token.kind == Kind::Punct => {
+                let inline = stack.contains(&Context::Inline);
+                printer.write("{");
+                if inline {
+                    stack.push(Context::Inline);
+                    printer.space();
+                } else {
+                    stack.push(Context::Block);
+                    printer.newline();
+                    printer.indent();
+                }
+            }
+            "}" if token.kind == Kind::Punct => {
+                if stack.pop() == Some(Context::Block) {
+                    printer.dedent();
+                    printer.newline();
+                    printer.write("}");
+                    printer.newline();
+                } else {
+                    printer.space();
+                    printer.write("}");
+                }
+            }
+            "(" | "[" if token.kind == Kind::Punct => {
+                stack.push(Context::Inline);
+                printer.write(token.text);
+            }
+            ")" | "]" if token.kind == Kind::Punct => {
+                stack.pop();
+                printer.write(token.text);
+            }
+            _ => printer.write(token.text),
+        }
+        before = previous;
+        previous = Some(token);
+    }
+}
+
+/// 同一行中两个记号之间是否需要空格
+fn needs_space(previous: &Token, token: &Token) -> bool {
+    if previous.kind == Kind::Punct && matches!(previous.text, "(" | "[" | "...") {
+        return previous.text == "..." && token.is("on");
+    }
+    if token.kind == Kind::Punct {
+        return !matches!(token.text, ")" | "]" | "!" | ":" | "(" | "}");
+    }
+    true
+}
+
+pub struct GraphqlFormatter;
+
+impl Formatter for GraphqlFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "graphql",
+            label: "GraphQL",
+            extensions: &["graphql", "gql"],
+            capabilities: Capabilities::new(CODE_INDENTS, false),
+            options: Vec::new(),
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        _: bool,
+        _: &Options,
+    ) -> Result<String, registry::Error> {
+        format_graphql(input, indent).map_err(registry::Error::format)
+    }
+}
+
+command_error! {
+    (Syntax, "syntax error: {0}", #[from] SyntaxError),
+}
@@ -0,0 +1,523 @@
+use super::code::{Position, Printer, Scanner, SyntaxError};
+use super::indent::{Indent, CODE_INDENTS};
+use super::registry::{self, Capabilities, Descriptor, Formatter, Options};
+use super::{css, javascript};
+use crate::command_error;
+
+/// 没有结束标签的空元素
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// 内容不解析为标签的元素，`pre` 和 `textarea` 的空白有意义，原样保留
+const RAW_ELEMENTS: &[&str] = &["script", "style", "pre", "textarea"];
+
+/// 结束标签可以省略的元素
+const OPTIONAL_END: &[&str] = &[
+    "html", "head", "body", "p", "li", "dt", "dd", "option", "optgroup", "thead", "tbody", "tfoot",
+    "tr", "td", "th", "colgroup", "caption", "rt", "rp",
+];
+
+/// 隐含结束 `<p>` 的块级元素
+const CLOSES_PARAGRAPH: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// 与文本排在同一行的行内元素
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "button", "cite", "code", "data", "dfn", "em", "i",
+    "img", "input", "kbd", "label", "mark", "q", "s", "samp", "select", "small", "span", "strong",
+    "sub", "sup", "time", "u", "var", "wbr",
+];
+
+/// 行内内容超过该长度时拆成多行
+const MAX_INLINE_WIDTH: usize = 100;
+
+///
+/// 格式化 HTML，识别空元素和可省略的结束标签，`<script>` 和 `<style>` 的内容按 JavaScript 和 CSS 格式化
+///
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn format_html(input: &str, indent: Indent) -> Result<String, Error> {
+    let nodes = parse(input)?;
+    let mut printer = Printer::new(indent);
+    write_nodes(&mut printer, &nodes, indent);
+    Ok(printer.finish())
+}
+
+#[derive(Debug)]
+enum Node<'a> {
+    Doctype(&'a str),
+    Comment(&'a str),
+    Text(&'a str),
+    Element(Element<'a>),
+}
+
+#[derive(Debug)]
+struct Element<'a> {
+    /// 小写的标签名
+    name: String,
+    /// 原文中的标签名
+    tag: &'a str,
+    attributes: Vec<(&'a str, Option<&'a str>)>,
+    children: Vec<Node<'a>>,
+    /// 原文中以 `/>` 结尾
+    self_closing: bool,
+    /// 原样保留的内容
+    raw: Option<&'a str>,
+    position: Position,
+}
+
+impl Element<'_> {
+    fn is_inline(&self) -> bool {
+        INLINE_ELEMENTS.contains(&self.name.as_str()) && self.children.iter().all(Node::is_inline)
+    }
+}
+
+impl Node<'_> {
+    fn is_inline(&self) -> bool {
+        match self {
+            Node::Text(_) => true,
+            Node::Element(element) => element.is_inline(),
+            Node::Doctype(_) | Node::Comment(_) => false,
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Node<'_>>, SyntaxError> {
+    let mut scanner = Scanner::new(input);
+    // 根节点列表和尚未闭合的元素
+    let mut root = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+    while !scanner.is_eof() {
+        let position = scanner.position();
+        let start = scanner.offset();
+        let node = if scanner.starts_with("<!--") {
+            if !scanner.skip_until("-->") {
+                return Err(position.error("unterminated comment"));
+            }
+            Node::Comment(scanner.slice(start))
+        } else if scanner.starts_with("<!") || scanner.starts_with("<?") {
+            if !scanner.skip_until(">") {
+                return Err(position.error("unterminated declaration"));
+            }
+            Node::Doctype(scanner.slice(start))
+        } else if scanner.starts_with("</") {
+            scanner.eat_str("</");
+            let name = scanner
+                .eat_while(|c| !c.is_whitespace() && c != '>')
+                .to_ascii_lowercase();
+            scanner.eat_while(char::is_whitespace);
+            if !scanner.eat('>') {
+                return Err(position.error(format!("expected '>' after '</{name}'")));
+            }
+            close(&mut root, &mut stack, &name, position)?;
+            continue;
+        } else if scanner.peek() == Some('<')
+            && scanner.peek_nth(1).is_some_and(|c| c.is_ascii_alphabetic())
+        {
+            let element = open_tag(&mut scanner, position)?;
+            while stack
+                .last()
+                .is_some_and(|open| implies_end(&element.name, &open.name))
+            {
+                let open = stack.pop().into_iter().map(Node::Element);
+                push(&mut root, &mut stack, open);
+            }
+            if element.self_closing
+                || element.raw.is_some()
+                || VOID_ELEMENTS.contains(&element.name.as_str())
+            {
+                Node::Element(element)
+            } else {
+                stack.push(element);
+                continue;
+            }
+        } else {
+            scanner.bump();
+            scanner.eat_while(|c| c != '<');
+            Node::Text(scanner.slice(start))
+        };
+        push(&mut root, &mut stack, Some(node));
+    }
+    while let Some(element) = stack.pop() {
+        if !OPTIONAL_END.contains(&element.name.as_str()) {
+            return Err(element
+                .position
+                .error(format!("unclosed <{}>", element.tag)));
+        }
+        push(&mut root, &mut stack, Some(Node::Element(element)));
+    }
+    Ok(root)
+}
+
+fn push<'a>(
+    root: &mut Vec<Node<'a>>,
+    stack: &mut [Element<'a>],
+    nodes: impl IntoIterator<Item = Node<'a>>,
+) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.extend(nodes),
+        None => root.extend(nodes),
+    }
+}
+
+///
+/// 闭合元素，中间结束标签可省略的元素一并闭合
+///
+fn close<'a>(
+    root: &mut Vec<Node<'a>>,
+    stack: &mut Vec<Element<'a>>,
+    name: &str,
+    position: Position,
+) -> Result<(), SyntaxError> {
+    let Some(index) = stack.iter().rposition(|element| element.name == name) else {
+        if VOID_ELEMENTS.contains(&name) {
+            return Ok(());
+        }
+        return Err(position.error(format!("unexpected </{name}>")));
+    };
+    if let Some(element) = stack[index + 1..]
+        .iter()
+        .find(|element| !OPTIONAL_END.contains(&element.name.as_str()))
+    {
+        return Err(position.error(format!(
+            "expected </{}> (opened at line {}, column {}), found </{name}>",
+            element.tag, element.position.line, element.position.column
+        )));
+    }
+    while stack.len() > index {
+        let element = stack.pop().into_iter().map(Node::Element);
+        push(root, stack, element);
+    }
+    Ok(())
+}
+
+/// 新打开的元素是否隐含结束当前元素，如连续的 `<li>`
+fn implies_end(name: &str, open: &str) -> bool {
+    match (name, open) {
+        ("li", "li") | ("option" | "optgroup", "option") | ("optgroup", "optgroup") => true,
+        ("dt" | "dd", "dt" | "dd") | ("td" | "th", "td" | "th") | ("tr", "tr" | "td" | "th") => {
+            true
+        }
+        ("thead" | "tbody" | "tfoot", "thead" | "tbody" | "tfoot" | "tr" | "td" | "th") => true,
+        (name, "p") => CLOSES_PARAGRAPH.contains(&name),
+        _ => false,
+    }
+}
+
+fn open_tag<'a>(scanner: &mut Scanner<'a>, position: Position) -> Result<Element<'a>, SyntaxError> {
+    let unterminated = || position.error("unterminated tag");
+    scanner.bump();
+    let tag = scanner.eat_while(|c| !c.is_whitespace() && c != '>' && c != '/');
+    let name = tag.to_ascii_lowercase();
+    let mut attributes = Vec::new();
+    let self_closing = loop {
+        scanner.eat_while(char::is_whitespace);
+        if scanner.eat_str("/>") {
+            break true;
+        }
+        if scanner.eat('>') {
+            break false;
+        }
+        if scanner.is_eof() {
+            return Err(unterminated());
+        }
+        let attribute =
+            scanner.eat_while(|c| !c.is_whitespace() && !matches!(c, '>' | '=') && c != '/');
+        if attribute.is_empty() {
+            // 单独的 `/`
+            scanner.bump();
+            continue;
+        }
+        scanner.eat_while(char::is_whitespace);
+        let value = if scanner.eat('=') {
+            scanner.eat_while(char::is_whitespace);
+            let start = scanner.offset();
+            match scanner.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value_position = scanner.position();
+                    scanner.bump();
+                    scanner.eat_while(|c| c != quote);
+                    if !scanner.eat(quote) {
+                        return Err(value_position.error("unterminated attribute value"));
+                    }
+                }
+                Some(_) => {
+                    scanner.eat_while(|c| !c.is_whitespace() && c != '>');
+                }
+                None => return Err(unterminated()),
+            }
+            Some(scanner.slice(start))
+        } else {
+            None
+        };
+        attributes.push((attribute, value));
+    };
+    let raw = if !self_closing && RAW_ELEMENTS.contains(&name.as_str()) {
+        let start = scanner.offset();
+        let end = format!("</{name}");
+        loop {
+            if scanner.is_eof() {
+                return Err(position.error(format!("unclosed <{tag}>")));
+            }
+            if scanner
+                .rest()
+                .get(..end.len())
+                .is_some_and(|rest| rest.eq_ignore_ascii_case(&end))
+            {
+                break;
+            }
+            scanner.bump();
+        }
+        let content = scanner.slice(start);
+        scanner.skip_until(">");
+        Some(content)
+    } else {
+        None
+    };
+    Ok(Element {
+        name,
+        tag,
+        attributes,
+        children: Vec::new(),
+        self_closing,
+        raw,
+        position,
+    })
+}
+
+fn open_text(element: &Element) -> String {
+    let mut text = format!("<{}", element.tag);
+    for (name, value) in &element.attributes {
+        text.push(' ');
+        text.push_str(name);
+        if let Some(value) = value {
+            text.push('=');
+            text.push_str(value);
+        }
+    }
+    text.push_str(if element.self_closing { " />" } else { ">" });
+    text
+}
+
+fn close_text(element: &Element) -> String {
+    if element.self_closing || VOID_ELEMENTS.contains(&element.name.as_str()) {
+        String::new()
+    } else {
+        format!("</{}>", element.tag)
+    }
+}
+
+/// 压缩连续空白为一个空格
+fn collapse(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !output.ends_with(' ') {
+                output.push(' ');
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// 将行内内容写成一行
+fn inline(nodes: &[Node]) -> String {
+    let mut output = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => {
+                let text = collapse(text);
+                if output.ends_with(' ') {
+                    output.push_str(text.trim_start());
+                } else {
+                    output.push_str(&text);
+                }
+            }
+            Node::Element(element) => {
+                output.push_str(&open_text(element));
+                match element.raw {
+                    Some(raw) => output.push_str(raw),
+                    None => output.push_str(&inline(&element.children)),
+                }
+                output.push_str(&close_text(element));
+            }
+            Node::Doctype(text) | Node::Comment(text) => output.push_str(text),
+        }
+    }
+    output
+}
+
+///
+/// 行内节点连续排成一行，块级节点各占一行
+///
+fn write_nodes(printer: &mut Printer, nodes: &[Node], indent: Indent) {
+    let mut index = 0;
+    while index < nodes.len() {
+        let run = nodes[index..]
+            .iter()
+            .take_while(|node| node.is_inline())
+            .count();
+        if run > 0 {
+            let text = inline(&nodes[index..index + run]);
+            let text = if printer.minify() {
+                // 块级元素之间的空白没有意义，行内内容两端的空白保留一个
+                let blocks = (index > 0, index + run < nodes.len());
+                match blocks {
+                    (true, true) => text.trim().to_string(),
+                    (true, false) => text.trim_start().to_string(),
+                    (false, true) => text.trim_end().to_string(),
+                    (false, false) => text,
+                }
+            } else {
+                text.trim().to_string()
+            };
+            if !text.is_empty() {
+                printer.newline();
+                printer.write(&text);
+                printer.newline();
+            }
+            index += run;
+            continue;
+        }
+        match &nodes[index] {
+            Node::Doctype(text) => {
+                printer.newline();
+                printer.write(text);
+                printer.newline();
+            }
+            Node::Comment(text) => {
+                // 条件注释在压缩时也要保留
+                if !printer.minify() || text.starts_with("<!--[if") {
+                    printer.newline();
+                    printer.write(text);
+                    printer.newline();
+                }
+            }
+            Node::Element(element) => write_element(printer, element, indent),
+            Node::Text(_) => {}
+        }
+        index += 1;
+    }
+}
+
+fn write_element(printer: &mut Printer, element: &Element, indent: Indent) {
+    printer.newline();
+    printer.write(&open_text(element));
+    if let Some(raw) = element.raw {
+        write_raw(printer, element, raw, indent);
+    } else if !element.children.is_empty() {
+        let inline = element.children.iter().all(Node::is_inline);
+        let text = inline.then(|| collapse(self::inline(&element.children).trim()));
+        match text {
+            Some(text) if printer.minify() || text.len() <= MAX_INLINE_WIDTH => {
+                printer.write(&text)
+            }
+            _ => {
+                printer.newline();
+                printer.indent();
+                write_nodes(printer, &element.children, indent);
+                printer.dedent();
+                printer.newline();
+            }
+        }
+    }
+    printer.write(&close_text(element));
+    printer.newline();
+}
+
+///
+/// `<script>` 和 `<style>` 的内容按对应语言格式化，格式化失败或其他元素的内容原样保留
+///
+fn write_raw(printer: &mut Printer, element: &Element, raw: &str, indent: Indent) {
+    let script = element.attributes.iter().all(|(name, value)| {
+        !name.eq_ignore_ascii_case("type")
+            || value.is_some_and(|value| {
+                let value = value.trim_matches(['"', '\'']).to_ascii_lowercase();
+                value.contains("javascript") || value == "module" || value.contains("typescript")
+            })
+    });
+    let formatted = match element.name.as_str() {
+        "script" if script => javascript::format_javascript(raw, indent).ok(),
+        "style" => css::format_css(raw, indent).ok(),
+        _ => None,
+    };
+    match formatted {
+        Some(formatted) if formatted.trim().is_empty() => {}
+        Some(formatted) if printer.minify() => printer.write(&formatted),
+        Some(formatted) => {
+            printer.newline();
+            printer.indent();
+            for line in formatted.lines() {
+                if line.is_empty() {
+                    printer.blank_line();
+                } else {
+                    printer.write(line);
+                    printer.newline();
+                }
+            }
+            printer.dedent();
+        }
+        None => printer.write(raw),
+    }
+}
+
+pub struct HtmlFormatter;
+
+impl Formatter for HtmlFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "html",
+            label: "HTML",
+            extensions: &["html", "htm"],
+            capabilities: Capabilities::new(CODE_INDENTS, false),
+            options: Vec::new(),
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        _: bool,
+        _: &Options,
+    ) -> Result<String, registry::Error> {
+        format_html(input, indent).map_err(registry::Error::format)
+    }
+}
+
+command_error! {
+    (Syntax, "syntax error: {0}", #[from] SyntaxError),
+}
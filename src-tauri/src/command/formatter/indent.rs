@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+///
+/// 各格式化器共用的缩进方式
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Indent {
+    TwoSpace,
+    FourSpace,
+    Tab,
+    /// 不调整空白，只应用其他选项；不能保留空白的格式按压缩处理
+    None,
+    /// 去除所有无意义的空白
+    Minify,
+}
+
+/// 全部缩进方式
+pub const ALL_INDENTS: &[Indent] = &[
+    Indent::TwoSpace,
+    Indent::FourSpace,
+    Indent::Tab,
+    Indent::None,
+    Indent::Minify,
+];
+
+/// 代码类格式化器支持的缩进方式
+pub const CODE_INDENTS: &[Indent] = &[
+    Indent::TwoSpace,
+    Indent::FourSpace,
+    Indent::Tab,
+    Indent::Minify,
+];
+
+impl Indent {
+    /// 一级缩进的内容，`None` 和 `Minify` 不缩进也不换行
+    pub fn unit(self) -> Option<&'static str> {
+        match self {
+            Indent::TwoSpace => Some("  "),
+            Indent::FourSpace => Some("    "),
+            Indent::Tab => Some("\t"),
+            Indent::None | Indent::Minify => None,
+        }
+    }
+}
@@ -0,0 +1,762 @@
+use super::code::{Brackets, Position, Printer, Scanner, SyntaxError};
+use super::indent::{Indent, CODE_INDENTS};
+use super::registry::{self, Capabilities, Descriptor, Formatter, Options};
+use crate::command_error;
+
+/// 按长度从长到短排列，保证最长匹配
+const PUNCTUATORS: &[&str] = &[
+    ">>>=", "...", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "=>", "==", "!=",
+    "<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=",
+    "<<", ">>", "**",
+];
+
+/// 之后出现的 `/` 是正则表达式而不是除号的关键字
+const REGEX_KEYWORDS: &[&str] = &[
+    "return",
+    "typeof",
+    "instanceof",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "case",
+    "do",
+    "else",
+    "yield",
+    "await",
+];
+
+/// 与下一个记号之间不允许换行的关键字，源码中的换行会插入分号，压缩时必须保留
+const RESTRICTED_KEYWORDS: &[&str] = &["return", "break", "continue", "throw", "yield", "async"];
+
+/// 与后面的 `(` 之间保留空格的关键字
+const SPACED_KEYWORDS: &[&str] = &[
+    "if",
+    "for",
+    "while",
+    "switch",
+    "catch",
+    "with",
+    "return",
+    "typeof",
+    "await",
+    "yield",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "case",
+    "else",
+    "do",
+    "instanceof",
+    "extends",
+    "as",
+    "from",
+    "const",
+    "let",
+    "var",
+    "export",
+    "import",
+    "async",
+];
+
+///
+/// 格式化 JavaScript/TypeScript，`Indent::Minify` 时压缩，源码中可能触发自动分号插入的换行会保留
+///
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn format_javascript(input: &str, indent: Indent) -> Result<String, Error> {
+    let mut tokens = tokenize(input)?;
+    mark_generics(&mut tokens);
+    let mut printer = Printer::new(indent);
+    if printer.minify() {
+        minify(&mut printer, &tokens);
+    } else {
+        Beautifier::new(&mut printer).write(&tokens);
+    }
+    Ok(printer.finish())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Word,
+    Number,
+    String,
+    Template,
+    Regex,
+    Punct,
+    LineComment,
+    BlockComment,
+    /// TypeScript 泛型的尖括号
+    Angle,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    /// 与前一个记号之间的换行数
+    newlines: usize,
+}
+
+impl Token<'_> {
+    fn is(&self, text: &str) -> bool {
+        matches!(self.kind, Kind::Punct | Kind::Word | Kind::Angle) && self.text == text
+    }
+
+    fn is_comment(&self) -> bool {
+        matches!(self.kind, Kind::LineComment | Kind::BlockComment)
+    }
+
+    /// 可以作为表达式结尾的记号
+    fn ends_expression(&self) -> bool {
+        match self.kind {
+            Kind::Word => !REGEX_KEYWORDS.contains(&self.text),
+            Kind::Number | Kind::String | Kind::Template | Kind::Regex => true,
+            Kind::Punct => matches!(self.text, ")" | "]" | "}" | "++" | "--"),
+            Kind::Angle => self.text == ">",
+            Kind::LineComment | Kind::BlockComment => false,
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>, SyntaxError> {
+    let mut scanner = Scanner::new(input);
+    let mut brackets = Brackets::default();
+    let mut tokens: Vec<Token> = Vec::new();
+    loop {
+        let newlines = scanner.skip_whitespace();
+        let Some(c) = scanner.peek() else {
+            break;
+        };
+        let start = scanner.offset();
+        let position = scanner.position();
+        let previous = tokens.iter().rev().find(|token| !token.is_comment());
+        let kind = if scanner.starts_with("//") {
+            scanner.eat_while(|c| c != '\n');
+            Kind::LineComment
+        } else if scanner.starts_with("/*") {
+            if !scanner.skip_until("*/") {
+                return Err(position.error("unterminated comment"));
+            }
+            Kind::BlockComment
+        } else if c == '/' && !previous.is_some_and(Token::ends_expression) {
+            regex(&mut scanner, position)?;
+            Kind::Regex
+        } else if c == '"' || c == '\'' {
+            scanner.string(false)?;
+            Kind::String
+        } else if c == '`' {
+            template(&mut scanner)?;
+            Kind::Template
+        } else if c.is_ascii_digit()
+            || (c == '.' && scanner.peek_nth(1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            number(&mut scanner);
+            Kind::Number
+        } else if is_word(c) || c == '#' && scanner.peek_nth(1).is_some_and(is_word) {
+            scanner.bump();
+            scanner.eat_while(|c| is_word(c) || c.is_ascii_digit());
+            Kind::Word
+        } else {
+            if !PUNCTUATORS
+                .iter()
+                .any(|punctuator| scanner.eat_str(punctuator))
+            {
+                scanner.bump();
+            }
+            match c {
+                '(' | '[' | '{' => brackets.open(c, position),
+                ')' | ']' | '}' => brackets.close(c, position)?,
+                c if c.is_ascii_punctuation() => {}
+                c => return Err(position.error(format!("unexpected character '{c}'"))),
+            }
+            Kind::Punct
+        };
+        tokens.push(Token {
+            kind,
+            text: scanner.slice(start),
+            newlines,
+        });
+    }
+    brackets.finish()?;
+    Ok(tokens)
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$' || (!c.is_ascii() && !c.is_whitespace())
+}
+
+fn number(scanner: &mut Scanner) {
+    let hex = scanner.starts_with("0x") || scanner.starts_with("0X");
+    loop {
+        let text = scanner.eat_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        let exponent = !hex && text.ends_with(['e', 'E']);
+        if !(exponent && (scanner.eat('+') || scanner.eat('-'))) {
+            break;
+        }
+    }
+}
+
+fn regex(scanner: &mut Scanner, position: Position) -> Result<(), SyntaxError> {
+    scanner.bump();
+    let mut class = false;
+    loop {
+        match scanner.bump() {
+            Some('\\') => {
+                scanner.bump();
+            }
+            Some('[') => class = true,
+            Some(']') => class = false,
+            Some('/') if !class => break,
+            Some('\n') | None => return Err(position.error("unterminated regular expression")),
+            Some(_) => {}
+        }
+    }
+    scanner.eat_while(|c| c.is_ascii_alphabetic());
+    Ok(())
+}
+
+///
+/// 读取模板字符串，`${}` 中的表达式可以继续嵌套字符串和模板
+///
+fn template(scanner: &mut Scanner) -> Result<(), SyntaxError> {
+    let position = scanner.position();
+    scanner.bump();
+    loop {
+        match scanner.bump() {
+            Some('\\') => {
+                scanner.bump();
+            }
+            Some('`') => return Ok(()),
+            Some('$') if scanner.eat('{') => {
+                let mut depth = 1;
+                while depth > 0 {
+                    match scanner.peek() {
+                        Some('{') => depth += 1,
+                        Some('}') => depth -= 1,
+                        Some('"' | '\'') => {
+                            scanner.string(false)?;
+                            continue;
+                        }
+                        Some('`') => {
+                            template(scanner)?;
+                            continue;
+                        }
+                        Some(_) => {}
+                        None => return Err(position.error("unterminated template literal")),
+                    }
+                    scanner.bump();
+                }
+            }
+            Some(_) => {}
+            None => return Err(position.error("unterminated template literal")),
+        }
+    }
+}
+
+///
+/// 将看起来像泛型参数的 `<...>` 标记为尖括号，避免按比较运算符加空格
+///
+fn mark_generics(tokens: &mut [Token]) {
+    let mut index = 0;
+    while index < tokens.len() {
+        let after_name = index > 0 && tokens[index - 1].kind == Kind::Word;
+        if !(tokens[index].is("<") && after_name) {
+            index += 1;
+            continue;
+        }
+        let mut depth = 0;
+        let mut end = None;
+        for (offset, token) in tokens[index..].iter().enumerate().take(64) {
+            let allowed = match token.kind {
+                Kind::Word | Kind::String | Kind::Number => true,
+                Kind::Punct => matches!(
+                    token.text,
+                    "<" | ">"
+                        | ">>"
+                        | ","
+                        | "."
+                        | "["
+                        | "]"
+                        | "|"
+                        | "&"
+                        | "("
+                        | ")"
+                        | ":"
+                        | "?"
+                        | "=>"
+                        | "{"
+                        | "}"
+                        | ";"
+                        | "="
+                        | "..."
+                ),
+                _ => false,
+            };
+            if !allowed {
+                break;
+            }
+            match token.text {
+                "<" => depth += 1,
+                ">" => depth -= 1,
+                ">>" => depth -= 2,
+                _ => {}
+            }
+            if depth <= 0 {
+                end = Some(index + offset);
+                break;
+            }
+        }
+        // `a < b > c` 这样的比较表达式后面紧跟着操作数
+        let end = end.filter(|end| {
+            depth == 0
+                && tokens.get(end + 1).map_or(true, |next| {
+                    !matches!(next.kind, Kind::Word | Kind::Number | Kind::String)
+                        || next.is("extends")
+                        || next.is("implements")
+                })
+        });
+        match end {
+            Some(end) => {
+                for token in &mut tokens[index..=end] {
+                    if matches!(token.text, "<" | ">" | ">>") {
+                        token.kind = Kind::Angle;
+                    }
+                }
+                index = end + 1;
+            }
+            None => index += 1,
+        }
+    }
+}
+
+///
+/// 去掉注释和多余空白，只在记号会粘连或源码换行可能影响自动分号插入时保留分隔
+///
+fn minify(printer: &mut Printer, tokens: &[Token]) {
+    let mut previous: Option<&Token> = None;
+    for token in tokens {
+        if token.is_comment() {
+            if token.kind == Kind::BlockComment && token.text.starts_with("/*!") {
+                printer.write(token.text);
+                printer.line_break();
+            }
+            continue;
+        }
+        if let Some(previous) = previous {
+            let word = |token: &Token| {
+                token
+                    .text
+                    .ends_with(|c: char| is_word(c) || c.is_ascii_digit())
+                    && matches!(token.kind, Kind::Word | Kind::Number | Kind::Regex)
+            };
+            let starts_word = token
+                .text
+                .starts_with(|c: char| is_word(c) || c.is_ascii_digit() || c == '#');
+            let glued = (word(previous) && starts_word)
+                || (previous.kind == Kind::Number && token.text.starts_with('.'))
+                || (previous.text.ends_with('+') && token.text.starts_with('+'))
+                || (previous.text.ends_with('-') && token.text.starts_with('-'))
+                || (previous.text.ends_with('/') && token.text.starts_with('/'));
+            let restricted =
+                previous.kind == Kind::Word && RESTRICTED_KEYWORDS.contains(&previous.text);
+            if token.newlines > 0
+                && (restricted || (previous.ends_expression() && starts_statement(token)))
+            {
+                printer.line_break();
+            } else if glued {
+                printer.space();
+            }
+        }
+        printer.write(token.text);
+        previous = Some(token);
+    }
+}
+
+/// 可能开始一条新语句的记号
+fn starts_statement(token: &Token) -> bool {
+    match token.kind {
+        Kind::Word | Kind::Number | Kind::String | Kind::Template | Kind::Regex => {
+            !matches!(token.text, "in" | "of" | "instanceof" | "as" | "satisfies")
+        }
+        Kind::Punct => matches!(
+            token.text,
+            "(" | "[" | "{" | "++" | "--" | "+" | "-" | "!" | "~" | "@" | "<"
+        ),
+        _ => false,
+    }
+}
+
+///
+/// 括号和块的上下文
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Context {
+    /// 语句块，`switch` 块中的 `case` 比语句少缩进一级
+    Block { switch: bool },
+    /// 对象字面量，每个属性一行
+    Object,
+    /// 圆括号或方括号，`multiline` 表示源码中内容另起一行，此时每项一行
+    Group { multiline: bool, for_header: bool },
+}
+
+struct Beautifier<'a> {
+    printer: &'a mut Printer,
+    stack: Vec<Context>,
+    /// 当前层级中尚未匹配 `:` 的三元运算符个数
+    ternaries: Vec<usize>,
+    /// 已输出 `case`/`default`，等待对应的 `:`
+    case: bool,
+}
+
+impl<'a> Beautifier<'a> {
+    fn new(printer: &'a mut Printer) -> Self {
+        Beautifier {
+            printer,
+            stack: Vec::new(),
+            ternaries: vec![0],
+            case: false,
+        }
+    }
+
+    fn context(&self) -> Context {
+        self.stack
+            .last()
+            .copied()
+            .unwrap_or(Context::Block { switch: false })
+    }
+
+    fn in_block(&self) -> bool {
+        matches!(self.context(), Context::Block { .. })
+    }
+
+    fn write(&mut self, tokens: &[Token]) {
+        let mut before: Option<Token> = None;
+        let mut previous: Option<Token> = None;
+        for (index, token) in tokens.iter().enumerate() {
+            let next = tokens.get(index + 1);
+            if token.is_comment() {
+                self.comment(token, previous.is_some());
+                continue;
+            }
+            // TypeScript 的可选标记 `name?: T`
+            let optional = token.is("?")
+                && previous.is_some_and(|previous| previous.kind == Kind::Word)
+                && next.is_some_and(|next| matches!(next.text, ":" | ")" | "," | "="));
+            if let Some(previous) = previous.as_ref().filter(|_| !optional) {
+                self.separate(before.as_ref(), previous, token);
+            }
+            match (token.kind, token.text) {
+                (Kind::Punct, "{") => self.open_brace(tokens, index, previous.as_ref()),
+                (Kind::Punct, "}") => self.close_brace(previous.as_ref(), next),
+                (Kind::Punct, "(" | "[") => {
+                    let multiline = next
+                        .is_some_and(|next| next.newlines > 0 && !next.is(")") && !next.is("]"));
+                    let for_header = previous.is_some_and(|previous| previous.is("for"));
+                    self.printer.write(token.text);
+                    self.stack.push(Context::Group {
+                        multiline,
+                        for_header,
+                    });
+                    self.ternaries.push(0);
+                    if multiline {
+                        self.printer.newline();
+                        self.printer.indent();
+                    }
+                }
+                (Kind::Punct, ")" | "]") => {
+                    if let Some(Context::Group {
+                        multiline: true, ..
+                    }) = self.stack.pop()
+                    {
+                        self.printer.dedent();
+                        self.printer.newline();
+                    }
+                    self.ternaries.pop();
+                    self.printer.write(token.text);
+                }
+                (Kind::Punct, ";") => {
+                    self.printer.write(";");
+                    if !matches!(
+                        self.context(),
+                        Context::Group {
+                            for_header: true,
+                            ..
+                        }
+                    ) {
+                        self.printer.newline();
+                    }
+                }
+                (Kind::Punct, ",") => {
+                    self.printer.write(",");
+                    if matches!(
+                        self.context(),
+                        Context::Object
+                            | Context::Group {
+                                multiline: true,
+                                ..
+                            }
+                    ) {
+                        self.printer.newline();
+                    }
+                }
+                (Kind::Punct, "?") if !optional => {
+                    if let Some(count) = self.ternaries.last_mut() {
+                        *count += 1;
+                    }
+                    self.printer.write("?");
+                }
+                (Kind::Punct, ":") if self.case && self.ternaries.last() == Some(&0) => {
+                    self.case = false;
+                    self.printer.write(":");
+                    self.printer.newline();
+                }
+                (Kind::Word, "case" | "default")
+                    if self.printer.is_line_start()
+                        && self.context() == (Context::Block { switch: true }) =>
+                {
+                    self.case = true;
+                    self.printer.dedent();
+                    self.printer.write(token.text);
+                    self.printer.indent();
+                }
+                _ => self.printer.write(token.text),
+            }
+            before = previous;
+            previous = Some(*token);
+        }
+    }
+
+    ///
+    /// 跟在赋值、参数、属性值等位置的 `{` 是对象字面量，其余是语句块
+    ///
+    fn open_brace(&mut self, tokens: &[Token], index: usize, previous: Option<&Token>) {
+        let next = tokens.get(index + 1);
+        // 导入导出列表和解构赋值写在一行，除非源码中已经换行
+        if previous.is_some_and(|previous| {
+            previous.kind == Kind::Word
+                && matches!(
+                    previous.text,
+                    "import" | "export" | "type" | "const" | "let" | "var"
+                )
+        }) {
+            let multiline = next.is_some_and(|next| next.newlines > 0 && !next.is("}"));
+            self.printer.write("{");
+            self.stack.push(Context::Group {
+                multiline,
+                for_header: false,
+            });
+            self.ternaries.push(0);
+            if multiline {
+                self.printer.newline();
+                self.printer.indent();
+            }
+            return;
+        }
+        let object = previous.is_some_and(|previous| {
+            previous.kind == Kind::Punct
+                && matches!(
+                    previous.text,
+                    "=" | "(" | "," | ":" | "[" | "?" | "||" | "&&" | "??" | "..." | "<"
+                )
+                || previous.is("return")
+        });
+        let switch = !object && is_switch_block(tokens, index);
+        self.printer.write("{");
+        self.stack.push(if object {
+            Context::Object
+        } else {
+            Context::Block { switch }
+        });
+        self.ternaries.push(0);
+        if next.is_some_and(|next| next.is("}")) {
+            return;
+        }
+        self.printer.newline();
+        self.printer.indent();
+        if switch {
+            self.printer.indent();
+        }
+    }
+
+    fn close_brace(&mut self, previous: Option<&Token>, next: Option<&Token>) {
+        let context = self.stack.pop();
+        self.ternaries.pop();
+        if let Some(Context::Group { multiline, .. }) = context {
+            if multiline {
+                self.printer.dedent();
+                self.printer.newline();
+            }
+            self.printer.write("}");
+            return;
+        }
+        if !previous.is_some_and(|previous| previous.is("{")) {
+            self.printer.dedent();
+            if context == Some(Context::Block { switch: true }) {
+                self.printer.dedent();
+            }
+            self.printer.newline();
+        }
+        self.printer.write("}");
+        // `} else`、`}, `、`})` 以及解构赋值等不换行
+        let attached = next.is_some_and(|next| match next.kind {
+            Kind::Punct => !matches!(next.text, "{" | "++" | "--" | "!" | "~" | "@"),
+            Kind::Angle => true,
+            Kind::Word => {
+                matches!(
+                    next.text,
+                    "else" | "catch" | "finally" | "from" | "as" | "satisfies" | "in" | "of"
+                ) || (next.text == "while" && next.newlines == 0)
+            }
+            _ => false,
+        });
+        if !attached && self.in_block() {
+            self.printer.newline();
+        }
+    }
+
+    fn comment(&mut self, token: &Token, started: bool) {
+        let trailing = token.newlines == 0 && started;
+        if trailing {
+            // 行尾注释跟在刚输出的换行之前
+            if self.printer.ends_with("\n") {
+                self.printer.pop();
+            }
+            self.printer.space();
+        } else if token.newlines > 1 {
+            self.printer.blank_line();
+        } else {
+            self.printer.newline();
+        }
+        self.printer.write(token.text);
+        if token.kind == Kind::LineComment || !trailing {
+            self.printer.newline();
+        } else {
+            self.printer.space();
+        }
+    }
+
+    ///
+    /// 在两个记号之间输出换行或空格
+    ///
+    fn separate(&mut self, before: Option<&Token>, previous: &Token, token: &Token) {
+        // 语句块中保留源码的换行，最多保留一个空行
+        if self.in_block() && token.newlines > 0 && !token.is("}") {
+            let statement = previous.ends_expression() && starts_statement(token)
+                || matches!(previous.text, "return" | "throw" | "break" | "continue")
+                    && previous.kind == Kind::Word;
+            if token.newlines > 1 && !previous.is("{") {
+                self.printer.blank_line();
+            } else if statement {
+                self.printer.newline();
+            }
+        }
+        if self.printer.is_line_start() {
+            return;
+        }
+        let ternary = !self.case && self.ternaries.last().is_some_and(|count| *count > 0);
+        if needs_space(before, previous, token, ternary) {
+            self.printer.space();
+        }
+        if ternary && token.is(":") {
+            if let Some(count) = self.ternaries.last_mut() {
+                *count -= 1;
+            }
+        }
+    }
+}
+
+/// 当前 `{` 是否为 `switch (...)` 的块
+fn is_switch_block(tokens: &[Token], index: usize) -> bool {
+    if !(index > 0 && tokens[index - 1].is(")")) {
+        return false;
+    }
+    let mut depth = 0;
+    for position in (0..index).rev() {
+        let token = &tokens[position];
+        if token.is(")") {
+            depth += 1;
+        } else if token.is("(") {
+            depth -= 1;
+            if depth == 0 {
+                return position > 0 && tokens[position - 1].is("switch");
+            }
+        }
+    }
+    false
+}
+
+///
+/// 判断两个同一行的记号之间是否需要空格，`before` 是 `previous` 之前的记号
+///
+fn needs_space(before: Option<&Token>, previous: &Token, token: &Token, ternary: bool) -> bool {
+    let punct =
+        |token: &Token, texts: &[&str]| token.kind == Kind::Punct && texts.contains(&token.text);
+    let prefix = previous.newlines > 0 || before.map_or(true, |before| !before.ends_expression());
+    if previous.kind == Kind::Angle {
+        return previous.text != "<"
+            && token.kind != Kind::Angle
+            && !punct(
+                token,
+                &["(", ")", ",", ";", ".", "[", "]", "?", ":", "!", "?."],
+            );
+    }
+    if token.kind == Kind::Angle {
+        return false;
+    }
+    if punct(previous, &["(", "[", ".", "?.", "@", "#", "...", "~"])
+        || punct(previous, &["!", "+", "-", "++", "--"]) && prefix
+    {
+        return false;
+    }
+    if previous.is("{") && token.is("}") {
+        return false;
+    }
+    if token.kind == Kind::Punct {
+        match token.text {
+            ")" | "]" | "," | ";" | "." | "?." => return false,
+            ":" => return ternary,
+            "++" | "--" | "!" if previous.ends_expression() => return false,
+            "(" => {
+                return !(previous.kind == Kind::Word && !SPACED_KEYWORDS.contains(&previous.text)
+                    || punct(previous, &[")", "]"])
+                    || previous.kind == Kind::Template)
+            }
+            "[" => return !previous.ends_expression() || SPACED_KEYWORDS.contains(&previous.text),
+            _ => {}
+        }
+    }
+    true
+}
+
+pub struct JavascriptFormatter;
+
+impl Formatter for JavascriptFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "javascript",
+            label: "JavaScript",
+            extensions: &["js", "mjs", "cjs", "jsx", "ts", "tsx"],
+            capabilities: Capabilities::new(CODE_INDENTS, false),
+            options: Vec::new(),
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        _: bool,
+        _: &Options,
+    ) -> Result<String, registry::Error> {
+        format_javascript(input, indent).map_err(registry::Error::format)
+    }
+}
+
+command_error! {
+    (Syntax, "syntax error: {0}", #[from] SyntaxError),
+}
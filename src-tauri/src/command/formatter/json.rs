@@ -1,16 +1,9 @@
+use super::indent::{Indent, ALL_INDENTS};
+use super::registry::{self, Capabilities, Descriptor, Formatter, Options};
 use crate::command_error;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use serde_json::{ser::PrettyFormatter, ser::Serializer, Value};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub enum Indent {
-    TwoSpace,
-    FourSpace,
-    Tab,
-    None,
-}
-
-#[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
 pub fn format_json(input: &str, indent: Indent, sortable: bool) -> Result<String, Error> {
     let mut value = serde_json::from_str::<Value>(input)?;
@@ -18,7 +11,7 @@ pub fn format_json(input: &str, indent: Indent, sortable: bool) -> Result<String
         value.sort_all_objects();
     }
     match indent {
-        Indent::None => serde_json::to_string(&value).map_err(Into::into),
+        Indent::None | Indent::Minify => serde_json::to_string(&value).map_err(Into::into),
         Indent::TwoSpace => to_string_pretty(&value, b"  "),
         Indent::FourSpace => to_string_pretty(&value, b"    "),
         Indent::Tab => to_string_pretty(&value, b"\t"),
@@ -33,6 +26,30 @@ fn to_string_pretty(value: &Value, ident: &[u8]) -> Result<String, Error> {
     String::from_utf8(writer).map_err(Into::into)
 }
 
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "json",
+            label: "JSON",
+            extensions: &["json"],
+            capabilities: Capabilities::new(ALL_INDENTS, true),
+            options: Vec::new(),
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        sort: bool,
+        _: &Options,
+    ) -> Result<String, registry::Error> {
+        format_json(input, indent, sort).map_err(registry::Error::format)
+    }
+}
+
 command_error! {
     (Json, "json error: {0}", #[from] serde_json::Error),
     (Utf8, "utf8 error: {0}", #[from] std::string::FromUtf8Error),
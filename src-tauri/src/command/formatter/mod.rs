@@ -1,3 +1,15 @@
+mod code;
+pub mod css;
+mod dtd;
+pub mod graphql;
+pub mod html;
+pub mod indent;
+pub mod javascript;
 pub mod json;
+pub mod proto;
+pub mod registry;
 pub mod sql;
+pub mod toml;
 pub mod xml;
+pub mod xml_schema;
+mod xsd;
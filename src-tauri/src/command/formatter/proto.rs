@@ -0,0 +1,251 @@
+use super::code::{Brackets, Position, Printer, Scanner, SyntaxError};
+use super::indent::{Indent, CODE_INDENTS};
+use super::registry::{self, Capabilities, Descriptor, Formatter, Options};
+use crate::command_error;
+
+///
+/// 格式化 Protocol Buffers 定义，保留注释和源文件中的空行
+///
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn format_proto(input: &str, indent: Indent) -> Result<String, Error> {
+    let tokens = tokenize(input)?;
+    let mut printer = Printer::new(indent);
+    if printer.minify() {
+        minify(&mut printer, &tokens);
+    } else {
+        beautify(&mut printer, &tokens);
+    }
+    Ok(printer.finish())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// 标识符和关键字，可以包含 `.`
+    Name,
+    Number,
+    String,
+    Punct,
+    Comment,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    newlines: usize,
+}
+
+impl Token<'_> {
+    fn is(&self, text: &str) -> bool {
+        matches!(self.kind, Kind::Punct | Kind::Name) && self.text == text
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>, SyntaxError> {
+    let mut scanner = Scanner::new(input);
+    let mut brackets = Brackets::default();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut last: Option<Position> = None;
+    loop {
+        let newlines = scanner.skip_whitespace();
+        let Some(c) = scanner.peek() else {
+            break;
+        };
+        let start = scanner.offset();
+        let position = scanner.position();
+        let kind = match c {
+            '/' if scanner.starts_with("//") => {
+                scanner.eat_while(|c| c != '\n' && c != '\r');
+                Kind::Comment
+            }
+            '/' if scanner.starts_with("/*") => {
+                if !scanner.skip_until("*/") {
+                    return Err(position.error("unterminated comment"));
+                }
+                Kind::Comment
+            }
+            '"' | '\'' => {
+                scanner.string(false)?;
+                Kind::String
+            }
+            '0'..='9' => {
+                scanner.eat_while(|c| c.is_ascii_alphanumeric() || c == '.');
+                if scanner.peek_nth(0).is_some_and(|c| c == '+' || c == '-')
+                    && scanner.slice(start).ends_with(['e', 'E'])
+                    && !scanner.slice(start).starts_with("0x")
+                {
+                    scanner.bump();
+                    scanner.eat_while(|c| c.is_ascii_digit());
+                }
+                Kind::Number
+            }
+            c if c.is_ascii_alphabetic() || c == '_' || c == '.' => {
+                scanner.eat_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+                if scanner.slice(start) == "." {
+                    Kind::Punct
+                } else {
+                    Kind::Name
+                }
+            }
+            '{' | '(' | '[' | '<' => {
+                scanner.bump();
+                brackets.open(c, position);
+                Kind::Punct
+            }
+            '}' | ')' | ']' | '>' => {
+                scanner.bump();
+                brackets.close(c, position)?;
+                Kind::Punct
+            }
+            ';' | '=' | ',' | ':' | '-' | '+' => {
+                scanner.bump();
+                Kind::Punct
+            }
+            c => return Err(position.error(format!("unexpected character '{c}'"))),
+        };
+        if kind != Kind::Comment {
+            last = Some(position);
+        }
+        tokens.push(Token {
+            kind,
+            text: scanner.slice(start),
+            newlines,
+        });
+    }
+    brackets.finish()?;
+    let end = tokens
+        .iter()
+        .rev()
+        .find(|token| token.kind != Kind::Comment);
+    match (end, last) {
+        (Some(token), Some(position)) if !token.is(";") && !token.is("}") => {
+            Err(position.error(format!("expected ';' after '{}'", token.text)))
+        }
+        _ => Ok(tokens),
+    }
+}
+
+fn minify(printer: &mut Printer, tokens: &[Token]) {
+    let mut previous: Option<&Token> = None;
+    for token in tokens.iter().filter(|token| token.kind != Kind::Comment) {
+        let word = |token: &Token| matches!(token.kind, Kind::Name | Kind::Number);
+        if previous.is_some_and(|previous| word(previous) && word(token)) {
+            printer.space();
+        }
+        printer.write(token.text);
+        previous = Some(token);
+    }
+}
+
+fn beautify(printer: &mut Printer, tokens: &[Token]) {
+    let mut previous: Option<&Token> = None;
+    let mut before: Option<&Token> = None;
+    let mut depth = 0;
+    for token in tokens {
+        if let Some(previous) = previous {
+            if token.kind == Kind::Comment && token.newlines == 0 {
+                // 行尾注释跟在上一条语句后面
+                if printer.ends_with("\n") {
+                    printer.pop();
+                }
+                printer.space();
+            } else if (token.newlines > 1 && !token.is("}"))
+                || (depth == 0 && previous.is("}") && !token.is(";"))
+            {
+                printer.blank_line();
+            } else if token.kind == Kind::Comment {
+                printer.newline();
+            } else if needs_space(before, previous, token) {
+                printer.space();
+            }
+        }
+        match token.text {
+            "{" if token.kind == Kind::Punct => {
+                printer.write("{");
+                printer.newline();
+                printer.indent();
+                depth += 1;
+            }
+            "}" if token.kind == Kind::Punct => {
+                printer.dedent();
+                // 空的块写成 `{}`
+                if previous.is_some_and(|previous| previous.is("{")) && printer.ends_with("\n") {
+                    printer.pop();
+                } else {
+                    printer.newline();
+                }
+                printer.write("}");
+                printer.newline();
+                depth -= 1;
+            }
+            ";" if token.kind == Kind::Punct => {
+                // `{};` 中的分号写在右括号后面
+                if previous.is_some_and(|previous| previous.is("}")) && printer.ends_with("\n") {
+                    printer.pop();
+                }
+                printer.write(";");
+                printer.newline();
+            }
+            _ => {
+                printer.write(token.text);
+                if token.kind == Kind::Comment {
+                    printer.newline();
+                }
+            }
+        }
+        before = previous;
+        previous = Some(token);
+    }
+}
+
+/// 同一行中两个记号之间是否需要空格
+fn needs_space(before: Option<&Token>, previous: &Token, token: &Token) -> bool {
+    if previous.kind == Kind::Punct && matches!(previous.text, "(" | "[" | "<" | "." | "-" | "+") {
+        // 只有二元的加减号后面需要空格
+        return matches!(previous.text, "-" | "+")
+            && before.is_some_and(|before| !before.is("=") && !before.is(":"));
+    }
+    if previous.kind == Kind::Comment {
+        return false;
+    }
+    // `(my.option).field`
+    if previous.is(")") && token.kind == Kind::Name && token.text.starts_with('.') {
+        return false;
+    }
+    match token.text {
+        ")" | "]" | ">" | ";" | "," | ":" | "." if token.kind == Kind::Punct => false,
+        // `map<string, int32>`
+        "<" => !previous.is("map"),
+        // `rpc Method(Request)`
+        "(" => !before.is_some_and(|before| before.is("rpc")),
+        _ => true,
+    }
+}
+
+pub struct ProtoFormatter;
+
+impl Formatter for ProtoFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "proto",
+            label: "Protocol Buffers",
+            extensions: &["proto"],
+            capabilities: Capabilities::new(CODE_INDENTS, false),
+            options: Vec::new(),
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        _: bool,
+        _: &Options,
+    ) -> Result<String, registry::Error> {
+        format_proto(input, indent).map_err(registry::Error::format)
+    }
+}
+
+command_error! {
+    (Syntax, "syntax error: {0}", #[from] SyntaxError),
+}
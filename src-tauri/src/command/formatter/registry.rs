@@ -0,0 +1,170 @@
+//!
+//! 格式化器注册表，前端通过 [`list_formatters`] 获取可用的格式和选项，再统一调用 [`format`]
+//!
+
+use super::indent::Indent;
+use super::{css, graphql, html, javascript, json, proto, sql, toml, xml};
+use crate::command_error;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// 已注册的格式化器，新增格式时在这里加上即可
+static FORMATTERS: &[&dyn Formatter] = &[
+    &json::JsonFormatter,
+    &xml::XmlFormatter,
+    &sql::SqlFormatter,
+    &html::HtmlFormatter,
+    &css::CssFormatter,
+    &javascript::JavascriptFormatter,
+    &toml::TomlFormatter,
+    &graphql::GraphqlFormatter,
+    &proto::ProtoFormatter,
+];
+
+///
+/// 格式化器，`sort` 和 `options` 只在描述中声明支持时才有意义
+///
+pub trait Formatter: Sync {
+    fn descriptor(&self) -> Descriptor;
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        sort: bool,
+        options: &Options,
+    ) -> Result<String, Error>;
+}
+
+///
+/// 格式化器的名称、能力和可用选项
+///
+#[derive(Debug, Serialize)]
+pub struct Descriptor {
+    /// 调用 [`format`] 时使用的名称
+    pub name: &'static str,
+    pub label: &'static str,
+    /// 文件扩展名，不含 `.`
+    pub extensions: &'static [&'static str],
+    pub capabilities: Capabilities,
+    pub options: Vec<OptionSchema>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    pub indents: &'static [Indent],
+    /// 是否支持排序键或属性
+    pub sort: bool,
+    pub minify: bool,
+}
+
+impl Capabilities {
+    pub fn new(indents: &'static [Indent], sort: bool) -> Self {
+        Capabilities {
+            indents,
+            sort,
+            minify: indents.contains(&Indent::Minify),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OptionSchema {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: OptionKind,
+}
+
+///
+/// 选项的类型和默认值，前端据此生成表单
+///
+#[derive(Debug, Serialize)]
+pub enum OptionKind {
+    Boolean {
+        default: bool,
+    },
+    /// `default` 为空时表示不限制
+    Integer {
+        default: Option<u64>,
+        min: u64,
+        max: Option<u64>,
+    },
+    Choice {
+        choices: &'static [&'static str],
+        default: &'static str,
+    },
+    Text {
+        placeholder: &'static str,
+    },
+}
+
+///
+/// 前端传入的选项，缺少或为 `null` 时使用默认值
+///
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Options(Map<String, Value>);
+
+impl Options {
+    pub fn get<T: DeserializeOwned>(&self, name: &str, default: T) -> Result<T, Error> {
+        match self.0.get(name) {
+            None | Some(Value::Null) => Ok(default),
+            Some(value) => serde_json::from_value(value.clone())
+                .map_err(|error| Error::Option(name.to_string(), error)),
+        }
+    }
+}
+
+///
+/// 列出所有可用的格式化器
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret)]
+pub fn list_formatters() -> Vec<Descriptor> {
+    FORMATTERS
+        .iter()
+        .map(|formatter| formatter.descriptor())
+        .collect()
+}
+
+///
+/// 使用名为 `name` 的格式化器格式化输入
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn format(
+    name: &str,
+    input: &str,
+    indent: Indent,
+    sort: bool,
+    options: Options,
+) -> Result<String, Error> {
+    let formatter = FORMATTERS
+        .iter()
+        .find(|formatter| formatter.descriptor().name == name)
+        .ok_or_else(|| Error::Unknown(name.to_string()))?;
+    if !formatter
+        .descriptor()
+        .capabilities
+        .indents
+        .contains(&indent)
+    {
+        return Err(Error::Indent(name.to_string(), indent));
+    }
+    formatter.format(input, indent, sort, &options)
+}
+
+impl Error {
+    /// 包装各格式化器自己的错误
+    pub fn format(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Error::Format(Box::new(error))
+    }
+}
+
+command_error! {
+    (Unknown, "unknown formatter: {0}", String),
+    (Indent, "{0} does not support indent {1:?}", String, Indent),
+    (Option, "invalid option {0}: {1}", String, serde_json::Error),
+    (Format, "{0}", Box<dyn std::error::Error + Send + Sync>),
+}
@@ -1,36 +1,867 @@
+use super::indent::{Indent, CODE_INDENTS};
+use super::registry::{
+    self, Capabilities, Descriptor, Formatter, OptionKind, OptionSchema, Options,
+};
+use crate::command_error;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sqlformat::{format, FormatOptions, QueryParams};
+use sqlparser::dialect::{
+    ClickHouseDialect, GenericDialect, HiveDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect,
+    SQLiteDialect,
+};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Location, Token, TokenWithSpan, Tokenizer, TokenizerError, Whitespace};
+use std::collections::HashSet;
+use std::sync::OnceLock;
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
-pub enum Indent {
-    TwoSpace,
-    FourSpace,
-    Tab,
-}
+/// 压缩时转换大小写的关键字，其余关键字可能是列名等标识符，保持原样
+const KEYWORDS: &[Keyword] = &[
+    Keyword::SELECT,
+    Keyword::DISTINCT,
+    Keyword::FROM,
+    Keyword::WHERE,
+    Keyword::AND,
+    Keyword::OR,
+    Keyword::NOT,
+    Keyword::NULL,
+    Keyword::IS,
+    Keyword::IN,
+    Keyword::EXISTS,
+    Keyword::BETWEEN,
+    Keyword::LIKE,
+    Keyword::AS,
+    Keyword::ON,
+    Keyword::JOIN,
+    Keyword::INNER,
+    Keyword::OUTER,
+    Keyword::CROSS,
+    Keyword::GROUP,
+    Keyword::ORDER,
+    Keyword::BY,
+    Keyword::HAVING,
+    Keyword::LIMIT,
+    Keyword::UNION,
+    Keyword::CASE,
+    Keyword::WHEN,
+    Keyword::THEN,
+    Keyword::ELSE,
+    Keyword::WITH,
+    Keyword::INSERT,
+    Keyword::INTO,
+    Keyword::VALUES,
+    Keyword::UPDATE,
+    Keyword::SET,
+    Keyword::DELETE,
+    Keyword::CREATE,
+    Keyword::ALTER,
+    Keyword::DROP,
+    Keyword::TABLE,
+    Keyword::PRIMARY,
+    Keyword::FOREIGN,
+    Keyword::REFERENCES,
+    Keyword::UNIQUE,
+    Keyword::DEFAULT,
+    Keyword::CONSTRAINT,
+];
+
+/// 常被用作列名或函数名的关键字，只在关键字的位置转换大小写
+const CONTEXTUAL_KEYWORDS: &[Keyword] = &[
+    Keyword::LEFT,
+    Keyword::RIGHT,
+    Keyword::FULL,
+    Keyword::OFFSET,
+    Keyword::ALL,
+    Keyword::END,
+    Keyword::ASC,
+    Keyword::DESC,
+];
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum Dialect {
     Generic,
+    MySql,
     PostgreSql,
     SQLServer,
+    SQLite,
+    Oracle,
+    ClickHouse,
+    Hive,
 }
 
-#[tauri::command]
-#[tracing::instrument(level = tracing::Level::DEBUG, ret)]
-pub fn format_sql(input: &str, indent: Indent, dialect: Dialect, uppercase: bool) -> String {
+///
+/// 关键字的大小写
+///
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub enum KeywordCase {
+    Upper,
+    Lower,
+    Preserve,
+}
+
+///
+/// 语法检查的结果
+///
+#[derive(Debug, Serialize)]
+pub struct Report {
+    valid: bool,
+    errors: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    /// 行号和列号，从 1 开始
+    line: u64,
+    column: u64,
+    message: String,
+}
+
+///
+/// 格式化 SQL，`parameters` 不为空时先将参数替换到占位符中
+///
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+#[allow(clippy::too_many_arguments)]
+pub fn format_sql(
+    input: &str,
+    indent: Indent,
+    dialect: Dialect,
+    keyword_case: KeywordCase,
+    lines_between_queries: u8,
+    max_inline_block: usize,
+    max_inline_arguments: Option<usize>,
+    max_inline_top_level: Option<usize>,
+    comments: bool,
+    parameters: Option<String>,
+) -> Result<String, Error> {
+    let interpolated;
+    let input = match parameters {
+        Some(parameters) => {
+            interpolated = interpolate(input, &parameters, dialect)?;
+            interpolated.as_str()
+        }
+        None => input,
+    };
+    if indent.unit().is_none() {
+        let tokens = tokenize(input, dialect)?;
+        return Ok(minify(input, &tokens, keyword_case, comments));
+    }
+    let input = match comments {
+        true => input.to_string(),
+        false => strip_comments(input, &tokenize(input, dialect)?),
+    };
     let options = FormatOptions {
         indent: match indent {
             Indent::TwoSpace => sqlformat::Indent::Spaces(2),
             Indent::FourSpace => sqlformat::Indent::Spaces(4),
-            Indent::Tab => sqlformat::Indent::Tabs,
+            Indent::Tab | Indent::None | Indent::Minify => sqlformat::Indent::Tabs,
         },
+        // 格式化器只区分这三种方言，其余方言按标准 SQL 处理
         dialect: match dialect {
-            Dialect::Generic => sqlformat::Dialect::Generic,
             Dialect::PostgreSql => sqlformat::Dialect::PostgreSql,
             Dialect::SQLServer => sqlformat::Dialect::SQLServer,
+            _ => sqlformat::Dialect::Generic,
+        },
+        uppercase: match keyword_case {
+            KeywordCase::Upper => Some(true),
+            KeywordCase::Lower => Some(false),
+            KeywordCase::Preserve => None,
         },
-        uppercase: Some(uppercase),
+        lines_between_queries,
+        max_inline_block,
+        max_inline_arguments,
+        max_inline_top_level,
         ..Default::default()
     };
-    format(input, &QueryParams::None, &options)
+    Ok(format(&input, &QueryParams::None, &options))
+}
+
+///
+/// 按照所选方言解析 SQL，报告每条语句中的语法错误
+///
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret)]
+pub fn check_sql(input: &str, dialect: Dialect) -> Report {
+    let errors = check(input, dialect);
+    Report {
+        valid: errors.is_empty(),
+        errors,
+    }
+}
+
+fn parser_dialect(dialect: Dialect) -> Box<dyn sqlparser::dialect::Dialect> {
+    match dialect {
+        // 解析器没有 Oracle 方言，按标准 SQL 解析
+        Dialect::Generic | Dialect::Oracle => Box::new(GenericDialect {}),
+        Dialect::MySql => Box::new(MySqlDialect {}),
+        Dialect::PostgreSql => Box::new(PostgreSqlDialect {}),
+        Dialect::SQLServer => Box::new(MsSqlDialect {}),
+        Dialect::SQLite => Box::new(SQLiteDialect {}),
+        Dialect::ClickHouse => Box::new(ClickHouseDialect {}),
+        Dialect::Hive => Box::new(HiveDialect {}),
+    }
+}
+
+fn tokenize(input: &str, dialect: Dialect) -> Result<Vec<TokenWithSpan>, TokenizerError> {
+    let dialect = parser_dialect(dialect);
+    Tokenizer::new(dialect.as_ref(), input).tokenize_with_location()
+}
+
+///
+/// 将行号和列号转换为字节偏移，预先记录每行的起始位置，
+/// 并从上一次的结果继续向后查找，按顺序转换所有词法单元的总开销是线性的
+///
+struct Offsets<'a> {
+    input: &'a str,
+    lines: Vec<usize>,
+    /// 上一次转换的行号、从 0 开始的列号和字节偏移
+    cursor: (u64, u64, usize),
+}
+
+impl<'a> Offsets<'a> {
+    fn new(input: &'a str) -> Self {
+        let lines = std::iter::once(0)
+            .chain(input.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Offsets {
+            input,
+            lines,
+            cursor: (1, 0, 0),
+        }
+    }
+
+    fn get(&mut self, location: Location) -> usize {
+        let column = location.column.saturating_sub(1);
+        let (line, from_column, from) = self.cursor;
+        let (from_column, from) = match location.line == line && column >= from_column {
+            true => (from_column, from),
+            false => match self.lines.get(location.line.saturating_sub(1) as usize) {
+                Some(&start) => (0, start),
+                None => return self.input.len(),
+            },
+        };
+        let offset = self.input[from..]
+            .char_indices()
+            .nth((column - from_column) as usize)
+            .map_or(self.input.len(), |(index, _)| from + index);
+        self.cursor = (location.line, column, offset);
+        offset
+    }
+}
+
+///
+/// 判断可能作为标识符的关键字是否处于关键字的位置，`previous` 和 `next` 为前后第一个非空白的词法单元
+///
+fn is_keyword_position(keyword: Keyword, previous: Option<&Token>, next: Option<&Token>) -> bool {
+    let is = |token: Option<&Token>, keywords: &[Keyword]| matches!(token, Some(Token::Word(word)) if keywords.contains(&word.keyword));
+    match keyword {
+        Keyword::LEFT | Keyword::RIGHT | Keyword::FULL => {
+            is(next, &[Keyword::JOIN, Keyword::OUTER])
+        }
+        Keyword::OFFSET => matches!(
+            next,
+            Some(Token::Number(..) | Token::Placeholder(_) | Token::Question)
+        ),
+        Keyword::ALL => {
+            is(
+                previous,
+                &[
+                    Keyword::UNION,
+                    Keyword::EXCEPT,
+                    Keyword::INTERSECT,
+                    Keyword::SELECT,
+                ],
+            ) || matches!(next, Some(Token::LParen))
+        }
+        // `ASC`、`DESC` 和 `END` 跟在值的后面，而不是出现在列的位置或比较中
+        _ => {
+            !matches!(
+                previous,
+                None | Some(Token::Comma | Token::LParen | Token::Period)
+            ) && !is(
+                previous,
+                &[
+                    Keyword::SELECT,
+                    Keyword::DISTINCT,
+                    Keyword::BY,
+                    Keyword::FROM,
+                    Keyword::WHERE,
+                    Keyword::AND,
+                    Keyword::OR,
+                    Keyword::ON,
+                    Keyword::SET,
+                    Keyword::WHEN,
+                    Keyword::THEN,
+                    Keyword::ELSE,
+                ],
+            ) && !matches!(
+                next,
+                Some(
+                    Token::Period
+                        | Token::LParen
+                        | Token::Eq
+                        | Token::Neq
+                        | Token::Lt
+                        | Token::Gt
+                        | Token::LtEq
+                        | Token::GtEq
+                )
+            )
+        }
+    }
+}
+
+fn is_comment(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Whitespace(Whitespace::SingleLineComment { .. })
+            | Token::Whitespace(Whitespace::MultiLineComment(_))
+    )
+}
+
+/// 去除注释，保留其余部分的原文
+fn strip_comments(input: &str, tokens: &[TokenWithSpan]) -> String {
+    let mut offsets = Offsets::new(input);
+    let mut output = String::with_capacity(input.len());
+    let mut last = 0;
+    for token in tokens.iter().filter(|token| is_comment(&token.token)) {
+        let start = offsets.get(token.span.start);
+        output.push_str(&input[last..start]);
+        // 单行注释包含换行符，用换行符代替以免与下一行相连
+        output.push(match token.token {
+            Token::Whitespace(Whitespace::SingleLineComment { .. }) => '\n',
+            _ => ' ',
+        });
+        last = offsets.get(token.span.end);
+    }
+    output.push_str(&input[last..]);
+    output
+}
+
+///
+/// 压缩为一行，只在必要的地方保留空格。保留的单行注释改写为 `/* */` 的形式
+///
+fn minify(
+    input: &str,
+    tokens: &[TokenWithSpan],
+    keyword_case: KeywordCase,
+    comments: bool,
+) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || "_$@#'\"`[]".contains(c);
+    let is_operator = |c: char| "+-*/<>=!|&^%~:".contains(c);
+
+    let mut offsets = Offsets::new(input);
+    let mut source = |token: &TokenWithSpan| {
+        let start = offsets.get(token.span.start);
+        &input[start..offsets.get(token.span.end)]
+    };
+    let mut output = String::with_capacity(input.len());
+    let mut space = false;
+    let mut previous: Option<&Token> = None;
+    for (index, token) in tokens.iter().enumerate() {
+        let next = || {
+            tokens[index + 1..]
+                .iter()
+                .map(|token| &token.token)
+                .find(|token| !matches!(token, Token::Whitespace(_)))
+        };
+        let text = match &token.token {
+            Token::Whitespace(Whitespace::SingleLineComment { comment, .. }) if comments => {
+                format!("/* {} */", comment.trim())
+            }
+            Token::Whitespace(Whitespace::MultiLineComment(_)) if comments => {
+                source(token).to_string()
+            }
+            Token::Whitespace(_) => {
+                space = true;
+                continue;
+            }
+            Token::Word(word)
+                if word.quote_style.is_none()
+                    && (KEYWORDS.contains(&word.keyword)
+                        || (CONTEXTUAL_KEYWORDS.contains(&word.keyword)
+                            && is_keyword_position(word.keyword, previous, next()))) =>
+            {
+                let text = source(token);
+                match keyword_case {
+                    KeywordCase::Upper => text.to_uppercase(),
+                    KeywordCase::Lower => text.to_lowercase(),
+                    KeywordCase::Preserve => text.to_string(),
+                }
+            }
+            Token::EOF => continue,
+            _ => source(token).to_string(),
+        };
+        if !matches!(token.token, Token::Whitespace(_)) {
+            previous = Some(&token.token);
+        }
+        // 注释前后以及相邻的两个单词或两个运算符之间需要空格，否则会改变含义
+        if let (Some(previous), Some(next)) = (output.chars().last(), text.chars().next()) {
+            let comment = text.starts_with("/*") || output.ends_with("*/");
+            if comment
+                || (space && is_word(previous) && is_word(next))
+                || (space && is_operator(previous) && is_operator(next))
+            {
+                output.push(' ');
+            }
+        }
+        output.push_str(&text);
+        space = false;
+    }
+    output
+}
+
+fn check(input: &str, dialect: Dialect) -> Vec<Diagnostic> {
+    let tokens = match tokenize(input, dialect) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            return vec![Diagnostic {
+                line: e.location.line,
+                column: e.location.column,
+                message: e.message,
+            }]
+        }
+    };
+    let parser = parser_dialect(dialect);
+    let start = Location { line: 1, column: 1 };
+    let error = match Parser::parse_sql(parser.as_ref(), input) {
+        Ok(_) => return Vec::new(),
+        Err(e) => diagnostic(input, start, e),
+    };
+
+    // 逐条语句解析，以便报告所有语句中的错误
+    let mut errors = Vec::new();
+    let mut offsets = Offsets::new(input);
+    let mut statement_start: Option<(Location, usize)> = None;
+    for token in &tokens {
+        match &token.token {
+            Token::SemiColon | Token::EOF => {
+                let Some((location, start)) = statement_start.take() else {
+                    continue;
+                };
+                let statement = &input[start..offsets.get(token.span.start)];
+                if let Err(e) = Parser::parse_sql(parser.as_ref(), statement) {
+                    errors.push(diagnostic(statement, location, e));
+                }
+            }
+            Token::Whitespace(_) => {}
+            _ => {
+                if statement_start.is_none() {
+                    statement_start = Some((token.span.start, offsets.get(token.span.start)));
+                }
+            }
+        }
+    }
+    if let Some((location, start)) = statement_start {
+        let statement = &input[start..];
+        if let Err(e) = Parser::parse_sql(parser.as_ref(), statement) {
+            errors.push(diagnostic(statement, location, e));
+        }
+    }
+    // 存储过程等语句中包含分号，逐条解析可能没有错误，此时报告整体解析的错误
+    if errors.is_empty() {
+        errors.push(error);
+    }
+    errors
+}
+
+///
+/// 将解析错误转换为诊断信息，`start` 为被解析的语句在原文中的位置
+///
+fn diagnostic(statement: &str, start: Location, error: ParserError) -> Diagnostic {
+    let message = match error {
+        ParserError::TokenizerError(message) | ParserError::ParserError(message) => message,
+        ParserError::RecursionLimitExceeded => "recursion limit exceeded".to_string(),
+    };
+    // 错误信息以 ` at Line: 1, Column: 2` 结尾，没有位置时指向语句末尾
+    let located = message
+        .rsplit_once(" at Line: ")
+        .and_then(|(text, position)| {
+            let (line, column) = position.split_once(", Column: ")?;
+            Some((text.to_string(), line.parse().ok()?, column.parse().ok()?))
+        });
+    let (message, line, column) = located.unwrap_or_else(|| {
+        let trimmed = statement.trim_end();
+        let line = trimmed.matches('\n').count() as u64 + 1;
+        let column = trimmed.rsplit('\n').next().map_or(0, |l| l.chars().count()) as u64 + 1;
+        (message, line, column)
+    });
+    Diagnostic {
+        line: start.line + line - 1,
+        column: match line {
+            1 => start.column + column - 1,
+            _ => column,
+        },
+        message,
+    }
+}
+
+/// MyBatis 日志中的 SQL 和参数行
+const PREPARING: &str = "Preparing:";
+const PARAMETERS: &str = "Parameters:";
+
+/// 不需要加引号的参数类型
+const NUMERIC_TYPES: &[&str] = &[
+    "Byte",
+    "Short",
+    "Integer",
+    "Long",
+    "Float",
+    "Double",
+    "BigInteger",
+    "BigDecimal",
+    "Boolean",
+    "byte",
+    "short",
+    "int",
+    "long",
+    "float",
+    "double",
+    "boolean",
+];
+
+///
+/// 参数列表中的一项
+///
+/// `name=value` 和 `name: value` 的写法记录在 `named` 中，只有 SQL 中使用了同名的命名占位符时
+/// 才按名称替换，否则整项作为位置参数，避免把 `http://host` 这样的值当成名称
+///
+struct Parameter {
+    literal: String,
+    named: Option<(String, String)>,
+}
+
+///
+/// 将参数替换到占位符中。
+///
+/// 输入中包含 MyBatis 的 `Preparing:` 行时，从日志中提取 SQL 和紧随其后的 `Parameters:` 行，
+/// 日志中没有参数行时使用 `parameters`。
+///
+fn interpolate(input: &str, parameters: &str, dialect: Dialect) -> Result<String, Error> {
+    let mut statements: Vec<(&str, &str)> = Vec::new();
+    for line in input.lines() {
+        if let Some((_, sql)) = line.split_once(PREPARING) {
+            statements.push((sql.trim(), parameters));
+        } else if let (Some((_, list)), Some(last)) =
+            (line.split_once(PARAMETERS), statements.last_mut())
+        {
+            last.1 = list;
+        }
+    }
+    if statements.is_empty() {
+        return substitute(input, &parse_parameters(parameters), dialect);
+    }
+    let statements = statements
+        .into_iter()
+        .map(|(sql, list)| substitute(sql, &parse_parameters(list), dialect))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(statements
+        .iter()
+        .map(|sql| format!("{};", sql.trim_end().trim_end_matches(';')))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+///
+/// 解析参数列表，支持 MyBatis 的 `value(Type)` 写法和逗号分隔的普通列表
+///
+fn parse_parameters(list: &str) -> Vec<Parameter> {
+    let list = list.trim();
+    if list.is_empty() {
+        return Vec::new();
+    }
+    let typed = typed_parameter();
+    if !typed.is_match(list) {
+        return split_list(list)
+            .into_iter()
+            .map(|item| Parameter {
+                literal: literal(item, None),
+                named: item
+                    .split_once(['=', ':'])
+                    .map(|(name, value)| (name.trim(), value.trim()))
+                    .filter(|(name, _)| {
+                        !name.is_empty()
+                            && !name.starts_with(|c: char| c.is_ascii_digit())
+                            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    })
+                    .map(|(name, value)| (name.to_string(), literal(value, None))),
+            })
+            .collect();
+    }
+
+    // 值中可能包含逗号，以 `(Type), ` 作为分隔
+    let mut parameters = Vec::new();
+    let mut rest = list;
+    while !rest.is_empty() {
+        if rest == "null" || rest.starts_with("null, ") {
+            parameters.push(Parameter {
+                literal: "NULL".to_string(),
+                named: None,
+            });
+            rest = rest.get(6..).unwrap_or_default();
+            continue;
+        }
+        let Some(captures) = typed.captures(rest) else {
+            parameters.extend(split_list(rest).into_iter().map(|item| Parameter {
+                literal: literal(item, None),
+                named: None,
+            }));
+            break;
+        };
+        let whole = captures.get(0).unwrap();
+        parameters.push(Parameter {
+            literal: literal(&rest[..whole.start()], Some(&captures[1])),
+            named: None,
+        });
+        rest = &rest[whole.end()..];
+    }
+    parameters
+}
+
+/// MyBatis 参数的类型后缀，例如 `1(Integer), `
+fn typed_parameter() -> &'static Regex {
+    static TYPED: OnceLock<Regex> = OnceLock::new();
+    TYPED.get_or_init(|| Regex::new(r"\(([A-Za-z_][\w.$]*)\)(?:,\s|\s*$)").unwrap())
+}
+
+/// 按逗号分隔，忽略引号中的逗号
+fn split_list(list: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (index, c) in list.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, ',') => {
+                items.push(list[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(list[start..].trim());
+    items
+}
+
+/// 将参数值转换为 SQL 字面量，字符串和日期等类型加上单引号
+fn literal(value: &str, kind: Option<&str>) -> String {
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    match kind {
+        Some(kind) if NUMERIC_TYPES.contains(&kind) => value.to_string(),
+        Some(_) => quote(value),
+        None => {
+            let quoted = value.len() >= 2
+                && ((value.starts_with('\'') && value.ends_with('\''))
+                    || (value.starts_with('"') && value.ends_with('"')));
+            let bare = value.eq_ignore_ascii_case("null")
+                || value.eq_ignore_ascii_case("true")
+                || value.eq_ignore_ascii_case("false")
+                || value.parse::<f64>().is_ok_and(f64::is_finite);
+            match (quoted, bare) {
+                (true, _) if value.starts_with('"') => quote(&value[1..value.len() - 1]),
+                (true, _) => value.to_string(),
+                (false, true) if value.eq_ignore_ascii_case("null") => "NULL".to_string(),
+                (false, true) => value.to_string(),
+                (false, false) => quote(value),
+            }
+        }
+    }
+}
+
+///
+/// 替换 `?`、`?1`、`$1`、`$name` 和 `:name` 形式的占位符，字符串和注释中的内容不会被替换
+///
+fn substitute(sql: &str, parameters: &[Parameter], dialect: Dialect) -> Result<String, Error> {
+    let tokens = tokenize(sql, dialect)?;
+    // SQL 中使用的命名占位符
+    let mut names = HashSet::new();
+    for (index, token) in tokens.iter().enumerate() {
+        match &token.token {
+            Token::Placeholder(text) if text.len() > 1 && text[1..].parse::<usize>().is_err() => {
+                names.insert(text[1..].to_string());
+            }
+            Token::Colon => {
+                if let Some(Token::Word(word)) = tokens
+                    .get(index + 1)
+                    .filter(|next| next.span.start == token.span.end)
+                    .map(|next| &next.token)
+                {
+                    names.insert(word.value.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    let is_named = |p: &&Parameter| {
+        p.named
+            .as_ref()
+            .is_some_and(|(name, _)| names.contains(name))
+    };
+    let positional = parameters
+        .iter()
+        .filter(|p| !is_named(p))
+        .collect::<Vec<_>>();
+    let named = |name: &str| {
+        parameters
+            .iter()
+            .filter(is_named)
+            .find_map(|p| p.named.as_ref().filter(|(key, _)| key == name))
+            .map(|(_, literal)| literal.as_str())
+    };
+
+    let mut offsets = Offsets::new(sql);
+    let mut output = String::with_capacity(sql.len());
+    let mut last = 0;
+    let mut next = 0;
+    let mut iter = tokens.iter().peekable();
+    while let Some(token) = iter.next() {
+        let start = offsets.get(token.span.start);
+        let mut end = offsets.get(token.span.end);
+        let (placeholder, value) = match &token.token {
+            Token::Question => {
+                next += 1;
+                (
+                    "?".to_string(),
+                    positional.get(next - 1).map(|p| p.literal.as_str()),
+                )
+            }
+            Token::Placeholder(text) => {
+                let key = &text[1..];
+                let value = match key.parse::<usize>() {
+                    Ok(index) => positional
+                        .get(index.saturating_sub(1))
+                        .map(|p| p.literal.as_str()),
+                    Err(_) if key.is_empty() => {
+                        next += 1;
+                        positional.get(next - 1).map(|p| p.literal.as_str())
+                    }
+                    Err(_) => named(key),
+                };
+                (text.clone(), value)
+            }
+            // `:name` 由冒号和紧随的单词组成
+            Token::Colon => match iter.peek() {
+                Some(word)
+                    if matches!(word.token, Token::Word(_))
+                        && word.span.start == token.span.end =>
+                {
+                    let Token::Word(word_token) = &word.token else {
+                        unreachable!()
+                    };
+                    end = offsets.get(word.span.end);
+                    iter.next();
+                    (format!(":{}", word_token.value), named(&word_token.value))
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let value = value.ok_or(Error::MissingParameter(placeholder))?;
+        output.push_str(&sql[last..start]);
+        output.push_str(value);
+        last = end;
+    }
+    output.push_str(&sql[last..]);
+    Ok(output)
+}
+
+pub struct SqlFormatter;
+
+impl Formatter for SqlFormatter {
+    fn descriptor(&self) -> Descriptor {
+        let limit = |name, label, default| OptionSchema {
+            name,
+            label,
+            kind: OptionKind::Integer {
+                default,
+                min: 0,
+                max: None,
+            },
+        };
+        Descriptor {
+            name: "sql",
+            label: "SQL",
+            extensions: &["sql"],
+            capabilities: Capabilities::new(CODE_INDENTS, false),
+            options: vec![
+                OptionSchema {
+                    name: "dialect",
+                    label: "方言",
+                    kind: OptionKind::Choice {
+                        choices: &[
+                            "Generic",
+                            "MySql",
+                            "PostgreSql",
+                            "SQLServer",
+                            "SQLite",
+                            "Oracle",
+                            "ClickHouse",
+                            "Hive",
+                        ],
+                        default: "Generic",
+                    },
+                },
+                OptionSchema {
+                    name: "keyword_case",
+                    label: "关键字大小写",
+                    kind: OptionKind::Choice {
+                        choices: &["Upper", "Lower", "Preserve"],
+                        default: "Preserve",
+                    },
+                },
+                OptionSchema {
+                    name: "lines_between_queries",
+                    label: "语句间空行",
+                    kind: OptionKind::Integer {
+                        default: Some(1),
+                        min: 0,
+                        max: Some(u8::MAX as u64),
+                    },
+                },
+                limit("max_inline_block", "单行块最大长度", Some(50)),
+                limit("max_inline_arguments", "单行参数最大长度", None),
+                limit("max_inline_top_level", "单行子句最大长度", None),
+                OptionSchema {
+                    name: "comments",
+                    label: "保留注释",
+                    kind: OptionKind::Boolean { default: true },
+                },
+                OptionSchema {
+                    name: "parameters",
+                    label: "参数",
+                    kind: OptionKind::Text {
+                        placeholder: "MyBatis 日志或以逗号分隔的参数",
+                    },
+                },
+            ],
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        _: bool,
+        options: &Options,
+    ) -> Result<String, registry::Error> {
+        format_sql(
+            input,
+            indent,
+            options.get("dialect", Dialect::Generic)?,
+            options.get("keyword_case", KeywordCase::Preserve)?,
+            options.get("lines_between_queries", 1)?,
+            options.get("max_inline_block", 50)?,
+            options.get("max_inline_arguments", None)?,
+            options.get("max_inline_top_level", None)?,
+            options.get("comments", true)?,
+            options.get("parameters", None)?,
+        )
+        .map_err(registry::Error::format)
+    }
+}
+
+command_error! {
+    (Tokenize, "sql error: {0}", #[from] TokenizerError),
+    (MissingParameter, "no parameter for placeholder {0}", String),
 }
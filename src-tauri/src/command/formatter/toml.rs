@@ -0,0 +1,459 @@
+use super::code::{Position, Printer, Scanner, SyntaxError};
+use super::indent::{Indent, CODE_INDENTS};
+use super::registry::{self, Capabilities, Descriptor, Formatter, Options};
+use crate::command_error;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// 不带引号的值：布尔、数字、日期时间
+const BARE_VALUE: &str = r"^(?:true|false|[+-]?(?:inf|nan)|[+-]?(?:0|[1-9](?:_?[0-9])*)(?:\.[0-9](?:_?[0-9])*)?(?:[eE][+-]?[0-9](?:_?[0-9])*)?|0x[0-9A-Fa-f](?:_?[0-9A-Fa-f])*|0o[0-7](?:_?[0-7])*|0b[01](?:_?[01])*|[0-9]{4}-[0-9]{2}-[0-9]{2}(?:[Tt ][0-9]{2}:[0-9]{2}(?::[0-9]{2}(?:\.[0-9]+)?)?(?:[Zz]|[+-][0-9]{2}:[0-9]{2})?)?|[0-9]{2}:[0-9]{2}(?::[0-9]{2}(?:\.[0-9]+)?)?)$";
+
+///
+/// 格式化 TOML，保留注释和空行，`Indent` 用于多行数组，压缩时去掉注释和空行
+///
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn format_toml(input: &str, indent: Indent) -> Result<String, Error> {
+    let lines = Parser {
+        scanner: Scanner::new(input),
+        bare: Regex::new(BARE_VALUE)?,
+    }
+    .parse()?;
+    let mut printer = Printer::new(indent);
+    write_lines(&mut printer, &lines);
+    Ok(printer.finish())
+}
+
+#[derive(Debug)]
+enum Line<'a> {
+    Blank,
+    Comment(&'a str),
+    Table {
+        key: Vec<&'a str>,
+        /// `[[array]]`
+        array: bool,
+        comment: Option<&'a str>,
+    },
+    Pair {
+        key: Vec<&'a str>,
+        value: Value<'a>,
+        comment: Option<&'a str>,
+    },
+}
+
+#[derive(Debug)]
+enum Value<'a> {
+    /// 字符串、数字、布尔和日期时间，保留原文
+    Scalar(&'a str),
+    Array {
+        items: Vec<Item<'a>>,
+        /// 源码中跨行或包含注释
+        multiline: bool,
+    },
+    Table(Vec<(Vec<&'a str>, Value<'a>)>),
+}
+
+#[derive(Debug)]
+enum Item<'a> {
+    Value(Value<'a>),
+    /// 注释和是否跟在值的同一行
+    Comment(&'a str, bool),
+}
+
+struct Parser<'a> {
+    scanner: Scanner<'a>,
+    bare: Regex,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(mut self) -> Result<Vec<Line<'a>>, SyntaxError> {
+        let mut lines = Vec::new();
+        let mut table = String::new();
+        let mut tables = HashSet::new();
+        let mut keys = HashSet::new();
+        while !self.scanner.is_eof() {
+            self.skip_spaces();
+            let position = self.scanner.position();
+            match self.scanner.peek() {
+                Some('\n') | Some('\r') => {
+                    self.end_of_line()?;
+                    lines.push(Line::Blank);
+                    continue;
+                }
+                Some('#') => {
+                    lines.push(Line::Comment(self.comment()));
+                }
+                Some('[') => {
+                    self.scanner.bump();
+                    let array = self.scanner.eat('[');
+                    let key = self.key()?;
+                    if !self.scanner.eat(']') || (array && !self.scanner.eat(']')) {
+                        return Err(self.scanner.position().error("expected ']'"));
+                    }
+                    table = key.join(".");
+                    if !array && !tables.insert(table.clone()) {
+                        return Err(position.error(format!("duplicate table [{table}]")));
+                    }
+                    keys.retain(|key: &String| !key.starts_with(&format!("{table}.")));
+                    self.skip_spaces();
+                    let comment = self.trailing_comment();
+                    lines.push(Line::Table {
+                        key,
+                        array,
+                        comment,
+                    });
+                }
+                Some(_) => {
+                    let key = self.key()?;
+                    if !self.scanner.eat('=') {
+                        return Err(self.scanner.position().error("expected '=' after key"));
+                    }
+                    self.skip_spaces();
+                    let path = format!("{table}.{}", key.join("."));
+                    if !keys.insert(path) {
+                        return Err(position.error(format!("duplicate key {}", key.join("."))));
+                    }
+                    let value = self.value()?;
+                    self.skip_spaces();
+                    let comment = self.trailing_comment();
+                    lines.push(Line::Pair {
+                        key,
+                        value,
+                        comment,
+                    });
+                }
+                None => break,
+            }
+            self.end_of_line()?;
+        }
+        Ok(lines)
+    }
+
+    fn skip_spaces(&mut self) {
+        self.scanner.eat_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// 跳过空白、换行和注释，数组中使用
+    fn skip_blank(&mut self, comments: &mut Vec<Item<'a>>) -> bool {
+        let mut newline = false;
+        loop {
+            newline |= self.scanner.skip_whitespace() > 0;
+            if self.scanner.peek() == Some('#') {
+                let trailing = !newline && !comments.is_empty();
+                comments.push(Item::Comment(self.comment(), trailing));
+                newline = true;
+            } else {
+                return newline;
+            }
+        }
+    }
+
+    fn comment(&mut self) -> &'a str {
+        self.scanner
+            .eat_while(|c| c != '\n' && c != '\r')
+            .trim_end()
+    }
+
+    fn trailing_comment(&mut self) -> Option<&'a str> {
+        (self.scanner.peek() == Some('#')).then(|| self.comment())
+    }
+
+    fn end_of_line(&mut self) -> Result<(), SyntaxError> {
+        let position = self.scanner.position();
+        if self.scanner.is_eof() || self.scanner.eat('\n') || self.scanner.eat_str("\r\n") {
+            Ok(())
+        } else {
+            let c = self.scanner.peek().unwrap_or_default();
+            Err(position.error(format!("unexpected '{c}', expected end of line")))
+        }
+    }
+
+    ///
+    /// 读取键，点分的各段分别保存，引号保持原样
+    ///
+    fn key(&mut self) -> Result<Vec<&'a str>, SyntaxError> {
+        let mut segments = Vec::new();
+        loop {
+            self.skip_spaces();
+            let position = self.scanner.position();
+            let segment = match self.scanner.peek() {
+                Some('"') => self.scanner.string(false)?,
+                Some('\'') => self.literal(position)?,
+                _ => self
+                    .scanner
+                    .eat_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+            };
+            if segment.is_empty() {
+                return Err(position.error("expected key"));
+            }
+            segments.push(segment);
+            self.skip_spaces();
+            if !self.scanner.eat('.') {
+                return Ok(segments);
+            }
+        }
+    }
+
+    /// 单引号字符串，不处理转义
+    fn literal(&mut self, position: Position) -> Result<&'a str, SyntaxError> {
+        let start = self.scanner.offset();
+        self.scanner.bump();
+        self.scanner.eat_while(|c| c != '\'' && c != '\n');
+        if !self.scanner.eat('\'') {
+            return Err(position.error("unterminated string"));
+        }
+        Ok(self.scanner.slice(start))
+    }
+
+    fn value(&mut self) -> Result<Value<'a>, SyntaxError> {
+        let position = self.scanner.position();
+        let start = self.scanner.offset();
+        match self.scanner.peek() {
+            Some('"') if self.scanner.starts_with("\"\"\"") => {
+                self.multiline_string("\"\"\"", true, position)?;
+                Ok(Value::Scalar(self.scanner.slice(start)))
+            }
+            Some('\'') if self.scanner.starts_with("'''") => {
+                self.multiline_string("'''", false, position)?;
+                Ok(Value::Scalar(self.scanner.slice(start)))
+            }
+            Some('"') => Ok(Value::Scalar(self.scanner.string(false)?)),
+            Some('\'') => Ok(Value::Scalar(self.literal(position)?)),
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            _ => {
+                let mut text = self.scanner.eat_while(|c| {
+                    c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-' | '.' | ':')
+                });
+                // 日期和时间之间可以用空格分隔
+                let rest = self.scanner.rest();
+                if text.len() == 10
+                    && rest.starts_with(' ')
+                    && rest[1..].chars().take(2).all(|c| c.is_ascii_digit())
+                {
+                    self.scanner.bump();
+                    self.scanner.eat_while(|c| {
+                        c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.' | ':')
+                    });
+                    text = self.scanner.slice(start);
+                }
+                if text.is_empty() {
+                    return Err(position.error("expected value"));
+                }
+                if !self.bare.is_match(text) {
+                    return Err(position.error(format!("invalid value '{text}'")));
+                }
+                Ok(Value::Scalar(text))
+            }
+        }
+    }
+
+    fn multiline_string(
+        &mut self,
+        quote: &str,
+        escape: bool,
+        position: Position,
+    ) -> Result<(), SyntaxError> {
+        self.scanner.eat_str(quote);
+        loop {
+            if self.scanner.is_eof() {
+                return Err(position.error("unterminated string"));
+            }
+            if escape && self.scanner.eat('\\') {
+                self.scanner.bump();
+            } else if self.scanner.eat_str(quote) {
+                // 结尾最多可以再有两个引号
+                let mark = quote.chars().next().unwrap_or('"');
+                self.scanner.eat(mark);
+                self.scanner.eat(mark);
+                return Ok(());
+            } else {
+                self.scanner.bump();
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value<'a>, SyntaxError> {
+        let position = self.scanner.position();
+        self.scanner.bump();
+        let mut items = Vec::new();
+        let mut multiline = self.skip_blank(&mut items);
+        loop {
+            if self.scanner.eat(']') {
+                return Ok(Value::Array { items, multiline });
+            }
+            if self.scanner.is_eof() {
+                return Err(position.error("unclosed '['"));
+            }
+            items.push(Item::Value(self.value()?));
+            multiline |= self.skip_blank(&mut items);
+            if self.scanner.eat(',') {
+                multiline |= self.skip_blank(&mut items);
+            } else if self.scanner.peek() != Some(']') {
+                return Err(self.scanner.position().error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Value<'a>, SyntaxError> {
+        let position = self.scanner.position();
+        self.scanner.bump();
+        let mut pairs = Vec::new();
+        loop {
+            self.skip_spaces();
+            if self.scanner.eat('}') {
+                return Ok(Value::Table(pairs));
+            }
+            if self.scanner.is_eof() || self.scanner.peek() == Some('\n') {
+                return Err(position.error("unclosed '{'"));
+            }
+            if !pairs.is_empty() && !self.scanner.eat(',') {
+                return Err(self.scanner.position().error("expected ',' or '}'"));
+            }
+            let key = self.key()?;
+            if !self.scanner.eat('=') {
+                return Err(self.scanner.position().error("expected '=' after key"));
+            }
+            self.skip_spaces();
+            pairs.push((key, self.value()?));
+        }
+    }
+}
+
+fn write_lines(printer: &mut Printer, lines: &[Line]) {
+    let minify = printer.minify();
+    let mut previous: Option<&Line> = None;
+    for line in lines {
+        match line {
+            Line::Blank => {
+                if !minify && previous.is_some() {
+                    printer.blank_line();
+                }
+            }
+            Line::Comment(comment) => {
+                if !minify {
+                    printer.write(comment);
+                }
+            }
+            Line::Table {
+                key,
+                array,
+                comment,
+            } => {
+                // 表头前总是空一行，紧跟在注释后面的除外
+                if !matches!(previous, None | Some(Line::Comment(_))) {
+                    printer.blank_line();
+                }
+                let (open, close) = if *array { ("[[", "]]") } else { ("[", "]") };
+                printer.write(&format!("{open}{}{close}", key.join(".")));
+                write_comment(printer, *comment);
+            }
+            Line::Pair {
+                key,
+                value,
+                comment,
+            } => {
+                printer.write(&key.join("."));
+                printer.write(if minify { "=" } else { " = " });
+                write_value(printer, value);
+                write_comment(printer, *comment);
+            }
+        }
+        printer.line_break();
+        previous = Some(line);
+    }
+}
+
+fn write_comment(printer: &mut Printer, comment: Option<&str>) {
+    if let Some(comment) = comment.filter(|_| !printer.minify()) {
+        printer.write(" ");
+        printer.write(comment);
+    }
+}
+
+fn write_value(printer: &mut Printer, value: &Value) {
+    let minify = printer.minify();
+    match value {
+        Value::Scalar(text) => printer.write(text),
+        Value::Table(pairs) if pairs.is_empty() => printer.write("{}"),
+        Value::Table(pairs) => {
+            printer.write(if minify { "{" } else { "{ " });
+            for (index, (key, value)) in pairs.iter().enumerate() {
+                if index > 0 {
+                    printer.write(if minify { "," } else { ", " });
+                }
+                printer.write(&key.join("."));
+                printer.write(if minify { "=" } else { " = " });
+                write_value(printer, value);
+            }
+            printer.write(if minify { "}" } else { " }" });
+        }
+        Value::Array { items, multiline } if *multiline && !minify => {
+            printer.write("[");
+            printer.newline();
+            printer.indent();
+            for item in items {
+                match item {
+                    Item::Value(value) => {
+                        printer.newline();
+                        write_value(printer, value);
+                        printer.write(",");
+                    }
+                    Item::Comment(comment, true) => {
+                        printer.write(" ");
+                        printer.write(comment);
+                    }
+                    Item::Comment(comment, false) => {
+                        printer.newline();
+                        printer.write(comment);
+                    }
+                }
+            }
+            printer.dedent();
+            printer.newline();
+            printer.write("]");
+        }
+        Value::Array { items, .. } => {
+            printer.write("[");
+            let values = items.iter().filter_map(|item| match item {
+                Item::Value(value) => Some(value),
+                Item::Comment(..) => None,
+            });
+            for (index, value) in values.enumerate() {
+                if index > 0 {
+                    printer.write(if minify { "," } else { ", " });
+                }
+                write_value(printer, value);
+            }
+            printer.write("]");
+        }
+    }
+}
+
+pub struct TomlFormatter;
+
+impl Formatter for TomlFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "toml",
+            label: "TOML",
+            extensions: &["toml"],
+            capabilities: Capabilities::new(CODE_INDENTS, false),
+            options: Vec::new(),
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        _: bool,
+        _: &Options,
+    ) -> Result<String, registry::Error> {
+        format_toml(input, indent).map_err(registry::Error::format)
+    }
+}
+
+command_error! {
+    (Syntax, "syntax error: {0}", #[from] SyntaxError),
+    (Regex, "regex error: {0}", #[from] regex::Error),
+}
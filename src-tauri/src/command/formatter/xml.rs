@@ -1,43 +1,460 @@
+use super::indent::{Indent, ALL_INDENTS};
+use super::registry::{
+    self, Capabilities, Descriptor, Formatter, OptionKind, OptionSchema, Options,
+};
 use crate::command_error;
-use quick_xml::{events::Event, Writer};
+use quick_xml::{events::Event, Reader};
 use serde::{Deserialize, Serialize};
-use std::io::Cursor;
 
+/// 自动补充的 XML 声明
+const DEFAULT_DECLARATION: &str = r#"xml version="1.0" encoding="UTF-8""#;
+
+///
+/// 没有子节点的元素的写法
+///
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub enum Indent {
-    TwoSpace,
-    FourSpace,
-    Tab,
-    None,
+pub enum EmptyElement {
+    /// 保持原来的写法
+    Preserve,
+    /// `<a></a>`
+    Expand,
+    /// `<a/>`
+    Collapse,
+}
+
+///
+/// XML 声明的处理方式
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Declaration {
+    Preserve,
+    /// 没有声明时补充 `<?xml version="1.0" encoding="UTF-8"?>`
+    Add,
+    Remove,
+}
+
+///
+/// 格式检查的结果
+///
+#[derive(Debug, Serialize)]
+pub struct Report {
+    well_formed: bool,
+    errors: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    /// 行号和列号，从 1 开始
+    line: u32,
+    column: u32,
+    message: String,
 }
 
-#[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn format_xml(input: &str, indent: Indent) -> Result<String, Error> {
-    let cursor = Cursor::new(Vec::with_capacity(128));
-    let mut writer = match indent {
-        Indent::TwoSpace => Writer::new_with_indent(cursor, ' ' as u8, 2),
-        Indent::FourSpace => Writer::new_with_indent(cursor, ' ' as u8, 4),
-        Indent::Tab => Writer::new_with_indent(cursor, '\t' as u8, 1),
-        Indent::None => Writer::new(cursor),
+#[allow(clippy::too_many_arguments)]
+pub fn format_xml(
+    input: &str,
+    indent: Indent,
+    wrap_attributes: Option<usize>,
+    sort_attributes: bool,
+    comments: bool,
+    processing_instructions: bool,
+    empty_element: EmptyElement,
+    declaration: Declaration,
+) -> Result<String, Error> {
+    let mut nodes = parse(input)?;
+    let has_declaration = matches!(nodes.first(), Some(Node::Declaration(_)));
+    match declaration {
+        Declaration::Preserve => {}
+        Declaration::Add if has_declaration => {}
+        Declaration::Add => {
+            nodes.insert(0, Node::Declaration(DEFAULT_DECLARATION.to_string()));
+            if let Indent::None = indent {
+                nodes.insert(1, Node::Text("\n".to_string()));
+            }
+        }
+        Declaration::Remove => nodes.retain(|node| !matches!(node, Node::Declaration(_))),
+    }
+
+    let mut printer = Printer {
+        output: String::with_capacity(input.len()),
+        unit: indent.unit(),
+        keep_whitespace: matches!(indent, Indent::None),
+        wrap_attributes,
+        sort_attributes,
+        comments,
+        processing_instructions,
+        empty_element,
+    };
+    printer.write_document(&nodes);
+    Ok(printer.output)
+}
+
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret)]
+pub fn check_xml(input: &str) -> Report {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let errors = match roxmltree::Document::parse_with_options(input, options) {
+        Ok(_) => Vec::new(),
+        Err(e) => vec![Diagnostic {
+            line: e.pos().row,
+            column: e.pos().col,
+            message: e.to_string(),
+        }],
+    };
+    Report {
+        well_formed: errors.is_empty(),
+        errors,
+    }
+}
+
+/// 解析后的节点，文本、属性值等均保留原文中的转义
+#[derive(Debug)]
+enum Node {
+    Declaration(String),
+    DocType(String),
+    ProcessingInstruction(String),
+    Comment(String),
+    CData(String),
+    Text(String),
+    Element(Element),
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+    /// 原文中是否为 `<a/>` 的写法
+    self_closing: bool,
+}
+
+impl Element {
+    /// `xml:space` 属性的值
+    fn space(&self) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| name == "xml:space")
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Node>, Error> {
+    let string = |bytes: &[u8]| String::from_utf8_lossy(bytes).into_owned();
+    let element = |start: &quick_xml::events::BytesStart, self_closing: bool| {
+        let attributes = start
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute?;
+                Ok((string(attribute.key.as_ref()), string(&attribute.value)))
+            })
+            .collect::<Result<Vec<_>, quick_xml::Error>>()?;
+        Ok::<_, quick_xml::Error>(Element {
+            name: string(start.name().as_ref()),
+            attributes,
+            children: Vec::new(),
+            self_closing,
+        })
     };
-    let mut reader = quick_xml::Reader::from_str(input);
-    reader.config_mut().trim_text(true);
+
+    let mut reader = Reader::from_str(input);
+    let mut root = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
     loop {
-        let event = reader.read_event()?;
-        match event {
+        let event = reader.read_event().map_err(|e| {
+            let (line, column) = position(input, reader.error_position() as usize);
+            Error::Xml(e, line, column)
+        })?;
+        let node = match event {
             Event::Eof => break,
-            event => {
-                writer.write_event(event)?;
+            Event::Start(start) => {
+                let element = element(&start, false).map_err(|e| {
+                    let (line, column) = position(input, reader.buffer_position() as usize);
+                    Error::Xml(e, line, column)
+                })?;
+                stack.push(element);
+                continue;
+            }
+            Event::End(_) => match stack.pop() {
+                Some(element) => Node::Element(element),
+                None => continue,
+            },
+            Event::Empty(start) => Node::Element(element(&start, true).map_err(|e| {
+                let (line, column) = position(input, reader.buffer_position() as usize);
+                Error::Xml(e, line, column)
+            })?),
+            Event::Text(text) => Node::Text(string(&text)),
+            // 实体引用与相邻的文本合并
+            Event::GeneralRef(reference) => Node::Text(format!("&{};", string(&reference))),
+            Event::CData(cdata) => Node::CData(string(&cdata)),
+            Event::Comment(comment) => Node::Comment(string(&comment)),
+            Event::Decl(declaration) => Node::Declaration(string(&declaration)),
+            Event::PI(pi) => Node::ProcessingInstruction(string(&pi)),
+            Event::DocType(doctype) => Node::DocType(string(&doctype)),
+        };
+        let siblings = match stack.last_mut() {
+            Some(parent) => &mut parent.children,
+            None => &mut root,
+        };
+        match (siblings.last_mut(), node) {
+            (Some(Node::Text(previous)), Node::Text(text)) => previous.push_str(&text),
+            (_, node) => siblings.push(node),
+        }
+    }
+    match stack.pop() {
+        Some(element) => Err(Error::Unclosed(element.name)),
+        None => Ok(root),
+    }
+}
+
+/// 将字节偏移转换为行号和列号，从 1 开始
+fn position(input: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(input.len());
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |line| line.chars().count())
+        + 1;
+    (line, column)
+}
+
+fn is_blank(node: &Node) -> bool {
+    matches!(node, Node::Text(text) if text.trim().is_empty())
+}
+
+struct Printer {
+    output: String,
+    /// 缩进单位，为空时不换行
+    unit: Option<&'static str>,
+    /// 保留原文中的所有空白
+    keep_whitespace: bool,
+    wrap_attributes: Option<usize>,
+    sort_attributes: bool,
+    comments: bool,
+    processing_instructions: bool,
+    empty_element: EmptyElement,
+}
+
+impl Printer {
+    fn write_document(&mut self, nodes: &[Node]) {
+        let nodes = self.visible(nodes);
+        if self.keep_whitespace {
+            for node in nodes {
+                self.write_node(node, 0, true);
+            }
+            return;
+        }
+        for (index, node) in nodes.into_iter().filter(|node| !is_blank(node)).enumerate() {
+            if index > 0 && self.unit.is_some() {
+                self.output.push('\n');
+            }
+            self.write_node(node, 0, false);
+        }
+    }
+
+    /// 过滤掉需要去除的注释和处理指令
+    fn visible<'a>(&self, nodes: &'a [Node]) -> Vec<&'a Node> {
+        nodes
+            .iter()
+            .filter(|node| match node {
+                Node::Comment(_) => self.comments,
+                Node::ProcessingInstruction(_) => self.processing_instructions,
+                _ => true,
+            })
+            .collect()
+    }
+
+    fn newline(&mut self, depth: usize) {
+        if let Some(unit) = self.unit {
+            self.output.push('\n');
+            self.output.push_str(&unit.repeat(depth));
+        }
+    }
+
+    ///
+    /// 输出一个节点，`inline` 为真时节点内部不再调整空白
+    ///
+    fn write_node(&mut self, node: &Node, depth: usize, inline: bool) {
+        match node {
+            Node::Declaration(content) | Node::ProcessingInstruction(content) => {
+                self.output.push_str(&format!("<?{}?>", content));
+            }
+            Node::DocType(content) => self.output.push_str(&format!("<!DOCTYPE {}>", content)),
+            Node::Comment(content) => self.output.push_str(&format!("<!--{}-->", content)),
+            Node::CData(content) => self.output.push_str(&format!("<![CDATA[{}]]>", content)),
+            Node::Text(text) => self.output.push_str(text),
+            Node::Element(element) => self.write_element(element, depth, inline),
+        }
+    }
+
+    fn write_element(&mut self, element: &Element, depth: usize, inline: bool) {
+        // xml:space="preserve" 的区域保持原样，xml:space="default" 恢复格式化
+        let inline = match element.space() {
+            Some("preserve") => true,
+            Some("default") => self.keep_whitespace,
+            _ => inline,
+        };
+        let all = self.visible(&element.children);
+        let children = match inline {
+            true => all.clone(),
+            false => all.iter().copied().filter(|node| !is_blank(node)).collect(),
+        };
+
+        self.write_start_tag(element, depth, inline);
+        if children.is_empty() {
+            let collapse = match self.empty_element {
+                EmptyElement::Preserve => element.self_closing,
+                EmptyElement::Expand => false,
+                EmptyElement::Collapse => true,
+            };
+            match collapse {
+                true => self.output.push_str("/>"),
+                false => self.output.push_str(&format!("></{}>", element.name)),
+            }
+            return;
+        }
+        self.output.push('>');
+
+        // 只有文本或者混合内容时，文本中的空白是有意义的
+        let text_only = children
+            .iter()
+            .all(|node| matches!(node, Node::Text(_) | Node::CData(_)));
+        let mixed = children
+            .iter()
+            .any(|node| matches!(node, Node::Text(_)) && !is_blank(node));
+        if inline || text_only || mixed {
+            for node in all {
+                self.write_node(node, depth + 1, true);
             }
+        } else {
+            for node in children {
+                self.newline(depth + 1);
+                self.write_node(node, depth + 1, false);
+            }
+            self.newline(depth);
         }
+        self.output.push_str(&format!("</{}>", element.name));
+    }
+
+    fn write_start_tag(&mut self, element: &Element, depth: usize, inline: bool) {
+        let mut attributes = element
+            .attributes
+            .iter()
+            .map(|(name, value)| {
+                let quote = if value.contains('"') { '\'' } else { '"' };
+                (
+                    name.as_str(),
+                    format!("{}={}{}{}", name, quote, value, quote),
+                )
+            })
+            .collect::<Vec<_>>();
+        // 命名空间声明保持在最前面
+        if self.sort_attributes {
+            attributes
+                .sort_by_key(|(name, _)| (*name != "xmlns" && !name.starts_with("xmlns:"), *name));
+        }
+
+        let single = attributes
+            .iter()
+            .fold(format!("<{}", element.name), |tag, (_, attribute)| {
+                format!("{} {}", tag, attribute)
+            });
+        let width = self.unit.map_or(0, |unit| unit.len() * depth) + single.len() + 1;
+        let wrap = match (self.unit, self.wrap_attributes) {
+            (Some(_), Some(limit)) => !inline && attributes.len() > 1 && width > limit,
+            _ => false,
+        };
+        if !wrap {
+            self.output.push_str(&single);
+            return;
+        }
+        self.output.push_str(&format!("<{}", element.name));
+        for (_, attribute) in attributes {
+            self.newline(depth + 1);
+            self.output.push_str(&attribute);
+        }
+    }
+}
+
+pub struct XmlFormatter;
+
+impl Formatter for XmlFormatter {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            name: "xml",
+            label: "XML",
+            extensions: &["xml", "xsd", "xsl", "svg", "plist"],
+            capabilities: Capabilities::new(ALL_INDENTS, true),
+            options: vec![
+                OptionSchema {
+                    name: "wrap_attributes",
+                    label: "属性换行宽度",
+                    kind: OptionKind::Integer {
+                        default: None,
+                        min: 1,
+                        max: None,
+                    },
+                },
+                OptionSchema {
+                    name: "comments",
+                    label: "保留注释",
+                    kind: OptionKind::Boolean { default: true },
+                },
+                OptionSchema {
+                    name: "processing_instructions",
+                    label: "保留处理指令",
+                    kind: OptionKind::Boolean { default: true },
+                },
+                OptionSchema {
+                    name: "empty_element",
+                    label: "空元素",
+                    kind: OptionKind::Choice {
+                        choices: &["Preserve", "Expand", "Collapse"],
+                        default: "Preserve",
+                    },
+                },
+                OptionSchema {
+                    name: "declaration",
+                    label: "XML 声明",
+                    kind: OptionKind::Choice {
+                        choices: &["Preserve", "Add", "Remove"],
+                        default: "Preserve",
+                    },
+                },
+            ],
+        }
+    }
+
+    fn format(
+        &self,
+        input: &str,
+        indent: Indent,
+        sort: bool,
+        options: &Options,
+    ) -> Result<String, registry::Error> {
+        format_xml(
+            input,
+            indent,
+            options.get("wrap_attributes", None)?,
+            sort,
+            options.get("comments", true)?,
+            options.get("processing_instructions", true)?,
+            options.get("empty_element", EmptyElement::Preserve)?,
+            options.get("declaration", Declaration::Preserve)?,
+        )
+        .map_err(registry::Error::format)
     }
-    let bytes = writer.into_inner().into_inner();
-    String::from_utf8(bytes).map_err(Into::into)
 }
 
 command_error! {
-    (Io, "io error: {0}", #[from] std::io::Error),
-    (Xml, "xml error: {0}", #[from] quick_xml::Error),
-    (Utf8, "utf8 error: {0}", #[from] std::string::FromUtf8Error),
+    (Xml, "xml error at line {1} column {2}: {0}", quick_xml::Error, usize, usize),
+    (Unclosed, "unclosed element <{0}>", String),
 }
@@ -0,0 +1,327 @@
+//!
+//! XML 的 XSD 和 DTD 校验，只使用传入的内容，不会从网络加载任何 schema。
+//!
+use super::{dtd, xsd};
+use crate::command_error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+///
+/// 校验使用的 schema 类型
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SchemaKind {
+    /// 传入一个或多个 XSD，通过 `xs:import` 引用的 schema 需要一并传入
+    Xsd,
+    /// 使用文档中的内部 DTD，传入的内容作为外部 DTD
+    Dtd,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    valid: bool,
+    violations: Vec<Violation>,
+}
+
+///
+/// 一处不符合 schema 的地方
+///
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    /// 行号和列号，从 1 开始
+    line: u32,
+    column: u32,
+    /// 元素路径，如 `/order/item[2]/@id`
+    path: String,
+    message: String,
+}
+
+impl Violation {
+    pub fn position(&self) -> (u32, u32) {
+        (self.line, self.column)
+    }
+
+    pub fn element(node: roxmltree::Node, message: impl Into<String>) -> Self {
+        let pos = node.document().text_pos_at(node.range().start);
+        Self {
+            line: pos.row,
+            column: pos.col,
+            path: path(node),
+            message: message.into(),
+        }
+    }
+
+    pub fn attribute(
+        node: roxmltree::Node,
+        attribute: &roxmltree::Attribute,
+        message: impl Into<String>,
+    ) -> Self {
+        let pos = node.document().text_pos_at(attribute.range().start);
+        Self {
+            line: pos.row,
+            column: pos.col,
+            path: format!("{}/@{}", path(node), attribute.name()),
+            message: message.into(),
+        }
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn validate_xml(input: &str, kind: SchemaKind, schemas: Vec<String>) -> Result<Report, Error> {
+    let document = roxmltree::Document::parse_with_options(input, parsing_options())?;
+    let violations = match kind {
+        SchemaKind::Xsd => xsd::validate(&document, &schemas)?,
+        SchemaKind::Dtd => dtd::validate(&document, &schemas)?,
+    };
+    Ok(Report {
+        valid: violations.is_empty(),
+        violations,
+    })
+}
+
+pub fn parsing_options<'input>() -> roxmltree::ParsingOptions<'input> {
+    roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    }
+}
+
+/// 元素路径，同名的兄弟元素有多个时带上序号
+fn path(node: roxmltree::Node) -> String {
+    let mut steps = node
+        .ancestors()
+        .filter(|node| node.is_element())
+        .map(|node| {
+            let name = node.tag_name().name();
+            let same = |sibling: &roxmltree::Node| {
+                sibling.is_element() && sibling.tag_name().name() == name
+            };
+            let index = node.prev_siblings().skip(1).filter(same).count();
+            let total = index + 1 + node.next_siblings().skip(1).filter(same).count();
+            match total {
+                1 => format!("/{}", name),
+                _ => format!("/{}[{}]", name, index + 1),
+            }
+        })
+        .collect::<Vec<_>>();
+    steps.reverse();
+    steps.concat()
+}
+
+///
+/// 内容模型中的一项，XSD 的 particle 和 DTD 的 content particle 都转换为该结构
+///
+pub struct Particle {
+    pub term: Term,
+    pub min: usize,
+    /// 为空时表示不限次数
+    pub max: Option<usize>,
+}
+
+pub enum Term {
+    /// 元素名，XSD 中为 `{namespace}local`，DTD 中为原文中的限定名
+    Element(String),
+    /// 通配符，判断元素名是否匹配
+    Any(Box<dyn Fn(&str) -> bool>),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+    All(Vec<Particle>),
+}
+
+///
+/// 子元素与内容模型不匹配的原因
+///
+pub enum Mismatch {
+    /// 第 `index` 个子元素不应该出现
+    Unexpected { index: usize, expected: Vec<String> },
+    /// 缺少子元素
+    Incomplete { expected: Vec<String> },
+}
+
+impl Mismatch {
+    pub fn describe(expected: &[String]) -> String {
+        match expected.is_empty() {
+            true => "no more child elements are allowed".to_string(),
+            false => format!("expected one of: {}", expected.join(", ")),
+        }
+    }
+}
+
+impl Particle {
+    pub fn new(term: Term, min: usize, max: Option<usize>) -> Self {
+        Self { term, min, max }
+    }
+
+    pub fn one(term: Term) -> Self {
+        Self::new(term, 1, Some(1))
+    }
+
+    ///
+    /// 检查子元素序列是否符合内容模型
+    ///
+    pub fn check(&self, keys: &[String]) -> Result<(), Mismatch> {
+        if self.ends(keys, 0, false).contains(&keys.len()) {
+            return Ok(());
+        }
+        // 能够作为合法前缀的最长子序列，之后的第一个元素就是出错的位置
+        let viable = |len: usize| self.ends(&keys[..len], 0, true).contains(&len);
+        let (mut low, mut high) = (0, keys.len());
+        while low < high {
+            let mid = (low + high).div_ceil(2);
+            match viable(mid) {
+                true => low = mid,
+                false => high = mid - 1,
+            }
+        }
+        let expected = self.expected(&keys[..low]);
+        match low < keys.len() {
+            true => Err(Mismatch::Unexpected {
+                index: low,
+                expected,
+            }),
+            false => Err(Mismatch::Incomplete { expected }),
+        }
+    }
+
+    /// 在合法前缀之后可以出现的元素
+    fn expected(&self, prefix: &[String]) -> Vec<String> {
+        let mut leaves = BTreeSet::new();
+        self.leaves(&mut leaves);
+        leaves
+            .into_iter()
+            .filter(|leaf| {
+                let mut keys = prefix.to_vec();
+                keys.push(leaf.clone());
+                self.ends(&keys, 0, true).contains(&keys.len())
+            })
+            .collect()
+    }
+
+    fn leaves(&self, leaves: &mut BTreeSet<String>) {
+        match &self.term {
+            Term::Element(key) => {
+                leaves.insert(key.clone());
+            }
+            Term::Any(_) => {}
+            Term::Sequence(particles) | Term::Choice(particles) | Term::All(particles) => {
+                particles.iter().for_each(|p| p.leaves(leaves));
+            }
+        }
+    }
+
+    ///
+    /// 从 `start` 开始匹配，返回所有可能的结束位置。
+    ///
+    /// `partial` 为真时，到达序列末尾即视为匹配成功，用于判断前缀是否合法。
+    ///
+    fn ends(&self, keys: &[String], start: usize, partial: bool) -> BTreeSet<usize> {
+        let mut result = BTreeSet::new();
+        if self.min == 0 {
+            result.insert(start);
+        }
+        let mut current = BTreeSet::from([start]);
+        let mut count = 0;
+        loop {
+            count += 1;
+            if self.max.is_some_and(|max| count > max) || count > keys.len() + self.min + 1 {
+                break;
+            }
+            let next = current
+                .iter()
+                .flat_map(|&pos| self.term_ends(keys, pos, partial))
+                .collect::<BTreeSet<_>>();
+            if next.is_empty() {
+                break;
+            }
+            if count >= self.min {
+                let before = result.len();
+                result.extend(next.iter().copied());
+                // 没有新的位置说明后续的重复不会再有变化
+                if result.len() == before && count > self.min {
+                    break;
+                }
+            }
+            current = next;
+        }
+        result
+    }
+
+    fn term_ends(&self, keys: &[String], start: usize, partial: bool) -> BTreeSet<usize> {
+        if partial && start == keys.len() {
+            return BTreeSet::from([start]);
+        }
+        match &self.term {
+            Term::Element(key) => match keys.get(start) {
+                Some(k) if k == key => BTreeSet::from([start + 1]),
+                _ => BTreeSet::new(),
+            },
+            Term::Any(matches) => match keys.get(start) {
+                Some(k) if matches(k) => BTreeSet::from([start + 1]),
+                _ => BTreeSet::new(),
+            },
+            Term::Sequence(particles) => {
+                particles
+                    .iter()
+                    .fold(BTreeSet::from([start]), |positions, particle| {
+                        positions
+                            .into_iter()
+                            .flat_map(|pos| particle.ends(keys, pos, partial))
+                            .collect()
+                    })
+            }
+            Term::Choice(particles) => particles
+                .iter()
+                .flat_map(|particle| particle.ends(keys, start, partial))
+                .collect(),
+            Term::All(particles) => {
+                let mut result = BTreeSet::new();
+                all_ends(
+                    particles,
+                    &mut vec![false; particles.len()],
+                    keys,
+                    start,
+                    partial,
+                    &mut result,
+                );
+                result
+            }
+        }
+    }
+}
+
+/// `xs:all` 中的每一项以任意顺序最多出现一次
+fn all_ends(
+    particles: &[Particle],
+    used: &mut Vec<bool>,
+    keys: &[String],
+    pos: usize,
+    partial: bool,
+    result: &mut BTreeSet<usize>,
+) {
+    let complete = particles
+        .iter()
+        .zip(used.iter())
+        .all(|(particle, used)| *used || particle.min == 0);
+    if complete || (partial && pos == keys.len()) {
+        result.insert(pos);
+    }
+    for index in 0..particles.len() {
+        if used[index] {
+            continue;
+        }
+        for end in particles[index].ends(keys, pos, partial) {
+            if end > pos {
+                used[index] = true;
+                all_ends(particles, used, keys, end, partial, result);
+                used[index] = false;
+            }
+        }
+    }
+}
+
+command_error! {
+    (Xml, "parse xml error: {0}", #[from] roxmltree::Error),
+    (Xsd, "{0}", #[from] xsd::Error),
+    (Dtd, "{0}", #[from] dtd::Error),
+}
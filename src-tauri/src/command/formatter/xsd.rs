@@ -0,0 +1,1131 @@
+//!
+//! XML Schema 1.0 校验。
+//!
+//! 支持常用的结构：全局和局部元素、命名和匿名类型、`sequence`/`choice`/`all`、`group`、
+//! `attributeGroup`、`any`/`anyAttribute`、简单内容和复杂内容的扩展与限制、
+//! 内置类型以及 `enumeration`、`pattern`、长度、范围、位数等约束。
+//! `xs:import`/`xs:include` 不会加载远程文件，引用的 schema 需要一并传入。
+//!
+use super::xml_schema::{parsing_options, Mismatch, Particle, Term, Violation};
+use crate::command_error;
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+const XS: &str = "http://www.w3.org/2001/XMLSchema";
+const XSI: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// 引用解析的最大深度，避免循环引用
+const MAX_DEPTH: usize = 64;
+
+type Node<'a> = roxmltree::Node<'a, 'a>;
+
+pub fn validate(
+    document: &roxmltree::Document,
+    schemas: &[String],
+) -> Result<Vec<Violation>, Error> {
+    let documents = schemas
+        .iter()
+        .filter(|schema| !schema.trim().is_empty())
+        .map(|schema| roxmltree::Document::parse_with_options(schema, parsing_options()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if documents.is_empty() {
+        return Err(Error::NoSchema);
+    }
+    let schema = Schema::load(&documents)?;
+
+    let mut validator = Validator {
+        schema: &schema,
+        ids: HashSet::new(),
+        regexes: RefCell::new(HashMap::new()),
+        violations: Vec::new(),
+    };
+    let root = document.root_element();
+    match schema.elements.get(&instance_key(root)) {
+        Some(decl) => validator.validate_element(root, *decl)?,
+        None => validator.violations.push(Violation::element(
+            root,
+            format!(
+                "no global element declaration for <{}>",
+                display(&instance_key(root))
+            ),
+        )),
+    }
+    validator.violations.sort_by_key(Violation::position);
+    Ok(validator.violations)
+}
+
+/// 带命名空间的名称，格式为 `{namespace}local`
+fn key(namespace: Option<&str>, local: &str) -> String {
+    format!("{{{}}}{}", namespace.unwrap_or_default(), local)
+}
+
+fn instance_key(node: roxmltree::Node) -> String {
+    key(node.tag_name().namespace(), node.tag_name().name())
+}
+
+/// 用于提示信息的名称，没有命名空间时只显示本地名
+fn display(key: &str) -> &str {
+    key.strip_prefix("{}").unwrap_or(key)
+}
+
+fn namespace_of(key: &str) -> &str {
+    key.strip_prefix('{')
+        .and_then(|rest| rest.split_once('}'))
+        .map_or("", |(namespace, _)| namespace)
+}
+
+fn is_xs(node: &Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().namespace() == Some(XS) && node.tag_name().name() == name
+}
+
+fn xs_children<'a>(node: Node<'a>) -> impl Iterator<Item = Node<'a>> {
+    node.children()
+        .filter(|child| child.is_element() && child.tag_name().namespace() == Some(XS))
+}
+
+fn xs_child<'a>(node: Node<'a>, name: &str) -> Option<Node<'a>> {
+    xs_children(node).find(|child| child.tag_name().name() == name)
+}
+
+fn target_namespace<'a>(node: Node<'a>) -> Option<&'a str> {
+    node.document().root_element().attribute("targetNamespace")
+}
+
+///
+/// 按照 schema 文档中的命名空间声明解析 `prefix:local` 形式的引用
+///
+fn resolve_qname(node: Node, qname: &str) -> Result<String, Error> {
+    let (prefix, local) = match qname.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, qname),
+    };
+    match node.lookup_namespace_uri(prefix) {
+        Some(namespace) => Ok(key(Some(namespace), local)),
+        None if prefix.is_none() => Ok(key(None, local)),
+        None => Err(Error::UnboundPrefix(qname.to_string())),
+    }
+}
+
+fn occurs(node: Node) -> Result<(usize, Option<usize>), Error> {
+    let parse = |value: &str| {
+        value
+            .parse::<usize>()
+            .map_err(|_| Error::Schema(format!("invalid occurrence \"{}\"", value)))
+    };
+    let min = node
+        .attribute("minOccurs")
+        .map(parse)
+        .transpose()?
+        .unwrap_or(1);
+    let max = match node.attribute("maxOccurs") {
+        Some("unbounded") => None,
+        Some(value) => Some(parse(value)?),
+        None => Some(1),
+    };
+    Ok((min, max))
+}
+
+/// 所有 schema 文档中的全局声明
+struct Schema<'a> {
+    elements: HashMap<String, Node<'a>>,
+    types: HashMap<String, Node<'a>>,
+    groups: HashMap<String, Node<'a>>,
+    attribute_groups: HashMap<String, Node<'a>>,
+    attributes: HashMap<String, Node<'a>>,
+}
+
+impl<'a> Schema<'a> {
+    fn load(documents: &'a [roxmltree::Document<'a>]) -> Result<Self, Error> {
+        let mut schema = Schema {
+            elements: HashMap::new(),
+            types: HashMap::new(),
+            groups: HashMap::new(),
+            attribute_groups: HashMap::new(),
+            attributes: HashMap::new(),
+        };
+        for document in documents {
+            let root = document.root_element();
+            if !is_xs(&root, "schema") {
+                return Err(Error::Schema(format!(
+                    "expected <xs:schema> as the root element, found <{}>",
+                    root.tag_name().name()
+                )));
+            }
+            let namespace = root.attribute("targetNamespace");
+            for child in xs_children(root) {
+                let Some(name) = child.attribute("name") else {
+                    continue;
+                };
+                let map = match child.tag_name().name() {
+                    "element" => &mut schema.elements,
+                    "complexType" | "simpleType" => &mut schema.types,
+                    "group" => &mut schema.groups,
+                    "attributeGroup" => &mut schema.attribute_groups,
+                    "attribute" => &mut schema.attributes,
+                    _ => continue,
+                };
+                map.insert(key(namespace, name), child);
+            }
+        }
+        Ok(schema)
+    }
+
+    fn lookup(
+        &self,
+        map: &HashMap<String, Node<'a>>,
+        node: Node<'a>,
+        qname: &str,
+        kind: &str,
+    ) -> Result<Node<'a>, Error> {
+        let key = resolve_qname(node, qname)?;
+        map.get(&key)
+            .copied()
+            .ok_or_else(|| Error::Unresolved(kind.to_string(), display(&key).to_string()))
+    }
+
+    /// 解析类型引用
+    fn resolve_type(&self, node: Node<'a>, qname: &str) -> Result<Type<'a>, Error> {
+        let key = resolve_qname(node, qname)?;
+        if namespace_of(&key) == XS {
+            let local = &key[XS.len() + 2..];
+            return Ok(match local {
+                "anyType" => Type::Any,
+                _ => Type::Simple(Simple::Builtin(local.to_string())),
+            });
+        }
+        match self.types.get(&key) {
+            Some(node) if is_xs(node, "complexType") => Ok(Type::Complex(*node)),
+            Some(node) => Ok(Type::Simple(Simple::Node(*node))),
+            None => Err(Error::Unresolved(
+                "type".to_string(),
+                display(&key).to_string(),
+            )),
+        }
+    }
+
+    /// 元素声明的类型，没有声明类型时为 `xs:anyType`
+    fn element_type(&self, decl: Node<'a>) -> Result<Type<'a>, Error> {
+        if let Some(name) = decl.attribute("type") {
+            return self.resolve_type(decl, name);
+        }
+        if let Some(node) = xs_child(decl, "complexType") {
+            return Ok(Type::Complex(node));
+        }
+        if let Some(node) = xs_child(decl, "simpleType") {
+            return Ok(Type::Simple(Simple::Node(node)));
+        }
+        Ok(Type::Any)
+    }
+
+    /// 属性声明的类型，没有声明类型时为 `xs:anySimpleType`
+    fn attribute_type(&self, decl: Node<'a>) -> Result<Simple<'a>, Error> {
+        if let Some(name) = decl.attribute("type") {
+            return match self.resolve_type(decl, name)? {
+                Type::Simple(simple) => Ok(simple),
+                _ => Err(Error::Schema(format!(
+                    "attribute type \"{}\" is not a simple type",
+                    name
+                ))),
+            };
+        }
+        Ok(match xs_child(decl, "simpleType") {
+            Some(node) => Simple::Node(node),
+            None => Simple::Builtin("anySimpleType".to_string()),
+        })
+    }
+
+    ///
+    /// 展开复杂类型的内容模型和属性，包括从基类型继承的部分
+    ///
+    fn complex(&self, node: Node<'a>, depth: usize) -> Result<Complex<'a>, Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::Circular(
+                node.attribute("name").unwrap_or_default().to_string(),
+            ));
+        }
+        let mut complex = Complex {
+            mixed: node.attribute("mixed") == Some("true"),
+            ..Default::default()
+        };
+        for child in xs_children(node) {
+            match child.tag_name().name() {
+                "sequence" | "choice" | "all" | "group" => {
+                    complex.particle = self.particle(child, &mut complex.decls, depth + 1)?;
+                }
+                "attribute" | "attributeGroup" | "anyAttribute" => {
+                    self.attributes(child, &mut complex, depth + 1)?;
+                }
+                "simpleContent" => {
+                    let Some(derivation) = xs_children(child)
+                        .find(|c| is_xs(c, "extension") || is_xs(c, "restriction"))
+                    else {
+                        continue;
+                    };
+                    let base = derivation.attribute("base").unwrap_or("xs:anySimpleType");
+                    let simple = match self.resolve_type(derivation, base)? {
+                        Type::Simple(simple) => simple,
+                        Type::Complex(base) => {
+                            let base = self.complex(base, depth + 1)?;
+                            complex.uses = base.uses;
+                            complex.any_attribute = base.any_attribute;
+                            base.simple
+                                .unwrap_or(Simple::Builtin("anySimpleType".to_string()))
+                        }
+                        Type::Any => Simple::Builtin("anySimpleType".to_string()),
+                    };
+                    complex.simple = Some(match derivation.tag_name().name() {
+                        "restriction" => Simple::Restriction(derivation, Box::new(simple)),
+                        _ => simple,
+                    });
+                    for attribute in xs_children(derivation) {
+                        self.attributes(attribute, &mut complex, depth + 1)?;
+                    }
+                }
+                "complexContent" => {
+                    if child.attribute("mixed") == Some("true") {
+                        complex.mixed = true;
+                    }
+                    let Some(derivation) = xs_children(child)
+                        .find(|c| is_xs(c, "extension") || is_xs(c, "restriction"))
+                    else {
+                        continue;
+                    };
+                    let base = derivation.attribute("base").unwrap_or("xs:anyType");
+                    let extension = derivation.tag_name().name() == "extension";
+                    if let Type::Complex(base) = self.resolve_type(derivation, base)? {
+                        let base = self.complex(base, depth + 1)?;
+                        complex.uses = base.uses;
+                        complex.any_attribute = base.any_attribute;
+                        if extension {
+                            complex.decls = base.decls;
+                            complex.particle = base.particle;
+                        }
+                    }
+                    for item in xs_children(derivation) {
+                        match item.tag_name().name() {
+                            "sequence" | "choice" | "all" | "group" => {
+                                let particle =
+                                    self.particle(item, &mut complex.decls, depth + 1)?;
+                                // 扩展的内容追加在基类型的内容之后
+                                complex.particle = match (complex.particle.take(), particle) {
+                                    (Some(base), Some(own)) => {
+                                        Some(Particle::one(Term::Sequence(vec![base, own])))
+                                    }
+                                    (base, own) => own.or(base),
+                                };
+                            }
+                            _ => self.attributes(item, &mut complex, depth + 1)?,
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(complex)
+    }
+
+    /// 将 `sequence`、`choice`、`all`、`element`、`group`、`any` 转换为内容模型
+    fn particle(
+        &self,
+        node: Node<'a>,
+        decls: &mut HashMap<String, Node<'a>>,
+        depth: usize,
+    ) -> Result<Option<Particle>, Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::Circular(
+                node.attribute("ref").unwrap_or_default().to_string(),
+            ));
+        }
+        let (min, max) = occurs(node)?;
+        if max == Some(0) {
+            return Ok(None);
+        }
+        let term = match node.tag_name().name() {
+            "element" => {
+                let (key, decl) = match node.attribute("ref") {
+                    Some(name) => {
+                        let decl = self.lookup(&self.elements, node, name, "element")?;
+                        (resolve_qname(node, name)?, decl)
+                    }
+                    None => {
+                        let name = node.attribute("name").unwrap_or_default();
+                        let qualified = match node.attribute("form") {
+                            Some(form) => form == "qualified",
+                            None => {
+                                node.document()
+                                    .root_element()
+                                    .attribute("elementFormDefault")
+                                    == Some("qualified")
+                            }
+                        };
+                        let namespace = target_namespace(node).filter(|_| qualified);
+                        (key(namespace, name), node)
+                    }
+                };
+                decls.insert(key.clone(), decl);
+                Term::Element(key)
+            }
+            "sequence" | "choice" | "all" => {
+                let mut particles = Vec::new();
+                for child in xs_children(node) {
+                    if let Some(particle) = self.particle(child, decls, depth + 1)? {
+                        particles.push(particle);
+                    }
+                }
+                match node.tag_name().name() {
+                    "sequence" => Term::Sequence(particles),
+                    "choice" => Term::Choice(particles),
+                    _ => Term::All(particles),
+                }
+            }
+            "group" => {
+                let name = node.attribute("ref").unwrap_or_default();
+                let group = self.lookup(&self.groups, node, name, "group")?;
+                let Some(content) =
+                    xs_children(group).find(|c| c.tag_name().name() != "annotation")
+                else {
+                    return Ok(None);
+                };
+                match self.particle(content, decls, depth + 1)? {
+                    Some(particle) => particle.term,
+                    None => return Ok(None),
+                }
+            }
+            "any" => Term::Any(wildcard(node)),
+            _ => return Ok(None),
+        };
+        Ok(Some(Particle::new(term, min, max)))
+    }
+
+    fn attributes(
+        &self,
+        node: Node<'a>,
+        complex: &mut Complex<'a>,
+        depth: usize,
+    ) -> Result<(), Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::Circular(
+                node.attribute("ref").unwrap_or_default().to_string(),
+            ));
+        }
+        match node.tag_name().name() {
+            "anyAttribute" => complex.any_attribute = true,
+            "attributeGroup" => {
+                let name = node.attribute("ref").unwrap_or_default();
+                let group = self.lookup(&self.attribute_groups, node, name, "attribute group")?;
+                for child in xs_children(group) {
+                    self.attributes(child, complex, depth + 1)?;
+                }
+            }
+            "attribute" => {
+                let (key, decl) = match node.attribute("ref") {
+                    Some(name) => {
+                        let decl = self.lookup(&self.attributes, node, name, "attribute")?;
+                        (resolve_qname(node, name)?, decl)
+                    }
+                    None => {
+                        let name = node.attribute("name").unwrap_or_default();
+                        let qualified = match node.attribute("form") {
+                            Some(form) => form == "qualified",
+                            None => {
+                                node.document()
+                                    .root_element()
+                                    .attribute("attributeFormDefault")
+                                    == Some("qualified")
+                            }
+                        };
+                        let namespace = target_namespace(node).filter(|_| qualified);
+                        (key(namespace, name), node)
+                    }
+                };
+                let usage = AttributeUse {
+                    required: node.attribute("use") == Some("required"),
+                    prohibited: node.attribute("use") == Some("prohibited"),
+                    fixed: node.attribute("fixed").or(decl.attribute("fixed")),
+                    kind: self.attribute_type(decl)?,
+                    key,
+                };
+                // 派生类型中的声明覆盖基类型中的同名声明
+                complex.uses.retain(|u| u.key != usage.key);
+                complex.uses.push(usage);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// `xs:any` 的命名空间约束
+fn wildcard(node: Node) -> Box<dyn Fn(&str) -> bool> {
+    let target = target_namespace(node).unwrap_or_default().to_string();
+    let constraint = node.attribute("namespace").unwrap_or("##any");
+    match constraint {
+        "##any" => Box::new(|_| true),
+        "##other" => Box::new(move |key| {
+            let namespace = namespace_of(key);
+            namespace != target && !namespace.is_empty()
+        }),
+        list => {
+            let allowed = list
+                .split_whitespace()
+                .map(|item| match item {
+                    "##targetNamespace" => target.clone(),
+                    "##local" => String::new(),
+                    uri => uri.to_string(),
+                })
+                .collect::<Vec<_>>();
+            Box::new(move |key| allowed.iter().any(|uri| uri == namespace_of(key)))
+        }
+    }
+}
+
+enum Type<'a> {
+    /// `xs:anyType`，不做任何检查
+    Any,
+    Simple(Simple<'a>),
+    Complex(Node<'a>),
+}
+
+#[derive(Clone)]
+enum Simple<'a> {
+    /// 内置类型的本地名，如 `int`
+    Builtin(String),
+    /// `xs:simpleType` 节点
+    Node(Node<'a>),
+    /// `xs:simpleContent` 中的 `xs:restriction` 节点和它的基类型
+    Restriction(Node<'a>, Box<Simple<'a>>),
+}
+
+struct AttributeUse<'a> {
+    key: String,
+    required: bool,
+    prohibited: bool,
+    fixed: Option<&'a str>,
+    kind: Simple<'a>,
+}
+
+#[derive(Default)]
+struct Complex<'a> {
+    particle: Option<Particle>,
+    /// 内容模型中的元素声明
+    decls: HashMap<String, Node<'a>>,
+    uses: Vec<AttributeUse<'a>>,
+    any_attribute: bool,
+    mixed: bool,
+    /// 简单内容的类型
+    simple: Option<Simple<'a>>,
+}
+
+struct Validator<'s, 'a> {
+    schema: &'s Schema<'a>,
+    ids: HashSet<String>,
+    regexes: RefCell<HashMap<String, Regex>>,
+    violations: Vec<Violation>,
+}
+
+impl<'a> Validator<'_, 'a> {
+    fn validate_element(&mut self, node: roxmltree::Node, decl: Node<'a>) -> Result<(), Error> {
+        let decl = match decl.attribute("ref") {
+            Some(name) => self
+                .schema
+                .lookup(&self.schema.elements, decl, name, "element")?,
+            None => decl,
+        };
+        let name = instance_key(node);
+
+        // xsi:type 指定实际使用的派生类型
+        let mut kind = self.schema.element_type(decl)?;
+        if let Some(qname) = node.attribute((XSI, "type")) {
+            let (prefix, local) = match qname.split_once(':') {
+                Some((prefix, local)) => (Some(prefix), local),
+                None => (None, qname),
+            };
+            let key = key(node.lookup_namespace_uri(prefix), local);
+            match self.schema.types.get(&key) {
+                Some(node) if is_xs(node, "complexType") => kind = Type::Complex(*node),
+                Some(node) => kind = Type::Simple(Simple::Node(*node)),
+                None if namespace_of(&key) == XS => {
+                    kind = Type::Simple(Simple::Builtin(local.to_string()))
+                }
+                None => self.violations.push(Violation::element(
+                    node,
+                    format!("unknown xsi:type \"{}\"", qname),
+                )),
+            }
+        }
+
+        if node.attribute((XSI, "nil")) == Some("true") {
+            if decl.attribute("nillable") != Some("true") {
+                self.violations.push(Violation::element(
+                    node,
+                    format!("element <{}> is not nillable", display(&name)),
+                ));
+            } else if node.children().any(|c| c.is_element() || c.is_text()) {
+                self.violations.push(Violation::element(
+                    node,
+                    format!("nil element <{}> must be empty", display(&name)),
+                ));
+            }
+            return Ok(());
+        }
+
+        let text = text(node);
+        match kind {
+            Type::Any => {}
+            Type::Simple(simple) => {
+                if node.children().any(|child| child.is_element()) {
+                    self.violations.push(Violation::element(
+                        node,
+                        format!("element <{}> cannot contain child elements", display(&name)),
+                    ));
+                }
+                for attribute in node.attributes() {
+                    if attribute.namespace() != Some(XSI) {
+                        self.violations.push(Violation::attribute(
+                            node,
+                            &attribute,
+                            format!("attribute \"{}\" is not allowed", attribute.name()),
+                        ));
+                    }
+                }
+                self.check_text(node, decl, &simple, &text)?;
+            }
+            Type::Complex(complex) => {
+                let complex = self.schema.complex(complex, 0)?;
+                self.validate_attributes(node, &complex)?;
+                match &complex.simple {
+                    Some(simple) => {
+                        if node.children().any(|child| child.is_element()) {
+                            self.violations.push(Violation::element(
+                                node,
+                                format!(
+                                    "element <{}> cannot contain child elements",
+                                    display(&name)
+                                ),
+                            ));
+                        }
+                        self.check_text(node, decl, simple, &text)?;
+                    }
+                    None => self.validate_children(node, &complex)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_text(
+        &mut self,
+        node: roxmltree::Node,
+        decl: Node<'a>,
+        simple: &Simple<'a>,
+        text: &str,
+    ) -> Result<(), Error> {
+        // 空元素使用默认值
+        let value = match decl.attribute("default") {
+            Some(default) if text.is_empty() => default,
+            _ => text,
+        };
+        let value = self.normalize(simple, value);
+        if let Some(fixed) = decl.attribute("fixed") {
+            if value != self.normalize(simple, fixed) {
+                self.violations.push(Violation::element(
+                    node,
+                    format!("value must be the fixed value \"{}\"", fixed),
+                ));
+                return Ok(());
+            }
+        }
+        if let Some(message) = self.check_simple(simple, &value, 0)? {
+            self.violations.push(Violation::element(node, message));
+        } else {
+            self.track_id(simple, &value, |message| Violation::element(node, message))?;
+        }
+        Ok(())
+    }
+
+    fn validate_attributes(
+        &mut self,
+        node: roxmltree::Node,
+        complex: &Complex<'a>,
+    ) -> Result<(), Error> {
+        for attribute in node.attributes() {
+            if attribute.namespace() == Some(XSI) {
+                continue;
+            }
+            let key = key(attribute.namespace(), attribute.name());
+            let Some(usage) = complex.uses.iter().find(|u| u.key == key) else {
+                if !complex.any_attribute {
+                    self.violations.push(Violation::attribute(
+                        node,
+                        &attribute,
+                        format!("attribute \"{}\" is not allowed", display(&key)),
+                    ));
+                }
+                continue;
+            };
+            if usage.prohibited {
+                self.violations.push(Violation::attribute(
+                    node,
+                    &attribute,
+                    format!("attribute \"{}\" is prohibited", display(&key)),
+                ));
+                continue;
+            }
+            let value = self.normalize(&usage.kind, attribute.value());
+            let message = match usage.fixed {
+                Some(fixed) if value != self.normalize(&usage.kind, fixed) => Some(format!(
+                    "attribute \"{}\" must have the fixed value \"{}\"",
+                    display(&key),
+                    fixed
+                )),
+                _ => self.check_simple(&usage.kind, &value, 0)?,
+            };
+            match message {
+                Some(message) => {
+                    self.violations
+                        .push(Violation::attribute(node, &attribute, message));
+                }
+                None => self.track_id(&usage.kind, &value, |message| {
+                    Violation::attribute(node, &attribute, message)
+                })?,
+            }
+        }
+        for usage in complex.uses.iter().filter(|u| u.required) {
+            let present = node
+                .attributes()
+                .any(|a| key(a.namespace(), a.name()) == usage.key);
+            if !present {
+                self.violations.push(Violation::element(
+                    node,
+                    format!("required attribute \"{}\" is missing", display(&usage.key)),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_children(
+        &mut self,
+        node: roxmltree::Node,
+        complex: &Complex<'a>,
+    ) -> Result<(), Error> {
+        let name = instance_key(node);
+        if !complex.mixed && !text(node).trim().is_empty() {
+            self.violations.push(Violation::element(
+                node,
+                format!("element <{}> cannot contain text", display(&name)),
+            ));
+        }
+        let children = node
+            .children()
+            .filter(|child| child.is_element())
+            .collect::<Vec<_>>();
+        let keys = children
+            .iter()
+            .map(|child| instance_key(*child))
+            .collect::<Vec<_>>();
+        let empty = Particle::one(Term::Sequence(Vec::new()));
+        let particle = complex.particle.as_ref().unwrap_or(&empty);
+        let expected = |expected: &[String]| {
+            let expected = expected
+                .iter()
+                .map(|key| display(key).to_string())
+                .collect::<Vec<_>>();
+            Mismatch::describe(&expected)
+        };
+        match particle.check(&keys) {
+            Ok(()) => {}
+            Err(Mismatch::Unexpected {
+                index,
+                expected: names,
+            }) => self.violations.push(Violation::element(
+                children[index],
+                format!(
+                    "unexpected element <{}> in <{}>, {}",
+                    display(&keys[index]),
+                    display(&name),
+                    expected(&names)
+                ),
+            )),
+            Err(Mismatch::Incomplete { expected: names }) => {
+                self.violations.push(Violation::element(
+                    node,
+                    format!(
+                        "content of <{}> is incomplete, {}",
+                        display(&name),
+                        expected(&names)
+                    ),
+                ))
+            }
+        }
+
+        for (child, key) in children.into_iter().zip(keys) {
+            // 通配符匹配的元素只在有全局声明时校验
+            let decl = complex
+                .decls
+                .get(&key)
+                .or_else(|| self.schema.elements.get(&key));
+            if let Some(decl) = decl {
+                self.validate_element(child, *decl)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 除 `string` 和 `normalizedString` 外的类型都会合并空白字符
+    fn normalize(&self, simple: &Simple<'a>, value: &str) -> String {
+        match self.builtin(simple, 0).as_deref() {
+            Some("string") | Some("anySimpleType") => value.to_string(),
+            Some("normalizedString") => value.replace(['\t', '\n', '\r'], " "),
+            _ => value.split_whitespace().collect::<Vec<_>>().join(" "),
+        }
+    }
+
+    /// 派生链最终的内置类型，列表和联合类型返回空
+    fn builtin(&self, simple: &Simple<'a>, depth: usize) -> Option<String> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        match simple {
+            Simple::Builtin(name) => Some(name.clone()),
+            Simple::Restriction(_, base) => self.builtin(base, depth + 1),
+            Simple::Node(node) => {
+                let restriction = xs_child(*node, "restriction")?;
+                let base = self.restriction_base(restriction).ok()?;
+                self.builtin(&base, depth + 1)
+            }
+        }
+    }
+
+    fn restriction_base(&self, restriction: Node<'a>) -> Result<Simple<'a>, Error> {
+        if let Some(base) = restriction.attribute("base") {
+            return match self.schema.resolve_type(restriction, base)? {
+                Type::Simple(simple) => Ok(simple),
+                Type::Complex(node) => Ok(self
+                    .schema
+                    .complex(node, 0)?
+                    .simple
+                    .unwrap_or(Simple::Builtin("anySimpleType".to_string()))),
+                Type::Any => Ok(Simple::Builtin("anySimpleType".to_string())),
+            };
+        }
+        Ok(match xs_child(restriction, "simpleType") {
+            Some(node) => Simple::Node(node),
+            None => Simple::Builtin("anySimpleType".to_string()),
+        })
+    }
+
+    ///
+    /// 检查值是否符合简单类型，返回不符合的原因
+    ///
+    fn check_simple(
+        &self,
+        simple: &Simple<'a>,
+        value: &str,
+        depth: usize,
+    ) -> Result<Option<String>, Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::Circular(value.to_string()));
+        }
+        match simple {
+            Simple::Builtin(name) => Ok(self.check_builtin(name, value)),
+            Simple::Restriction(restriction, base) => {
+                if let Some(message) = self.check_simple(base, value, depth + 1)? {
+                    return Ok(Some(message));
+                }
+                self.check_facets(*restriction, value)
+            }
+            Simple::Node(node) => {
+                if let Some(restriction) = xs_child(*node, "restriction") {
+                    let base = self.restriction_base(restriction)?;
+                    if let Some(message) = self.check_simple(&base, value, depth + 1)? {
+                        return Ok(Some(message));
+                    }
+                    return self.check_facets(restriction, value);
+                }
+                if let Some(list) = xs_child(*node, "list") {
+                    let item = match list.attribute("itemType") {
+                        Some(name) => match self.schema.resolve_type(list, name)? {
+                            Type::Simple(simple) => simple,
+                            _ => Simple::Builtin("anySimpleType".to_string()),
+                        },
+                        None => xs_child(list, "simpleType")
+                            .map(Simple::Node)
+                            .unwrap_or(Simple::Builtin("anySimpleType".to_string())),
+                    };
+                    for value in value.split_whitespace() {
+                        if let Some(message) = self.check_simple(&item, value, depth + 1)? {
+                            return Ok(Some(message));
+                        }
+                    }
+                    return Ok(None);
+                }
+                if let Some(union) = xs_child(*node, "union") {
+                    let mut members = Vec::new();
+                    for name in union
+                        .attribute("memberTypes")
+                        .unwrap_or_default()
+                        .split_whitespace()
+                    {
+                        if let Type::Simple(simple) = self.schema.resolve_type(union, name)? {
+                            members.push(simple);
+                        }
+                    }
+                    members.extend(
+                        xs_children(union)
+                            .filter(|c| is_xs(c, "simpleType"))
+                            .map(Simple::Node),
+                    );
+                    for member in &members {
+                        if self.check_simple(member, value, depth + 1)?.is_none() {
+                            return Ok(None);
+                        }
+                    }
+                    return Ok(Some(format!(
+                        "\"{}\" is not valid for any member type of the union",
+                        value
+                    )));
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn check_facets(&self, restriction: Node<'a>, value: &str) -> Result<Option<String>, Error> {
+        let facets = xs_children(restriction)
+            .filter_map(|facet| Some((facet.tag_name().name(), facet.attribute("value")?)))
+            .collect::<Vec<_>>();
+
+        let enumeration = facets
+            .iter()
+            .filter(|(name, _)| *name == "enumeration")
+            .map(|(_, value)| *value)
+            .collect::<Vec<_>>();
+        if !enumeration.is_empty() && !enumeration.contains(&value) {
+            return Ok(Some(format!(
+                "\"{}\" is not one of: {}",
+                value,
+                enumeration.join(", ")
+            )));
+        }
+
+        // 同一步限制中的多个 pattern 满足其一即可
+        let patterns = facets
+            .iter()
+            .filter(|(name, _)| *name == "pattern")
+            .map(|(_, pattern)| *pattern)
+            .collect::<Vec<_>>();
+        if !patterns.is_empty() {
+            let mut matched = false;
+            for pattern in &patterns {
+                if self.regex(&xsd_regex(pattern))?.is_match(value) {
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                return Ok(Some(format!(
+                    "\"{}\" does not match the pattern {}",
+                    value,
+                    patterns.join(" | ")
+                )));
+            }
+        }
+
+        let length = value.chars().count();
+        let number = |value: &str| value.trim().parse::<f64>().ok();
+        let compare = |bound: &str| match (number(value), number(bound)) {
+            (Some(value), Some(bound)) => value.partial_cmp(&bound),
+            _ => Some(value.cmp(bound)),
+        };
+        let (integer, fraction) = {
+            let digits = value.trim_start_matches(['+', '-']);
+            let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+            let integer = integer.trim_start_matches('0');
+            let fraction = fraction.trim_end_matches('0');
+            (integer.len(), fraction.len())
+        };
+        for (name, bound) in facets {
+            let limit = || bound.parse::<usize>().unwrap_or(usize::MAX);
+            let message = match name {
+                "length" if length != limit() => Some(format!("length must be {}", bound)),
+                "minLength" if length < limit() => {
+                    Some(format!("length must be at least {}", bound))
+                }
+                "maxLength" if length > limit() => {
+                    Some(format!("length must be at most {}", bound))
+                }
+                "minInclusive" if compare(bound).is_some_and(|o| o.is_lt()) => {
+                    Some(format!("value must be >= {}", bound))
+                }
+                "maxInclusive" if compare(bound).is_some_and(|o| o.is_gt()) => {
+                    Some(format!("value must be <= {}", bound))
+                }
+                "minExclusive" if compare(bound).is_some_and(|o| o.is_le()) => {
+                    Some(format!("value must be > {}", bound))
+                }
+                "maxExclusive" if compare(bound).is_some_and(|o| o.is_ge()) => {
+                    Some(format!("value must be < {}", bound))
+                }
+                "totalDigits" if integer + fraction > limit() => {
+                    Some(format!("value must have at most {} digits", bound))
+                }
+                "fractionDigits" if fraction > limit() => {
+                    Some(format!("value must have at most {} fraction digits", bound))
+                }
+                _ => None,
+            };
+            if let Some(message) = message {
+                return Ok(Some(format!("\"{}\": {}", value, message)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn check_builtin(&self, name: &str, value: &str) -> Option<String> {
+        let integer = |min: i128, max: i128| {
+            let valid = value
+                .strip_prefix('+')
+                .unwrap_or(value)
+                .parse::<i128>()
+                .is_ok_and(|n| n >= min && n <= max);
+            (!valid).then(|| format!("\"{}\" is not a valid {}", value, name))
+        };
+        let matches = |pattern: &str| {
+            let valid = self.regex(pattern).is_ok_and(|regex| regex.is_match(value));
+            (!valid).then(|| format!("\"{}\" is not a valid {}", value, name))
+        };
+        const TZ: &str = r"(Z|[+-]\d{2}:\d{2})?";
+        const NAME: &str = r"[\p{L}_:][\p{L}\p{N}_:.\-]*";
+        const NCNAME: &str = r"[\p{L}_][\p{L}\p{N}_.\-]*";
+        match name {
+            "boolean" => matches("^(true|false|1|0)$"),
+            "decimal" => matches(r"^[+-]?(\d+(\.\d*)?|\.\d+)$"),
+            "float" | "double" => matches(r"^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|-?INF|NaN)$"),
+            "integer" => integer(i128::MIN, i128::MAX),
+            "nonPositiveInteger" => integer(i128::MIN, 0),
+            "negativeInteger" => integer(i128::MIN, -1),
+            "nonNegativeInteger" => integer(0, i128::MAX),
+            "positiveInteger" => integer(1, i128::MAX),
+            "long" => integer(i64::MIN as i128, i64::MAX as i128),
+            "int" => integer(i32::MIN as i128, i32::MAX as i128),
+            "short" => integer(i16::MIN as i128, i16::MAX as i128),
+            "byte" => integer(i8::MIN as i128, i8::MAX as i128),
+            "unsignedLong" => integer(0, u64::MAX as i128),
+            "unsignedInt" => integer(0, u32::MAX as i128),
+            "unsignedShort" => integer(0, u16::MAX as i128),
+            "unsignedByte" => integer(0, u8::MAX as i128),
+            "date" => matches(&format!(
+                r"^-?\d{{4,}}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01]){}$",
+                TZ
+            )),
+            "dateTime" => matches(&format!(
+                r"^-?\d{{4,}}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])T([01]\d|2[0-4]):[0-5]\d:[0-5]\d(\.\d+)?{}$",
+                TZ
+            )),
+            "time" => matches(&format!(r"^([01]\d|2[0-4]):[0-5]\d:[0-5]\d(\.\d+)?{}$", TZ)),
+            "gYear" => matches(&format!(r"^-?\d{{4,}}{}$", TZ)),
+            "gYearMonth" => matches(&format!(r"^-?\d{{4,}}-(0[1-9]|1[0-2]){}$", TZ)),
+            "gMonth" => matches(&format!(r"^--(0[1-9]|1[0-2]){}$", TZ)),
+            "gMonthDay" => matches(&format!(r"^--(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01]){}$", TZ)),
+            "gDay" => matches(&format!(r"^---(0[1-9]|[12]\d|3[01]){}$", TZ)),
+            "duration" => {
+                let valid = self
+                    .regex(r"^-?P(\d+Y)?(\d+M)?(\d+D)?(T(\d+H)?(\d+M)?(\d+(\.\d+)?S)?)?$")
+                    .is_ok_and(|regex| regex.is_match(value))
+                    && !value.ends_with('P')
+                    && !value.ends_with('T');
+                (!valid).then(|| format!("\"{}\" is not a valid {}", value, name))
+            }
+            "hexBinary" => matches("^([0-9a-fA-F]{2})*$"),
+            "base64Binary" => {
+                let compact = value.split_whitespace().collect::<String>();
+                let valid = compact.len() % 4 == 0
+                    && self
+                        .regex("^[A-Za-z0-9+/]*={0,2}$")
+                        .is_ok_and(|regex| regex.is_match(&compact));
+                (!valid).then(|| format!("\"{}\" is not a valid {}", value, name))
+            }
+            "language" => matches("^[a-zA-Z]{1,8}(-[a-zA-Z0-9]{1,8})*$"),
+            "Name" => matches(&format!("^{}$", NAME)),
+            "NCName" | "ID" | "IDREF" | "ENTITY" => matches(&format!("^{}$", NCNAME)),
+            "IDREFS" | "ENTITIES" => matches(&format!("^{}( {})*$", NCNAME, NCNAME)),
+            "NMTOKEN" => matches(r"^[\p{L}\p{N}_:.\-]+$"),
+            "NMTOKENS" => matches(r"^[\p{L}\p{N}_:.\-]+( [\p{L}\p{N}_:.\-]+)*$"),
+            "QName" => matches(&format!("^({}:)?{}$", NCNAME, NCNAME)),
+            _ => None,
+        }
+    }
+
+    /// 记录 `xs:ID` 类型的值，重复时报告错误
+    fn track_id(
+        &mut self,
+        simple: &Simple<'a>,
+        value: &str,
+        violation: impl FnOnce(String) -> Violation,
+    ) -> Result<(), Error> {
+        if self.builtin(simple, 0).as_deref() == Some("ID") && !self.ids.insert(value.to_string()) {
+            self.violations
+                .push(violation(format!("duplicate ID \"{}\"", value)));
+        }
+        Ok(())
+    }
+
+    fn regex(&self, pattern: &str) -> Result<Regex, Error> {
+        if let Some(regex) = self.regexes.borrow().get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex = Regex::new(pattern)?;
+        self.regexes
+            .borrow_mut()
+            .insert(pattern.to_string(), regex.clone());
+        Ok(regex)
+    }
+}
+
+/// 元素的直接文本内容
+fn text(node: roxmltree::Node) -> String {
+    node.children()
+        .filter(|child| child.is_text())
+        .filter_map(|child| child.text())
+        .collect()
+}
+
+///
+/// 将 XSD 正则表达式转换为 Rust 正则表达式。
+///
+/// XSD 的 pattern 隐式匹配整个值，并且支持 `\i`、`\c` 这样的名称字符类。
+///
+fn xsd_regex(pattern: &str) -> String {
+    let mut output = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('i') => output.push_str(r"[\p{L}_:]"),
+            Some('I') => output.push_str(r"[^\p{L}_:]"),
+            Some('c') => output.push_str(r"[\p{L}\p{N}_:.\-]"),
+            Some('C') => output.push_str(r"[^\p{L}\p{N}_:.\-]"),
+            Some(other) => {
+                output.push('\\');
+                output.push(other);
+            }
+            None => output.push('\\'),
+        }
+    }
+    format!("^(?:{})$", output)
+}
+
+command_error! {
+    (Xml, "parse xsd error: {0}", #[from] roxmltree::Error),
+    (Regex, "unsupported xsd pattern: {0}", #[from] regex::Error),
+    (NoSchema, "no xsd is given"),
+    (Schema, "xsd error: {0}", String),
+    (UnboundPrefix, "xsd error: namespace prefix of \"{0}\" is not declared", String),
+    (Unresolved, "xsd error: {0} \"{1}\" is not declared, referenced schemas must be given together", String, String),
+    (Circular, "xsd error: circular reference \"{0}\"", String),
+}
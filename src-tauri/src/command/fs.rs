@@ -1,6 +1,6 @@
 use crate::{command_error, util::get_app_temp_dir};
 use base64::{prelude::BASE64_STANDARD, Engine};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager, Runtime};
 use tracing::instrument;
 use uuid::Uuid;
@@ -75,6 +75,38 @@ pub fn copy_file(from: &str, to: &str) -> Result<(), Error> {
     std::fs::copy(from, to).map(|_| ()).map_err(Into::into)
 }
 
+///
+/// 在应用临时目录下创建一个新的空目录
+///
+pub(crate) fn create_temp_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, Error> {
+    let dir = get_app_temp_dir(app, true)?.join(Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+///
+/// 递归复制目录中的文件到目标目录，已存在的文件会被覆盖，返回复制的文件相对于 `from` 的路径
+///
+pub(crate) fn copy_dir(from: &Path, to: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        std::fs::create_dir_all(to.join(&relative))?;
+        for entry in std::fs::read_dir(from.join(&relative))? {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+            } else {
+                std::fs::copy(entry.path(), to.join(&path))?;
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
 command_error! {
     (Io, "io error: {0}", #[from] std::io::Error),
     (InvalidFileName, "invalid file name: {0}", String),
@@ -0,0 +1,381 @@
+use crate::command::fs::{copy_dir, create_temp_dir};
+use crate::command_error;
+use image::codecs::ico::{IcoEncoder, IcoFrame};
+use image::codecs::png::PngEncoder;
+use image::imageops::{overlay, FilterType};
+use image::{DynamicImage, ExtendedColorType, ImageEncoder, ImageError, Rgba, RgbaImage};
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::{Options, Tree};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use tauri::{AppHandle, Runtime};
+
+/// favicon.ico 中包含的尺寸
+const ICO_SIZES: [u32; 7] = [16, 24, 32, 48, 64, 128, 256];
+
+/// icns 中的图标类型与像素尺寸，均使用 PNG 数据，`ic11` 之后是对应尺寸的 @2x 版本
+const ICNS_TYPES: [(&[u8; 4], u32); 11] = [
+    (b"icp4", 16),
+    (b"icp5", 32),
+    (b"icp6", 64),
+    (b"ic07", 128),
+    (b"ic08", 256),
+    (b"ic09", 512),
+    (b"ic10", 1024),
+    (b"ic11", 32),
+    (b"ic12", 64),
+    (b"ic13", 256),
+    (b"ic14", 512),
+];
+
+/// Android 各密度的启动图标尺寸
+const ANDROID_SIZES: [(&str, u32); 5] = [
+    ("mdpi", 48),
+    ("hdpi", 72),
+    ("xhdpi", 96),
+    ("xxhdpi", 144),
+    ("xxxhdpi", 192),
+];
+
+/// iOS AppIcon.appiconset 中的设备、点尺寸和倍率
+const IOS_ICONS: [(&str, &str, u32); 18] = [
+    ("iphone", "20", 2),
+    ("iphone", "20", 3),
+    ("iphone", "29", 2),
+    ("iphone", "29", 3),
+    ("iphone", "40", 2),
+    ("iphone", "40", 3),
+    ("iphone", "60", 2),
+    ("iphone", "60", 3),
+    ("ipad", "20", 1),
+    ("ipad", "20", 2),
+    ("ipad", "29", 1),
+    ("ipad", "29", 2),
+    ("ipad", "40", 1),
+    ("ipad", "40", 2),
+    ("ipad", "76", 1),
+    ("ipad", "76", 2),
+    ("ipad", "83.5", 2),
+    ("ios-marketing", "1024", 1),
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Target {
+    /// 多尺寸的 favicon.ico
+    Ico,
+    /// macOS 的 icon.icns
+    Icns,
+    /// 网页使用的 PNG、manifest.json 和 `<link>` 标签
+    Web,
+    Android,
+    Ios,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Generated {
+    /// 生成的文件，相对于输出目录
+    files: Vec<String>,
+    manifest: Option<String>,
+    html: Option<String>,
+}
+
+///
+/// 根据 PNG 或 SVG 生成各平台的图标，先写入临时目录，全部成功后再复制到 `dir`
+///
+/// 非正方形的图片居中放置并填充透明背景，`background` 用于不支持透明的 iOS 图标和 apple-touch-icon，
+/// 同时作为 manifest 的背景色，默认为白色
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, skip(app), ret, err(level = tracing::Level::ERROR))]
+pub fn generate_icons<R: Runtime>(
+    source: &str,
+    targets: Vec<Target>,
+    name: Option<String>,
+    background: Option<String>,
+    dir: &str,
+    app: AppHandle<R>,
+) -> Result<Generated, Error> {
+    let background = match background.as_deref().map(str::trim) {
+        Some(color) if !color.is_empty() => parse_color(color)?,
+        _ => Rgba([255, 255, 255, 255]),
+    };
+    let mut renderer = Renderer::open(source)?;
+    let temp = create_temp_dir(&app)?;
+    let result =
+        generate(&mut renderer, &targets, name, background, &temp).and_then(|(manifest, html)| {
+            let files = copy_dir(&temp, Path::new(dir))?
+                .into_iter()
+                .map(|file| file.to_string_lossy().replace('\\', "/"))
+                .collect();
+            Ok(Generated {
+                files,
+                manifest,
+                html,
+            })
+        });
+    let _ = std::fs::remove_dir_all(&temp);
+    result
+}
+
+fn generate(
+    renderer: &mut Renderer,
+    targets: &[Target],
+    name: Option<String>,
+    background: Rgba<u8>,
+    dir: &Path,
+) -> Result<(Option<String>, Option<String>), Error> {
+    let mut manifest = None;
+    let mut html = None;
+
+    if targets.contains(&Target::Ico) {
+        let images = ICO_SIZES
+            .iter()
+            .map(|&size| renderer.render(size))
+            .collect::<Result<Vec<_>, _>>()?;
+        let frames = images
+            .iter()
+            .map(|image| {
+                let (width, height) = image.dimensions();
+                IcoFrame::as_png(image, width, height, ExtendedColorType::Rgba8)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        IcoEncoder::new(File::create(dir.join("favicon.ico"))?).encode_images(&frames)?;
+    }
+
+    if targets.contains(&Target::Icns) {
+        let mut entries = Vec::new();
+        for (kind, size) in ICNS_TYPES {
+            let png = png(&renderer.render(size)?)?;
+            entries.extend_from_slice(kind);
+            entries.extend_from_slice(&(png.len() as u32 + 8).to_be_bytes());
+            entries.extend_from_slice(&png);
+        }
+        let mut icns = b"icns".to_vec();
+        icns.extend_from_slice(&(entries.len() as u32 + 8).to_be_bytes());
+        icns.extend_from_slice(&entries);
+        std::fs::write(dir.join("icon.icns"), icns)?;
+    }
+
+    if targets.contains(&Target::Web) {
+        let web = [
+            ("favicon-16x16.png", 16, false),
+            ("favicon-32x32.png", 32, false),
+            ("apple-touch-icon.png", 180, true),
+            ("android-chrome-192x192.png", 192, false),
+            ("android-chrome-512x512.png", 512, false),
+        ];
+        for (file, size, opaque) in web {
+            let mut image = renderer.render(size)?;
+            if opaque {
+                image = flatten(&image, background);
+            }
+            std::fs::write(dir.join(file), png(&image)?)?;
+        }
+
+        let color = format!(
+            "#{:02x}{:02x}{:02x}",
+            background[0], background[1], background[2]
+        );
+        let name = name.unwrap_or_default();
+        let value = serde_json::json!({
+            "name": name,
+            "short_name": name,
+            "icons": [
+                {"src": "/android-chrome-192x192.png", "sizes": "192x192", "type": "image/png"},
+                {"src": "/android-chrome-512x512.png", "sizes": "512x512", "type": "image/png"},
+            ],
+            "theme_color": color,
+            "background_color": color,
+            "display": "standalone",
+        });
+        let text = serde_json::to_string_pretty(&value)?;
+        std::fs::write(dir.join("manifest.json"), &text)?;
+        manifest = Some(text);
+
+        let mut links = Vec::new();
+        if targets.contains(&Target::Ico) {
+            links.push(r#"<link rel="icon" href="/favicon.ico" sizes="any">"#.to_string());
+        }
+        if let Some(svg) = renderer.svg() {
+            std::fs::write(dir.join("icon.svg"), svg)?;
+            links.push(r#"<link rel="icon" href="/icon.svg" type="image/svg+xml">"#.to_string());
+        }
+        links.extend([
+            r#"<link rel="icon" type="image/png" sizes="32x32" href="/favicon-32x32.png">"#
+                .to_string(),
+            r#"<link rel="icon" type="image/png" sizes="16x16" href="/favicon-16x16.png">"#
+                .to_string(),
+            r#"<link rel="apple-touch-icon" sizes="180x180" href="/apple-touch-icon.png">"#
+                .to_string(),
+            r#"<link rel="manifest" href="/manifest.json">"#.to_string(),
+            format!(r#"<meta name="theme-color" content="{color}">"#),
+        ]);
+        let text = links.join("\n");
+        std::fs::write(dir.join("icons.html"), &text)?;
+        html = Some(text);
+    }
+
+    if targets.contains(&Target::Android) {
+        for (density, size) in ANDROID_SIZES {
+            let folder = dir.join("android").join(format!("mipmap-{density}"));
+            std::fs::create_dir_all(&folder)?;
+            std::fs::write(
+                folder.join("ic_launcher.png"),
+                png(&renderer.render(size)?)?,
+            )?;
+        }
+        let play = png(&renderer.render(512)?)?;
+        std::fs::write(dir.join("android").join("play-store-icon.png"), play)?;
+    }
+
+    if targets.contains(&Target::Ios) {
+        let folder = dir.join("ios").join("AppIcon.appiconset");
+        std::fs::create_dir_all(&folder)?;
+        let mut images = Vec::new();
+        for (idiom, points, scale) in IOS_ICONS {
+            let size = (points.parse::<f64>().unwrap_or_default() * scale as f64) as u32;
+            let file = format!("icon-{size}.png");
+            let path = folder.join(&file);
+            // 不同设备会用到相同的像素尺寸，只生成一次
+            if !path.exists() {
+                let image = flatten(&renderer.render(size)?, background);
+                std::fs::write(path, png(&image)?)?;
+            }
+            images.push(serde_json::json!({
+                "idiom": idiom,
+                "size": format!("{points}x{points}"),
+                "scale": format!("{scale}x"),
+                "filename": file,
+            }));
+        }
+        let contents = serde_json::json!({
+            "images": images,
+            "info": {"version": 1, "author": "xcode"},
+        });
+        std::fs::write(
+            folder.join("Contents.json"),
+            serde_json::to_string_pretty(&contents)?,
+        )?;
+    }
+
+    Ok((manifest, html))
+}
+
+///
+/// 按需渲染各尺寸的正方形图标，SVG 在每个尺寸下单独渲染以保持清晰
+///
+struct Renderer {
+    source: Source,
+    cache: HashMap<u32, RgbaImage>,
+}
+
+enum Source {
+    Raster(DynamicImage),
+    Vector(Tree, Vec<u8>),
+}
+
+impl Renderer {
+    fn open(path: &str) -> Result<Self, Error> {
+        let bytes = std::fs::read(path)?;
+        let svg = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| matches!(extension.to_lowercase().as_str(), "svg" | "svgz"));
+        let source = match svg {
+            true => {
+                let mut options = Options::default();
+                options.fontdb_mut().load_system_fonts();
+                Source::Vector(Tree::from_data(&bytes, &options)?, bytes)
+            }
+            false => Source::Raster(image::load_from_memory(&bytes)?),
+        };
+        Ok(Renderer {
+            source,
+            cache: HashMap::new(),
+        })
+    }
+
+    fn svg(&self) -> Option<&[u8]> {
+        match &self.source {
+            Source::Vector(_, bytes) => Some(bytes),
+            Source::Raster(_) => None,
+        }
+    }
+
+    fn render(&mut self, size: u32) -> Result<RgbaImage, Error> {
+        if let Some(image) = self.cache.get(&size) {
+            return Ok(image.clone());
+        }
+        let image = match &self.source {
+            Source::Raster(image) => {
+                let scaled = image.resize(size, size, FilterType::Lanczos3).to_rgba8();
+                let mut canvas = RgbaImage::new(size, size);
+                let x = (size - scaled.width()) / 2;
+                let y = (size - scaled.height()) / 2;
+                overlay(&mut canvas, &scaled, x as i64, y as i64);
+                canvas
+            }
+            Source::Vector(tree, _) => {
+                let mut pixmap = Pixmap::new(size, size).ok_or(Error::Render(size))?;
+                let (width, height) = (tree.size().width(), tree.size().height());
+                let scale = size as f32 / width.max(height);
+                let transform = Transform::from_scale(scale, scale).post_translate(
+                    (size as f32 - width * scale) / 2.0,
+                    (size as f32 - height * scale) / 2.0,
+                );
+                resvg::render(tree, transform, &mut pixmap.as_mut());
+                // tiny-skia 的像素是预乘透明度的，借助 PNG 编码转换为普通的 RGBA
+                let png = pixmap.encode_png().map_err(|_| Error::Render(size))?;
+                image::load_from_memory(&png)?.to_rgba8()
+            }
+        };
+        self.cache.insert(size, image.clone());
+        Ok(image)
+    }
+}
+
+fn png(image: &RgbaImage) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    PngEncoder::new(&mut bytes).write_image(
+        image,
+        image.width(),
+        image.height(),
+        ExtendedColorType::Rgba8,
+    )?;
+    Ok(bytes)
+}
+
+///
+/// 将透明的图标合成到纯色背景上
+///
+fn flatten(image: &RgbaImage, background: Rgba<u8>) -> RgbaImage {
+    let mut canvas = RgbaImage::from_pixel(image.width(), image.height(), background);
+    overlay(&mut canvas, image, 0, 0);
+    canvas
+}
+
+///
+/// 解析 `#rgb` 或 `#rrggbb` 格式的颜色
+///
+fn parse_color(color: &str) -> Result<Rgba<u8>, Error> {
+    let digits = color.strip_prefix('#').unwrap_or(color);
+    let digits = match digits.len() {
+        3 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 => digits.to_string(),
+        _ => return Err(Error::Color(color.to_string())),
+    };
+    let bytes = hex::decode(digits).map_err(|_| Error::Color(color.to_string()))?;
+    Ok(Rgba([bytes[0], bytes[1], bytes[2], 255]))
+}
+
+command_error! {
+    (Io, "io error: {0}", #[from] std::io::Error),
+    (Image, "image error: {0}", #[from] ImageError),
+    (Svg, "svg error: {0}", #[from] resvg::usvg::Error),
+    (Json, "json error: {0}", #[from] serde_json::Error),
+    (Fs, "{0}", #[from] crate::command::fs::Error),
+    (Render, "failed to render icon of size {0}", u32),
+    (Color, "invalid color `{0}`, expected #rgb or #rrggbb", String),
+}
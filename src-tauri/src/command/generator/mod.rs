@@ -1,3 +1,4 @@
 pub mod hash;
+pub mod icon;
 pub mod password;
 pub mod uuid;
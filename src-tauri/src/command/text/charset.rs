@@ -0,0 +1,109 @@
+use crate::charset::{Charset, CharsetError, Detection};
+use crate::command_error;
+use crate::encoding::{Encoding, EncodingError, EncodingText};
+use serde::{Deserialize, Serialize};
+use std::fs::{read, write, File};
+use std::io::Read;
+
+/// 探测字符集时读取的采样字节数
+const SAMPLE_SIZE: usize = 64 * 1024;
+
+///
+/// 待处理的字节，可以是编码后的文本，也可以是文件路径
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Source {
+    Bytes(EncodingText),
+    File(String),
+}
+
+#[derive(Debug, Serialize)]
+pub struct Transcoded {
+    from: Charset,
+    to: Charset,
+    /// 是否有非法字节被替换成了 U+FFFD
+    replaced: bool,
+    /// 写入文件时不返回文本和字节
+    text: Option<String>,
+    bytes: Option<String>,
+}
+
+///
+/// 探测字符集，文件只读取开头的一部分
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn detect_charset(source: Source) -> Result<Detection, Error> {
+    let bytes = match source {
+        Source::Bytes(text) => text.to_bytes()?,
+        Source::File(path) => {
+            let mut head = Vec::with_capacity(SAMPLE_SIZE);
+            File::open(path)?
+                .take(SAMPLE_SIZE as u64)
+                .read_to_end(&mut head)?;
+            head
+        }
+    };
+    Ok(Charset::detect(&bytes))
+}
+
+///
+/// 转换字符集，`from` 为空时自动探测
+///
+/// `lossy` 为真时非法字节替换为 U+FFFD，否则报告出错的位置；指定 `output` 时写入文件，
+/// 否则返回解码后的文本以及按 `encoding` 编码的转换结果
+///
+#[tauri::command(async)]
+#[tracing::instrument(level = tracing::Level::DEBUG, err(level = tracing::Level::ERROR))]
+pub fn transcode_charset(
+    source: Source,
+    from: Option<Charset>,
+    to: Charset,
+    bom: bool,
+    lossy: bool,
+    encoding: Encoding,
+    output: Option<String>,
+) -> Result<Transcoded, Error> {
+    let bytes = match source {
+        Source::Bytes(text) => text.to_bytes()?,
+        Source::File(path) => read(path)?,
+    };
+    let from =
+        from.unwrap_or_else(|| Charset::detect(&bytes[..bytes.len().min(SAMPLE_SIZE)]).charset);
+    let (text, replaced) = match lossy {
+        true => from.decode_lossy(&bytes),
+        false => (from.decode(&bytes)?, false),
+    };
+
+    let mut transcoded = match bom {
+        true => to.bom().to_vec(),
+        false => Vec::new(),
+    };
+    transcoded.extend(to.encode(&text)?);
+
+    match output {
+        Some(output) => {
+            write(output, &transcoded)?;
+            Ok(Transcoded {
+                from,
+                to,
+                replaced,
+                text: None,
+                bytes: None,
+            })
+        }
+        None => Ok(Transcoded {
+            from,
+            to,
+            replaced,
+            text: Some(text),
+            bytes: Some(encoding.encode(&transcoded)?),
+        }),
+    }
+}
+
+command_error! {
+    (Io, "io error: {0}", #[from] std::io::Error),
+    (Encoding, "encoding error: {0}", #[from] EncodingError),
+    (Charset, "charset error: {0}", #[from] CharsetError),
+}
@@ -1,19 +1,204 @@
+use jaq_core::{
+    compile::Undefined,
+    load::{self, Arena, File, Loader},
+    Compiler, Ctx, RcIter,
+};
+use jaq_json::Val;
 use jsonpath_rust::{parser::errors::JsonPathError, query::js_path};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use serde_json_path::JsonPath;
 
 use crate::command_error;
 
+///
+/// 查询语言
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Dialect {
+    /// JSONPath (jsonpath-rust)
+    JsonPath,
+    /// 严格遵循 RFC 9535 的 JSONPath
+    Rfc9535,
+    /// JMESPath
+    JmesPath,
+    /// jq
+    Jq,
+}
+
+///
+/// 一个匹配结果，只有 JSONPath 能给出匹配值在文档中的位置
+///
+#[derive(Debug, Serialize)]
+pub struct Match {
+    /// 规范化路径，如 `$['store']['book'][0]`
+    path: Option<String>,
+    /// JSON Pointer，如 `/store/book/0`
+    pointer: Option<String>,
+    value: Value,
+}
+
+impl Match {
+    fn value(value: Value) -> Self {
+        Self {
+            path: None,
+            pointer: None,
+            value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Output {
+    /// 执行表达式的查询语言
+    dialect: Dialect,
+    matches: Vec<Match>,
+    /// 所有匹配值组成的数组，格式化后的 JSON
+    text: String,
+}
+
 #[tauri::command]
 #[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
-pub fn parse_jsonpath(text: &str, pattern: &str) -> Result<String, Error> {
+pub fn parse_jsonpath(text: &str, pattern: &str, dialect: Dialect) -> Result<Output, Error> {
     let value = serde_json::from_str::<Value>(text)?;
-    let refs = js_path(&pattern, &value)?;
-    let values = refs.into_iter().map(|r| r.val()).collect::<Vec<_>>();
-    let output = serde_json::to_string_pretty(&values)?;
-    Ok(output)
+    let matches = match dialect {
+        Dialect::JsonPath => query_jsonpath(&value, pattern)?,
+        Dialect::Rfc9535 => query_rfc9535(&value, pattern)?,
+        Dialect::JmesPath => query_jmespath(value, pattern)?,
+        Dialect::Jq => query_jq(value, pattern)?,
+    };
+    let values = matches.iter().map(|m| &m.value).collect::<Vec<_>>();
+    let text = serde_json::to_string_pretty(&values)?;
+    Ok(Output {
+        dialect,
+        matches,
+        text,
+    })
+}
+
+fn query_jsonpath(value: &Value, pattern: &str) -> Result<Vec<Match>, Error> {
+    let refs = js_path(pattern, value)?;
+    Ok(refs
+        .into_iter()
+        .map(|r| {
+            let value = r.clone().val().clone();
+            let path = r.path();
+            Match {
+                pointer: Some(to_json_pointer(&path)),
+                path: Some(path),
+                value,
+            }
+        })
+        .collect())
+}
+
+fn query_rfc9535(value: &Value, pattern: &str) -> Result<Vec<Match>, Error> {
+    let path = JsonPath::parse(pattern)?;
+    Ok(path
+        .query_located(value)
+        .into_iter()
+        .map(|node| Match {
+            path: Some(node.location().to_string()),
+            pointer: Some(node.location().to_json_pointer()),
+            value: node.node().clone(),
+        })
+        .collect())
+}
+
+fn query_jmespath(value: Value, pattern: &str) -> Result<Vec<Match>, Error> {
+    let expression = jmespath::compile(pattern)?;
+    let result = expression.search(value)?;
+    Ok(vec![Match::value(serde_json::to_value(&*result)?)])
+}
+
+fn query_jq(value: Value, pattern: &str) -> Result<Vec<Match>, Error> {
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let arena = Arena::default();
+    let program = File {
+        code: pattern,
+        path: (),
+    };
+    let modules = loader
+        .load(&arena, program)
+        .map_err(|errors| Error::Jq(load_errors(pattern, errors)))?;
+    let filter = Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(|errors| {
+            let undefined = errors
+                .into_iter()
+                .flat_map(|(_, errors)| errors)
+                .map(|(name, undefined)| match undefined {
+                    Undefined::Filter(arity) => format!("undefined filter `{}/{}`", name, arity),
+                    undefined => format!("undefined {:?} `{}`", undefined, name),
+                })
+                .collect::<Vec<_>>();
+            Error::Jq(undefined.join(", "))
+        })?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    filter
+        .run((Ctx::new([], &inputs), Val::from(value)))
+        .map(|output| match output {
+            Ok(output) => Ok(Match::value(output.into())),
+            Err(e) => Err(Error::Jq(e.to_string())),
+        })
+        .collect()
+}
+
+/// 将词法和语法错误转换为带位置的描述
+fn load_errors(code: &str, errors: load::Errors<&str, ()>) -> String {
+    let position = |found: &str| found.as_ptr() as usize - code.as_ptr() as usize;
+    errors
+        .into_iter()
+        .flat_map(|(_, error)| match error {
+            load::Error::Io(errors) => errors
+                .into_iter()
+                .map(|(path, error)| format!("{}: {}", path, error))
+                .collect::<Vec<_>>(),
+            load::Error::Lex(errors) => errors
+                .into_iter()
+                .map(|(expect, found)| {
+                    format!("expected {} at {}", expect.as_str(), position(found))
+                })
+                .collect(),
+            load::Error::Parse(errors) => errors
+                .into_iter()
+                .map(|(expect, found)| {
+                    format!("expected {} at {}", expect.as_str(), position(found))
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 将 jsonpath-rust 的路径 `$['a'][0]` 转换为 JSON Pointer `/a/0`
+fn to_json_pointer(path: &str) -> String {
+    let mut pointer = String::new();
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+    while let Some(segment) = rest.strip_prefix('[') {
+        let (token, next) = match segment.strip_prefix('\'') {
+            Some(key) => match key.find("']") {
+                Some(end) => (&key[..end], &key[end + 2..]),
+                None => break,
+            },
+            None => match segment.find(']') {
+                Some(end) => (&segment[..end], &segment[end + 1..]),
+                None => break,
+            },
+        };
+        pointer.push('/');
+        pointer.push_str(&token.replace('~', "~0").replace('/', "~1"));
+        rest = next;
+    }
+    pointer
 }
 
 command_error! {
     (Json, "parse json error: {0}", #[from] serde_json::Error),
     (JsonPath, "parse json path error: {0}", #[from] JsonPathError),
+    (Rfc9535, "parse json path error: {0}", #[from] serde_json_path::ParseError),
+    (JmesPath, "jmespath error: {0}", #[from] jmespath::JmespathError),
+    (Jq, "jq error: {0}", String),
 }
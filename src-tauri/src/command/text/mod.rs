@@ -1,4 +1,6 @@
+pub mod charset;
 pub mod escape;
 pub mod jsonpath;
 pub mod markdown;
 pub mod regex;
+pub mod xpath;
@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use sxd_document::{dom, QName};
+use sxd_xpath::{nodeset, Context, Factory, Value};
+
+use crate::command_error;
+
+///
+/// 查询语言
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Language {
+    /// XPath 1.0，用于 XML
+    XPath,
+    /// CSS 选择器，用于 HTML
+    Css,
+}
+
+///
+/// 从匹配节点中提取的内容
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Extract {
+    /// 节点在原文中的标记
+    Markup,
+    /// 节点的文本内容
+    Text,
+    /// 元素指定属性的值
+    Attribute(String),
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum Kind {
+    Root,
+    Element,
+    Attribute,
+    Text,
+    Comment,
+    ProcessingInstruction,
+    Namespace,
+    /// XPath 表达式的结果不是节点集，如 `count(//item)`
+    Value,
+}
+
+///
+/// 一个匹配结果
+///
+#[derive(Debug, Serialize)]
+pub struct Match {
+    kind: Kind,
+    name: Option<String>,
+    /// 节点在文档中的位置，XML 为 XPath 路径，HTML 为 CSS 选择器路径
+    path: String,
+    /// 节点在原文中的行号和列号，从 1 开始，HTML 不提供
+    line: Option<u32>,
+    column: Option<u32>,
+    /// 提取的内容，元素没有指定的属性时为空
+    value: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Output {
+    /// 匹配的节点数量
+    count: usize,
+    matches: Vec<Match>,
+    /// 所有提取的内容，每行一个
+    text: String,
+}
+
+#[tauri::command]
+#[tracing::instrument(level = tracing::Level::DEBUG, ret, err(level = tracing::Level::ERROR))]
+pub fn parse_xpath(
+    text: &str,
+    pattern: &str,
+    language: Language,
+    namespaces: HashMap<String, String>,
+    extract: Extract,
+) -> Result<Output, Error> {
+    let matches = match language {
+        Language::XPath => query_xml(text, pattern, &namespaces, &extract)?,
+        Language::Css => query_html(text, pattern, &extract)?,
+    };
+    let text = matches
+        .iter()
+        .filter_map(|m| m.value.as_deref())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(Output {
+        count: matches.len(),
+        matches,
+        text,
+    })
+}
+
+fn query_xml(
+    text: &str,
+    pattern: &str,
+    namespaces: &HashMap<String, String>,
+    extract: &Extract,
+) -> Result<Vec<Match>, Error> {
+    // roxmltree 提供更准确的错误位置以及节点在原文中的范围
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let source = roxmltree::Document::parse_with_options(text, options)?;
+    let package = sxd_document::parser::parse(text)?;
+    let document = package.as_document();
+
+    let xpath = Factory::new()
+        .build(pattern)?
+        .ok_or(Error::EmptyExpression)?;
+    // 文档中声明的前缀默认可用，用户绑定的前缀优先
+    let mut bindings = HashMap::new();
+    for namespace in source.descendants().flat_map(|node| node.namespaces()) {
+        if let Some(prefix) = namespace.name() {
+            bindings.entry(prefix).or_insert(namespace.uri());
+        }
+    }
+    bindings.extend(namespaces.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+    // sxd-xpath 遇到未绑定的前缀会直接 panic，需要提前检查
+    if let Some(prefix) = prefixes(pattern).find(|p| !bindings.contains_key(p)) {
+        return Err(Error::UnboundPrefix(prefix.to_string()));
+    }
+    let mut context = Context::new();
+    for (prefix, uri) in bindings {
+        context.set_namespace(prefix, uri);
+    }
+    let nodes = match xpath.evaluate(&context, document.root())? {
+        Value::Nodeset(nodes) => nodes.document_order(),
+        value => {
+            return Ok(vec![Match {
+                kind: Kind::Value,
+                name: None,
+                path: pattern.to_string(),
+                line: None,
+                column: None,
+                value: Some(value.string()),
+            }])
+        }
+    };
+
+    Ok(nodes
+        .into_iter()
+        .map(|node| {
+            let steps = steps(node);
+            let located = locate(&source, &steps);
+            let (line, column) = match located {
+                Some(Located::Node(n)) => position(&source, n.range().start),
+                Some(Located::Attribute(a)) => position(&source, a.range().start),
+                None => (None, None),
+            };
+            let value = match extract {
+                // 文本节点在两个解析器中的切分方式不同，直接使用文本内容
+                Extract::Markup => Some(match (node, located) {
+                    (
+                        nodeset::Node::Element(_)
+                        | nodeset::Node::Comment(_)
+                        | nodeset::Node::ProcessingInstruction(_),
+                        Some(Located::Node(n)),
+                    ) => text[n.range()].to_string(),
+                    (nodeset::Node::Attribute(_), Some(Located::Attribute(a))) => {
+                        text[a.range()].to_string()
+                    }
+                    _ => node.string_value(),
+                }),
+                Extract::Text => Some(node.string_value()),
+                Extract::Attribute(name) => node
+                    .element()
+                    .and_then(|element| element.attribute_value(name.as_str()))
+                    .map(str::to_string),
+            };
+            Match {
+                kind: kind(node),
+                name: node.prefixed_name(),
+                path: format_steps(&steps),
+                line,
+                column,
+                value,
+            }
+        })
+        .collect())
+}
+
+/// 表达式中使用的命名空间前缀，忽略字符串字面量和 `child::` 这样的轴
+fn prefixes(pattern: &str) -> impl Iterator<Item = &str> {
+    let literal = Regex::new(r#""[^"]*"|'[^']*'"#).unwrap();
+    let prefix = Regex::new(r"(?:^|[^\w.:-])([A-Za-z_][\w.-]*):[A-Za-z_*]").unwrap();
+    let mut ranges = Vec::new();
+    let mut last = 0;
+    for m in literal.find_iter(pattern) {
+        ranges.push(last..m.start());
+        last = m.end();
+    }
+    ranges.push(last..pattern.len());
+    ranges.into_iter().flat_map(move |range| {
+        prefix
+            .captures_iter(&pattern[range])
+            .filter_map(|c| c.get(1).map(|m| m.as_str()))
+            .collect::<Vec<_>>()
+    })
+}
+
+fn kind(node: nodeset::Node) -> Kind {
+    match node {
+        nodeset::Node::Root(_) => Kind::Root,
+        nodeset::Node::Element(_) => Kind::Element,
+        nodeset::Node::Attribute(_) => Kind::Attribute,
+        nodeset::Node::Text(_) => Kind::Text,
+        nodeset::Node::Comment(_) => Kind::Comment,
+        nodeset::Node::Namespace(_) => Kind::Namespace,
+        nodeset::Node::ProcessingInstruction(_) => Kind::ProcessingInstruction,
+    }
+}
+
+/// 从根节点到目标节点的一步
+struct Step<'d> {
+    kind: Kind,
+    name: Option<QName<'d>>,
+    display: String,
+    /// 在同类型同名的兄弟节点中的序号，从 1 开始
+    index: usize,
+}
+
+fn steps(node: nodeset::Node) -> Vec<Step> {
+    let mut steps = Vec::new();
+    let mut current = Some(node);
+    while let Some(node) = current {
+        current = node.parent();
+        let node_kind = kind(node);
+        let display = match node {
+            nodeset::Node::Root(_) => continue,
+            nodeset::Node::Element(element) => qualified_name(element),
+            nodeset::Node::Attribute(attribute) => format!("@{}", attribute.name().local_part()),
+            nodeset::Node::Text(_) => "text()".to_string(),
+            nodeset::Node::Comment(_) => "comment()".to_string(),
+            nodeset::Node::ProcessingInstruction(_) => "processing-instruction()".to_string(),
+            nodeset::Node::Namespace(namespace) => {
+                format!("namespace::{}", namespace.prefix())
+            }
+        };
+        let name = node.expanded_name();
+        let index = node
+            .preceding_siblings()
+            .into_iter()
+            .filter(|sibling| {
+                std::mem::discriminant(&kind(*sibling)) == std::mem::discriminant(&node_kind)
+                    && sibling.expanded_name() == name
+            })
+            .count()
+            + 1;
+        steps.push(Step {
+            kind: node_kind,
+            name,
+            display,
+            index,
+        });
+    }
+    steps.reverse();
+    steps
+}
+
+fn qualified_name(element: dom::Element) -> String {
+    let name = element.name();
+    let prefix = name
+        .namespace_uri()
+        .and_then(|uri| element.prefix_for_namespace_uri(uri, element.preferred_prefix()));
+    match prefix {
+        Some(prefix) => format!("{}:{}", prefix, name.local_part()),
+        None => name.local_part().to_string(),
+    }
+}
+
+fn format_steps(steps: &[Step]) -> String {
+    if steps.is_empty() {
+        return "/".to_string();
+    }
+    steps
+        .iter()
+        .map(|step| match step.kind {
+            Kind::Attribute | Kind::Namespace => format!("/{}", step.display),
+            _ => format!("/{}[{}]", step.display, step.index),
+        })
+        .collect()
+}
+
+enum Located<'a, 'input> {
+    Node(roxmltree::Node<'a, 'input>),
+    Attribute(roxmltree::Attribute<'a, 'input>),
+}
+
+///
+/// 按照路径在 roxmltree 中找到对应的节点，两个解析器对文本节点的切分可能不同，
+/// 找不到时退回到最近的上级节点
+///
+fn locate<'a, 'input>(
+    document: &'a roxmltree::Document<'input>,
+    steps: &[Step],
+) -> Option<Located<'a, 'input>> {
+    let same_name = |name: &str, namespace: Option<&str>, expected: &Option<QName>| match expected {
+        Some(expected) => name == expected.local_part() && namespace == expected.namespace_uri(),
+        None => false,
+    };
+
+    let mut current = document.root();
+    for step in steps {
+        let found = match step.kind {
+            Kind::Attribute => {
+                return current
+                    .attributes()
+                    .find(|a| same_name(a.name(), a.namespace(), &step.name))
+                    .map(Located::Attribute)
+                    .or(Some(Located::Node(current)));
+            }
+            Kind::Namespace => return Some(Located::Node(current)),
+            Kind::Element => current
+                .children()
+                .filter(|child| {
+                    let tag = child.tag_name();
+                    child.is_element() && same_name(tag.name(), tag.namespace(), &step.name)
+                })
+                .nth(step.index - 1),
+            Kind::Text => current
+                .children()
+                .filter(|c| c.is_text())
+                .nth(step.index - 1),
+            Kind::Comment => current
+                .children()
+                .filter(|c| c.is_comment())
+                .nth(step.index - 1),
+            Kind::ProcessingInstruction => {
+                current.children().filter(|c| c.is_pi()).nth(step.index - 1)
+            }
+            Kind::Root | Kind::Value => None,
+        };
+        match found {
+            Some(node) => current = node,
+            None => break,
+        }
+    }
+    Some(Located::Node(current))
+}
+
+fn position(document: &roxmltree::Document, offset: usize) -> (Option<u32>, Option<u32>) {
+    let pos = document.text_pos_at(offset);
+    (Some(pos.row), Some(pos.col))
+}
+
+fn query_html(text: &str, pattern: &str, extract: &Extract) -> Result<Vec<Match>, Error> {
+    let html = Html::parse_document(text);
+    let selector = Selector::parse(pattern).map_err(|e| Error::Selector(e.to_string()))?;
+    Ok(html
+        .select(&selector)
+        .map(|element| {
+            let value = match extract {
+                Extract::Markup => Some(element.html()),
+                Extract::Text => Some(element.text().collect()),
+                Extract::Attribute(name) => element.attr(name).map(str::to_string),
+            };
+            Match {
+                kind: Kind::Element,
+                name: Some(element.value().name().to_string()),
+                path: css_path(element),
+                line: None,
+                column: None,
+                value,
+            }
+        })
+        .collect())
+}
+
+/// 生成元素的 CSS 选择器路径，如 `html > body > div:nth-of-type(2)`
+fn css_path(element: ElementRef) -> String {
+    let mut path = Vec::new();
+    let mut current = Some(element);
+    while let Some(element) = current {
+        let name = element.value().name();
+        let index = element
+            .prev_siblings()
+            .filter_map(ElementRef::wrap)
+            .filter(|sibling| sibling.value().name() == name)
+            .count();
+        let total = index
+            + 1
+            + element
+                .next_siblings()
+                .filter_map(ElementRef::wrap)
+                .filter(|sibling| sibling.value().name() == name)
+                .count();
+        path.push(match total {
+            1 => name.to_string(),
+            _ => format!("{}:nth-of-type({})", name, index + 1),
+        });
+        current = element.parent().and_then(ElementRef::wrap);
+    }
+    path.reverse();
+    path.join(" > ")
+}
+
+command_error! {
+    (Xml, "parse xml error: {0}", #[from] roxmltree::Error),
+    (Document, "parse xml error: {0}", #[from] sxd_document::parser::Error),
+    (XPathParse, "parse xpath error: {0}", #[from] sxd_xpath::ParserError),
+    (XPath, "xpath error: {0}", #[from] sxd_xpath::ExecutionError),
+    (EmptyExpression, "empty xpath expression"),
+    (UnboundPrefix, "namespace prefix is not bound: {0}", String),
+    (Selector, "parse css selector error: {0}", String),
+}
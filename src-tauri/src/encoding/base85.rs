@@ -0,0 +1,117 @@
+use super::{Encoding, EncodingError};
+
+///
+/// Base85 字母表，`zero` 为全零分组的缩写字符（仅 Ascii85 使用）
+///
+pub struct Alphabet {
+    encoding: Encoding,
+    symbols: &'static [u8; 85],
+    zero: Option<u8>,
+    delimiters: Option<(&'static str, &'static str)>,
+}
+
+pub const RFC1924: Alphabet = Alphabet {
+    encoding: Encoding::Base85,
+    symbols:
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+    zero: None,
+    delimiters: None,
+};
+
+pub const ASCII85: Alphabet = Alphabet {
+    encoding: Encoding::Ascii85,
+    symbols:
+        b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+    zero: Some(b'z'),
+    delimiters: Some(("<~", "~>")),
+};
+
+pub const Z85: Alphabet = Alphabet {
+    encoding: Encoding::Z85,
+    symbols:
+        b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+    zero: None,
+    delimiters: None,
+};
+
+///
+/// 每 4 字节编码为 5 个字符，末尾不足 4 字节时补零编码后截断
+///
+pub fn encode(alphabet: &Alphabet, bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(4) * 5);
+    for chunk in bytes.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+        if chunk.len() == 4 && value == 0 {
+            if let Some(zero) = alphabet.zero {
+                text.push(zero as char);
+                continue;
+            }
+        }
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = alphabet.symbols[(value % 85) as usize];
+            value /= 85;
+        }
+        text.extend(digits[..chunk.len() + 1].iter().map(|&b| b as char));
+    }
+    text
+}
+
+///
+/// 解码时忽略空白字符与 Ascii85 的 `<~ ~>` 定界符
+///
+pub fn decode(alphabet: &Alphabet, text: &str) -> Result<Vec<u8>, EncodingError> {
+    let mut text = text.trim();
+    if let Some((start, end)) = alphabet.delimiters {
+        text = text.strip_prefix(start).unwrap_or(text);
+        text = text.strip_suffix(end).unwrap_or(text);
+    }
+
+    let mut table = [u8::MAX; 256];
+    for (index, &symbol) in alphabet.symbols.iter().enumerate() {
+        table[symbol as usize] = index as u8;
+    }
+
+    let mut bytes = Vec::with_capacity(text.len() / 5 * 4);
+    let mut group = Vec::with_capacity(5);
+    for (index, c) in text.chars().enumerate() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        if group.is_empty() && alphabet.zero.is_some_and(|zero| zero as char == c) {
+            bytes.extend_from_slice(&[0; 4]);
+            continue;
+        }
+        let digit = match u8::try_from(c) {
+            Ok(b) if table[b as usize] != u8::MAX => table[b as usize],
+            _ => return Err(EncodingError::Character(alphabet.encoding, c, index)),
+        };
+        group.push(digit);
+        if group.len() == 5 {
+            bytes.extend_from_slice(&decode_group(alphabet, &group)?);
+            group.clear();
+        }
+    }
+
+    match group.len() {
+        0 => {}
+        1 => return Err(EncodingError::Length(alphabet.encoding)),
+        len => {
+            // 用最大值补齐后解码，再截掉补出来的字节
+            group.resize(5, 84);
+            bytes.extend_from_slice(&decode_group(alphabet, &group)?[..len - 1]);
+        }
+    }
+    Ok(bytes)
+}
+
+fn decode_group(alphabet: &Alphabet, group: &[u8]) -> Result<[u8; 4], EncodingError> {
+    let value = group
+        .iter()
+        .fold(0u64, |value, &digit| value * 85 + digit as u64);
+    u32::try_from(value)
+        .map(u32::to_be_bytes)
+        .map_err(|_| EncodingError::Invalid(alphabet.encoding))
+}
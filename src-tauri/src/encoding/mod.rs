@@ -0,0 +1,163 @@
+mod base85;
+mod uuencode;
+
+use base64::prelude::{
+    BASE64_STANDARD, BASE64_STANDARD_NO_PAD, BASE64_URL_SAFE, BASE64_URL_SAFE_NO_PAD,
+};
+use base64::Engine;
+use data_encoding::{Specification, BASE32};
+use hex::FromHexError;
+use serde::{Deserialize, Serialize};
+use std::string::FromUtf8Error;
+use std::sync::OnceLock;
+
+const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE62: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+///
+/// 二进制与文本之间的编码方式
+///
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Encoding {
+    Utf8,
+    Hex,
+    #[serde(alias = "Standard")]
+    Base64,
+    #[serde(alias = "StandardNoPad")]
+    Base64NoPad,
+    #[serde(alias = "UrlSafe")]
+    Base64Url,
+    #[serde(alias = "UrlSafeNoPad")]
+    Base64UrlNoPad,
+    /// RFC 4648
+    Base32,
+    Base32Crockford,
+    /// Bitcoin 字母表
+    Base58,
+    Base62,
+    /// RFC 1924
+    Base85,
+    Ascii85,
+    Z85,
+    Uuencode,
+    QuotedPrintable,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncodingError {
+    #[error(transparent)]
+    FromUtf8(#[from] FromUtf8Error),
+    #[error(transparent)]
+    FromHex(#[from] FromHexError),
+    #[error(transparent)]
+    DecodeBase64(#[from] base64::DecodeError),
+    #[error(transparent)]
+    DecodeBase32(#[from] data_encoding::DecodeError),
+    #[error(transparent)]
+    DecodeQuotedPrintable(#[from] quoted_printable::QuotedPrintableError),
+    #[error("invalid {0:?} character `{1}` at position {2}")]
+    Character(Encoding, char, usize),
+    #[error("invalid {0:?} length")]
+    Length(Encoding),
+    #[error("invalid {0:?} text")]
+    Invalid(Encoding),
+}
+
+impl Encoding {
+    pub fn encode(self, value: &[u8]) -> Result<String, EncodingError> {
+        match self {
+            Encoding::Utf8 => String::from_utf8(value.to_vec()).map_err(Into::into),
+            Encoding::Hex => Ok(hex::encode(value)),
+            Encoding::Base64 => Ok(BASE64_STANDARD.encode(value)),
+            Encoding::Base64NoPad => Ok(BASE64_STANDARD_NO_PAD.encode(value)),
+            Encoding::Base64Url => Ok(BASE64_URL_SAFE.encode(value)),
+            Encoding::Base64UrlNoPad => Ok(BASE64_URL_SAFE_NO_PAD.encode(value)),
+            Encoding::Base32 => Ok(BASE32.encode(value)),
+            Encoding::Base32Crockford => Ok(crockford().encode(value)),
+            Encoding::Base58 => Ok(base_x::encode(BASE58, value)),
+            Encoding::Base62 => Ok(base_x::encode(BASE62, value)),
+            Encoding::Base85 => Ok(base85::encode(&base85::RFC1924, value)),
+            Encoding::Ascii85 => Ok(base85::encode(&base85::ASCII85, value)),
+            Encoding::Z85 => Ok(base85::encode(&base85::Z85, value)),
+            Encoding::Uuencode => Ok(uuencode::encode(value)),
+            Encoding::QuotedPrintable => Ok(quoted_printable::encode_binary_to_str(value)),
+        }
+    }
+
+    pub fn decode(self, value: &str) -> Result<Vec<u8>, EncodingError> {
+        match self {
+            Encoding::Utf8 => Ok(value.as_bytes().to_vec()),
+            Encoding::Hex => hex::decode(value).map_err(Into::into),
+            Encoding::Base64 => BASE64_STANDARD.decode(value).map_err(Into::into),
+            Encoding::Base64NoPad => BASE64_STANDARD_NO_PAD.decode(value).map_err(Into::into),
+            Encoding::Base64Url => BASE64_URL_SAFE.decode(value).map_err(Into::into),
+            Encoding::Base64UrlNoPad => BASE64_URL_SAFE_NO_PAD.decode(value).map_err(Into::into),
+            Encoding::Base32 => BASE32.decode(value.as_bytes()).map_err(Into::into),
+            Encoding::Base32Crockford => crockford().decode(value.as_bytes()).map_err(Into::into),
+            Encoding::Base58 => base_x::decode(BASE58, value).map_err(|_| self.invalid(value)),
+            Encoding::Base62 => base_x::decode(BASE62, value).map_err(|_| self.invalid(value)),
+            Encoding::Base85 => base85::decode(&base85::RFC1924, value),
+            Encoding::Ascii85 => base85::decode(&base85::ASCII85, value),
+            Encoding::Z85 => base85::decode(&base85::Z85, value),
+            Encoding::Uuencode => uuencode::decode(value),
+            Encoding::QuotedPrintable => {
+                quoted_printable::decode(value, quoted_printable::ParseMode::Robust)
+                    .map_err(Into::into)
+            }
+        }
+    }
+
+    ///
+    /// base-x 只返回笼统的错误，这里补上第一个非法字符的位置
+    ///
+    fn invalid(self, value: &str) -> EncodingError {
+        let alphabet = match self {
+            Encoding::Base58 => BASE58,
+            _ => BASE62,
+        };
+        match value
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !alphabet.contains(*c))
+        {
+            Some((index, c)) => EncodingError::Character(self, c, index),
+            None => EncodingError::Invalid(self),
+        }
+    }
+}
+
+///
+/// Crockford Base32：不填充，解码时忽略连字符并兼容小写与易混淆字符
+///
+fn crockford() -> &'static data_encoding::Encoding {
+    static CROCKFORD: OnceLock<data_encoding::Encoding> = OnceLock::new();
+    CROCKFORD.get_or_init(|| {
+        let mut spec = Specification::new();
+        spec.symbols.push_str("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+        spec.translate.from.push_str("abcdefghjkmnpqrstvwxyziIlLoO");
+        spec.translate.to.push_str("ABCDEFGHJKMNPQRSTVWXYZ111100");
+        spec.ignore.push('-');
+        spec.encoding()
+            .expect("valid crockford base32 specification")
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodingText {
+    pub text: String,
+    pub encoding: Encoding,
+}
+
+impl EncodingText {
+    #[allow(unused)]
+    pub fn new(value: impl Into<String>, encoding: Encoding) -> Self {
+        Self {
+            text: value.into(),
+            encoding,
+        }
+    }
+
+    pub fn to_bytes(self) -> Result<Vec<u8>, EncodingError> {
+        self.encoding.decode(&self.text)
+    }
+}
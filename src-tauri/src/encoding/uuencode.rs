@@ -0,0 +1,86 @@
+use super::{Encoding, EncodingError};
+
+/// 每行最多编码的字节数
+const LINE: usize = 45;
+
+///
+/// 输出带 `begin`/`end` 的完整 uuencode 文本
+///
+pub fn encode(bytes: &[u8]) -> String {
+    let mut text = String::from("begin 644 data\n");
+    for line in bytes.chunks(LINE) {
+        text.push(symbol(line.len() as u8));
+        for chunk in line.chunks(3) {
+            let mut group = [0u8; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            text.push(symbol(group[0] >> 2));
+            text.push(symbol((group[0] << 4 | group[1] >> 4) & 0x3f));
+            text.push(symbol((group[1] << 2 | group[2] >> 6) & 0x3f));
+            text.push(symbol(group[2] & 0x3f));
+        }
+        text.push('\n');
+    }
+    text.push_str("`\nend\n");
+    text
+}
+
+///
+/// 解码时 `begin`/`end` 行可省略，行尾被截掉的空格按零处理
+///
+pub fn decode(text: &str) -> Result<Vec<u8>, EncodingError> {
+    let mut bytes = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() || line.starts_with("begin ") {
+            continue;
+        }
+        if line == "end" {
+            break;
+        }
+
+        let mut values = Vec::with_capacity(line.len());
+        for (index, c) in line.char_indices() {
+            match c {
+                ' '..='`' => values.push((c as u8 - b' ') & 0x3f),
+                _ => {
+                    return Err(EncodingError::Character(
+                        Encoding::Uuencode,
+                        c,
+                        start + index,
+                    ))
+                }
+            }
+        }
+        let len = values[0] as usize;
+        if len == 0 {
+            break;
+        }
+        if len > LINE {
+            return Err(EncodingError::Length(Encoding::Uuencode));
+        }
+        values.resize(1 + len.div_ceil(3) * 4, 0);
+
+        let decoded = values[1..].chunks(4).flat_map(|group| {
+            [
+                group[0] << 2 | group[1] >> 4,
+                group[1] << 4 | group[2] >> 2,
+                group[2] << 6 | group[3],
+            ]
+        });
+        bytes.extend(decoded.take(len));
+    }
+    Ok(bytes)
+}
+
+///
+/// 0 用反引号表示，避免行尾出现容易被丢弃的空格
+///
+fn symbol(value: u8) -> char {
+    match value {
+        0 => '`',
+        value => (b' ' + value) as char,
+    }
+}
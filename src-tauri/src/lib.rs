@@ -1,3 +1,4 @@
+mod charset;
 mod command;
 mod encoding;
 mod error;
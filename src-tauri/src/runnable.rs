@@ -18,17 +18,22 @@ impl<R: Runtime> Runnable for Builder<R> {
             command::fs::copy_file,
             command::fs::open_log_dir,
             command::font::get_system_fonts,
-            command::formatter::json::format_json,
-            command::formatter::xml::format_xml,
-            command::formatter::sql::format_sql,
+            command::formatter::xml::check_xml,
+            command::formatter::xml_schema::validate_xml,
+            command::formatter::sql::check_sql,
+            command::formatter::registry::list_formatters,
+            command::formatter::registry::format,
             command::generator::uuid::generate_uuid,
             command::generator::password::generate_password,
             command::generator::hash::generate_text_hash,
             command::generator::hash::generate_file_hash,
+            command::generator::icon::generate_icons,
             command::codec::base64_text::encode_text_base64,
             command::codec::base64_text::decode_text_base64,
             command::codec::base64_image::encode_image_base64,
             command::codec::base64_image::decode_image_base64,
+            command::codec::base64_file::encode_file_base64,
+            command::codec::base64_file::decode_file_base64,
             command::codec::jwt::encode_jwt,
             command::codec::jwt::decode_jwt,
             command::codec::jwt::generate_jwt_rsa_key_pair,
@@ -41,14 +46,43 @@ impl<R: Runtime> Runnable for Builder<R> {
             command::codec::qrcode::decode_qrcode,
             command::codec::html::encode_html,
             command::codec::html::decode_html,
+            command::codec::hexdump::dump_hex,
+            command::codec::hexdump::search_hex,
+            command::codec::hexdump::identify_file,
+            command::codec::hexdump::inspect_hex,
             command::text::markdown::parse_markdown,
             command::text::regex::parse_regex,
             command::text::jsonpath::parse_jsonpath,
+            command::text::xpath::parse_xpath,
             command::text::escape::escape_text,
             command::text::escape::unescape_text,
+            command::text::charset::detect_charset,
+            command::text::charset::transcode_charset,
             command::converter::cron::parse_cron,
+            command::converter::cron::build_cron,
+            command::converter::cron::convert_cron,
+            command::converter::color::convert_color,
+            command::converter::color::contrast_color,
+            command::converter::color::generate_palette,
+            command::converter::color::extract_colors,
+            command::converter::ddl::convert_ddl,
+            command::converter::image::inspect_image,
+            command::converter::image::convert_image,
+            command::converter::csv::parse_csv,
+            command::converter::csv::convert_csv,
+            command::converter::csv::convert_to_csv,
+            command::converter::csv::query_csv,
             command::converter::json_yaml::convert_json_to_yaml,
             command::converter::json_yaml::convert_yaml_to_json,
+            command::converter::json_yaml::convert_yaml_to_yaml,
+            command::converter::time::parse_time,
+            command::converter::time::time_between,
+            command::converter::time::shift_time,
+            command::converter::number::convert_number,
+            command::converter::number::convert_float,
+            command::converter::number::convert_permission,
+            command::converter::xml_json::convert_xml_to_json,
+            command::converter::xml_json::convert_json_to_xml,
             command::converter::yaml_properties::convert_yaml_to_properties,
             command::converter::yaml_properties::convert_properties_to_yaml,
             command::crypto::rsa::generate_rsa_key_pair,
@@ -1,37 +1,83 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Encoding } from "../crypto/type";
+import { Charset } from "../text/charset";
+import { FileType } from "./hexdump";
 
-export enum Base64Mode {
-  Standard = "Standard",
-  StandardNoPad = "StandardNoPad",
-  UrlSafe = "UrlSafe",
-  UrlSafeNoPad = "UrlSafeNoPad",
-}
+export type Base64Source = { Text: string } | { File: string };
 
-const encodeImageBase64 = (image: string, mode: Base64Mode) => {
+export type EncodedFile = {
+  kind: FileType | null;
+  size: number;
+  text: string | null;
+};
+
+export type DecodedFile = {
+  kind: FileType | null;
+  declared: string | null;
+  size: number;
+};
+
+const encodeImageBase64 = (image: string, mode: Encoding) => {
   return invoke<string>("encode_image_base64", { image, mode });
 };
 
-const decodeImageBase64 = (base64: string, mode: Base64Mode) => {
+const decodeImageBase64 = (base64: string, mode: Encoding) => {
   return invoke<[string, string]>("decode_image_base64", { base64, mode });
 };
 
-const encodeTextBase64 = async (text: string, mode: Base64Mode) => {
+const encodeTextBase64 = async (
+  text: string,
+  mode: Encoding,
+  charset: Charset | null = null,
+) => {
   return invoke<string>("encode_text_base64", {
     text,
     mode,
+    charset,
   });
 };
 
-const decodeTextBase64 = async (base64: string, mode: Base64Mode) => {
+const decodeTextBase64 = async (
+  base64: string,
+  mode: Encoding,
+  charset: Charset | null = null,
+) => {
   return invoke<string>("decode_text_base64", {
     base64,
     mode,
+    charset,
+  });
+};
+
+const encodeFileBase64 = (
+  path: string,
+  mode: Encoding,
+  wrap: boolean = false,
+  dataUri: boolean = false,
+  output: string | null = null,
+) => {
+  return invoke<EncodedFile>("encode_file_base64", {
+    path,
+    mode,
+    wrap,
+    dataUri,
+    output,
   });
 };
 
+const decodeFileBase64 = (
+  source: Base64Source,
+  mode: Encoding,
+  output: string | null = null,
+) => {
+  return invoke<DecodedFile>("decode_file_base64", { source, mode, output });
+};
+
 export {
   encodeImageBase64,
   decodeImageBase64,
   encodeTextBase64,
   decodeTextBase64,
+  encodeFileBase64,
+  decodeFileBase64,
 };
@@ -0,0 +1,77 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Charset, Source } from "../text/charset";
+
+export enum Endian {
+  Little = "Little",
+  Big = "Big",
+}
+
+export type Pattern =
+  | { Hex: string }
+  | { Text: { text: string; charset: Charset | null } };
+
+export type Line = { offset: number; hex: string; ascii: string };
+
+export type Dump = {
+  size: number;
+  offset: number;
+  lines: Line[];
+  text: string;
+};
+
+export type Matches = {
+  offsets: number[];
+  length: number;
+  truncated: boolean;
+};
+
+export type FileType = {
+  extension: string;
+  mime: string;
+  description: string;
+};
+
+export type Values = {
+  offset: number;
+  binary: string | null;
+  int8: string | null;
+  uint8: string | null;
+  int16: string | null;
+  uint16: string | null;
+  int32: string | null;
+  uint32: string | null;
+  int64: string | null;
+  uint64: string | null;
+  float32: string | null;
+  float64: string | null;
+  leb128: string | null;
+};
+
+const dumpHex = (
+  source: Source,
+  offset: number = 0,
+  length: number | null = null,
+  width: number | null = null,
+  group: number | null = null,
+) => {
+  return invoke<Dump>("dump_hex", { source, offset, length, width, group });
+};
+
+const searchHex = (
+  source: Source,
+  pattern: Pattern,
+  start: number | null = null,
+  limit: number | null = null,
+) => {
+  return invoke<Matches>("search_hex", { source, pattern, start, limit });
+};
+
+const identifyFile = (source: Source) => {
+  return invoke<FileType | null>("identify_file", { source });
+};
+
+const inspectHex = (source: Source, offset: number, endian: Endian) => {
+  return invoke<Values>("inspect_hex", { source, offset, endian });
+};
+
+export { dumpHex, searchHex, identifyFile, inspectHex };
@@ -0,0 +1,57 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export enum Palette {
+  Tints = "Tints",
+  Shades = "Shades",
+  Tones = "Tones",
+  Complementary = "Complementary",
+  SplitComplementary = "SplitComplementary",
+  Analogous = "Analogous",
+  Triadic = "Triadic",
+  Tetradic = "Tetradic",
+}
+
+export type Color = {
+  hex: string;
+  rgb: string;
+  hsl: string;
+  hsv: string;
+  hwb: string;
+  cmyk: string;
+  lab: string;
+  oklch: string;
+  name: string | null;
+  rgba: [number, number, number, number];
+};
+
+export type Contrast = {
+  ratio: number;
+  aa: boolean;
+  aa_large: boolean;
+  aaa: boolean;
+  aaa_large: boolean;
+};
+
+export type Swatch = { color: Color; ratio: number };
+
+const convertColor = async (input: string) => {
+  return invoke<Color>("convert_color", { input });
+};
+
+const contrastColor = async (foreground: string, background: string) => {
+  return invoke<Contrast>("contrast_color", { foreground, background });
+};
+
+const generatePalette = async (
+  input: string,
+  palette: Palette,
+  count: number = 5,
+) => {
+  return invoke<Color[]>("generate_palette", { input, palette, count });
+};
+
+const extractColors = async (image: string, count: number = 6) => {
+  return invoke<Swatch[]>("extract_colors", { image, count });
+};
+
+export { contrastColor, convertColor, extractColors, generatePalette };
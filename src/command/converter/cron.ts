@@ -1,7 +1,94 @@
 import { invoke } from "@tauri-apps/api/core";
 
-const parseCron = async (cron: string, size: number, pattern: string) => {
-  return invoke<string[]>("parse_cron", { cron, size, pattern });
+export enum Dialect {
+  Unix = "Unix",
+  Quartz = "Quartz",
+  Spring = "Spring",
+}
+
+export type Description = {
+  english: string;
+  chinese: string;
+};
+
+export type Plan = {
+  dialect: Dialect;
+  description: Description;
+  times: string[];
+};
+
+export type Part =
+  | "Any"
+  | { Value: number }
+  | { Range: [number, number] }
+  | { Step: [Part, number] };
+
+export type Day =
+  | { Part: Part }
+  | { Last: number }
+  | { Nearest: number }
+  | "LastWeekday";
+
+export type Weekday =
+  | { Part: Part }
+  | { Last: number }
+  | { Nth: [number, number] };
+
+export type Conversion = {
+  dialect: Dialect;
+  expression: string | null;
+  description: Description;
+  unsupported: Description[];
+};
+
+const parseCron = async (
+  cron: string,
+  size: number,
+  pattern: string,
+  dialect: Dialect | null = null,
+  timezone: string | null = null,
+  start: string | null = null,
+) => {
+  return invoke<Plan>("parse_cron", {
+    cron,
+    dialect,
+    pattern,
+    size,
+    timezone,
+    start,
+  });
+};
+
+const buildCron = async (
+  dialect: Dialect,
+  fields: {
+    seconds?: Part[];
+    minutes: Part[];
+    hours: Part[];
+    days: Day[];
+    months: Part[];
+    weekdays: Weekday[];
+    years?: Part[];
+  },
+) => {
+  return invoke<Conversion>("build_cron", {
+    dialect,
+    seconds: fields.seconds ?? null,
+    minutes: fields.minutes,
+    hours: fields.hours,
+    days: fields.days,
+    months: fields.months,
+    weekdays: fields.weekdays,
+    years: fields.years ?? null,
+  });
+};
+
+const convertCron = async (
+  cron: string,
+  to: Dialect,
+  from: Dialect | null = null,
+) => {
+  return invoke<Conversion>("convert_cron", { cron, from, to });
 };
 
-export { parseCron };
+export { buildCron, convertCron, parseCron };
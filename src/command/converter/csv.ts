@@ -0,0 +1,87 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Charset } from "../text/charset";
+
+export type Source = { Text: string } | { File: string };
+
+export enum Format {
+  Json = "Json",
+  Markdown = "Markdown",
+  Sql = "Sql",
+}
+
+export type Table = {
+  charset: Charset;
+  delimiter: string;
+  quote: string;
+  headers: string[];
+  rows: string[][];
+  total: number;
+};
+
+const parseCsv = (
+  source: Source,
+  header: boolean = true,
+  limit: number | null = null,
+  charset: Charset | null = null,
+  delimiter: string | null = null,
+  quote: string | null = null,
+) => {
+  return invoke<Table>("parse_csv", {
+    source,
+    charset,
+    delimiter,
+    quote,
+    header,
+    limit,
+  });
+};
+
+const convertCsv = (
+  source: Source,
+  format: Format,
+  header: boolean = true,
+  table: string | null = null,
+  output: string | null = null,
+  charset: Charset | null = null,
+  delimiter: string | null = null,
+  quote: string | null = null,
+) => {
+  return invoke<string>("convert_csv", {
+    source,
+    charset,
+    delimiter,
+    quote,
+    header,
+    format,
+    table,
+    output,
+  });
+};
+
+const convertToCsv = (
+  input: string,
+  format: Format,
+  delimiter: string | null = null,
+) => {
+  return invoke<string>("convert_to_csv", { input, format, delimiter });
+};
+
+const queryCsv = (
+  source: Source,
+  query: string,
+  header: boolean = true,
+  charset: Charset | null = null,
+  delimiter: string | null = null,
+  quote: string | null = null,
+) => {
+  return invoke<Table>("query_csv", {
+    source,
+    charset,
+    delimiter,
+    quote,
+    header,
+    query,
+  });
+};
+
+export { convertCsv, convertToCsv, parseCsv, queryCsv };
@@ -0,0 +1,20 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export enum Dialect {
+  MySql = "MySql",
+  PostgreSql = "PostgreSql",
+  SQLite = "SQLite",
+}
+
+export enum Target {
+  Rust = "Rust",
+  Java = "Java",
+  TypeScript = "TypeScript",
+  JsonSchema = "JsonSchema",
+}
+
+const convertDdl = (ddl: string, dialect: Dialect, target: Target) => {
+  return invoke<string>("convert_ddl", { ddl, dialect, target });
+};
+
+export { convertDdl };
@@ -0,0 +1,82 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export enum ImageFormat {
+  Png = "Png",
+  Jpeg = "Jpeg",
+  WebP = "WebP",
+  Gif = "Gif",
+  Bmp = "Bmp",
+  Ico = "Ico",
+  Tiff = "Tiff",
+}
+
+export enum Fit {
+  Contain = "Contain",
+  Cover = "Cover",
+  Stretch = "Stretch",
+}
+
+export enum Filter {
+  Nearest = "Nearest",
+  Triangle = "Triangle",
+  CatmullRom = "CatmullRom",
+  Gaussian = "Gaussian",
+  Lanczos3 = "Lanczos3",
+}
+
+export type Resize = {
+  width: number | null;
+  height: number | null;
+  fit: Fit;
+  filter: Filter | null;
+};
+
+export type Crop = { x: number; y: number; width: number; height: number };
+
+export type ExifField = { ifd: string; tag: string; value: string };
+
+export type ImageInfo = {
+  format: ImageFormat | null;
+  width: number;
+  height: number;
+  color: string;
+  bits: number;
+  alpha: boolean;
+  size: number;
+  orientation: string;
+  exif: ExifField[];
+};
+
+export type Converted = {
+  base64: string;
+  extension: string;
+  width: number;
+  height: number;
+  size: number;
+};
+
+const inspectImage = (image: string) => {
+  return invoke<ImageInfo>("inspect_image", { image });
+};
+
+const convertImage = (
+  image: string,
+  format: ImageFormat,
+  crop: Crop | null = null,
+  resize: Resize | null = null,
+  quality: number | null = null,
+  strip: boolean = false,
+  output: string | null = null,
+) => {
+  return invoke<Converted>("convert_image", {
+    image,
+    format,
+    crop,
+    resize,
+    quality,
+    strip,
+    output,
+  });
+};
+
+export { inspectImage, convertImage };
@@ -1,11 +1,15 @@
 import { invoke } from "@tauri-apps/api/core";
 
-const convertJsonToYaml = (json: string) => {
-  return invoke<string>("convert_json_to_yaml", { json });
+const convertJsonToYaml = (json: string, tags: boolean = false) => {
+  return invoke<string>("convert_json_to_yaml", { json, tags });
 };
 
 const convertYamlToJson = (yaml: string) => {
   return invoke<string>("convert_yaml_to_json", { yaml });
 };
 
-export { convertJsonToYaml, convertYamlToJson };
+const convertYamlToYaml = (yaml: string) => {
+  return invoke<string>("convert_yaml_to_yaml", { yaml });
+};
+
+export { convertJsonToYaml, convertYamlToJson, convertYamlToYaml };
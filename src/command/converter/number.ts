@@ -0,0 +1,92 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Encoding } from "../crypto/type";
+
+export type Radix = {
+  radix: number;
+  text: string;
+};
+
+export type Width = {
+  bits: number;
+  overflow: boolean;
+  signed: string;
+  unsigned: string;
+  binary: string;
+  hex: string;
+  big_endian: string;
+  little_endian: string;
+};
+
+export type Number = {
+  value: string;
+  radixes: Radix[];
+  widths: Width[];
+};
+
+export enum Category {
+  Zero = "Zero",
+  Subnormal = "Subnormal",
+  Normal = "Normal",
+  Infinite = "Infinite",
+  Nan = "Nan",
+}
+
+export type Ieee = {
+  value: string;
+  category: Category;
+  bits: string;
+  binary: string;
+  sign: number;
+  exponent: number;
+  unbiased: number | null;
+  mantissa: string;
+  big_endian: string;
+  little_endian: string;
+};
+
+export type Floats = {
+  single: Ieee;
+  double: Ieee;
+};
+
+export type Access = {
+  read: boolean;
+  write: boolean;
+  execute: boolean;
+};
+
+export type Permission = {
+  octal: string;
+  symbolic: string;
+  chmod: string;
+  owner: Access;
+  group: Access;
+  others: Access;
+  setuid: boolean;
+  setgid: boolean;
+  sticky: boolean;
+};
+
+const convertNumber = async (
+  input: string,
+  encoding: Encoding,
+  radix: number | null = null,
+  radixes: number[] = [],
+) => {
+  return invoke<Number>("convert_number", {
+    input,
+    radix,
+    radixes,
+    encoding,
+  });
+};
+
+const convertFloat = async (input: string, encoding: Encoding) => {
+  return invoke<Floats>("convert_float", { input, encoding });
+};
+
+const convertPermission = async (input: string) => {
+  return invoke<Permission>("convert_permission", { input });
+};
+
+export { convertFloat, convertNumber, convertPermission };
@@ -0,0 +1,110 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export enum Source {
+  Seconds = "Seconds",
+  Milliseconds = "Milliseconds",
+  Microseconds = "Microseconds",
+  Nanoseconds = "Nanoseconds",
+  Rfc3339 = "Rfc3339",
+  Rfc2822 = "Rfc2822",
+  IsoWeek = "IsoWeek",
+  Pattern = "Pattern",
+  Local = "Local",
+}
+
+export enum Unit {
+  Seconds = "Seconds",
+  Minutes = "Minutes",
+  Hours = "Hours",
+  Days = "Days",
+  BusinessDays = "BusinessDays",
+  Weeks = "Weeks",
+  Months = "Months",
+  Years = "Years",
+}
+
+export type Phrase = {
+  english: string;
+  chinese: string;
+};
+
+export type Zoned = {
+  zone: string;
+  time: string;
+  offset: string;
+  abbreviation: string;
+};
+
+export type Time = {
+  source: Source;
+  seconds: string;
+  milliseconds: string;
+  microseconds: string;
+  nanoseconds: string;
+  rfc3339: string;
+  rfc2822: string | null;
+  iso_week: string;
+  zones: Zoned[];
+  relative: Phrase;
+};
+
+export type Span = {
+  negative: boolean;
+  milliseconds: number;
+  iso: string;
+  years: number;
+  months: number;
+  days: number;
+  hours: number;
+  minutes: number;
+  seconds: number;
+  business_days: number;
+  description: Phrase;
+};
+
+const parseTime = async (
+  input: string,
+  timezone: string | null = null,
+  zones: string[] = [],
+  pattern: string | null = null,
+  output: string | null = null,
+) => {
+  return invoke<Time>("parse_time", {
+    input,
+    pattern,
+    timezone,
+    zones,
+    output,
+  });
+};
+
+const timeBetween = async (
+  start: string,
+  end: string,
+  timezone: string | null = null,
+  pattern: string | null = null,
+) => {
+  return invoke<Span>("time_between", { start, end, pattern, timezone });
+};
+
+const shiftTime = async (
+  input: string,
+  amount: number,
+  unit: Unit,
+  timezone: string | null = null,
+  zones: string[] = [],
+  pattern: string | null = null,
+  output: string | null = null,
+) => {
+  return invoke<Time>("shift_time", {
+    input,
+    pattern,
+    timezone,
+    amount,
+    unit,
+    zones,
+    output,
+  });
+};
+
+export { parseTime, shiftTime, timeBetween };
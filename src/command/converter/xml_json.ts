@@ -0,0 +1,48 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Indent } from "../formatter/indent";
+
+export enum Convention {
+  BadgerFish = "BadgerFish",
+  Parker = "Parker",
+  Attribute = "Attribute",
+}
+
+export enum Namespace {
+  Preserve = "Preserve",
+  Strip = "Strip",
+  Expand = "Expand",
+}
+
+export type Arrays = "Repeated" | "Always" | { Elements: string[] };
+
+const convertXmlToJson = (
+  xml: string,
+  convention: Convention,
+  namespace: Namespace = Namespace.Preserve,
+  arrays: Arrays = "Repeated",
+  coerceTypes: boolean = false,
+) => {
+  return invoke<string>("convert_xml_to_json", {
+    xml,
+    convention,
+    namespace,
+    arrays,
+    coerceTypes,
+  });
+};
+
+const convertJsonToXml = (
+  json: string,
+  convention: Convention,
+  indent: Indent = Indent.TwoSpace,
+  root: string | null = null,
+) => {
+  return invoke<string>("convert_json_to_xml", {
+    json,
+    convention,
+    indent,
+    root,
+  });
+};
+
+export { convertJsonToXml, convertXmlToJson };
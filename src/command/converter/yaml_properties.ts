@@ -1,11 +1,19 @@
 import { invoke } from "@tauri-apps/api/core";
 
-const convertYamlToProperties = async (yaml: string) => {
-  return invoke<string>("convert_yaml_to_properties", { yaml });
+export enum ValueMode {
+  String = "String",
+  Infer = "Infer",
+}
+
+const convertYamlToProperties = async (yaml: string, profile?: string) => {
+  return invoke<string>("convert_yaml_to_properties", { yaml, profile });
 };
 
-const convertPropertiesToYaml = async (properties: string) => {
-  return invoke<string>("convert_properties_to_yaml", { properties });
+const convertPropertiesToYaml = async (
+  properties: string,
+  mode: ValueMode = ValueMode.Infer,
+) => {
+  return invoke<string>("convert_properties_to_yaml", { properties, mode });
 };
 
 export { convertYamlToProperties, convertPropertiesToYaml };
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Charset } from "../text/charset";
 import { AesBitSize, BlockMode, Encoding, EncodingText, Padding } from "./type";
 
 export function generateAesKey(bitSize: AesBitSize, encoding: Encoding) {
@@ -41,6 +42,7 @@ export function decryptAes(
   blockMode: BlockMode,
   padding: Padding,
   encoding: Encoding,
+  charset: Charset | null = null,
 ) {
   return invoke<string>("decrypt_aes", {
     bitSize,
@@ -50,5 +52,6 @@ export function decryptAes(
     blockMode,
     padding,
     encoding,
+    charset,
   });
 }
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Charset } from "../text/charset";
 import { BlockMode, DesBitSize, Encoding, EncodingText, Padding } from "./type";
 
 export function generateDesKey(bitSize: DesBitSize, encoding: Encoding) {
@@ -41,6 +42,7 @@ export function decryptDes(
   blockMode: BlockMode,
   padding: Padding,
   encoding: Encoding,
+  charset: Charset | null = null,
 ) {
   return invoke<string>("decrypt_des", {
     bitSize,
@@ -50,5 +52,6 @@ export function decryptDes(
     blockMode,
     padding,
     encoding,
+    charset,
   });
 }
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Charset } from "../text/charset";
 import { KeyFormat, RsaBitSize } from "./type";
 
 export function generateRsaKeyPair(keyFormat: KeyFormat, bitSize: RsaBitSize) {
@@ -20,6 +21,12 @@ export function decryptRsa(
   keyFormat: KeyFormat,
   privateKey: string,
   text: string,
+  charset: Charset | null = null,
 ) {
-  return invoke<string>("decrypt_rsa", { keyFormat, privateKey, text });
+  return invoke<string>("decrypt_rsa", {
+    keyFormat,
+    privateKey,
+    text,
+    charset,
+  });
 }
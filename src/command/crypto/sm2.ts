@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Charset } from "../text/charset";
 
 export enum Sm2KeyFormat {
   Sec1 = "Sec1",
@@ -22,6 +23,12 @@ export function decryptSm2(
   keyFormat: Sm2KeyFormat,
   privateKey: string,
   text: string,
+  charset: Charset | null = null,
 ) {
-  return invoke<string>("decrypt_sm2", { keyFormat, privateKey, text });
+  return invoke<string>("decrypt_sm2", {
+    keyFormat,
+    privateKey,
+    text,
+    charset,
+  });
 }
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Charset } from "../text/charset";
 import { BlockMode, Encoding, EncodingText, Padding } from "./type";
 
 export function generateSm4Key(encoding: Encoding) {
@@ -34,6 +35,7 @@ export function decryptSm4(
   blockMode: BlockMode,
   padding: Padding,
   encoding: Encoding,
+  charset: Charset | null = null,
 ) {
   return invoke<string>("decrypt_sm4", {
     input,
@@ -42,5 +44,6 @@ export function decryptSm4(
     blockMode,
     padding,
     encoding,
+    charset,
   });
 }
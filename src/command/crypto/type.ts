@@ -47,6 +47,43 @@ export enum Encoding {
   Utf8 = "Utf8",
   Base64 = "Base64",
   Hex = "Hex",
+  Base64NoPad = "Base64NoPad",
+  Base64Url = "Base64Url",
+  Base64UrlNoPad = "Base64UrlNoPad",
+  Base32 = "Base32",
+  Base32Crockford = "Base32Crockford",
+  Base58 = "Base58",
+  Base62 = "Base62",
+  Base85 = "Base85",
+  Ascii85 = "Ascii85",
+  Z85 = "Z85",
+  Uuencode = "Uuencode",
+  QuotedPrintable = "QuotedPrintable",
+}
+
+export const BASE64_ENCODINGS = [
+  Encoding.Base64,
+  Encoding.Base64NoPad,
+  Encoding.Base64Url,
+  Encoding.Base64UrlNoPad,
+];
+
+// 旧版本的 Base64Mode，缓存中可能还保存着这些值
+const LEGACY_BASE64_MODES: Record<string, Encoding> = {
+  Standard: Encoding.Base64,
+  StandardNoPad: Encoding.Base64NoPad,
+  UrlSafe: Encoding.Base64Url,
+  UrlSafeNoPad: Encoding.Base64UrlNoPad,
+};
+
+/**
+ * 把缓存中旧的 Base64Mode 迁移为对应的 Encoding，无法识别时使用标准 Base64
+ */
+export function migrateBase64Mode(mode: string) {
+  if (BASE64_ENCODINGS.includes(mode as Encoding)) {
+    return mode as Encoding;
+  }
+  return LEGACY_BASE64_MODES[mode] ?? Encoding.Base64;
 }
 
 export type EncodingText = { text: string; encoding: Encoding };
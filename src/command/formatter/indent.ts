@@ -0,0 +1,7 @@
+export enum Indent {
+  TwoSpace = "TwoSpace",
+  FourSpace = "FourSpace",
+  Tab = "Tab",
+  None = "None",
+  Minify = "Minify",
+}
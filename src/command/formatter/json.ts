@@ -1,11 +1,12 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Indent } from "./indent";
+import { format } from "./registry";
 
 const formatJson = async (
   input: string,
-  indent: string = "TwoSpace",
+  indent: Indent = Indent.TwoSpace,
   sortable: boolean = false,
 ) => {
-  return invoke<string>("format_json", { input, indent, sortable });
+  return format("json", input, indent, sortable);
 };
 
 export { formatJson };
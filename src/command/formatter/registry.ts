@@ -0,0 +1,44 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Indent } from "./indent";
+
+export type OptionKind =
+  | { Boolean: { default: boolean } }
+  | { Integer: { default: number | null; min: number; max: number | null } }
+  | { Choice: { choices: string[]; default: string } }
+  | { Text: { placeholder: string } };
+
+export type OptionSchema = {
+  name: string;
+  label: string;
+  kind: OptionKind;
+};
+
+export type Capabilities = {
+  indents: Indent[];
+  sort: boolean;
+  minify: boolean;
+};
+
+export type Descriptor = {
+  name: string;
+  label: string;
+  extensions: string[];
+  capabilities: Capabilities;
+  options: OptionSchema[];
+};
+
+const listFormatters = async () => {
+  return invoke<Descriptor[]>("list_formatters");
+};
+
+const format = async (
+  name: string,
+  input: string,
+  indent: Indent = Indent.TwoSpace,
+  sort: boolean = false,
+  options: Record<string, unknown> = {},
+) => {
+  return invoke<string>("format", { name, input, indent, sort, options });
+};
+
+export { format, listFormatters };
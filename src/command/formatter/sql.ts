@@ -1,12 +1,61 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Indent } from "./indent";
+import { format } from "./registry";
+
+export enum Dialect {
+  Generic = "Generic",
+  MySql = "MySql",
+  PostgreSql = "PostgreSql",
+  SQLServer = "SQLServer",
+  SQLite = "SQLite",
+  Oracle = "Oracle",
+  ClickHouse = "ClickHouse",
+  Hive = "Hive",
+}
+
+export enum KeywordCase {
+  Upper = "Upper",
+  Lower = "Lower",
+  Preserve = "Preserve",
+}
+
+export type Diagnostic = {
+  line: number;
+  column: number;
+  message: string;
+};
+
+export type Report = {
+  valid: boolean;
+  errors: Diagnostic[];
+};
 
 const formatSql = async (
   input: string,
-  indent: string,
-  dialect: string,
-  uppercase: boolean,
+  indent: Indent,
+  dialect: Dialect,
+  keywordCase: KeywordCase = KeywordCase.Preserve,
+  linesBetweenQueries: number = 1,
+  maxInlineBlock: number = 50,
+  maxInlineArguments: number | null = null,
+  maxInlineTopLevel: number | null = null,
+  comments: boolean = true,
+  parameters: string | null = null,
 ) => {
-  return invoke<string>("format_sql", { input, indent, dialect, uppercase });
+  return format("sql", input, indent, false, {
+    dialect,
+    keyword_case: keywordCase,
+    lines_between_queries: linesBetweenQueries,
+    max_inline_block: maxInlineBlock,
+    max_inline_arguments: maxInlineArguments,
+    max_inline_top_level: maxInlineTopLevel,
+    comments,
+    parameters,
+  });
+};
+
+const checkSql = async (input: string, dialect: Dialect) => {
+  return invoke<Report>("check_sql", { input, dialect });
 };
 
-export { formatSql };
+export { checkSql, formatSql };
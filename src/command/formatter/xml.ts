@@ -1,7 +1,51 @@
 import { invoke } from "@tauri-apps/api/core";
+import { Indent } from "./indent";
+import { format } from "./registry";
 
-const formatXml = async (input: string, indent: string) => {
-  return invoke<string>("format_xml", { input, indent });
+export enum EmptyElement {
+  Preserve = "Preserve",
+  Expand = "Expand",
+  Collapse = "Collapse",
+}
+
+export enum Declaration {
+  Preserve = "Preserve",
+  Add = "Add",
+  Remove = "Remove",
+}
+
+export type Diagnostic = {
+  line: number;
+  column: number;
+  message: string;
+};
+
+export type Report = {
+  well_formed: boolean;
+  errors: Diagnostic[];
+};
+
+const formatXml = async (
+  input: string,
+  indent: Indent,
+  wrapAttributes: number | null = null,
+  sortAttributes: boolean = false,
+  comments: boolean = true,
+  processingInstructions: boolean = true,
+  emptyElement: EmptyElement = EmptyElement.Preserve,
+  declaration: Declaration = Declaration.Preserve,
+) => {
+  return format("xml", input, indent, sortAttributes, {
+    wrap_attributes: wrapAttributes,
+    comments,
+    processing_instructions: processingInstructions,
+    empty_element: emptyElement,
+    declaration,
+  });
+};
+
+const checkXml = async (input: string) => {
+  return invoke<Report>("check_xml", { input });
 };
 
-export { formatXml };
+export { checkXml, formatXml };
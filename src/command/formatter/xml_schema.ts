@@ -0,0 +1,28 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export enum SchemaKind {
+  Xsd = "Xsd",
+  Dtd = "Dtd",
+}
+
+export type Violation = {
+  line: number;
+  column: number;
+  path: string;
+  message: string;
+};
+
+export type Report = {
+  valid: boolean;
+  violations: Violation[];
+};
+
+const validateXml = async (
+  input: string,
+  kind: SchemaKind,
+  schemas: string[] = [],
+) => {
+  return invoke<Report>("validate_xml", { input, kind, schemas });
+};
+
+export { validateXml };
@@ -0,0 +1,33 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export enum IconTarget {
+  Ico = "Ico",
+  Icns = "Icns",
+  Web = "Web",
+  Android = "Android",
+  Ios = "Ios",
+}
+
+export type GeneratedIcons = {
+  files: string[];
+  manifest: string | null;
+  html: string | null;
+};
+
+const generateIcons = async (
+  source: string,
+  targets: IconTarget[],
+  dir: string,
+  name: string | null = null,
+  background: string | null = null,
+) => {
+  return invoke<GeneratedIcons>("generate_icons", {
+    source,
+    targets,
+    name,
+    background,
+    dir,
+  });
+};
+
+export { generateIcons };
@@ -0,0 +1,60 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Encoding, EncodingText } from "../crypto/type";
+
+export enum Charset {
+  Utf8 = "Utf8",
+  Utf16Le = "Utf16Le",
+  Utf16Be = "Utf16Be",
+  Gbk = "Gbk",
+  Gb18030 = "Gb18030",
+  Big5 = "Big5",
+  ShiftJis = "ShiftJis",
+  EucJp = "EucJp",
+  EucKr = "EucKr",
+  Latin1 = "Latin1",
+}
+
+export type Source = { Bytes: EncodingText } | { File: string };
+
+export type Candidate = { charset: Charset; confidence: number };
+
+export type Detection = {
+  charset: Charset;
+  confidence: number;
+  bom: boolean;
+  candidates: Candidate[];
+};
+
+export type Transcoded = {
+  from: Charset;
+  to: Charset;
+  replaced: boolean;
+  text: string | null;
+  bytes: string | null;
+};
+
+const detectCharset = (source: Source) => {
+  return invoke<Detection>("detect_charset", { source });
+};
+
+const transcodeCharset = (
+  source: Source,
+  to: Charset,
+  from: Charset | null = null,
+  encoding: Encoding = Encoding.Hex,
+  bom: boolean = false,
+  lossy: boolean = false,
+  output: string | null = null,
+) => {
+  return invoke<Transcoded>("transcode_charset", {
+    source,
+    from,
+    to,
+    bom,
+    lossy,
+    encoding,
+    output,
+  });
+};
+
+export { detectCharset, transcodeCharset };
@@ -1,7 +1,30 @@
 import { invoke } from "@tauri-apps/api/core";
 
-const parseJsonPath = async (text: string, pattern: string) => {
-  return invoke<string>("parse_jsonpath", { text, pattern });
+export enum Dialect {
+  JsonPath = "JsonPath",
+  Rfc9535 = "Rfc9535",
+  JmesPath = "JmesPath",
+  Jq = "Jq",
+}
+
+export type Match = {
+  path: string | null;
+  pointer: string | null;
+  value: any;
+};
+
+export type Output = {
+  dialect: Dialect;
+  matches: Match[];
+  text: string;
+};
+
+const parseJsonPath = async (
+  text: string,
+  pattern: string,
+  dialect: Dialect = Dialect.JsonPath,
+) => {
+  return invoke<Output>("parse_jsonpath", { text, pattern, dialect });
 };
 
 export { parseJsonPath };
@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export enum Language {
+  XPath = "XPath",
+  Css = "Css",
+}
+
+export type Extract = "Markup" | "Text" | { Attribute: string };
+
+export type Kind =
+  | "Root"
+  | "Element"
+  | "Attribute"
+  | "Text"
+  | "Comment"
+  | "ProcessingInstruction"
+  | "Namespace"
+  | "Value";
+
+export type Match = {
+  kind: Kind;
+  name: string | null;
+  path: string;
+  line: number | null;
+  column: number | null;
+  value: string | null;
+};
+
+export type Output = {
+  count: number;
+  matches: Match[];
+  text: string;
+};
+
+const parseXPath = async (
+  text: string,
+  pattern: string,
+  language: Language = Language.XPath,
+  namespaces: Record<string, string> = {},
+  extract: Extract = "Markup",
+) => {
+  return invoke<Output>("parse_xpath", {
+    text,
+    pattern,
+    language,
+    namespaces,
+    extract,
+  });
+};
+
+export { parseXPath };
@@ -0,0 +1,46 @@
+import { twMerge } from "tailwind-merge";
+import { Charset } from "@/command/text/charset";
+import { Show } from "solid-js";
+
+export const CHARSET_OPTIONS = [
+  { value: Charset.Utf8, label: "UTF-8" },
+  { value: Charset.Utf16Le, label: "UTF-16LE" },
+  { value: Charset.Utf16Be, label: "UTF-16BE" },
+  { value: Charset.Gbk, label: "GBK" },
+  { value: Charset.Gb18030, label: "GB18030" },
+  { value: Charset.Big5, label: "Big5" },
+  { value: Charset.ShiftJis, label: "Shift_JIS" },
+  { value: Charset.EucJp, label: "EUC-JP" },
+  { value: Charset.EucKr, label: "EUC-KR" },
+  { value: Charset.Latin1, label: "Latin-1" },
+];
+
+export function CharsetSelect(props: {
+  label?: string;
+  value?: Charset;
+  class?: string;
+  onChange?: (value: Charset) => void;
+}) {
+  const select = () => (
+    <select
+      class={twMerge(
+        "select select-sm w-25 rounded-md outline-none",
+        props.class,
+      )}
+      value={props.value}
+      onChange={(e) => props.onChange?.(e.target.value as Charset)}
+    >
+      {CHARSET_OPTIONS.map((option) => (
+        <option value={option.value}>{option.label}</option>
+      ))}
+    </select>
+  );
+  return (
+    <Show when={props.label} fallback={select()}>
+      <label class="select select-sm rounded-md outline-none">
+        <span class="label text-base-content text-sm">{props.label}</span>
+        {select()}
+      </label>
+    </Show>
+  );
+}
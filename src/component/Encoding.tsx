@@ -7,6 +7,18 @@ const ENCODING_OPTIONS = [
   { value: "Utf8", label: "UTF-8" },
   { value: "Hex", label: "Hex" },
   { value: "Base64", label: "Base64" },
+  { value: "Base64NoPad", label: "Base64 (无填充)" },
+  { value: "Base64Url", label: "Base64 URL" },
+  { value: "Base64UrlNoPad", label: "Base64 URL (无填充)" },
+  { value: "Base32", label: "Base32" },
+  { value: "Base32Crockford", label: "Base32 Crockford" },
+  { value: "Base58", label: "Base58" },
+  { value: "Base62", label: "Base62" },
+  { value: "Base85", label: "Base85" },
+  { value: "Ascii85", label: "Ascii85" },
+  { value: "Z85", label: "Z85" },
+  { value: "Uuencode", label: "uuencode" },
+  { value: "QuotedPrintable", label: "Quoted-Printable" },
 ];
 
 export function EncodingSelect(props: {
@@ -3,6 +3,7 @@ import {
   ArrowLeftRight,
   Badge,
   BadgePlus,
+  Binary,
   Braces,
   Bubbles,
   CirclePercent,
@@ -14,6 +15,7 @@ import {
   Earth,
   FileArchive,
   FileBracesCorner,
+  FileCode,
   FileText,
   GitCompareArrows,
   Hash,
@@ -21,14 +23,19 @@ import {
   History,
   House,
   Image,
+  Images,
+  Languages,
   LifeBuoy,
   Link,
   Network,
   PaintRoller,
+  Pipette,
   QrCode,
   Rainbow,
   Regex,
   Settings,
+  Shapes,
+  Sheet,
   Shell,
   ShieldPlus,
   SquareAsterisk,
@@ -76,6 +83,21 @@ const routeMetas: RouteMeta[] = [
         icon: CodeXml,
         component: lazy(() => import("./view/formatter/xml")),
       },
+      {
+        label: "代码",
+        path: "/code",
+        keywords: [
+          "format",
+          "html",
+          "css",
+          "javascript",
+          "toml",
+          "graphql",
+          "proto",
+        ],
+        icon: FileCode,
+        component: lazy(() => import("./view/formatter/code")),
+      },
     ],
   },
   {
@@ -97,6 +119,13 @@ const routeMetas: RouteMeta[] = [
         component: lazy(() => import("./view/codec/base64_image")),
         icon: Image,
       },
+      {
+        label: "Base64 文件",
+        path: "/base64_file",
+        keywords: ["codec", "file", "data uri"],
+        component: lazy(() => import("./view/codec/base64_file")),
+        icon: FileCode,
+      },
       {
         label: "GZip",
         path: "/gzip",
@@ -132,6 +161,13 @@ const routeMetas: RouteMeta[] = [
         component: lazy(() => import("./view/codec/html")),
         icon: Heading,
       },
+      {
+        label: "十六进制",
+        path: "/hexdump",
+        keywords: ["codec", "hex", "binary", "magic"],
+        component: lazy(() => import("./view/codec/hexdump")),
+        icon: Binary,
+      },
     ],
   },
   {
@@ -160,6 +196,13 @@ const routeMetas: RouteMeta[] = [
         component: lazy(() => import("./view/text/jsonpath")),
         icon: FileBracesCorner,
       },
+      {
+        label: "XPath",
+        path: "/xpath",
+        keywords: ["text", "xml", "html", "xpath", "css", "selector"],
+        component: lazy(() => import("./view/text/xpath")),
+        icon: CodeXml,
+      },
       {
         label: "转义 / 反转义",
         path: "/escape",
@@ -167,6 +210,13 @@ const routeMetas: RouteMeta[] = [
         component: lazy(() => import("./view/text/escape")),
         icon: Type,
       },
+      {
+        label: "字符集",
+        path: "/charset",
+        keywords: ["text", "charset", "encoding", "gbk", "utf8"],
+        component: lazy(() => import("./view/text/charset")),
+        icon: Languages,
+      },
     ],
   },
   {
@@ -195,6 +245,13 @@ const routeMetas: RouteMeta[] = [
         icon: Hash,
         component: lazy(() => import("./view/generator/hash")),
       },
+      {
+        label: "图标",
+        path: "/icon",
+        keywords: ["generate", "icon", "favicon", "ico", "icns"],
+        icon: Shapes,
+        component: lazy(() => import("./view/generator/icon")),
+      },
     ],
   },
   {
@@ -214,12 +271,30 @@ const routeMetas: RouteMeta[] = [
         component: lazy(() => import("./view/converter/json_yaml")),
         icon: CodeXml,
       },
+      {
+        label: "XML <> JSON",
+        path: "/xml_json",
+        component: lazy(() => import("./view/converter/xml_json")),
+        icon: CodeXml,
+      },
       {
         label: "YAML <> PROPERTIES",
         path: "/yaml_properties",
         component: lazy(() => import("./view/converter/yaml_properties")),
         icon: FileBracesCorner,
       },
+      {
+        label: "DDL 转实体",
+        path: "/ddl",
+        component: lazy(() => import("./view/converter/ddl")),
+        icon: Database,
+      },
+      {
+        label: "CSV",
+        path: "/csv",
+        component: lazy(() => import("./view/converter/csv")),
+        icon: Sheet,
+      },
       {
         label: "数字进制",
         path: "/number",
@@ -232,6 +307,18 @@ const routeMetas: RouteMeta[] = [
         component: lazy(() => import("./view/converter/time")),
         icon: Timer,
       },
+      {
+        label: "图片",
+        path: "/image",
+        component: lazy(() => import("./view/converter/image")),
+        icon: Images,
+      },
+      {
+        label: "颜色",
+        path: "/color",
+        component: lazy(() => import("./view/converter/color")),
+        icon: Pipette,
+      },
     ],
   },
   {
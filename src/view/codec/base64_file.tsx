@@ -0,0 +1,260 @@
+import {
+  Base64Source,
+  decodeFileBase64,
+  encodeFileBase64,
+} from "@/command/codec/base64";
+import { BASE64_ENCODINGS, Encoding } from "@/command/crypto/type";
+import {
+  ClearButton,
+  CopyButton,
+  PasteButton,
+  PickFileButton,
+} from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import Flex from "@/component/Flex";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { save } from "@tauri-apps/plugin-dialog";
+import { ArrowLeftRight, Layers, Link, WrapText } from "lucide-solid";
+import { createResource, Show } from "solid-js";
+
+export default function Base64FileCodec() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    file: "",
+    base64: "",
+    mode: Encoding.Base64,
+    encode: true,
+    wrap: false,
+    dataUri: false,
+  });
+
+  // 切换操作模式
+  const setEncode = (value: boolean) => {
+    setStore({ file: "", base64: "", encode: value });
+  };
+
+  // 解码时优先使用选择的文件
+  const source = (): Base64Source | undefined => {
+    if (store.file) {
+      return { File: store.file };
+    }
+    if (store.base64.trim()) {
+      return { Text: store.base64 };
+    }
+  };
+
+  // 编码
+  const [encoded] = createResource(
+    () => {
+      const { file, mode, wrap, dataUri } = store;
+      return store.encode && file && { file, mode, wrap, dataUri };
+    },
+    ({ file, mode, wrap, dataUri }) =>
+      encodeFileBase64(file, mode, wrap, dataUri).catch(stringify),
+  );
+
+  // 解码，只识别文件类型，保存时再写入文件
+  const [decoded] = createResource(
+    () => {
+      const value = source();
+      return !store.encode && value && { source: value, mode: store.mode };
+    },
+    ({ source, mode }) => decodeFileBase64(source, mode).catch(stringify),
+  );
+
+  const result = () => {
+    const value = store.encode ? encoded() : decoded();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const failure = () => {
+    const value = store.encode ? encoded() : decoded();
+    return typeof value === "string" ? value : undefined;
+  };
+
+  const text = () => {
+    const value = encoded();
+    return typeof value === "string" ? "" : (value?.text ?? "");
+  };
+
+  // data URI 中声明的类型
+  const declared = () => {
+    const value = decoded();
+    return typeof value === "string" ? undefined : value?.declared;
+  };
+
+  // 编码结果另存为文本文件，解码结果按识别的类型保存
+  const saveAs = () => {
+    if (store.encode) {
+      const { file, mode, wrap, dataUri } = store;
+      save({ defaultPath: `${file.split(/[\\/]/).pop()}.b64` })
+        .then((output) => {
+          if (output) {
+            return encodeFileBase64(file, mode, wrap, dataUri, output);
+          }
+        })
+        .catch((e) => console.error("save base64 file error!", e));
+      return;
+    }
+    const value = source();
+    if (value) {
+      const extension = result()?.kind?.extension ?? "bin";
+      save({ defaultPath: `decoded.${extension}` })
+        .then((output) => output && decodeFileBase64(value, store.mode, output))
+        .catch((e) => console.error("save decoded file error!", e));
+    }
+  };
+
+  const size = (value: number) => {
+    if (value < 1024) {
+      return `${value} B`;
+    }
+    if (value < 1024 * 1024) {
+      return `${(value / 1024).toFixed(1)} KB`;
+    }
+    return `${(value / 1024 / 1024).toFixed(1)} MB`;
+  };
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        <Config.Option
+          label="操作"
+          description="选择操作的类型"
+          icon={() => <ArrowLeftRight size={16} />}
+        >
+          <Config.Switch
+            value={store.encode}
+            onChange={setEncode}
+            on="编码"
+            off="解码"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="模式"
+          description="选择Base64的模式"
+          icon={() => <Layers size={16} />}
+        >
+          <Config.Select
+            class="w-35"
+            value={store.mode}
+            options={BASE64_ENCODINGS}
+            onChange={(value) => setStore("mode", value)}
+          />
+        </Config.Option>
+
+        <Show when={store.encode}>
+          <Config.Option
+            label="换行"
+            description="按 MIME 的要求每 76 个字符换行"
+            icon={() => <WrapText size={16} />}
+          >
+            <Config.Switch
+              value={store.wrap}
+              onChange={(value) => setStore("wrap", value)}
+            />
+          </Config.Option>
+
+          <Config.Option
+            label="Data URI"
+            description="根据文件类型加上 data: 前缀"
+            icon={() => <Link size={16} />}
+          >
+            <Config.Switch
+              value={store.dataUri}
+              onChange={(value) => setStore("dataUri", value)}
+            />
+          </Config.Option>
+        </Show>
+      </Config.Card>
+
+      {/* 文件 */}
+      <Card
+        title="文件"
+        loading={store.encode ? encoded.loading : decoded.loading}
+        operation={
+          <Flex>
+            <PickFileButton
+              label={store.encode ? "选择文件" : "导入 Base64 文件"}
+              onPick={(file) => setStore({ file: file ?? "", base64: "" })}
+            />
+            <button class="btn btn-sm" disabled={!result()} onClick={saveAs}>
+              另存为
+            </button>
+          </Flex>
+        }
+      >
+        <Show
+          when={result()}
+          fallback={<span class="text-error text-sm">{failure()}</span>}
+        >
+          {(value) => (
+            <div class="flex flex-wrap items-center gap-2 text-sm">
+              <Show when={store.file}>
+                <span class="font-mono">{store.file}</span>
+              </Show>
+              <span class="badge badge-sm badge-primary">
+                {value().kind?.description ?? "未知类型"}
+              </span>
+              <Show when={value().kind}>
+                {(kind) => (
+                  <span class="opacity-60">
+                    .{kind().extension} {kind().mime}
+                  </span>
+                )}
+              </Show>
+              <Show when={!store.encode && declared()}>
+                <span class="opacity-60">（声明为 {declared()}）</span>
+              </Show>
+              <span class="opacity-60">{size(value().size)}</span>
+            </div>
+          )}
+        </Show>
+      </Card>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-1"
+          title="Base64"
+          operation={
+            <Show
+              when={store.encode}
+              fallback={
+                <Flex>
+                  <PasteButton
+                    onRead={(value) => setStore({ file: "", base64: value })}
+                  />
+                  <ClearButton
+                    onClick={() => setStore({ file: "", base64: "" })}
+                  />
+                </Flex>
+              }
+            >
+              <CopyButton value={text()} />
+            </Show>
+          }
+        >
+          <Show
+            when={store.encode}
+            fallback={
+              <Editor
+                value={store.base64}
+                onChange={(value) => setStore({ file: "", base64: value })}
+                placeholder="输入要解码的 Base64 或 data URI"
+              />
+            }
+          >
+            <Editor value={text()} readOnly={true} />
+          </Show>
+        </Card>
+      </Main>
+    </Container>
+  );
+}
@@ -1,8 +1,9 @@
+import { decodeImageBase64, encodeImageBase64 } from "@/command/codec/base64";
 import {
-  Base64Mode,
-  decodeImageBase64,
-  encodeImageBase64,
-} from "@/command/codec/base64";
+  BASE64_ENCODINGS,
+  Encoding,
+  migrateBase64Mode,
+} from "@/command/crypto/type";
 import {
   Base64ImageButtons,
   PickImageFileButton,
@@ -26,10 +27,13 @@ export default function Base64ImageCodec() {
   const [store, setStore] = createCachableStore({
     file: "",
     base64: "",
-    mode: Base64Mode.Standard,
+    mode: Encoding.Base64,
     encode: true,
   });
 
+  // 迁移旧版本缓存的模式
+  setStore("mode", (mode) => migrateBase64Mode(mode));
+
   // 是否解码
   const decode = () => !store.encode;
 
@@ -93,7 +97,7 @@ export default function Base64ImageCodec() {
           <Config.Select
             class="w-35"
             value={store.mode}
-            options={Object.keys(Base64Mode)}
+            options={BASE64_ENCODINGS}
             onChange={(value) => setStore("mode", value)}
           />
         </Config.Option>
@@ -1,10 +1,13 @@
+import { decodeTextBase64, encodeTextBase64 } from "@/command/codec/base64";
 import {
-  Base64Mode,
-  decodeTextBase64,
-  encodeTextBase64,
-} from "@/command/codec/base64";
+  BASE64_ENCODINGS,
+  Encoding,
+  migrateBase64Mode,
+} from "@/command/crypto/type";
+import { Charset } from "@/command/text/charset";
 import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
 import Card from "@/component/Card";
+import { CHARSET_OPTIONS } from "@/component/Charset";
 import Config from "@/component/Config";
 import ConfigSwitch from "@/component/Config/Switch";
 import Container from "@/component/Container";
@@ -12,17 +15,21 @@ import Editor from "@/component/Editor";
 import Main from "@/component/Main";
 import { createCachableStore } from "@/lib/cache";
 import { stringify } from "@/lib/util";
-import { ArrowLeftRight, Layers } from "lucide-solid";
+import { ArrowLeftRight, Languages, Layers } from "lucide-solid";
 import { createResource } from "solid-js";
 
 export default function Base64TextCodec() {
   // 页面参数
   const [store, setStore] = createCachableStore({
     input: "",
-    mode: Base64Mode.Standard,
+    mode: Encoding.Base64,
+    charset: Charset.Utf8,
     encode: true,
   });
 
+  // 迁移旧版本缓存的模式
+  setStore("mode", (mode) => migrateBase64Mode(mode));
+
   // 切换操作模式
   const setEncode = (value: boolean) => {
     setStore((prev) => ({ ...prev, input: "", encode: value }));
@@ -31,10 +38,12 @@ export default function Base64TextCodec() {
   // 获取输出
   const [output] = createResource(
     () => ({ ...store }),
-    ({ input, mode, encode }) => {
+    ({ input, mode, charset, encode }) => {
       if (input) {
         return (
-          encode ? encodeTextBase64(input, mode) : decodeTextBase64(input, mode)
+          encode
+            ? encodeTextBase64(input, mode, charset)
+            : decodeTextBase64(input, mode, charset)
         ).catch(stringify);
       }
     },
@@ -67,11 +76,25 @@ export default function Base64TextCodec() {
         >
           <Config.Select
             value={store.mode}
-            options={Object.keys(Base64Mode)}
+            options={BASE64_ENCODINGS}
             onChange={(value) => setStore("mode", value)}
             class="w-35"
           />
         </Config.Option>
+
+        {/*字符集配置*/}
+        <Config.Option
+          label="字符集"
+          description="文本按该字符集与字节相互转换"
+          icon={() => <Languages size={16} />}
+        >
+          <Config.Select
+            value={store.charset}
+            options={CHARSET_OPTIONS}
+            onChange={(value) => setStore("charset", value)}
+            class="w-35"
+          />
+        </Config.Option>
       </Config.Card>
 
       <Main>
@@ -0,0 +1,413 @@
+import { Encoding } from "@/command/crypto/type";
+import {
+  dumpHex,
+  Endian,
+  identifyFile,
+  inspectHex,
+  Pattern,
+  searchHex,
+  Values,
+} from "@/command/codec/hexdump";
+import { Charset, Source } from "@/command/text/charset";
+import {
+  ClearButton,
+  CopyButton,
+  PasteButton,
+  PickFileButton,
+} from "@/component/Buttons";
+import Card from "@/component/Card";
+import { CharsetSelect } from "@/component/Charset";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import { EncodingSelect } from "@/component/Encoding";
+import Flex from "@/component/Flex";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { ArrowDownUp, Columns3, Group, Rows3 } from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
+
+const GROUPS = [
+  { label: "不分组", value: "0" },
+  { label: "1 字节", value: "1" },
+  { label: "2 字节", value: "2" },
+  { label: "4 字节", value: "4" },
+  { label: "8 字节", value: "8" },
+];
+
+const ENDIANS = [
+  { label: "小端", value: Endian.Little },
+  { label: "大端", value: Endian.Big },
+];
+
+const VALUES: { label: string; key: keyof Values }[] = [
+  { label: "Binary", key: "binary" },
+  { label: "Int8", key: "int8" },
+  { label: "UInt8", key: "uint8" },
+  { label: "Int16", key: "int16" },
+  { label: "UInt16", key: "uint16" },
+  { label: "Int32", key: "int32" },
+  { label: "UInt32", key: "uint32" },
+  { label: "Int64", key: "int64" },
+  { label: "UInt64", key: "uint64" },
+  { label: "Float32", key: "float32" },
+  { label: "Float64", key: "float64" },
+  { label: "LEB128", key: "leb128" },
+];
+
+export default function HexDump() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    file: "",
+    input: "",
+    encoding: Encoding.Utf8,
+    offset: 0,
+    lines: 64,
+    width: 16,
+    group: "2",
+    endian: Endian.Little,
+    cursor: 0,
+    keyword: "",
+    text: false,
+    charset: Charset.Utf8,
+  });
+
+  // 优先使用选择的文件
+  const source = (): Source | undefined => {
+    if (store.file) {
+      return { File: store.file };
+    }
+    if (store.input) {
+      return { Bytes: { text: store.input, encoding: store.encoding } };
+    }
+  };
+
+  const width = () => Math.min(Math.max(store.width || 16, 1), 64);
+  const page = () => width() * Math.max(store.lines || 64, 1);
+
+  // 当前页
+  const [dump] = createResource(
+    () => {
+      const value = source();
+      const { offset, group } = store;
+      return value && { source: value, offset, group, page: page() };
+    },
+    ({ source, offset, page, group }) =>
+      dumpHex(source, offset, page, width(), Number(group)).catch(stringify),
+  );
+
+  // 文件类型
+  const [type] = createResource(source, (source) =>
+    identifyFile(source).catch(stringify),
+  );
+
+  // 光标处的数值
+  const [values] = createResource(
+    () => {
+      const value = source();
+      const { cursor, endian } = store;
+      return value && { source: value, cursor, endian };
+    },
+    ({ source, cursor, endian }) =>
+      inspectHex(source, cursor, endian).catch(stringify),
+  );
+
+  // 搜索
+  const [matches] = createResource(
+    () => {
+      const value = source();
+      const { keyword, text, charset } = store;
+      return value && keyword && { source: value, keyword, text, charset };
+    },
+    ({ source, keyword, text, charset }) => {
+      const pattern: Pattern = text
+        ? { Text: { text: keyword, charset } }
+        : { Hex: keyword };
+      return searchHex(source, pattern).catch(stringify);
+    },
+  );
+
+  const dumped = () => {
+    const value = dump();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const identified = () => {
+    const value = type();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const inspected = () => {
+    const value = values();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const found = () => {
+    const value = matches();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const failure = (value: unknown) =>
+    typeof value === "string" ? value : undefined;
+
+  const size = () => dumped()?.size ?? 0;
+
+  // 跳转到偏移所在的页，并把光标移到该处
+  const jump = (offset: number) => {
+    setStore({
+      offset: Math.floor(offset / page()) * page(),
+      cursor: offset,
+    });
+  };
+
+  const previous = () => {
+    setStore("offset", Math.max(store.offset - page(), 0));
+  };
+
+  const next = () => {
+    if (store.offset + page() < size()) {
+      setStore("offset", store.offset + page());
+    }
+  };
+
+  const hex = (value: number) => `0x${value.toString(16).padStart(8, "0")}`;
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        <Config.Option
+          label="每行字节数"
+          description="每行显示的字节数，最多 64"
+          icon={() => <Columns3 size={16} />}
+        >
+          <Config.NumberInput
+            value={store.width}
+            min={1}
+            max={64}
+            onInput={(value) => setStore({ width: value, offset: 0 })}
+            class="w-30"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="每页行数"
+          description="大文件按页读取，不会一次性载入"
+          icon={() => <Rows3 size={16} />}
+        >
+          <Config.NumberInput
+            value={store.lines}
+            min={1}
+            onInput={(value) => setStore({ lines: value, offset: 0 })}
+            class="w-30"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="分组"
+          description="十六进制列中每组的字节数"
+          icon={() => <Group size={16} />}
+        >
+          <Config.Select
+            value={store.group}
+            options={GROUPS}
+            onChange={(value) => setStore("group", value)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="字节序"
+          description="解读数值时使用的字节序"
+          icon={() => <ArrowDownUp size={16} />}
+        >
+          <Config.Select
+            value={store.endian}
+            options={ENDIANS}
+            onChange={(value) => setStore("endian", value)}
+            class="w-30"
+          />
+        </Config.Option>
+      </Config.Card>
+
+      {/* 输入 */}
+      <Card
+        title="输入"
+        loading={type.loading}
+        operation={
+          <Flex>
+            <EncodingSelect
+              label="编码"
+              value={store.encoding}
+              onChange={(value) => setStore("encoding", value)}
+            />
+            <PickFileButton
+              onPick={(file) =>
+                setStore({ file: file ?? "", offset: 0, cursor: 0 })
+              }
+            />
+            <PasteButton
+              onRead={(value) =>
+                setStore({ file: "", input: value, offset: 0, cursor: 0 })
+              }
+            />
+            <ClearButton
+              onClick={() =>
+                setStore({ file: "", input: "", offset: 0, cursor: 0 })
+              }
+            />
+          </Flex>
+        }
+      >
+        <Show
+          when={!store.file}
+          fallback={<span class="font-mono text-sm">{store.file}</span>}
+        >
+          <input
+            class="input input-md w-full rounded-md font-mono outline-none"
+            placeholder="输入文本或编码后的字节，或者导入文件"
+            value={store.input}
+            onInput={(e) =>
+              setStore({ input: e.target.value, offset: 0, cursor: 0 })
+            }
+          />
+        </Show>
+        <Show
+          when={identified()}
+          fallback={<span class="text-error text-sm">{failure(type())}</span>}
+        >
+          {(value) => (
+            <span class="text-sm">
+              {value().description}（.{value().extension}，{value().mime}）
+            </span>
+          )}
+        </Show>
+      </Card>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-[2]"
+          title="十六进制"
+          loading={dump.loading}
+          notification={
+            <Show when={dumped()}>
+              {(value) => (
+                <span class="text-sm opacity-60">
+                  {hex(value().offset)} / {hex(value().size)}
+                </span>
+              )}
+            </Show>
+          }
+          operation={
+            <Flex>
+              <button class="btn btn-sm" onClick={previous}>
+                上一页
+              </button>
+              <button class="btn btn-sm" onClick={next}>
+                下一页
+              </button>
+              <CopyButton value={dumped()?.text ?? ""} />
+            </Flex>
+          }
+        >
+          <Editor value={dumped()?.text ?? failure(dump())} readOnly={true} />
+        </Card>
+
+        <div class="flex h-full w-0 flex-1 flex-col gap-4">
+          <Card
+            title="数值"
+            loading={values.loading}
+            operation={
+              <Config.NumberInput
+                value={store.cursor}
+                min={0}
+                onInput={(value) => value >= 0 && jump(value)}
+                class="w-30"
+              />
+            }
+          >
+            <Show
+              when={inspected()}
+              fallback={
+                <span class="text-error text-sm">{failure(values())}</span>
+              }
+            >
+              {(value) => (
+                <table class="table-sm table font-mono">
+                  <tbody>
+                    <For each={VALUES}>
+                      {(item) => (
+                        <tr>
+                          <td class="opacity-60">{item.label}</td>
+                          <td class="select-text">
+                            {value()[item.key] ?? "-"}
+                          </td>
+                        </tr>
+                      )}
+                    </For>
+                  </tbody>
+                </table>
+              )}
+            </Show>
+          </Card>
+
+          <Card
+            class="min-h-0 flex-1"
+            title="搜索"
+            loading={matches.loading}
+            operation={
+              <Flex>
+                <Config.Switch
+                  value={store.text}
+                  onChange={(value) => setStore("text", value)}
+                />
+                <span class="text-sm">文本</span>
+                <Show when={store.text}>
+                  <CharsetSelect
+                    value={store.charset}
+                    onChange={(value) => setStore("charset", value)}
+                  />
+                </Show>
+              </Flex>
+            }
+          >
+            <input
+              class="input input-sm w-full rounded-md font-mono outline-none"
+              placeholder={
+                store.text ? "要搜索的文本" : "十六进制字节，?? 匹配任意字节"
+              }
+              value={store.keyword}
+              onChange={(e) => setStore("keyword", e.target.value)}
+            />
+            <Show
+              when={found()}
+              fallback={
+                <span class="text-error text-sm">{failure(matches())}</span>
+              }
+            >
+              {(value) => (
+                <div class="flex flex-col gap-1 overflow-auto text-sm">
+                  <span class="opacity-60">
+                    找到 {value().offsets.length}
+                    {value().truncated ? "+" : ""} 处
+                  </span>
+                  <For each={value().offsets}>
+                    {(offset) => (
+                      <button
+                        class="btn btn-ghost btn-xs justify-start font-mono"
+                        onClick={() => jump(offset)}
+                      >
+                        {hex(offset)}
+                      </button>
+                    )}
+                  </For>
+                </div>
+              )}
+            </Show>
+          </Card>
+        </div>
+      </Main>
+    </Container>
+  );
+}
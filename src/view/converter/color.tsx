@@ -0,0 +1,310 @@
+import {
+  Color,
+  contrastColor,
+  convertColor,
+  extractColors,
+  generatePalette,
+  Palette,
+} from "@/command/converter/color";
+import { CopyButton, PickImageFileButton } from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Flex from "@/component/Flex";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { Hash, Palette as PaletteIcon, SwatchBook } from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
+
+const PALETTES = [
+  { label: "浅色", value: Palette.Tints },
+  { label: "深色", value: Palette.Shades },
+  { label: "灰度", value: Palette.Tones },
+  { label: "互补色", value: Palette.Complementary },
+  { label: "分裂互补色", value: Palette.SplitComplementary },
+  { label: "邻近色", value: Palette.Analogous },
+  { label: "三角色", value: Palette.Triadic },
+  { label: "四角色", value: Palette.Tetradic },
+];
+
+// 需要展示的写法
+const FORMATS: { label: string; key: keyof Color }[] = [
+  { label: "HEX", key: "hex" },
+  { label: "RGB", key: "rgb" },
+  { label: "HSL", key: "hsl" },
+  { label: "HSV", key: "hsv" },
+  { label: "HWB", key: "hwb" },
+  { label: "CMYK", key: "cmyk" },
+  { label: "Lab", key: "lab" },
+  { label: "OKLCH", key: "oklch" },
+];
+
+// 色块，点击后设为当前颜色
+const Swatches = (props: {
+  colors: Color[];
+  ratios?: number[];
+  onPick: (color: Color) => void;
+}) => {
+  return (
+    <div class="flex flex-wrap gap-2">
+      <For each={props.colors}>
+        {(color, index) => (
+          <button
+            class="flex w-20 cursor-pointer flex-col items-center gap-1"
+            title={color.rgb}
+            onClick={() => props.onPick(color)}
+          >
+            <span
+              class="border-base-content/20 h-12 w-full rounded-md border"
+              style={{ "background-color": color.hex }}
+            />
+            <span class="font-mono text-xs">{color.hex}</span>
+            <Show when={props.ratios}>
+              {(ratios) => (
+                <span class="text-xs opacity-60">
+                  {(ratios()[index()] * 100).toFixed(1)}%
+                </span>
+              )}
+            </Show>
+          </button>
+        )}
+      </For>
+    </div>
+  );
+};
+
+export default function ColorConverter() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    color: "#3366cc",
+    background: "#ffffff",
+    palette: Palette.Tints,
+    count: 5,
+    image: "",
+    colors: 6,
+  });
+
+  // 各种写法
+  const [converted] = createResource(
+    () => store.color,
+    (color) => convertColor(color).catch(stringify),
+  );
+
+  // 对比度
+  const [contrast] = createResource(
+    () => ({ color: store.color, background: store.background }),
+    ({ color, background }) =>
+      contrastColor(color, background).catch(stringify),
+  );
+
+  // 色板
+  const [palette] = createResource(
+    () => ({ color: store.color, palette: store.palette, count: store.count }),
+    ({ color, palette, count }) =>
+      generatePalette(color, palette, count).catch(stringify),
+  );
+
+  // 图片的主色调
+  const [swatches] = createResource(
+    () => store.image && { image: store.image, count: store.colors },
+    ({ image, count }) => extractColors(image, count).catch(stringify),
+  );
+
+  const result = <T,>(value: T | string | undefined) =>
+    typeof value === "string" ? undefined : value;
+
+  const failure = (value: unknown) =>
+    typeof value === "string" ? value : undefined;
+
+  // 取色器只支持不透明的 #rrggbb
+  const picker = () => result(converted())?.hex.slice(0, 7) ?? "#000000";
+
+  const pick = (color: Color) => setStore("color", color.hex);
+
+  const level = (passed: boolean, label: string) => (
+    <span
+      class="badge badge-sm"
+      classList={{ "badge-success": passed, "badge-error": !passed }}
+    >
+      {label}
+    </span>
+  );
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        <Config.Option
+          label="色板"
+          description="浅色、深色、灰度为混合，其他按色相旋转"
+          icon={() => <PaletteIcon size={16} />}
+        >
+          <Config.Select
+            value={store.palette}
+            options={PALETTES}
+            onChange={(value) => setStore("palette", value)}
+            class="w-35"
+          />
+        </Config.Option>
+
+        <Show
+          when={[Palette.Tints, Palette.Shades, Palette.Tones].includes(
+            store.palette,
+          )}
+        >
+          <Config.Option
+            label="步数"
+            description="混合的步数，1 - 32"
+            icon={() => <Hash size={16} />}
+          >
+            <Config.NumberInput
+              value={store.count}
+              min={1}
+              max={32}
+              onInput={(value) => setStore("count", value)}
+              class="w-30"
+            />
+          </Config.Option>
+        </Show>
+
+        <Config.Option
+          label="主色调数量"
+          description="从图片中提取的颜色数量，1 - 32"
+          icon={() => <SwatchBook size={16} />}
+        >
+          <Config.NumberInput
+            value={store.colors}
+            min={1}
+            max={32}
+            onInput={(value) => setStore("colors", value)}
+            class="w-30"
+          />
+        </Config.Option>
+      </Config.Card>
+
+      <Card title="颜色">
+        <Flex>
+          <input
+            type="color"
+            class="h-10 w-14 cursor-pointer"
+            value={picker()}
+            onInput={(e) => setStore("color", e.target.value)}
+          />
+          <input
+            class="input input-md w-full font-mono text-lg font-bold outline-none"
+            placeholder="#3366cc、rgb(51 102 204)、oklch(...) 或命名颜色"
+            value={store.color}
+            onInput={(e) => setStore("color", e.target.value)}
+          />
+        </Flex>
+        <Show
+          when={result(converted())}
+          fallback={
+            <span class="text-error text-sm">{failure(converted())}</span>
+          }
+        >
+          {(value) => (
+            <table class="table-sm table font-mono">
+              <tbody>
+                <For each={FORMATS}>
+                  {(format) => (
+                    <tr>
+                      <td class="w-20 opacity-60">{format.label}</td>
+                      <td class="select-text">{value()[format.key]}</td>
+                      <td class="w-0">
+                        <CopyButton value={String(value()[format.key])} />
+                      </td>
+                    </tr>
+                  )}
+                </For>
+                <Show when={value().name}>
+                  {(name) => (
+                    <tr>
+                      <td class="w-20 opacity-60">名称</td>
+                      <td class="select-text">{name()}</td>
+                      <td class="w-0">
+                        <CopyButton value={name()} />
+                      </td>
+                    </tr>
+                  )}
+                </Show>
+              </tbody>
+            </table>
+          )}
+        </Show>
+      </Card>
+
+      <Card title="对比度">
+        <input
+          class="input input-md w-full font-mono outline-none"
+          placeholder="背景色"
+          value={store.background}
+          onInput={(e) => setStore("background", e.target.value)}
+        />
+        <Show
+          when={result(contrast())}
+          fallback={
+            <span class="text-error text-sm">{failure(contrast())}</span>
+          }
+        >
+          {(value) => (
+            <div class="flex flex-wrap items-center gap-2">
+              <span
+                class="border-base-content/20 rounded-md border px-3 py-1 font-bold"
+                style={{
+                  color: store.color,
+                  "background-color": store.background,
+                }}
+              >
+                示例文字 Aa
+              </span>
+              <span class="font-mono text-lg font-bold">
+                {value().ratio.toFixed(2)}:1
+              </span>
+              {level(value().aa, "AA")}
+              {level(value().aa_large, "AA 大号")}
+              {level(value().aaa, "AAA")}
+              {level(value().aaa_large, "AAA 大号")}
+            </div>
+          )}
+        </Show>
+      </Card>
+
+      <Card title="色板">
+        <Show
+          when={result(palette())}
+          fallback={
+            <span class="text-error text-sm">{failure(palette())}</span>
+          }
+        >
+          {(value) => <Swatches colors={value()} onPick={pick} />}
+        </Show>
+      </Card>
+
+      <Card
+        title="图片主色调"
+        loading={swatches.loading}
+        operation={
+          <PickImageFileButton
+            onPick={(file) => file && setStore("image", file)}
+          />
+        }
+      >
+        <Show
+          when={result(swatches())}
+          fallback={
+            <span class="text-error text-sm">{failure(swatches())}</span>
+          }
+        >
+          {(value) => (
+            <Swatches
+              colors={value().map((swatch) => swatch.color)}
+              ratios={value().map((swatch) => swatch.ratio)}
+              onPick={pick}
+            />
+          )}
+        </Show>
+      </Card>
+    </Container>
+  );
+}
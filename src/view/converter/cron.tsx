@@ -1,5 +1,10 @@
-import { parseCron } from "@/command/converter/cron";
-import { ClearButton, PasteButton, TextReadButtons } from "@/component/Buttons";
+import { convertCron, Dialect, parseCron } from "@/command/converter/cron";
+import {
+  ClearButton,
+  CopyButton,
+  PasteButton,
+  TextReadButtons,
+} from "@/component/Buttons";
 import Card from "@/component/Card";
 import Config from "@/component/Config";
 import Container from "@/component/Container";
@@ -7,8 +12,22 @@ import Editor from "@/component/Editor";
 import Flex from "@/component/Flex";
 import { createCachableStore } from "@/lib/cache";
 import { stringify } from "@/lib/util";
-import { ALargeSmall, RefreshCcw, Sigma } from "lucide-solid";
-import { createResource } from "solid-js";
+import {
+  ALargeSmall,
+  Clock,
+  Globe,
+  Languages,
+  RefreshCcw,
+  Sigma,
+} from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
+
+const DIALECTS = [
+  { label: "自动识别", value: "" },
+  { label: "Unix", value: Dialect.Unix },
+  { label: "Quartz", value: Dialect.Quartz },
+  { label: "Spring", value: Dialect.Spring },
+];
 
 export default function CronConverter() {
   // 页面参数
@@ -16,21 +35,94 @@ export default function CronConverter() {
     cron: "* * * * * *",
     size: 10,
     pattern: "%Y-%m-%d %H:%M:%S",
+    dialect: "" as Dialect | "",
+    timezone: "",
+    start: "",
+    target: Dialect.Quartz,
   });
 
   // 输出结果
-  const [output, { refetch }] = createResource(
+  const [plan, { refetch }] = createResource(
     () => ({ ...store }),
-    ({ cron, size, pattern }) =>
-      parseCron(cron, size, pattern)
-        .then((times) => times.join("\n"))
-        .catch(stringify),
+    ({ cron, size, pattern, dialect, timezone, start }) =>
+      parseCron(
+        cron,
+        size,
+        pattern,
+        dialect || null,
+        timezone.trim() || null,
+        start.trim() || null,
+      ).catch(stringify),
+  );
+
+  // 转换为其他方言
+  const [conversion] = createResource(
+    () => ({ cron: store.cron, dialect: store.dialect, target: store.target }),
+    ({ cron, dialect, target }) =>
+      convertCron(cron, target, dialect || null).catch(stringify),
   );
 
+  const converted = () => {
+    const value = conversion();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const failure = () => {
+    const value = conversion();
+    return typeof value === "string" ? value : undefined;
+  };
+
+  const output = () => {
+    const value = plan();
+    return typeof value === "string" ? value : value?.times.join("\n");
+  };
+
+  const description = () => {
+    const value = plan();
+    return typeof value === "string" ? undefined : value;
+  };
+
   return (
     <Container>
       {/* 配置 */}
       <Config.Card>
+        <Config.Option
+          label="表达式类型"
+          description="Unix 为 5 位，Spring 为 6 位，Quartz 为 6 或 7 位"
+          icon={() => <Languages size={16} />}
+        >
+          <Config.Select
+            value={store.dialect}
+            options={DIALECTS}
+            onChange={(value) => setStore("dialect", value)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="时区"
+          description="IANA 时区名称，例如 Asia/Shanghai，为空时使用本地时区"
+          icon={() => <Globe size={16} />}
+        >
+          <Config.Input
+            value={store.timezone}
+            onInput={(value) => setStore("timezone", value)}
+            class="w-50"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="开始时间"
+          description="从该时间开始计算（包含该时间），为空时从当前时间开始"
+          icon={() => <Clock size={16} />}
+        >
+          <Config.Input
+            value={store.start}
+            onInput={(value) => setStore("start", value)}
+            class="w-50"
+          />
+        </Config.Option>
+
         <Config.Option
           label="输出格式"
           description="计划执行时间的格式"
@@ -74,13 +166,63 @@ export default function CronConverter() {
           value={store.cron}
           onInput={(e) => setStore("cron", e.target.value)}
         />
+        <Show when={description()}>
+          {(value) => (
+            <div class="mt-2 flex flex-col gap-1 text-sm">
+              <span class="badge badge-sm badge-outline">
+                {value().dialect}
+              </span>
+              <span>{value().description.chinese}</span>
+              <span class="opacity-60">{value().description.english}</span>
+            </div>
+          )}
+        </Show>
+      </Card>
+
+      {/*方言转换*/}
+      <Card
+        title="转换为其他写法"
+        loading={conversion.loading}
+        operation={
+          <Flex>
+            <Config.Select
+              value={store.target}
+              options={DIALECTS.filter(({ value }) => value)}
+              onChange={(value) => setStore("target", value as Dialect)}
+              class="w-30"
+            />
+            <CopyButton value={converted()?.expression ?? ""} />
+          </Flex>
+        }
+      >
+        <Show
+          when={converted()}
+          fallback={<span class="text-error text-sm">{failure()}</span>}
+        >
+          {(value) => (
+            <Show
+              when={value().expression}
+              fallback={
+                <ul class="text-warning list-inside list-disc text-sm">
+                  <For each={value().unsupported}>
+                    {(reason) => <li>{reason.chinese}</li>}
+                  </For>
+                </ul>
+              }
+            >
+              {(expression) => (
+                <span class="font-mono font-bold">{expression()}</span>
+              )}
+            </Show>
+          )}
+        </Show>
       </Card>
 
       {/*计划时间*/}
       <Card
         class="h-0 flex-1"
         title="计划执行时间"
-        loading={output.loading}
+        loading={plan.loading}
         operation={
           <TextReadButtons value={output()} position="before">
             <button class="btn btn-sm" onClick={() => refetch()}>
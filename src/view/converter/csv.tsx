@@ -0,0 +1,388 @@
+import {
+  convertCsv,
+  convertToCsv,
+  Format,
+  queryCsv,
+  Source,
+} from "@/command/converter/csv";
+import { Charset } from "@/command/text/charset";
+import {
+  ClearButton,
+  PasteButton,
+  PickFileButton,
+  TextReadButtons,
+} from "@/component/Buttons";
+import Card from "@/component/Card";
+import { CHARSET_OPTIONS } from "@/component/Charset";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import Flex from "@/component/Flex";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { save } from "@tauri-apps/plugin-dialog";
+import {
+  ArrowLeftRight,
+  Columns3,
+  FileCode,
+  Heading,
+  Languages,
+} from "lucide-solid";
+import { createResource, For, Match, Show, Switch } from "solid-js";
+
+enum Mode {
+  Convert = "Convert",
+  Query = "Query",
+  Import = "Import",
+}
+
+const MODE_OPTIONS = [
+  { label: "CSV -> 其他格式", value: Mode.Convert },
+  { label: "SQL 查询", value: Mode.Query },
+  { label: "其他格式 -> CSV", value: Mode.Import },
+];
+
+const FORMAT_OPTIONS = [
+  { label: "JSON", value: Format.Json },
+  { label: "Markdown", value: Format.Markdown },
+  { label: "INSERT 语句", value: Format.Sql },
+];
+
+// 编辑器只在创建时设置语言
+const FORMAT_LANGUAGES: Record<Format, string> = {
+  [Format.Json]: "json",
+  [Format.Markdown]: "markdown",
+  [Format.Sql]: "sql",
+};
+
+const DELIMITER_OPTIONS = [
+  { label: "自动识别", value: "" },
+  { label: "逗号", value: "," },
+  { label: "制表符", value: "\t" },
+  { label: "分号", value: ";" },
+  { label: "竖线", value: "|" },
+];
+
+const SOURCE_CHARSETS = [{ label: "自动识别", value: "" }, ...CHARSET_OPTIONS];
+
+export default function CsvConverter() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    mode: Mode.Convert,
+    format: Format.Json,
+    header: true,
+    charset: "" as Charset | "",
+    delimiter: "",
+    table: "",
+    query: "",
+    file: "",
+    input: "",
+  });
+
+  // 优先使用选择的文件
+  const source = (): Source | undefined => {
+    if (store.file) {
+      return { File: store.file };
+    }
+    if (store.input) {
+      return { Text: store.input };
+    }
+  };
+
+  // 切换操作模式
+  const setMode = (mode: Mode) => {
+    setStore({ mode, file: "", input: "" });
+  };
+
+  // 转换为其他格式，指定 output 时写入文件
+  const convert = (source: Source, output: string | null = null) =>
+    convertCsv(
+      source,
+      store.format,
+      store.header,
+      store.table || null,
+      output,
+      store.charset || null,
+      store.delimiter || null,
+    );
+
+  // 转换结果
+  const [output] = createResource(
+    () => {
+      const value = source();
+      return value && store.mode !== Mode.Query && { ...store, source: value };
+    },
+    ({ mode, format, delimiter, input, source }) => {
+      if (mode === Mode.Import) {
+        return convertToCsv(input, format, delimiter || null).catch(stringify);
+      }
+      return convert(source).catch(stringify);
+    },
+  );
+
+  // 查询结果
+  const [result] = createResource(
+    () => {
+      const value = source();
+      return (
+        value &&
+        store.mode === Mode.Query &&
+        store.query.trim() && { ...store, source: value }
+      );
+    },
+    ({ query, header, charset, delimiter, source }) =>
+      queryCsv(
+        source,
+        query,
+        header,
+        charset || null,
+        delimiter || null,
+      ).catch(stringify),
+  );
+
+  const table = () => {
+    const value = result();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const failure = () => {
+    const value = result();
+    return typeof value === "string" ? value : undefined;
+  };
+
+  // 转换后另存为文件
+  const saveAs = () => {
+    const value = source();
+    if (value) {
+      save()
+        .then((file) => file && convert(value, file))
+        .catch((e) => console.error("convert csv error!", e));
+    }
+  };
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        {/*操作配置*/}
+        <Config.Option
+          label="操作"
+          description="选择操作的类型"
+          icon={() => <ArrowLeftRight size={16} />}
+        >
+          <Config.Select
+            value={store.mode}
+            options={MODE_OPTIONS}
+            onChange={setMode}
+            class="w-40"
+          />
+        </Config.Option>
+
+        {/*格式配置*/}
+        <Show when={store.mode !== Mode.Query}>
+          <Config.Option
+            label="格式"
+            description="输出 INSERT 语句时可以指定表名，默认为 data"
+            icon={() => <FileCode size={16} />}
+          >
+            <Flex>
+              <Show
+                when={
+                  store.mode === Mode.Convert && store.format === Format.Sql
+                }
+              >
+                <Config.Input
+                  value={store.table}
+                  onInput={(value) => setStore("table", value)}
+                  class="w-30"
+                />
+              </Show>
+              <Config.Select
+                value={store.format}
+                options={FORMAT_OPTIONS}
+                onChange={(value) => setStore("format", value)}
+                class="w-30"
+              />
+            </Flex>
+          </Config.Option>
+        </Show>
+
+        <Show when={store.mode !== Mode.Import}>
+          {/*表头配置*/}
+          <Config.Option
+            label="表头"
+            description="第一行是否为表头，没有表头时列名为 c1、c2..."
+            icon={() => <Heading size={16} />}
+          >
+            <Config.Switch
+              value={store.header}
+              onChange={(value) => setStore("header", value)}
+            />
+          </Config.Option>
+
+          {/*字符集配置*/}
+          <Config.Option
+            label="字符集"
+            description="导入文件的字符集，为空时自动识别"
+            icon={() => <Languages size={16} />}
+          >
+            <Config.Select
+              value={store.charset}
+              options={SOURCE_CHARSETS}
+              onChange={(value) => setStore("charset", value)}
+              class="w-35"
+            />
+          </Config.Option>
+        </Show>
+
+        {/*分隔符配置*/}
+        <Config.Option
+          label="分隔符"
+          description="字段之间的分隔符，输出 CSV 时自动识别即为逗号"
+          icon={() => <Columns3 size={16} />}
+        >
+          <Config.Select
+            value={store.delimiter}
+            options={DELIMITER_OPTIONS}
+            onChange={(value) => setStore("delimiter", value)}
+            class="w-30"
+          />
+        </Config.Option>
+      </Config.Card>
+
+      {/* 查询 */}
+      <Show when={store.mode === Mode.Query}>
+        <Card
+          title="查询"
+          operation={
+            <Flex>
+              <PasteButton onRead={(value) => setStore("query", value)} />
+              <ClearButton onClick={() => setStore("query", "")} />
+            </Flex>
+          }
+        >
+          <input
+            class="input input-md w-full rounded-md font-mono font-bold outline-none"
+            placeholder="SELECT name, COUNT(*) FROM csv WHERE age > 18 GROUP BY name"
+            value={store.query}
+            onInput={(e) => setStore("query", e.target.value)}
+          />
+        </Card>
+      </Show>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-1"
+          title="输入"
+          operation={
+            <Flex>
+              <Show when={store.mode !== Mode.Import}>
+                <PickFileButton
+                  onPick={(file) => setStore("file", file ?? "")}
+                />
+              </Show>
+              <PasteButton
+                onRead={(value) => setStore({ file: "", input: value })}
+              />
+              <ClearButton onClick={() => setStore({ file: "", input: "" })} />
+            </Flex>
+          }
+        >
+          <Show
+            when={!store.file}
+            fallback={<span class="font-mono text-sm">{store.file}</span>}
+          >
+            <Editor
+              value={store.input}
+              onChange={(value) => setStore("input", value)}
+              placeholder={
+                store.mode === Mode.Import
+                  ? "输入 JSON 数组、Markdown 表格或 INSERT 语句"
+                  : "输入 CSV 或 TSV 数据，或者导入文件"
+              }
+            />
+          </Show>
+        </Card>
+
+        <Switch>
+          {/*转换结果*/}
+          <Match when={store.mode === Mode.Convert}>
+            <Card
+              class="h-full w-0 flex-1"
+              title="输出"
+              loading={output.loading}
+              operation={
+                <Flex>
+                  <TextReadButtons value={output()} />
+                  <button class="btn btn-sm" onClick={saveAs}>
+                    另存为
+                  </button>
+                </Flex>
+              }
+            >
+              <Show when={store.format} keyed>
+                {(format) => (
+                  <Editor
+                    value={output()}
+                    readOnly={true}
+                    language={FORMAT_LANGUAGES[format]}
+                  />
+                )}
+              </Show>
+            </Card>
+          </Match>
+
+          {/*导入结果*/}
+          <Match when={store.mode === Mode.Import}>
+            <Card
+              class="h-full w-0 flex-1"
+              title="输出"
+              loading={output.loading}
+              operation={<TextReadButtons value={output()} />}
+            >
+              <Editor value={output()} readOnly={true} />
+            </Card>
+          </Match>
+
+          {/*查询结果*/}
+          <Match when={store.mode === Mode.Query}>
+            <Card
+              class="h-full w-0 flex-1 overflow-x-hidden"
+              title={table() ? `结果 ${table()!.total} 行` : "结果"}
+              loading={result.loading}
+            >
+              <Show
+                when={table()}
+                fallback={<span class="text-error text-sm">{failure()}</span>}
+              >
+                {(table) => (
+                  <div class="size-full overflow-auto">
+                    <table class="table-pin-rows table-sm table">
+                      <thead>
+                        <tr>
+                          <For each={table().headers}>
+                            {(header) => <th>{header}</th>}
+                          </For>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        <For each={table().rows}>
+                          {(row) => (
+                            <tr class="hover:bg-base-300">
+                              <For each={row}>{(cell) => <td>{cell}</td>}</For>
+                            </tr>
+                          )}
+                        </For>
+                      </tbody>
+                    </table>
+                  </div>
+                )}
+              </Show>
+            </Card>
+          </Match>
+        </Switch>
+      </Main>
+    </Container>
+  );
+}
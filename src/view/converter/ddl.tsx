@@ -0,0 +1,119 @@
+import { convertDdl, Dialect, Target } from "@/command/converter/ddl";
+import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { Code, Database } from "lucide-solid";
+import { createResource, Show } from "solid-js";
+
+const DIALECT_OPTIONS = [
+  { label: "MySQL", value: Dialect.MySql },
+  { label: "PostgreSQL", value: Dialect.PostgreSql },
+  { label: "SQLite", value: Dialect.SQLite },
+];
+
+const TARGET_OPTIONS = [
+  { label: "Rust", value: Target.Rust },
+  { label: "Java", value: Target.Java },
+  { label: "TypeScript", value: Target.TypeScript },
+  { label: "JSON Schema", value: Target.JsonSchema },
+];
+
+// 编辑器只在创建时设置语言
+const TARGET_LANGUAGES: Record<Target, string> = {
+  [Target.Rust]: "rust",
+  [Target.Java]: "java",
+  [Target.TypeScript]: "typescript",
+  [Target.JsonSchema]: "json",
+};
+
+export default function DdlConverter() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    dialect: Dialect.MySql,
+    target: Target.Rust,
+    input: "",
+  });
+
+  // 输出结果
+  const [output] = createResource(
+    () => ({ ...store }),
+    ({ dialect, target, input }) => {
+      if (input) {
+        return convertDdl(input, dialect, target).catch(stringify);
+      }
+    },
+  );
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        {/*方言配置*/}
+        <Config.Option
+          label="数据库"
+          description="建表语句使用的 SQL 方言"
+          icon={() => <Database size={16} />}
+        >
+          <Config.Select
+            value={store.dialect}
+            options={DIALECT_OPTIONS}
+            onChange={(value) => setStore("dialect", value)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*目标配置*/}
+        <Config.Option
+          label="生成代码"
+          description="根据 CREATE TABLE 语句生成的实体代码类型"
+          icon={() => <Code size={16} />}
+        >
+          <Config.Select
+            value={store.target}
+            options={TARGET_OPTIONS}
+            onChange={(value) => setStore("target", value)}
+            class="w-30"
+          />
+        </Config.Option>
+      </Config.Card>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-1"
+          title="输入"
+          operation={
+            <TextWriteButtons callback={(value) => setStore("input", value)} />
+          }
+        >
+          <Editor
+            value={store.input}
+            onChange={(value) => setStore("input", value)}
+            language="sql"
+            placeholder="输入 CREATE TABLE 建表语句"
+          />
+        </Card>
+        <Card
+          class="h-full w-0 flex-1"
+          title="输出"
+          operation={<TextReadButtons value={output()} />}
+          loading={output.loading}
+        >
+          <Show when={store.target} keyed>
+            {(target) => (
+              <Editor
+                value={output()}
+                readOnly={true}
+                language={TARGET_LANGUAGES[target]}
+              />
+            )}
+          </Show>
+        </Card>
+      </Main>
+    </Container>
+  );
+}
@@ -0,0 +1,352 @@
+import {
+  convertImage,
+  Fit,
+  ImageFormat,
+  inspectImage,
+} from "@/command/converter/image";
+import { Base64ImageButtons, PickImageFileButton } from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Flex from "@/component/Flex";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { convertFileSrc } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Crop, Eraser, FileImage, Gauge, Image, Scaling } from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
+
+const FORMATS = [
+  { label: "PNG", value: ImageFormat.Png },
+  { label: "JPEG", value: ImageFormat.Jpeg },
+  { label: "WebP", value: ImageFormat.WebP },
+  { label: "GIF", value: ImageFormat.Gif },
+  { label: "BMP", value: ImageFormat.Bmp },
+  { label: "ICO", value: ImageFormat.Ico },
+  { label: "TIFF", value: ImageFormat.Tiff },
+];
+
+const FITS = [
+  { label: "等比缩放", value: Fit.Contain },
+  { label: "等比填充", value: Fit.Cover },
+  { label: "拉伸", value: Fit.Stretch },
+];
+
+export default function ImageConverter() {
+  // 页面参数，宽高为 0 表示不限制
+  const [store, setStore] = createCachableStore({
+    file: "",
+    format: ImageFormat.Png,
+    quality: 80,
+    strip: false,
+    width: 0,
+    height: 0,
+    fit: Fit.Contain,
+    crop: false,
+    x: 0,
+    y: 0,
+    cropWidth: 0,
+    cropHeight: 0,
+  });
+
+  // 原图信息
+  const [info] = createResource(
+    () => store.file,
+    (file) => inspectImage(file).catch(stringify),
+  );
+
+  // 转换参数，指定 output 时写入文件
+  const convert = (output: string | null = null) => {
+    const crop = store.crop
+      ? {
+          x: store.x || 0,
+          y: store.y || 0,
+          width: store.cropWidth || 0,
+          height: store.cropHeight || 0,
+        }
+      : null;
+    const resize =
+      store.width || store.height
+        ? {
+            width: store.width || null,
+            height: store.height || null,
+            fit: store.fit,
+            filter: null,
+          }
+        : null;
+    return convertImage(
+      store.file,
+      store.format,
+      crop,
+      resize,
+      store.quality || null,
+      store.strip,
+      output,
+    );
+  };
+
+  // 转换结果
+  const [converted] = createResource(
+    () => store.file && { ...store },
+    () => convert().catch(stringify),
+  );
+
+  const inspected = () => {
+    const value = info();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const result = () => {
+    const value = converted();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const failure = (value: unknown) =>
+    typeof value === "string" ? value : undefined;
+
+  // 按选择的文件名保存
+  const saveAs = () => {
+    const extension = result()?.extension;
+    if (extension) {
+      save({ filters: [{ name: "Image", extensions: [extension] }] })
+        .then((output) => output && convert(output))
+        .catch((e) => console.error("save image error!", e));
+    }
+  };
+
+  const size = (value: number) => {
+    if (value < 1024) {
+      return `${value} B`;
+    }
+    if (value < 1024 * 1024) {
+      return `${(value / 1024).toFixed(1)} KB`;
+    }
+    return `${(value / 1024 / 1024).toFixed(1)} MB`;
+  };
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        <Config.Option
+          label="目标格式"
+          description="WebP 使用无损编码"
+          icon={() => <FileImage size={16} />}
+        >
+          <Config.Select
+            value={store.format}
+            options={FORMATS}
+            onChange={(value) => setStore("format", value)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        <Show when={store.format === ImageFormat.Jpeg}>
+          <Config.Option
+            label="质量"
+            description="JPEG 压缩质量，1 - 100"
+            icon={() => <Gauge size={16} />}
+          >
+            <Config.NumberInput
+              value={store.quality}
+              min={1}
+              max={100}
+              onInput={(value) => setStore("quality", value)}
+              class="w-30"
+            />
+          </Config.Option>
+        </Show>
+
+        <Config.Option
+          label="去除元数据"
+          description="去除 EXIF 等元数据，并按原来的方向旋转图片"
+          icon={() => <Eraser size={16} />}
+        >
+          <Config.Switch
+            value={store.strip}
+            onChange={(value) => setStore("strip", value)}
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="缩放"
+          description="目标宽高，为 0 时按另一边等比计算，都为 0 时不缩放"
+          icon={() => <Scaling size={16} />}
+        >
+          <Flex>
+            <Config.NumberInput
+              value={store.width}
+              min={0}
+              placeholder="宽"
+              onInput={(value) => setStore("width", value)}
+              class="w-25"
+            />
+            <Config.NumberInput
+              value={store.height}
+              min={0}
+              placeholder="高"
+              onInput={(value) => setStore("height", value)}
+              class="w-25"
+            />
+            <Config.Select
+              value={store.fit}
+              options={FITS}
+              onChange={(value) => setStore("fit", value)}
+              class="w-30"
+            />
+          </Flex>
+        </Config.Option>
+
+        <Config.Option
+          label="裁剪"
+          description="在缩放之前按左上角坐标和宽高裁剪"
+          icon={() => <Crop size={16} />}
+        >
+          <Flex>
+            <Show when={store.crop}>
+              <Config.NumberInput
+                value={store.x}
+                min={0}
+                placeholder="X"
+                onInput={(value) => setStore("x", value)}
+                class="w-20"
+              />
+              <Config.NumberInput
+                value={store.y}
+                min={0}
+                placeholder="Y"
+                onInput={(value) => setStore("y", value)}
+                class="w-20"
+              />
+              <Config.NumberInput
+                value={store.cropWidth}
+                min={1}
+                placeholder="宽"
+                onInput={(value) => setStore("cropWidth", value)}
+                class="w-20"
+              />
+              <Config.NumberInput
+                value={store.cropHeight}
+                min={1}
+                placeholder="高"
+                onInput={(value) => setStore("cropHeight", value)}
+                class="w-20"
+              />
+            </Show>
+            <Config.Switch
+              value={store.crop}
+              onChange={(value) => setStore("crop", value)}
+            />
+          </Flex>
+        </Config.Option>
+      </Config.Card>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-1"
+          title="原图"
+          loading={info.loading}
+          operation={
+            <PickImageFileButton
+              onPick={(file) => file && setStore("file", file)}
+            />
+          }
+          notification={
+            <Show when={inspected()}>
+              {(value) => (
+                <span class="text-sm opacity-60">
+                  {value().format} {value().width}×{value().height}{" "}
+                  {value().color} {size(value().size)}
+                </span>
+              )}
+            </Show>
+          }
+        >
+          <div class="border-base-content/20 bg-base-100 flex flex-1 items-center justify-center overflow-hidden rounded-md border p-2">
+            <Show
+              when={store.file}
+              fallback={
+                <span class="text-warning flex items-center justify-center gap-2 text-sm">
+                  <Image size={16} />
+                  选择需要转换的图片
+                </span>
+              }
+            >
+              <img
+                src={convertFileSrc(store.file)}
+                class="size-full object-scale-down"
+              />
+            </Show>
+          </div>
+          <Show when={failure(info())}>
+            {(value) => <span class="text-error text-sm">{value()}</span>}
+          </Show>
+          <Show when={inspected()?.exif.length}>
+            <div class="max-h-60 overflow-auto">
+              <table class="table-xs table font-mono">
+                <tbody>
+                  <For each={inspected()?.exif}>
+                    {(field) => (
+                      <tr>
+                        <td class="opacity-60">{field.tag}</td>
+                        <td class="select-text">{field.value}</td>
+                        <td class="opacity-40">{field.ifd}</td>
+                      </tr>
+                    )}
+                  </For>
+                </tbody>
+              </table>
+            </div>
+          </Show>
+        </Card>
+
+        <Card
+          class="h-full w-0 flex-1"
+          title="结果"
+          loading={converted.loading}
+          operation={
+            <Show when={result()}>
+              {(value) => (
+                <Flex>
+                  <button class="btn btn-sm" onClick={saveAs}>
+                    另存为
+                  </button>
+                  <Base64ImageButtons
+                    base64={value().base64}
+                    extension={value().extension}
+                  />
+                </Flex>
+              )}
+            </Show>
+          }
+          notification={
+            <Show when={result()}>
+              {(value) => (
+                <span class="text-sm opacity-60">
+                  {value().width}×{value().height} {size(value().size)}
+                </span>
+              )}
+            </Show>
+          }
+        >
+          <div class="border-base-content/20 bg-base-100 flex flex-1 items-center justify-center overflow-hidden rounded-md border p-2">
+            <Show
+              when={result()}
+              fallback={
+                <span class="text-error text-sm">{failure(converted())}</span>
+              }
+            >
+              {(value) => (
+                <img
+                  src={`data:image/${value().extension};base64,${value().base64}`}
+                  class="size-full object-scale-down"
+                />
+              )}
+            </Show>
+          </div>
+        </Card>
+      </Main>
+    </Container>
+  );
+}
@@ -1,6 +1,7 @@
 import {
   convertJsonToYaml,
   convertYamlToJson,
+  convertYamlToYaml,
 } from "@/command/converter/json_yaml";
 import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
 import Card from "@/component/Card";
@@ -10,19 +11,24 @@ import Editor from "@/component/Editor";
 import Main from "@/component/Main";
 import { createCachableStore } from "@/lib/cache";
 import { stringify } from "@/lib/util";
-import { ArrowLeftRight } from "lucide-solid";
-import { createResource, Match, Switch } from "solid-js";
+import { ArrowLeftRight, Tag, WandSparkles } from "lucide-solid";
+import { createResource, Match, Show, Switch } from "solid-js";
 
 export default function JsonYamlConverter() {
   // 页面参数
   const [store, setStore] = createCachableStore({
     encode: true,
     input: "",
+    tags: false,
+    reformat: false,
   });
 
   // 是否解码
   const decode = () => !store.encode;
 
+  // 输出是否为 YAML
+  const yaml = () => store.encode || store.reformat;
+
   // 切换操作模式
   const setEncode = (value: boolean) => {
     setStore({ encode: value, input: "" });
@@ -31,12 +37,16 @@ export default function JsonYamlConverter() {
   // 输出结果
   const [output] = createResource(
     () => ({ ...store }),
-    ({ input, encode }) => {
-      if (input) {
-        return (
-          encode ? convertJsonToYaml(input) : convertYamlToJson(input)
-        ).catch(stringify);
+    ({ input, encode, tags, reformat }) => {
+      if (!input) {
+        return;
+      }
+      if (encode) {
+        return convertJsonToYaml(input, tags).catch(stringify);
       }
+      return (
+        reformat ? convertYamlToYaml(input) : convertYamlToJson(input)
+      ).catch(stringify);
     },
   );
 
@@ -57,6 +67,32 @@ export default function JsonYamlConverter() {
             off="YAML -> JSON"
           />
         </Config.Option>
+
+        <Show when={store.encode}>
+          <Config.Option
+            label="还原标签"
+            description='把 {"!Tag": value} 形式的对象还原为 YAML 标签'
+            icon={() => <Tag size={16} />}
+          >
+            <Config.Switch
+              value={store.tags}
+              onChange={(value) => setStore("tags", value)}
+            />
+          </Config.Option>
+        </Show>
+
+        <Show when={decode()}>
+          <Config.Option
+            label="重新格式化"
+            description="输出格式化后的 YAML，保留注释"
+            icon={() => <WandSparkles size={16} />}
+          >
+            <Config.Switch
+              value={store.reformat}
+              onChange={(value) => setStore("reformat", value)}
+            />
+          </Config.Option>
+        </Show>
       </Config.Card>
 
       <Main>
@@ -93,10 +129,10 @@ export default function JsonYamlConverter() {
           loading={output.loading}
         >
           <Switch>
-            <Match when={store.encode}>
+            <Match when={yaml()}>
               <Editor value={output()} readOnly={true} language="yaml" />
             </Match>
-            <Match when={decode()}>
+            <Match when={!yaml()}>
               <Editor value={output()} readOnly={true} language="json" />
             </Match>
           </Switch>
@@ -1,9 +1,17 @@
+import {
+  convertFloat,
+  convertNumber,
+  convertPermission,
+} from "@/command/converter/number";
+import { Encoding } from "@/command/crypto/type";
 import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
 import Card from "@/component/Card";
 import Config from "@/component/Config";
 import Container from "@/component/Container";
 import { createCachableStore } from "@/lib/cache";
-import { PaintRoller } from "lucide-solid";
+import { stringify } from "@/lib/util";
+import { Binary, PaintRoller } from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
 
 type Radix = 2 | 8 | 10 | 16;
 
@@ -67,8 +75,29 @@ export default function NumberConverter() {
     format: true,
     value: "0",
     version: 0,
+    encoding: Encoding.Hex,
+    float: "3.14",
+    permission: "755",
   });
 
+  // 补码和字节序
+  const [number] = createResource(
+    () => ({ value: store.value, encoding: store.encoding }),
+    ({ value, encoding }) => convertNumber(value, encoding, 10).catch(stringify),
+  );
+
+  // IEEE-754 浮点数
+  const [float] = createResource(
+    () => ({ value: store.float, encoding: store.encoding }),
+    ({ value, encoding }) => convertFloat(value, encoding).catch(stringify),
+  );
+
+  // Unix 权限
+  const [permission] = createResource(
+    () => store.permission,
+    (value) => convertPermission(value).catch(stringify),
+  );
+
   // 十六进制
   const hex = () => {
     const _ = store.version;
@@ -120,6 +149,20 @@ export default function NumberConverter() {
             }
           />
         </Config.Option>
+        <Config.Option
+          label="字节编码"
+          description="字节序列的输出格式"
+          icon={() => <Binary size={16} />}
+        >
+          <Config.Select
+            value={store.encoding}
+            options={[Encoding.Hex, Encoding.Base64]}
+            onChange={(value) =>
+              setStore((prev) => ({ ...prev, encoding: value as Encoding }))
+            }
+            class="w-30"
+          />
+        </Config.Option>
       </Config.Card>
 
       <Card
@@ -181,6 +224,92 @@ export default function NumberConverter() {
           onInput={(e) => update(e.target.value, 2)}
         />
       </Card>
+
+      <Card title="补码与字节序">
+        <Show
+          when={typeof number() === "object" && number()}
+          fallback={<span class="text-error">{number() as string}</span>}
+        >
+          {(value) => (
+            <table class="table-sm table font-mono">
+              <thead>
+                <tr>
+                  <th>位宽</th>
+                  <th>有符号</th>
+                  <th>无符号</th>
+                  <th>大端</th>
+                  <th>小端</th>
+                </tr>
+              </thead>
+              <tbody>
+                <For each={value().widths}>
+                  {(width) => (
+                    <tr classList={{ "opacity-50": width.overflow }}>
+                      <td>{width.bits}</td>
+                      <td>{width.signed}</td>
+                      <td>{width.unsigned}</td>
+                      <td class="break-all">{width.big_endian}</td>
+                      <td class="break-all">{width.little_endian}</td>
+                    </tr>
+                  )}
+                </For>
+              </tbody>
+            </table>
+          )}
+        </Show>
+      </Card>
+
+      <Card title="IEEE-754 浮点数">
+        <input
+          class="input input-md w-full font-mono text-lg font-bold outline-none"
+          placeholder="十进制小数或 0x 开头的位模式"
+          value={store.float}
+          onInput={(e) =>
+            setStore((prev) => ({ ...prev, float: e.target.value }))
+          }
+        />
+        <Show
+          when={typeof float() === "object" && float()}
+          fallback={<span class="text-error">{float() as string}</span>}
+        >
+          {(value) => (
+            <For each={[value().single, value().double]}>
+              {(ieee) => (
+                <div class="flex flex-col font-mono text-sm">
+                  <span>
+                    {ieee.value} · {ieee.category} · 指数 {ieee.unbiased ?? "-"}
+                  </span>
+                  <span class="break-all">{ieee.binary}</span>
+                  <span class="break-all">
+                    {ieee.big_endian} / {ieee.little_endian}
+                  </span>
+                </div>
+              )}
+            </For>
+          )}
+        </Show>
+      </Card>
+
+      <Card title="Unix 权限">
+        <input
+          class="input input-md w-full font-mono text-lg font-bold outline-none"
+          placeholder="755 或 rwxr-xr-x"
+          value={store.permission}
+          onInput={(e) =>
+            setStore((prev) => ({ ...prev, permission: e.target.value }))
+          }
+        />
+        <Show
+          when={typeof permission() === "object" && permission()}
+          fallback={<span class="text-error">{permission() as string}</span>}
+        >
+          {(value) => (
+            <span class="font-mono font-bold">
+              {value().octal} · {value().symbolic} · {value().chmod}
+            </span>
+          )}
+        </Show>
+      </Card>
     </Container>
   );
 }
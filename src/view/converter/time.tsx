@@ -1,8 +1,15 @@
+import {
+  parseTime,
+  shiftTime,
+  timeBetween,
+  Unit,
+} from "@/command/converter/time";
 import { ClearButton, PasteButton } from "@/component/Buttons";
 import Card from "@/component/Card";
 import Container from "@/component/Container";
 import Flex from "@/component/Flex";
 import Main from "@/component/Main";
+import { stringify } from "@/lib/util";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
 import AirDatepicker from "air-datepicker";
 import "air-datepicker/air-datepicker.css";
@@ -14,17 +21,30 @@ import { CircleCheckBig } from "lucide-solid";
 import {
   createEffect,
   createMemo,
-  createRenderEffect,
+  createResource,
   createSignal,
+  For,
   onCleanup,
   onMount,
   Show,
 } from "solid-js";
+import { createStore } from "solid-js/store";
 import { twMerge } from "tailwind-merge";
 
 dayjs.extend(utc);
 dayjs.extend(timeZone);
 
+const UNITS = [
+  { label: "秒", value: Unit.Seconds },
+  { label: "分钟", value: Unit.Minutes },
+  { label: "小时", value: Unit.Hours },
+  { label: "天", value: Unit.Days },
+  { label: "工作日", value: Unit.BusinessDays },
+  { label: "周", value: Unit.Weeks },
+  { label: "月", value: Unit.Months },
+  { label: "年", value: Unit.Years },
+];
+
 function getWeekDay(day: number): string {
   const weekdays = ["日", "一", "二", "三", "四", "五", "六"];
   return weekdays[day];
@@ -88,33 +108,47 @@ export default function TimeConverter() {
   const seconds = createMemo(() => now().unix());
   const milliseconds = createMemo(() => now().valueOf());
 
-  // 时间戳转时间
-  const [timestamp, setTimestamp] = createSignal<number | undefined>(
-    dayjs().valueOf(),
+  // 解析时间戳或时间
+  const [timestamp, setTimestamp] = createSignal(`${dayjs().valueOf()}`);
+  const [parsed] = createResource(
+    () => ({ input: timestamp().trim(), zone: timeZone() }),
+    ({ input, zone }) =>
+      input
+        ? parseTime(input, zone, [zone, "UTC"]).catch(stringify)
+        : undefined,
   );
-  const [timestampUnit, setTimestampUnit] = createSignal<
-    "milliseconds" | "seconds"
-  >("milliseconds");
-  const [parseTimestampResult, setParseTimestampResult] = createSignal<{
-    local?: string;
-    utc?: string;
-    iso8601?: string;
-  }>({});
-  createRenderEffect(() => {
-    const _timestamp = timestamp();
-    if (_timestamp) {
-      const time = dayjs(
-        timestampUnit() === "milliseconds" ? _timestamp : _timestamp * 1000,
-      );
-      setParseTimestampResult({
-        local: time.format("YYYY-MM-DD HH:mm:ss"),
-        utc: time.utc().format("YYYY-MM-DD HH:mm:ss"),
-        iso8601: time.toISOString(),
-      });
-    } else {
-      setParseTimestampResult({});
-    }
+  const parseTimestampResult = () => {
+    const value = parsed();
+    return typeof value === "string" ? { error: value } : { time: value };
+  };
+
+  // 时间计算
+  const [span, setSpan] = createStore({
+    start: dayjs().format("YYYY-MM-DD HH:mm:ss"),
+    end: dayjs().add(7, "day").format("YYYY-MM-DD HH:mm:ss"),
+    amount: 5,
+    unit: Unit.BusinessDays,
   });
+  const [between] = createResource(
+    () => ({ ...span, zone: timeZone() }),
+    ({ start, end, zone }) => timeBetween(start, end, zone).catch(stringify),
+  );
+  const [shifted] = createResource(
+    () => ({ ...span, zone: timeZone() }),
+    ({ start, amount, unit, zone }) =>
+      shiftTime(start, amount, unit, zone, [zone]).catch(stringify),
+  );
+  const betweenText = () => {
+    const value = between();
+    if (typeof value !== "object") return value ?? "-";
+    const sign = value.negative ? "-" : "";
+    return `${sign}${value.description.chinese}（${value.iso}，${value.business_days} 个工作日）`;
+  };
+  const shiftedText = () => {
+    const value = shifted();
+    if (typeof value !== "object") return value ?? "-";
+    return value.zones[0]?.time ?? "-";
+  };
 
   // 时间转时间戳
   const [dateTime, setDateTime] = createSignal(
@@ -227,62 +261,56 @@ export default function TimeConverter() {
       <Main>
         <Card
           class="w-0 flex-1"
-          title="时间戳转时间"
+          title="解析时间戳或时间"
           operation={
             <Flex>
-              <PasteButton onRead={(value) => setTimestamp(Number(value))} />
-              <ClearButton onClick={() => setTimestamp(undefined)} />
+              <PasteButton onRead={(value) => setTimestamp(value)} />
+              <ClearButton onClick={() => setTimestamp("")} />
             </Flex>
           }
         >
-          <Flex>
-            <label class="select flex-1 outline-none">
-              <span class="label">单位</span>
-              <select
-                onChange={(e) =>
-                  setTimestampUnit(e.target.value as "milliseconds" | "seconds")
-                }
-              >
-                <option
-                  value="milliseconds"
-                  selected={timestampUnit() === "milliseconds"}
-                >
-                  毫秒
-                </option>
-                <option
-                  value="seconds"
-                  selected={timestampUnit() === "seconds"}
-                >
-                  秒
-                </option>
-              </select>
-            </label>
-            <input
-              type="number"
-              class="input flex-1 [appearance:textfield] font-mono text-lg font-bold outline-none [&::-webkit-inner-spin-button]:appearance-none [&::-webkit-outer-spin-button]:appearance-none"
-              placeholder="输入时间戳"
-              value={`${timestamp() || ""}`}
-              onInput={(e) => setTimestamp(e.target.valueAsNumber)}
-            />
-          </Flex>
+          <input
+            class="input w-full font-mono text-lg font-bold outline-none"
+            placeholder="时间戳（秒/毫秒/微秒/纳秒）、RFC 3339、RFC 2822 或 ISO 周日期"
+            value={timestamp()}
+            onInput={(e) => setTimestamp(e.target.value)}
+          />
           <fieldset class="fieldset bg-base-200 rounded-box w-full p-4">
-            <legend class="fieldset-legend">转换结果</legend>
-            <label class="label">本地时间</label>
+            <legend class="fieldset-legend">
+              转换结果
+              <Show when={parseTimestampResult().time}>
+                {(time) => (
+                  <span class="badge badge-sm badge-accent">
+                    {time().source} · {time().relative.chinese}
+                  </span>
+                )}
+              </Show>
+            </legend>
+            <Show when={parseTimestampResult().error}>
+              {(error) => <span class="text-error">{error()}</span>}
+            </Show>
+            <label class="label">所选时区时间</label>
             <ClickCopyButton
               class="w-full"
-              value={parseTimestampResult().local || "-"}
+              value={parseTimestampResult().time?.zones[0]?.time || "-"}
             />
 
             <label class="label">UTC 时间</label>
             <ClickCopyButton
               class="w-full"
-              value={parseTimestampResult().utc || "-"}
+              value={parseTimestampResult().time?.zones[1]?.time || "-"}
             />
 
-            <label class="label">ISO 8601</label>
+            <label class="label">RFC 3339</label>
             <ClickCopyButton
               class="w-full"
-              value={parseTimestampResult().iso8601 || "-"}
+              value={parseTimestampResult().time?.rfc3339 || "-"}
+            />
+
+            <label class="label">ISO 周日期</label>
+            <ClickCopyButton
+              class="w-full"
+              value={parseTimestampResult().time?.iso_week || "-"}
             />
           </fieldset>
         </Card>
@@ -291,8 +319,8 @@ export default function TimeConverter() {
           title="时间转时间戳"
           operation={
             <Flex>
-              <PasteButton onRead={(value) => setTimestamp(Number(value))} />
-              <ClearButton onClick={() => setTimestamp(undefined)} />
+              <PasteButton onRead={(value) => setTimestamp(value)} />
+              <ClearButton onClick={() => setTimestamp("")} />
             </Flex>
           }
         >
@@ -338,6 +366,46 @@ export default function TimeConverter() {
           </fieldset>
         </Card>
       </Main>
+
+      <Card title="时间计算">
+        <Flex>
+          <input
+            class="input flex-1 font-mono font-bold outline-none"
+            placeholder="开始时间"
+            value={span.start}
+            onInput={(e) => setSpan("start", e.target.value)}
+          />
+          <input
+            class="input flex-1 font-mono font-bold outline-none"
+            placeholder="结束时间"
+            value={span.end}
+            onInput={(e) => setSpan("end", e.target.value)}
+          />
+        </Flex>
+        <ClickCopyButton class="w-full" value={betweenText()} mono={false} />
+        <Flex>
+          <span class="text-nowrap">开始时间加上</span>
+          <input
+            type="number"
+            class="input w-24 font-mono font-bold outline-none"
+            value={span.amount}
+            onInput={(e) => setSpan("amount", e.target.valueAsNumber || 0)}
+          />
+          <select
+            class="select w-30 outline-none"
+            onChange={(e) => setSpan("unit", e.target.value as Unit)}
+          >
+            <For each={UNITS}>
+              {({ label, value }) => (
+                <option value={value} selected={span.unit === value}>
+                  {label}
+                </option>
+              )}
+            </For>
+          </select>
+          <ClickCopyButton class="flex-1" value={shiftedText()} />
+        </Flex>
+      </Card>
     </Container>
   );
 }
@@ -0,0 +1,275 @@
+import {
+  Arrays,
+  Convention,
+  convertJsonToXml,
+  convertXmlToJson,
+  Namespace,
+} from "@/command/converter/xml_json";
+import { Indent } from "@/command/formatter/indent";
+import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import Flex from "@/component/Flex";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import {
+  ArrowLeftRight,
+  Brackets,
+  Globe,
+  Hash,
+  Shuffle,
+  Space,
+  Tag,
+} from "lucide-solid";
+import { createResource, Match, Show, Switch } from "solid-js";
+
+const CONVENTION_OPTIONS = [
+  { label: "BadgerFish", value: Convention.BadgerFish },
+  { label: "Parker", value: Convention.Parker },
+  { label: "@属性 / #text", value: Convention.Attribute },
+];
+
+const NAMESPACE_OPTIONS = [
+  { label: "保留前缀", value: Namespace.Preserve },
+  { label: "去除", value: Namespace.Strip },
+  { label: "{uri}名称", value: Namespace.Expand },
+];
+
+const ARRAYS_OPTIONS = [
+  { label: "重复的元素", value: "Repeated" },
+  { label: "所有元素", value: "Always" },
+  { label: "指定元素", value: "Elements" },
+];
+
+const INDENT_OPTIONS = [
+  { label: "2个空格", value: Indent.TwoSpace },
+  { label: "4个空格", value: Indent.FourSpace },
+  { label: "1个制表符", value: Indent.Tab },
+  { label: "压缩", value: Indent.Minify },
+];
+
+export default function XmlJsonConverter() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    encode: true,
+    convention: Convention.BadgerFish,
+    namespace: Namespace.Preserve,
+    arrays: "Repeated",
+    elements: "",
+    coerceTypes: false,
+    indent: Indent.TwoSpace,
+    root: "",
+    input: "",
+  });
+
+  // 是否解码
+  const decode = () => !store.encode;
+
+  // 切换操作模式
+  const setEncode = (value: boolean) => {
+    setStore({ encode: value, input: "" });
+  };
+
+  // 输出结果
+  const [output] = createResource(
+    () => ({ ...store }),
+    ({
+      encode,
+      convention,
+      namespace,
+      arrays,
+      elements,
+      coerceTypes,
+      indent,
+      root,
+      input,
+    }) => {
+      if (!input) {
+        return;
+      }
+      if (encode) {
+        const rule: Arrays =
+          arrays === "Elements"
+            ? {
+                Elements: elements
+                  .split(",")
+                  .map((element) => element.trim())
+                  .filter(Boolean),
+              }
+            : (arrays as "Repeated" | "Always");
+        return convertXmlToJson(
+          input,
+          convention,
+          namespace,
+          rule,
+          coerceTypes,
+        ).catch(stringify);
+      }
+      return convertJsonToXml(input, convention, indent, root || null).catch(
+        stringify,
+      );
+    },
+  );
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        <Config.Option
+          label="操作"
+          description="选择操作的类型"
+          icon={() => <ArrowLeftRight size={16} />}
+        >
+          {/*转换配置*/}
+          <Config.Switch
+            value={store.encode}
+            onChange={setEncode}
+            on="XML -> JSON"
+            off="JSON -> XML"
+          />
+        </Config.Option>
+
+        {/*约定配置*/}
+        <Config.Option
+          label="映射约定"
+          description="属性、文本和元素在 JSON 中的表示方式"
+          icon={() => <Shuffle size={16} />}
+        >
+          <Config.Select
+            value={store.convention}
+            options={CONVENTION_OPTIONS}
+            onChange={(value) => setStore("convention", value)}
+            class="w-40"
+          />
+        </Config.Option>
+
+        <Show when={store.encode}>
+          {/*命名空间配置*/}
+          <Config.Option
+            label="命名空间"
+            description="带命名空间的名称的处理方式"
+            icon={() => <Globe size={16} />}
+          >
+            <Config.Select
+              value={store.namespace}
+              options={NAMESPACE_OPTIONS}
+              onChange={(value) => setStore("namespace", value)}
+              class="w-30"
+            />
+          </Config.Option>
+
+          {/*数组配置*/}
+          <Config.Option
+            label="数组"
+            description="转换为数组的子元素，指定元素时多个名称用逗号分隔"
+            icon={() => <Brackets size={16} />}
+          >
+            <Flex>
+              <Show when={store.arrays === "Elements"}>
+                <Config.Input
+                  value={store.elements}
+                  onInput={(value) => setStore("elements", value)}
+                  class="w-40"
+                />
+              </Show>
+              <Config.Select
+                value={store.arrays}
+                options={ARRAYS_OPTIONS}
+                onChange={(value) => setStore("arrays", value)}
+                class="w-30"
+              />
+            </Flex>
+          </Config.Option>
+
+          {/*类型配置*/}
+          <Config.Option
+            label="转换类型"
+            description="把数字、true 和 false 文本转换为对应的 JSON 类型"
+            icon={() => <Hash size={16} />}
+          >
+            <Config.Switch
+              value={store.coerceTypes}
+              onChange={(value) => setStore("coerceTypes", value)}
+            />
+          </Config.Option>
+        </Show>
+
+        <Show when={decode()}>
+          {/*缩进配置*/}
+          <Config.Option
+            label="缩进"
+            description="设置输出 XML 的缩进方式"
+            icon={() => <Space size={16} />}
+          >
+            <Config.Select
+              value={store.indent}
+              options={INDENT_OPTIONS}
+              onChange={(value) => setStore("indent", value)}
+              class="w-30"
+            />
+          </Config.Option>
+
+          {/*根元素配置*/}
+          <Config.Option
+            label="根元素"
+            description="留空时单键对象使用该键作为根元素，否则使用 root"
+            icon={() => <Tag size={16} />}
+          >
+            <Config.Input
+              value={store.root}
+              onInput={(value) => setStore("root", value)}
+              class="w-40"
+            />
+          </Config.Option>
+        </Show>
+      </Config.Card>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-1"
+          title="输入"
+          operation={
+            <TextWriteButtons callback={(value) => setStore("input", value)} />
+          }
+        >
+          <Switch>
+            <Match when={store.encode}>
+              <Editor
+                value={store.input}
+                onChange={(value) => setStore("input", value)}
+                language="xml"
+                placeholder="输入需要转换的 XML 数据"
+              />
+            </Match>
+            <Match when={decode()}>
+              <Editor
+                value={store.input}
+                onChange={(value) => setStore("input", value)}
+                language="json"
+                placeholder="输入需要转换的 JSON 数据"
+              />
+            </Match>
+          </Switch>
+        </Card>
+        <Card
+          class="h-full w-0 flex-1"
+          title="输出"
+          operation={<TextReadButtons value={output()} />}
+          loading={output.loading}
+        >
+          <Switch>
+            <Match when={store.encode}>
+              <Editor value={output()} readOnly={true} language="json" />
+            </Match>
+            <Match when={decode()}>
+              <Editor value={output()} readOnly={true} language="xml" />
+            </Match>
+          </Switch>
+        </Card>
+      </Main>
+    </Container>
+  );
+}
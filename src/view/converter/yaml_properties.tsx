@@ -1,6 +1,7 @@
 import {
   convertPropertiesToYaml,
   convertYamlToProperties,
+  ValueMode,
 } from "@/command/converter/yaml_properties";
 import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
 import Card from "@/component/Card";
@@ -10,13 +11,20 @@ import Editor from "@/component/Editor";
 import Main from "@/component/Main";
 import { createCachableStore } from "@/lib/cache";
 import { stringify } from "@/lib/util";
-import { ArrowLeftRight } from "lucide-solid";
-import { createResource, Match, Switch } from "solid-js";
+import { ArrowLeftRight, Binary, Layers } from "lucide-solid";
+import { createResource, Match, Show, Switch } from "solid-js";
+
+const VALUE_MODE_OPTIONS = [
+  { label: "推断类型", value: ValueMode.Infer },
+  { label: "保持字符串", value: ValueMode.String },
+];
 
 export default function YamlPropertiesConverter() {
   // 页面参数
   const [store, setStore] = createCachableStore({
     encode: true,
+    mode: ValueMode.Infer,
+    profile: "",
     input: "",
   });
 
@@ -31,12 +39,12 @@ export default function YamlPropertiesConverter() {
   // 输出结果
   const [output] = createResource(
     () => ({ ...store }),
-    ({ encode, input }) => {
+    ({ encode, mode, profile, input }) => {
       if (input) {
         return (
           encode
-            ? convertYamlToProperties(input)
-            : convertPropertiesToYaml(input)
+            ? convertYamlToProperties(input, profile)
+            : convertPropertiesToYaml(input, mode)
         ).catch(stringify);
       }
     },
@@ -60,6 +68,37 @@ export default function YamlPropertiesConverter() {
             off="PROPERTIES -> YAML"
           />
         </Config.Option>
+
+        {/*Profile 配置*/}
+        <Show when={store.encode}>
+          <Config.Option
+            label="Profile"
+            description="将指定 Profile 的文档合并到默认配置，多个用逗号分隔，留空则按文档分别输出"
+            icon={() => <Layers size={16} />}
+          >
+            <Config.Input
+              value={store.profile}
+              onInput={(value) => setStore("profile", value)}
+              class="w-30"
+            />
+          </Config.Option>
+        </Show>
+
+        {/*值类型配置*/}
+        <Show when={decode()}>
+          <Config.Option
+            label="值类型"
+            description="是否将数字和布尔值转换为对应的 YAML 类型"
+            icon={() => <Binary size={16} />}
+          >
+            <Config.Select
+              value={store.mode}
+              options={VALUE_MODE_OPTIONS}
+              onChange={(value) => setStore("mode", value)}
+              class="w-30"
+            />
+          </Config.Option>
+        </Show>
       </Config.Card>
 
       <Main>
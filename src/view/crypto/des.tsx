@@ -11,6 +11,7 @@ import {
   EncodingText,
   Padding,
 } from "@/command/crypto/type";
+import { Charset } from "@/command/text/charset";
 import {
   ClearButton,
   GenerateButton,
@@ -19,6 +20,7 @@ import {
   TextWriteButtons,
 } from "@/component/Buttons";
 import Card from "@/component/Card";
+import { CharsetSelect } from "@/component/Charset";
 import Config from "@/component/Config";
 import Container from "@/component/Container";
 import Editor from "@/component/Editor";
@@ -51,6 +53,7 @@ export default function DesCrypto() {
     key: { text: "", encoding: Encoding.Utf8 } as EncodingText,
     iv: { text: "", encoding: Encoding.Utf8 } as EncodingText,
     encoding: Encoding.Hex,
+    charset: Charset.Utf8,
   });
 
   // 切换模式
@@ -64,6 +67,7 @@ export default function DesCrypto() {
       key: { text: "", encoding: Encoding.Utf8 },
       iv: { text: "", encoding: Encoding.Utf8 },
       encoding: value ? Encoding.Hex : Encoding.Utf8,
+      charset: Charset.Utf8,
     });
   };
 
@@ -92,13 +96,33 @@ export default function DesCrypto() {
       blockMode: store.blockMode,
       padding: store.padding,
       encoding: store.encoding,
+      charset: store.charset,
     }),
-    ({ encryption, bitSize, input, key, iv, blockMode, padding, encoding }) => {
+    ({
+      encryption,
+      bitSize,
+      input,
+      key,
+      iv,
+      blockMode,
+      padding,
+      encoding,
+      charset,
+    }) => {
       if (input.text && key.text && (iv.text || blockMode === BlockMode.Ecb)) {
         return (
           encryption
             ? encryptDes(bitSize, input, key, iv, blockMode, padding, encoding)
-            : decryptDes(bitSize, input, key, iv, blockMode, padding, encoding)
+            : decryptDes(
+                bitSize,
+                input,
+                key,
+                iv,
+                blockMode,
+                padding,
+                encoding,
+                charset,
+              )
         ).catch(stringify);
       }
     },
@@ -250,6 +274,15 @@ export default function DesCrypto() {
                 value={store.encoding}
                 onChange={(value) => setStore("encoding", value)}
               />
+              <Show
+                when={!store.encryption && store.encoding === Encoding.Utf8}
+              >
+                <CharsetSelect
+                  label="字符集"
+                  value={store.charset}
+                  onChange={(value) => setStore("charset", value)}
+                />
+              </Show>
             </TextReadButtons>
           }
         >
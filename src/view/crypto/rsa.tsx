@@ -4,6 +4,7 @@ import {
   generateRsaKeyPair,
 } from "@/command/crypto/rsa";
 import { KeyFormat, RsaBitSize, RsaKeyPair } from "@/command/crypto/type";
+import { Charset } from "@/command/text/charset";
 import {
   CopyButton,
   GenerateButton,
@@ -11,6 +12,7 @@ import {
   TextWriteButtons,
 } from "@/component/Buttons";
 import Card from "@/component/Card";
+import { CharsetSelect } from "@/component/Charset";
 import Config from "@/component/Config";
 import Container from "@/component/Container";
 import Editor from "@/component/Editor";
@@ -18,7 +20,7 @@ import Main from "@/component/Main";
 import { createCachableStore } from "@/lib/cache";
 import { stringify } from "@/lib/util";
 import { ArrowLeftRight, PanelLeftRightDashed, Ruler } from "lucide-solid";
-import { createResource } from "solid-js";
+import { createResource, Show } from "solid-js";
 import { RSA_BIT_SIZE_OPTIONS } from "./options";
 
 export default function RsaCrypto() {
@@ -32,6 +34,7 @@ export default function RsaCrypto() {
       size: RsaBitSize.Bits2048,
     } as RsaKeyPair,
     input: "",
+    charset: Charset.Utf8,
   });
 
   // 加密或解密
@@ -41,6 +44,7 @@ export default function RsaCrypto() {
       keyFormat: KeyFormat.Pkcs8,
       keyPair: { private: "", public: "", size: RsaBitSize.Bits2048 },
       input: "",
+      charset: Charset.Utf8,
     });
   };
 
@@ -58,14 +62,17 @@ export default function RsaCrypto() {
       keyFormat: store.keyFormat,
       keyPair: { ...store.keyPair },
       input: store.input,
+      charset: store.charset,
     }),
-    ({ encryption, keyFormat, keyPair, input }) => {
+    ({ encryption, keyFormat, keyPair, input, charset }) => {
       if (input) {
         if (encryption && keyPair.public) {
           return encryptRsa(keyFormat, keyPair.public, input).catch(stringify);
         }
         if (!encryption && keyPair.private) {
-          return decryptRsa(keyFormat, keyPair.private, input).catch(stringify);
+          return decryptRsa(keyFormat, keyPair.private, input, charset).catch(
+            stringify,
+          );
         }
       }
     },
@@ -189,7 +196,17 @@ export default function RsaCrypto() {
         <Card
           class="h-full w-0 flex-1"
           title="输出"
-          operation={<TextReadButtons value={output()} />}
+          operation={
+            <TextReadButtons value={output()} position="before">
+              <Show when={!store.encryption}>
+                <CharsetSelect
+                  label="字符集"
+                  value={store.charset}
+                  onChange={(value) => setStore("charset", value)}
+                />
+              </Show>
+            </TextReadButtons>
+          }
           loading={output.loading}
         >
           <Editor value={output()} readOnly={true} />
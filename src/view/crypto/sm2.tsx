@@ -5,6 +5,7 @@ import {
   Sm2KeyFormat,
 } from "@/command/crypto/sm2";
 import { KeyFormat, KeyPair } from "@/command/crypto/type";
+import { Charset } from "@/command/text/charset";
 import {
   CopyButton,
   GenerateButton,
@@ -12,6 +13,7 @@ import {
   TextWriteButtons,
 } from "@/component/Buttons";
 import Card from "@/component/Card";
+import { CharsetSelect } from "@/component/Charset";
 import Config from "@/component/Config";
 import Container from "@/component/Container";
 import Editor from "@/component/Editor";
@@ -19,7 +21,7 @@ import Main from "@/component/Main";
 import { createCachableStore } from "@/lib/cache";
 import { stringify } from "@/lib/util";
 import { ArrowLeftRight, PanelLeftRightDashed } from "lucide-solid";
-import { createResource } from "solid-js";
+import { createResource, Show } from "solid-js";
 
 export default function Sm2Crypto() {
   // 页面参数
@@ -31,6 +33,7 @@ export default function Sm2Crypto() {
       public: "",
     } as KeyPair,
     input: "",
+    charset: Charset.Utf8,
   });
 
   // 加密或解密
@@ -40,6 +43,7 @@ export default function Sm2Crypto() {
       keyFormat: Sm2KeyFormat.Pkcs8,
       keyPair: { private: "", public: "" },
       input: "",
+      charset: Charset.Utf8,
     });
   };
 
@@ -57,14 +61,17 @@ export default function Sm2Crypto() {
       keyFormat: store.keyFormat,
       keyPair: { ...store.keyPair },
       input: store.input,
+      charset: store.charset,
     }),
-    ({ encryption, keyFormat, keyPair, input }) => {
+    ({ encryption, keyFormat, keyPair, input, charset }) => {
       if (input) {
         if (encryption && keyPair.public) {
           return encryptSm2(keyFormat, keyPair.public, input).catch(stringify);
         }
         if (!encryption && keyPair.private) {
-          return decryptSm2(keyFormat, keyPair.private, input).catch(stringify);
+          return decryptSm2(keyFormat, keyPair.private, input, charset).catch(
+            stringify,
+          );
         }
       }
     },
@@ -164,7 +171,17 @@ export default function Sm2Crypto() {
         <Card
           class="h-full w-0 flex-1"
           title="输出"
-          operation={<TextReadButtons value={output()} />}
+          operation={
+            <TextReadButtons value={output()} position="before">
+              <Show when={!store.encryption}>
+                <CharsetSelect
+                  label="字符集"
+                  value={store.charset}
+                  onChange={(value) => setStore("charset", value)}
+                />
+              </Show>
+            </TextReadButtons>
+          }
           loading={output.loading}
         >
           <Editor value={output()} readOnly={true} />
@@ -11,6 +11,7 @@ import {
   EncodingText,
   Padding,
 } from "@/command/crypto/type";
+import { Charset } from "@/command/text/charset";
 import {
   ClearButton,
   GenerateButton,
@@ -19,6 +20,7 @@ import {
   TextWriteButtons,
 } from "@/component/Buttons";
 import Card from "@/component/Card";
+import { CharsetSelect } from "@/component/Charset";
 import Config from "@/component/Config";
 import Container from "@/component/Container";
 import Editor from "@/component/Editor";
@@ -56,6 +58,7 @@ export default function Sm4Crypto() {
     key: { text: "", encoding: Encoding.Utf8 } as EncodingText,
     iv: { text: "", encoding: Encoding.Utf8 } as EncodingText,
     encoding: Encoding.Hex,
+    charset: Charset.Utf8,
   });
 
   // 切换模式
@@ -68,6 +71,7 @@ export default function Sm4Crypto() {
       key: { text: "", encoding: Encoding.Utf8 },
       iv: { text: "", encoding: Encoding.Utf8 },
       encoding: value ? Encoding.Hex : Encoding.Utf8,
+      charset: Charset.Utf8,
     });
   };
 
@@ -95,13 +99,14 @@ export default function Sm4Crypto() {
       blockMode: store.blockMode,
       padding: store.padding,
       encoding: store.encoding,
+      charset: store.charset,
     }),
-    ({ encryption, input, key, iv, blockMode, padding, encoding }) => {
+    ({ encryption, input, key, iv, blockMode, padding, encoding, charset }) => {
       if (input.text && key.text && (iv.text || blockMode === BlockMode.Ecb)) {
         return (
           encryption
             ? encryptSm4(input, key, iv, blockMode, padding, encoding)
-            : decryptSm4(input, key, iv, blockMode, padding, encoding)
+            : decryptSm4(input, key, iv, blockMode, padding, encoding, charset)
         ).catch(stringify);
       }
     },
@@ -239,6 +244,15 @@ export default function Sm4Crypto() {
                 value={store.encoding}
                 onChange={(value) => setStore("encoding", value)}
               />
+              <Show
+                when={!store.encryption && store.encoding === Encoding.Utf8}
+              >
+                <CharsetSelect
+                  label="字符集"
+                  value={store.charset}
+                  onChange={(value) => setStore("charset", value)}
+                />
+              </Show>
             </TextReadButtons>
           }
         >
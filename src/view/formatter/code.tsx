@@ -0,0 +1,239 @@
+import { Indent } from "@/command/formatter/indent";
+import {
+  format,
+  listFormatters,
+  OptionSchema,
+} from "@/command/formatter/registry";
+import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { ArrowDownAZ, FileCode, SlidersHorizontal, Space } from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
+
+// 有单独页面的格式化器
+const DEDICATED = ["json", "xml", "sql"];
+
+// 编辑器只在创建时设置语言，TOML 没有对应的高亮，按 INI 处理
+const EDITOR_LANGUAGES: Record<string, string> = { toml: "ini" };
+
+const editorLanguage = (name: string) => EDITOR_LANGUAGES[name] ?? name;
+
+const INDENT_LABELS: Record<Indent, string> = {
+  [Indent.TwoSpace]: "2个空格",
+  [Indent.FourSpace]: "4个空格",
+  [Indent.Tab]: "1个制表符",
+  [Indent.None]: "保持原样",
+  [Indent.Minify]: "压缩",
+};
+
+// 根据选项的类型生成对应的输入控件
+const OptionInput = (props: {
+  option: OptionSchema;
+  value: unknown;
+  onChange: (value: unknown) => void;
+}) => {
+  const kind = props.option.kind;
+  if ("Boolean" in kind) {
+    return (
+      <Config.Switch
+        value={(props.value as boolean | undefined) ?? kind.Boolean.default}
+        onChange={props.onChange}
+      />
+    );
+  }
+  if ("Integer" in kind) {
+    return (
+      <Config.NumberInput
+        value={(props.value as number | undefined) ?? kind.Integer.default ?? 0}
+        min={kind.Integer.min}
+        max={kind.Integer.max ?? undefined}
+        onInput={(value) => props.onChange(isNaN(value) ? null : value)}
+        class="w-30"
+      />
+    );
+  }
+  if ("Choice" in kind) {
+    return (
+      <Config.Select
+        value={(props.value as string | undefined) ?? kind.Choice.default}
+        options={kind.Choice.choices}
+        onChange={props.onChange}
+        class="w-30"
+      />
+    );
+  }
+  return (
+    <Config.Input
+      value={(props.value as string | undefined) ?? ""}
+      onInput={(value) => props.onChange(value || null)}
+      class="w-50"
+    />
+  );
+};
+
+export default function CodeFormatter() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    name: "html",
+    indent: Indent.TwoSpace,
+    sort: false,
+    options: {} as Record<string, unknown>,
+    input: "",
+  });
+
+  // 后端注册的格式化器
+  const [formatters] = createResource(() =>
+    listFormatters().then((formatters) =>
+      formatters.filter((formatter) => !DEDICATED.includes(formatter.name)),
+    ),
+  );
+
+  const descriptor = () =>
+    formatters()?.find((formatter) => formatter.name === store.name) ??
+    formatters()?.[0];
+
+  // 切换语言时清空输入，新的格式化器不支持当前缩进时使用它的第一种缩进
+  const setName = (name: string) => {
+    const indents =
+      formatters()?.find((formatter) => formatter.name === name)?.capabilities
+        .indents ?? [];
+    setStore({
+      name,
+      indent: indents.includes(store.indent) ? store.indent : indents[0],
+      options: {},
+      input: "",
+    });
+  };
+
+  // 输出结果
+  const [output] = createResource(
+    () =>
+      descriptor() && {
+        name: descriptor()!.name,
+        indent: store.indent,
+        sort: store.sort,
+        options: { ...store.options },
+        input: store.input,
+      },
+    ({ name, indent, sort, options, input }) => {
+      if (input) {
+        return format(name, input, indent, sort, options).catch(stringify);
+      }
+    },
+  );
+
+  return (
+    <Container>
+      <Show when={descriptor()} keyed>
+        {(descriptor) => (
+          <>
+            {/* 配置 */}
+            <Config.Card>
+              {/*语言配置*/}
+              <Config.Option
+                label="语言"
+                description="选择需要格式化的代码语言"
+                icon={() => <FileCode size={16} />}
+              >
+                <Config.Select
+                  value={descriptor.name}
+                  options={formatters()?.map((formatter) => ({
+                    label: formatter.label,
+                    value: formatter.name,
+                  }))}
+                  onChange={setName}
+                  class="w-30"
+                />
+              </Config.Option>
+
+              {/*缩进配置*/}
+              <Config.Option
+                label="缩进"
+                description="设置代码格式化的缩进方式"
+                icon={() => <Space size={16} />}
+              >
+                <Config.Select
+                  value={store.indent}
+                  options={descriptor.capabilities.indents.map((indent) => ({
+                    label: INDENT_LABELS[indent],
+                    value: indent,
+                  }))}
+                  onChange={(value) => setStore("indent", value)}
+                  class="w-30"
+                />
+              </Config.Option>
+
+              {/*排序配置*/}
+              <Show when={descriptor.capabilities.sort}>
+                <Config.Option
+                  label="排序"
+                  description="按名称排序"
+                  icon={() => <ArrowDownAZ size={16} />}
+                >
+                  <Config.Switch
+                    value={store.sort}
+                    onChange={(value) => setStore("sort", value)}
+                  />
+                </Config.Option>
+              </Show>
+
+              {/*格式化器自己的选项*/}
+              <For each={descriptor.options}>
+                {(option) => (
+                  <Config.Option
+                    label={option.label}
+                    icon={() => <SlidersHorizontal size={16} />}
+                  >
+                    <OptionInput
+                      option={option}
+                      value={store.options[option.name]}
+                      onChange={(value) =>
+                        setStore("options", option.name, value)
+                      }
+                    />
+                  </Config.Option>
+                )}
+              </For>
+            </Config.Card>
+
+            <Main>
+              <Card
+                class="h-full w-0 flex-1"
+                title="输入"
+                operation={
+                  <TextWriteButtons
+                    callback={(value) => setStore("input", value)}
+                  />
+                }
+              >
+                <Editor
+                  value={store.input}
+                  onChange={(value) => setStore("input", value)}
+                  language={editorLanguage(descriptor.name)}
+                  placeholder={`输入需要格式化的 ${descriptor.label} 代码`}
+                />
+              </Card>
+              <Card
+                class="h-full w-0 flex-1"
+                title="输出"
+                loading={output.loading}
+                operation={<TextReadButtons value={output()} />}
+              >
+                <Editor
+                  value={output()}
+                  language={editorLanguage(descriptor.name)}
+                  readOnly={true}
+                />
+              </Card>
+            </Main>
+          </>
+        )}
+      </Show>
+    </Container>
+  );
+}
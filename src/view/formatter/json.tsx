@@ -1,3 +1,4 @@
+import { Indent } from "@/command/formatter/indent";
 import { formatJson } from "@/command/formatter/json";
 import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
 import Card from "@/component/Card";
@@ -10,13 +11,6 @@ import { stringify } from "@/lib/util";
 import { ArrowDownAZ, Space } from "lucide-solid";
 import { createResource } from "solid-js";
 
-enum Indent {
-  TwoSpace = "TwoSpace",
-  FourSpace = "FourSpace",
-  Tab = "Tab",
-  None = "None",
-}
-
 const INDENT_OPTIONS = [
   { label: "2个空格", value: Indent.TwoSpace },
   { label: "4个空格", value: Indent.FourSpace },
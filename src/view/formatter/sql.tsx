@@ -1,4 +1,5 @@
-import { formatSql } from "@/command/formatter/sql";
+import { Indent } from "@/command/formatter/indent";
+import { Dialect, formatSql, KeywordCase } from "@/command/formatter/sql";
 import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
 import Card from "@/component/Card";
 import Config from "@/component/Config";
@@ -6,31 +7,41 @@ import Container from "@/component/Container";
 import Editor from "@/component/Editor";
 import Main from "@/component/Main";
 import { createCachableStore } from "@/lib/cache";
-import { CaseUpper, Code, Space } from "lucide-solid";
+import { stringify } from "@/lib/util";
+import {
+  ArrowDownToLine,
+  CaseUpper,
+  Braces,
+  Code,
+  MessageSquare,
+  Rows3,
+  Space,
+  WrapText,
+} from "lucide-solid";
 import { createResource } from "solid-js";
 
-enum Indent {
-  TwoSpace = "TwoSpace",
-  FourSpace = "FourSpace",
-  Tab = "Tab",
-}
-
 const INDENT_OPTIONS = [
   { label: "2个空格", value: Indent.TwoSpace },
   { label: "4个空格", value: Indent.FourSpace },
   { label: "1个制表符", value: Indent.Tab },
+  { label: "压缩", value: Indent.Minify },
 ];
 
-enum Dialect {
-  Generic = "Generic",
-  SQLServer = "SQLServer",
-  PostgreSql = "PostgreSql",
-}
-
 const DIALECT_OPTIONS = [
   { label: "标准", value: Dialect.Generic },
-  { label: "SQLServer", value: Dialect.SQLServer },
+  { label: "MySQL", value: Dialect.MySql },
   { label: "PostgreSQL", value: Dialect.PostgreSql },
+  { label: "SQLServer", value: Dialect.SQLServer },
+  { label: "SQLite", value: Dialect.SQLite },
+  { label: "Oracle", value: Dialect.Oracle },
+  { label: "ClickHouse", value: Dialect.ClickHouse },
+  { label: "Hive", value: Dialect.Hive },
+];
+
+const KEYWORD_CASE_OPTIONS = [
+  { label: "保持原样", value: KeywordCase.Preserve },
+  { label: "大写", value: KeywordCase.Upper },
+  { label: "小写", value: KeywordCase.Lower },
 ];
 
 export default function SqlFormatter() {
@@ -38,18 +49,46 @@ export default function SqlFormatter() {
   const [store, setStore] = createCachableStore({
     indent: Indent.TwoSpace,
     dialect: Dialect.Generic,
-    uppercase: false,
+    keywordCase: KeywordCase.Preserve,
+    linesBetweenQueries: 1,
+    maxInlineBlock: 50,
+    maxInlineArguments: 0,
+    maxInlineTopLevel: 0,
+    comments: true,
+    interpolate: false,
+    parameters: "",
     input: "",
   });
 
   // 输出结果
   const [output] = createResource(
     () => ({ ...store }),
-    ({ indent, dialect, uppercase, input }) => {
+    ({
+      indent,
+      dialect,
+      keywordCase,
+      linesBetweenQueries,
+      maxInlineBlock,
+      maxInlineArguments,
+      maxInlineTopLevel,
+      comments,
+      interpolate,
+      parameters,
+      input,
+    }) => {
       if (input) {
-        return formatSql(input, indent, dialect, uppercase).catch((e) =>
-          e.toString(),
-        );
+        return formatSql(
+          input,
+          indent,
+          dialect,
+          keywordCase,
+          linesBetweenQueries,
+          maxInlineBlock,
+          maxInlineArguments > 0 ? maxInlineArguments : null,
+          maxInlineTopLevel > 0 ? maxInlineTopLevel : null,
+          comments,
+          interpolate ? parameters : null,
+        ).catch(stringify);
       }
     },
   );
@@ -86,15 +125,111 @@ export default function SqlFormatter() {
           />
         </Config.Option>
 
-        {/*关键字大写配置*/}
+        {/*关键字大小写配置*/}
         <Config.Option
-          label="关键字大写"
-          description="将SQL关键字转为大写格式"
+          label="关键字大小写"
+          description="设置SQL关键字的大小写"
           icon={() => <CaseUpper size={16} />}
+        >
+          <Config.Select
+            value={store.keywordCase}
+            options={KEYWORD_CASE_OPTIONS}
+            onChange={(value) => setStore("keywordCase", value)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*语句间空行配置*/}
+        <Config.Option
+          label="语句间空行"
+          description="多条语句之间的空行数"
+          icon={() => <Rows3 size={16} />}
+        >
+          <Config.NumberInput
+            value={store.linesBetweenQueries}
+            min={0}
+            max={10}
+            onInput={(value) => setStore("linesBetweenQueries", value || 0)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*括号内联配置*/}
+        <Config.Option
+          label="括号内联宽度"
+          description="括号内的内容不超过该宽度时保持在一行"
+          icon={() => <WrapText size={16} />}
+        >
+          <Config.NumberInput
+            value={store.maxInlineBlock}
+            min={0}
+            onInput={(value) => setStore("maxInlineBlock", value || 0)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*参数内联配置*/}
+        <Config.Option
+          label="参数内联宽度"
+          description="函数参数和列不超过该宽度时保持在一行，0 表示每项一行"
+          icon={() => <WrapText size={16} />}
+        >
+          <Config.NumberInput
+            value={store.maxInlineArguments}
+            min={0}
+            onInput={(value) => setStore("maxInlineArguments", value || 0)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*子句内联配置*/}
+        <Config.Option
+          label="子句内联宽度"
+          description="SELECT、FROM 等子句不超过该宽度时与关键字保持在一行，0 表示换行"
+          icon={() => <ArrowDownToLine size={16} />}
+        >
+          <Config.NumberInput
+            value={store.maxInlineTopLevel}
+            min={0}
+            onInput={(value) => setStore("maxInlineTopLevel", value || 0)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*注释配置*/}
+        <Config.Option
+          label="保留注释"
+          description="关闭后去除所有注释"
+          icon={() => <MessageSquare size={16} />}
+        >
+          <Config.Switch
+            value={store.comments}
+            onChange={(value) => setStore("comments", value)}
+          />
+        </Config.Option>
+
+        {/*参数替换配置*/}
+        <Config.Option
+          label="参数替换"
+          description="将参数替换到 ?、$1、:name 占位符中，可直接粘贴 MyBatis 的 Preparing/Parameters 日志"
+          icon={() => <Braces size={16} />}
         >
           <Config.Switch
-            value={store.uppercase}
-            onChange={(value) => setStore("uppercase", value)}
+            value={store.interpolate}
+            onChange={(value) => setStore("interpolate", value)}
+          />
+        </Config.Option>
+
+        {/*参数列表配置*/}
+        <Config.Option
+          label="参数列表"
+          description="逗号分隔，支持 value(Type) 和 name=value 的写法"
+          icon={() => <Braces size={16} />}
+        >
+          <Config.Input
+            value={store.parameters}
+            onInput={(value) => setStore("parameters", value)}
+            class="w-50"
           />
         </Config.Option>
       </Config.Card>
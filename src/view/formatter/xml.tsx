@@ -1,46 +1,124 @@
-import { formatXml } from "@/command/formatter/xml";
+import { Indent } from "@/command/formatter/indent";
+import {
+  Declaration,
+  EmptyElement,
+  formatXml,
+} from "@/command/formatter/xml";
+import { SchemaKind, validateXml } from "@/command/formatter/xml_schema";
 import { TextReadButtons, TextWriteButtons } from "@/component/Buttons";
 import Card from "@/component/Card";
 import Config from "@/component/Config";
 import Container from "@/component/Container";
 import Editor from "@/component/Editor";
+import Flex from "@/component/Flex";
 import Main from "@/component/Main";
 import { createCachableStore } from "@/lib/cache";
 import { stringify } from "@/lib/util";
-import { Space } from "lucide-solid";
-import { createResource } from "solid-js";
-
-enum Indent {
-  TwoSpace = "TwoSpace",
-  FourSpace = "FourSpace",
-  Tab = "Tab",
-  None = "None",
-}
+import {
+  ArrowDownAZ,
+  CircleCheckBig,
+  Code,
+  FileCode,
+  MessageSquare,
+  Space,
+  SquareDashed,
+  WrapText,
+} from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
 
 const INDENT_OPTIONS = [
   { label: "2个空格", value: Indent.TwoSpace },
   { label: "4个空格", value: Indent.FourSpace },
   { label: "1个制表符", value: Indent.Tab },
-  { label: "无缩进", value: Indent.None },
+  { label: "保持原样", value: Indent.None },
+  { label: "压缩", value: Indent.Minify },
+];
+
+const EMPTY_ELEMENT_OPTIONS = [
+  { label: "保持原样", value: EmptyElement.Preserve },
+  { label: "<a></a>", value: EmptyElement.Expand },
+  { label: "<a/>", value: EmptyElement.Collapse },
+];
+
+const DECLARATION_OPTIONS = [
+  { label: "保持原样", value: Declaration.Preserve },
+  { label: "补充", value: Declaration.Add },
+  { label: "去除", value: Declaration.Remove },
+];
+
+const SCHEMA_OPTIONS = [
+  { label: "不校验", value: "" },
+  { label: "XSD", value: SchemaKind.Xsd },
+  { label: "DTD", value: SchemaKind.Dtd },
 ];
 
 export default function XmlFormatter() {
   // 页面参数
   const [store, setStore] = createCachableStore({
     indent: Indent.TwoSpace,
+    wrapAttributes: 0,
+    sortAttributes: false,
+    comments: true,
+    processingInstructions: true,
+    emptyElement: EmptyElement.Preserve,
+    declaration: Declaration.Preserve,
+    schemaKind: "",
+    schema: "",
     input: "",
   });
 
   // 输出结果
   const [output] = createResource(
     () => ({ ...store }),
-    ({ indent, input }) => {
+    ({
+      indent,
+      wrapAttributes,
+      sortAttributes,
+      comments,
+      processingInstructions,
+      emptyElement,
+      declaration,
+      input,
+    }) => {
       if (input) {
-        return formatXml(input, indent).catch(stringify);
+        return formatXml(
+          input,
+          indent,
+          wrapAttributes > 0 ? wrapAttributes : null,
+          sortAttributes,
+          comments,
+          processingInstructions,
+          emptyElement,
+          declaration,
+        ).catch(stringify);
       }
     },
   );
 
+  // 校验结果
+  const [report] = createResource(
+    () => ({
+      kind: store.schemaKind,
+      schema: store.schema,
+      input: store.input,
+    }),
+    ({ kind, schema, input }) => {
+      if (kind && input) {
+        return validateXml(
+          input,
+          kind as SchemaKind,
+          schema ? [schema] : [],
+        ).catch(stringify);
+      }
+    },
+  );
+
+  // 校验报告，出错时为空
+  const violations = () => {
+    const value = report();
+    return typeof value === "object" ? value : undefined;
+  };
+
   return (
     <Container>
       {/* 配置 */}
@@ -58,6 +136,98 @@ export default function XmlFormatter() {
             class="w-30"
           />
         </Config.Option>
+
+        {/*属性换行配置*/}
+        <Config.Option
+          label="属性换行"
+          description="起始标签超过该宽度时每个属性一行，0 表示不换行"
+          icon={() => <WrapText size={16} />}
+        >
+          <Config.NumberInput
+            value={store.wrapAttributes}
+            min={0}
+            onInput={(value) => setStore("wrapAttributes", value || 0)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*属性排序配置*/}
+        <Config.Option
+          label="属性排序"
+          description="按名称排序属性，命名空间声明保持在最前面"
+          icon={() => <ArrowDownAZ size={16} />}
+        >
+          <Config.Switch
+            value={store.sortAttributes}
+            onChange={(value) => setStore("sortAttributes", value)}
+          />
+        </Config.Option>
+
+        {/*注释配置*/}
+        <Config.Option
+          label="保留注释"
+          description="关闭后去除所有注释"
+          icon={() => <MessageSquare size={16} />}
+        >
+          <Config.Switch
+            value={store.comments}
+            onChange={(value) => setStore("comments", value)}
+          />
+        </Config.Option>
+
+        {/*处理指令配置*/}
+        <Config.Option
+          label="保留处理指令"
+          description="关闭后去除 <?target ...?> 形式的处理指令"
+          icon={() => <Code size={16} />}
+        >
+          <Config.Switch
+            value={store.processingInstructions}
+            onChange={(value) => setStore("processingInstructions", value)}
+          />
+        </Config.Option>
+
+        {/*空元素配置*/}
+        <Config.Option
+          label="空元素"
+          description="没有子节点的元素的写法"
+          icon={() => <SquareDashed size={16} />}
+        >
+          <Config.Select
+            value={store.emptyElement}
+            options={EMPTY_ELEMENT_OPTIONS}
+            onChange={(value) => setStore("emptyElement", value)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*声明配置*/}
+        <Config.Option
+          label="XML 声明"
+          description="处理 <?xml ...?> 声明"
+          icon={() => <FileCode size={16} />}
+        >
+          <Config.Select
+            value={store.declaration}
+            options={DECLARATION_OPTIONS}
+            onChange={(value) => setStore("declaration", value)}
+            class="w-30"
+          />
+        </Config.Option>
+
+        {/*校验配置*/}
+        <Config.Option
+          label="校验"
+          description="使用 XSD 或 DTD 校验输入的 XML"
+          icon={() => <CircleCheckBig size={16} />}
+        >
+          <Config.Select
+            value={store.schemaKind}
+            options={SCHEMA_OPTIONS}
+            onChange={(value) => setStore("schemaKind", value)}
+            class="w-30"
+          />
+        </Config.Option>
       </Config.Card>
 
       <Main>
@@ -84,6 +254,72 @@ export default function XmlFormatter() {
           <Editor value={output()} language="xml" readOnly={true} />
         </Card>
       </Main>
+
+      {/*校验*/}
+      <Show when={store.schemaKind}>
+        <Flex class="h-0 flex-1">
+          <Card
+            class="h-full w-0 flex-1"
+            title={store.schemaKind === SchemaKind.Xsd ? "XSD" : "外部 DTD"}
+            operation={
+              <TextWriteButtons
+                callback={(value) => setStore("schema", value)}
+              />
+            }
+          >
+            <Editor
+              value={store.schema}
+              onChange={(value) => setStore("schema", value)}
+              language="xml"
+              placeholder={
+                store.schemaKind === SchemaKind.Xsd
+                  ? "输入用于校验的 XSD"
+                  : "输入外部 DTD，留空则只使用文档中的内部 DTD"
+              }
+            />
+          </Card>
+          <Card
+            class="h-full w-0 flex-1 overflow-x-hidden"
+            title="校验结果"
+            loading={report.loading}
+          >
+            <Show
+              when={violations()}
+              fallback={<pre class="p-2 text-sm">{report() as string}</pre>}
+              keyed
+            >
+              {(report) =>
+                report.valid ? (
+                  <div class="text-success p-2">校验通过</div>
+                ) : (
+                  <div class="size-full overflow-x-auto">
+                    <table class="table-pin-rows table-sm table">
+                      <thead>
+                        <tr>
+                          <th>位置</th>
+                          <th>路径</th>
+                          <th>描述</th>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        <For each={report.violations}>
+                          {(violation) => (
+                            <tr class="hover:bg-base-300">
+                              <td>{`${violation.line}:${violation.column}`}</td>
+                              <td class="font-mono">{violation.path}</td>
+                              <td>{violation.message}</td>
+                            </tr>
+                          )}
+                        </For>
+                      </tbody>
+                    </table>
+                  </div>
+                )
+              }
+            </Show>
+          </Card>
+        </Flex>
+      </Show>
     </Container>
   );
 }
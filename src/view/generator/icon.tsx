@@ -0,0 +1,233 @@
+import {
+  GeneratedIcons,
+  generateIcons,
+  IconTarget,
+} from "@/command/generate/icon";
+import { CopyButton, PickFileButton } from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import Flex from "@/component/Flex";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { convertFileSrc } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import { Image, LayoutGrid, PaintBucket, Tag } from "lucide-solid";
+import { createSignal, For, Show } from "solid-js";
+
+const TARGETS = [
+  { label: "ICO", value: IconTarget.Ico },
+  { label: "ICNS", value: IconTarget.Icns },
+  { label: "Web", value: IconTarget.Web },
+  { label: "Android", value: IconTarget.Android },
+  { label: "iOS", value: IconTarget.Ios },
+];
+
+export default function IconGenerator() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    source: "",
+    targets: [IconTarget.Ico, IconTarget.Web] as IconTarget[],
+    name: "",
+    background: "#ffffff",
+  });
+
+  const [generated, setGenerated] = createSignal<GeneratedIcons | string>();
+  const [loading, setLoading] = createSignal(false);
+
+  const result = () => {
+    const value = generated();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const failure = () => {
+    const value = generated();
+    return typeof value === "string" ? value : undefined;
+  };
+
+  // 切换目标平台
+  const toggle = (target: IconTarget, value: boolean) => {
+    setStore("targets", (targets) =>
+      value
+        ? [...targets.filter((item) => item !== target), target]
+        : targets.filter((item) => item !== target),
+    );
+  };
+
+  // 选择输出目录后生成
+  const generate = () => {
+    open({ title: "选择输出目录", directory: true })
+      .then((dir) => {
+        if (!dir) {
+          return;
+        }
+        setLoading(true);
+        return generateIcons(
+          store.source,
+          store.targets,
+          dir,
+          store.name || null,
+          store.background || null,
+        )
+          .catch(stringify)
+          .then(setGenerated)
+          .finally(() => setLoading(false));
+      })
+      .catch((e) => console.error("generate icons error!", e));
+  };
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        <Config.Option
+          label="目标平台"
+          description="选择需要生成的图标"
+          icon={() => <LayoutGrid size={16} />}
+        >
+          <Flex>
+            <For each={TARGETS}>
+              {(target) => (
+                <label class="flex items-center gap-1 text-sm">
+                  <input
+                    type="checkbox"
+                    class="checkbox checkbox-sm"
+                    checked={store.targets.includes(target.value)}
+                    onChange={(e) =>
+                      toggle(target.value, e.currentTarget.checked)
+                    }
+                  />
+                  {target.label}
+                </label>
+              )}
+            </For>
+          </Flex>
+        </Config.Option>
+
+        <Config.Option
+          label="名称"
+          description="写入 manifest.json 的应用名称"
+          icon={() => <Tag size={16} />}
+        >
+          <Config.Input
+            value={store.name}
+            placeholder="App"
+            onInput={(value) => setStore("name", value)}
+            class="w-50"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="背景色"
+          description="apple-touch-icon 和 iOS 图标不支持透明，使用该颜色填充"
+          icon={() => <PaintBucket size={16} />}
+        >
+          <Config.Input
+            value={store.background}
+            placeholder="#ffffff"
+            onInput={(value) => setStore("background", value)}
+            class="w-50"
+          />
+        </Config.Option>
+      </Config.Card>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-1"
+          title="源图片"
+          operation={
+            <Flex>
+              <PickFileButton
+                label="选择图片"
+                filters={[{ name: "Images", extensions: ["png", "svg"] }]}
+                onPick={(file) => file && setStore("source", file)}
+              />
+              <button
+                class="btn btn-sm btn-primary"
+                disabled={!store.source || !store.targets.length || loading()}
+                onClick={generate}
+              >
+                生成
+              </button>
+            </Flex>
+          }
+        >
+          <div class="border-base-content/20 bg-base-100 flex flex-1 items-center justify-center overflow-hidden rounded-md border p-2">
+            <Show
+              when={store.source}
+              fallback={
+                <span class="text-warning flex items-center justify-center gap-2 text-sm">
+                  <Image size={16} />
+                  选择 512×512 以上的 PNG 或 SVG
+                </span>
+              }
+            >
+              <img
+                src={convertFileSrc(store.source)}
+                class="size-full object-scale-down"
+              />
+            </Show>
+          </div>
+        </Card>
+
+        <Card
+          class="h-full w-0 flex-1"
+          title="生成的文件"
+          loading={loading()}
+          notification={
+            <Show when={result()}>
+              {(value) => (
+                <span class="text-sm opacity-60">
+                  共 {value().files.length} 个文件
+                </span>
+              )}
+            </Show>
+          }
+        >
+          <Show
+            when={result()}
+            fallback={<span class="text-error text-sm">{failure()}</span>}
+          >
+            {(value) => (
+              <>
+                <div class="max-h-60 overflow-auto font-mono text-sm">
+                  <For each={value().files}>
+                    {(file) => <div class="select-text">{file}</div>}
+                  </For>
+                </div>
+                <Show when={value().html}>
+                  {(html) => (
+                    <Card
+                      class="h-0 flex-1"
+                      title="HTML"
+                      operation={<CopyButton value={html()} />}
+                    >
+                      <Editor value={html()} readOnly={true} language="html" />
+                    </Card>
+                  )}
+                </Show>
+                <Show when={value().manifest}>
+                  {(manifest) => (
+                    <Card
+                      class="h-0 flex-1"
+                      title="manifest.json"
+                      operation={<CopyButton value={manifest()} />}
+                    >
+                      <Editor
+                        value={manifest()}
+                        readOnly={true}
+                        language="json"
+                      />
+                    </Card>
+                  )}
+                </Show>
+              </>
+            )}
+          </Show>
+        </Card>
+      </Main>
+    </Container>
+  );
+}
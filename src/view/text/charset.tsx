@@ -0,0 +1,256 @@
+import { Encoding } from "@/command/crypto/type";
+import {
+  Charset,
+  detectCharset,
+  Source,
+  transcodeCharset,
+} from "@/command/text/charset";
+import {
+  ClearButton,
+  CopyButton,
+  PasteButton,
+  PickFileButton,
+  TextReadButtons,
+} from "@/component/Buttons";
+import Card from "@/component/Card";
+import { CHARSET_OPTIONS } from "@/component/Charset";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import { EncodingSelect } from "@/component/Encoding";
+import Flex from "@/component/Flex";
+import Main from "@/component/Main";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { save } from "@tauri-apps/plugin-dialog";
+import { ArrowRight, Bookmark, Languages, ShieldAlert } from "lucide-solid";
+import { createResource, For, Show } from "solid-js";
+
+const SOURCE_CHARSETS = [{ label: "自动识别", value: "" }, ...CHARSET_OPTIONS];
+
+export default function CharsetConverter() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    file: "",
+    input: "",
+    inputEncoding: Encoding.Hex,
+    from: "" as Charset | "",
+    to: Charset.Utf8,
+    encoding: Encoding.Hex,
+    bom: false,
+    lossy: false,
+  });
+
+  // 优先使用选择的文件
+  const source = (): Source | undefined => {
+    if (store.file) {
+      return { File: store.file };
+    }
+    if (store.input.trim()) {
+      return { Bytes: { text: store.input, encoding: store.inputEncoding } };
+    }
+  };
+
+  // 探测字符集
+  const [detection] = createResource(source, (source) =>
+    detectCharset(source).catch(stringify),
+  );
+
+  // 转换字符集，指定 output 时写入文件
+  const transcode = (source: Source, output: string | null = null) =>
+    transcodeCharset(
+      source,
+      store.to,
+      store.from || null,
+      store.encoding,
+      store.bom,
+      store.lossy,
+      output,
+    );
+
+  const [output] = createResource(
+    () => {
+      const value = source();
+      return value && { ...store, source: value };
+    },
+    ({ source }) => transcode(source).catch(stringify),
+  );
+
+  const detected = () => {
+    const value = detection();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const transcoded = () => {
+    const value = output();
+    return typeof value === "string" ? undefined : value;
+  };
+
+  const failure = () => {
+    const value = output();
+    return typeof value === "string" ? value : undefined;
+  };
+
+  // 转换后另存为文件
+  const saveAs = () => {
+    const value = source();
+    if (value) {
+      save()
+        .then((file) => file && transcode(value, file))
+        .catch((e) => console.error("transcode charset error!", e));
+    }
+  };
+
+  const percent = (value: number) => `${Math.round(value * 100)}%`;
+
+  return (
+    <Container>
+      {/* 配置 */}
+      <Config.Card>
+        <Config.Option
+          label="源字符集"
+          description="为空时根据 BOM 和内容自动识别"
+          icon={() => <Languages size={16} />}
+        >
+          <Config.Select
+            value={store.from}
+            options={SOURCE_CHARSETS}
+            onChange={(value) => setStore("from", value)}
+            class="w-35"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="目标字符集"
+          description="转换后的字符集"
+          icon={() => <ArrowRight size={16} />}
+        >
+          <Config.Select
+            value={store.to}
+            options={CHARSET_OPTIONS}
+            onChange={(value) => setStore("to", value)}
+            class="w-35"
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="BOM"
+          description="在输出开头写入 BOM，仅对 UTF-8 和 UTF-16 有效"
+          icon={() => <Bookmark size={16} />}
+        >
+          <Config.Switch
+            value={store.bom}
+            onChange={(value) => setStore("bom", value)}
+          />
+        </Config.Option>
+
+        <Config.Option
+          label="容错"
+          description="开启后非法字节替换为 �，否则提示出错的位置"
+          icon={() => <ShieldAlert size={16} />}
+        >
+          <Config.Switch
+            value={store.lossy}
+            onChange={(value) => setStore("lossy", value)}
+          />
+        </Config.Option>
+      </Config.Card>
+
+      {/* 输入 */}
+      <Card
+        title="输入"
+        loading={detection.loading}
+        operation={
+          <Flex>
+            <EncodingSelect
+              label="编码"
+              value={store.inputEncoding}
+              onChange={(value) => setStore("inputEncoding", value)}
+              exclude={[Encoding.Utf8]}
+            />
+            <PickFileButton onPick={(file) => setStore("file", file ?? "")} />
+            <PasteButton
+              onRead={(value) => setStore({ file: "", input: value })}
+            />
+            <ClearButton onClick={() => setStore({ file: "", input: "" })} />
+          </Flex>
+        }
+      >
+        <Show
+          when={!store.file}
+          fallback={<span class="font-mono text-sm">{store.file}</span>}
+        >
+          <input
+            class="input input-md w-full rounded-md font-mono outline-none"
+            placeholder="输入 Hex 或 Base64 编码的字节，或者导入文件"
+            value={store.input}
+            onInput={(e) => setStore("input", e.target.value)}
+          />
+        </Show>
+        <Show
+          when={detected()}
+          fallback={<span class="text-error text-sm">{detection()}</span>}
+        >
+          {(value) => (
+            <div class="flex flex-wrap items-center gap-2 text-sm">
+              <span>识别为</span>
+              <For each={value().candidates}>
+                {(candidate, index) => (
+                  <span
+                    class="badge badge-sm"
+                    classList={{
+                      "badge-primary": index() === 0,
+                      "badge-outline": index() !== 0,
+                    }}
+                  >
+                    {candidate.charset} {percent(candidate.confidence)}
+                  </span>
+                )}
+              </For>
+              <Show when={value().bom}>
+                <span class="opacity-60">（包含 BOM）</span>
+              </Show>
+            </div>
+          )}
+        </Show>
+      </Card>
+
+      <Main>
+        <Card
+          class="h-full w-0 flex-1"
+          title="文本"
+          loading={output.loading}
+          notification={
+            <Show when={transcoded()?.replaced}>
+              <span class="text-warning text-sm">部分字节已被替换</span>
+            </Show>
+          }
+          operation={<TextReadButtons value={transcoded()?.text ?? ""} />}
+        >
+          <Editor value={transcoded()?.text ?? failure()} readOnly={true} />
+        </Card>
+        <Card
+          class="h-full w-0 flex-1"
+          title="转换结果"
+          loading={output.loading}
+          operation={
+            <Flex>
+              <EncodingSelect
+                label="编码"
+                value={store.encoding}
+                onChange={(value) => setStore("encoding", value)}
+                exclude={[Encoding.Utf8]}
+              />
+              <CopyButton value={transcoded()?.bytes ?? ""} />
+              <button class="btn btn-sm" onClick={saveAs}>
+                另存为
+              </button>
+            </Flex>
+          }
+        >
+          <Editor value={transcoded()?.bytes ?? ""} readOnly={true} />
+        </Card>
+      </Main>
+    </Container>
+  );
+}
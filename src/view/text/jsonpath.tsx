@@ -1,4 +1,4 @@
-import { parseJsonPath } from "@/command/text/jsonpath";
+import { Dialect, parseJsonPath } from "@/command/text/jsonpath";
 import {
   ClearButton,
   PasteButton,
@@ -6,6 +6,7 @@ import {
   TextWriteButtons,
 } from "@/component/Buttons";
 import Card from "@/component/Card";
+import Config from "@/component/Config";
 import Container from "@/component/Container";
 import Editor from "@/component/Editor";
 import Flex from "@/component/Flex";
@@ -120,9 +121,17 @@ const JsonPathGrammars: Array<{ expression: string; description: string }> = [
   },
 ];
 
+const DIALECT_OPTIONS = [
+  { label: "JSONPath", value: Dialect.JsonPath },
+  { label: "RFC 9535", value: Dialect.Rfc9535 },
+  { label: "JMESPath", value: Dialect.JmesPath },
+  { label: "jq", value: Dialect.Jq },
+];
+
 export default function JSONPath() {
   // 页面参数
   const [store, setStore] = createCachableStore({
+    dialect: Dialect.JsonPath,
     pattern: "",
     text: "",
   });
@@ -130,9 +139,11 @@ export default function JSONPath() {
   // 输出结果
   const [output] = createResource(
     () => ({ ...store }),
-    ({ pattern, text }) => {
+    ({ dialect, pattern, text }) => {
       if (text && pattern) {
-        return parseJsonPath(text, pattern).catch(stringify);
+        return parseJsonPath(text, pattern, dialect)
+          .then((output) => output.text)
+          .catch(stringify);
       }
     },
   );
@@ -144,6 +155,12 @@ export default function JSONPath() {
         title="JSONPath"
         operation={
           <Flex>
+            <Config.Select
+              value={store.dialect}
+              options={DIALECT_OPTIONS}
+              onChange={(value) => setStore("dialect", value)}
+              class="w-30"
+            />
             <PasteButton onRead={(value) => setStore("pattern", value)} />
             <ClearButton onClick={() => setStore("pattern", "")} />
           </Flex>
@@ -0,0 +1,167 @@
+import { Extract, Language, Match, parseXPath } from "@/command/text/xpath";
+import {
+  ClearButton,
+  PasteButton,
+  TextReadButtons,
+  TextWriteButtons,
+} from "@/component/Buttons";
+import Card from "@/component/Card";
+import Config from "@/component/Config";
+import Container from "@/component/Container";
+import Editor from "@/component/Editor";
+import Flex from "@/component/Flex";
+import { createCachableStore } from "@/lib/cache";
+import { stringify } from "@/lib/util";
+import { createResource, For, Show } from "solid-js";
+
+const LANGUAGE_OPTIONS = [
+  { label: "XPath", value: Language.XPath },
+  { label: "CSS 选择器", value: Language.Css },
+];
+
+const EXTRACT_OPTIONS = [
+  { label: "标记", value: "Markup" },
+  { label: "文本", value: "Text" },
+  { label: "属性", value: "Attribute" },
+];
+
+// 匹配结果的位置
+const position = ({ line, column }: Match) =>
+  line === null ? "" : `${line}:${column ?? 1}`;
+
+export default function XPath() {
+  // 页面参数
+  const [store, setStore] = createCachableStore({
+    language: Language.XPath,
+    extract: "Markup",
+    attribute: "",
+    pattern: "",
+    text: "",
+  });
+
+  // 输出结果
+  const [output] = createResource(
+    () => ({ ...store }),
+    ({ language, extract, attribute, pattern, text }) => {
+      if (text && pattern) {
+        const target: Extract =
+          extract === "Attribute"
+            ? { Attribute: attribute }
+            : (extract as "Markup" | "Text");
+        return parseXPath(text, pattern, language, {}, target).catch(stringify);
+      }
+    },
+  );
+
+  const result = () => {
+    const value = output();
+    return typeof value === "string" ? value : value?.text;
+  };
+
+  const matches = () => {
+    const value = output();
+    return typeof value === "string" ? [] : (value?.matches ?? []);
+  };
+
+  return (
+    <Container>
+      {/*表达式*/}
+      <Card
+        title="表达式"
+        operation={
+          <Flex>
+            <Config.Select
+              value={store.language}
+              options={LANGUAGE_OPTIONS}
+              onChange={(value) => setStore("language", value)}
+              class="w-30"
+            />
+            <Config.Select
+              value={store.extract}
+              options={EXTRACT_OPTIONS}
+              onChange={(value) => setStore("extract", value)}
+              class="w-20"
+            />
+            <Show when={store.extract === "Attribute"}>
+              <Config.Input
+                value={store.attribute}
+                onInput={(value) => setStore("attribute", value)}
+                class="w-30"
+              />
+            </Show>
+            <PasteButton onRead={(value) => setStore("pattern", value)} />
+            <ClearButton onClick={() => setStore("pattern", "")} />
+          </Flex>
+        }
+      >
+        <input
+          class="input input-md w-full rounded-md font-mono font-bold outline-none"
+          placeholder={
+            store.language === Language.XPath
+              ? "输入 XPath 表达式"
+              : "输入 CSS 选择器"
+          }
+          value={store.pattern}
+          onInput={(e) => setStore("pattern", e.target.value)}
+        />
+      </Card>
+
+      {/*XML / HTML*/}
+      <Card
+        class="h-0 flex-1"
+        title="XML / HTML"
+        operation={
+          <TextWriteButtons callback={(value) => setStore("text", value)} />
+        }
+      >
+        <Editor
+          value={store.text}
+          onChange={(value) => setStore("text", value)}
+          language="xml"
+          placeholder="输入需要解析的 XML 或 HTML 文档"
+        />
+      </Card>
+
+      <Flex class="h-0 flex-1">
+        {/*匹配结果*/}
+        <Card
+          class="h-full flex-1 overflow-x-hidden"
+          title="结果"
+          loading={output.loading}
+          operation={<TextReadButtons value={result()} />}
+        >
+          <Editor language="xml" value={result()} readOnly={true} />
+        </Card>
+
+        {/*匹配信息*/}
+        <Card
+          class="h-full flex-1 overflow-x-hidden"
+          title={`匹配 ${matches().length} 项`}
+        >
+          <div class="size-full overflow-x-auto">
+            <table class="table-pin-rows table-sm table">
+              <thead>
+                <tr>
+                  <th>类型</th>
+                  <th>路径</th>
+                  <th>位置</th>
+                </tr>
+              </thead>
+              <tbody>
+                <For each={matches()}>
+                  {(match) => (
+                    <tr class="hover:bg-base-300">
+                      <td>{match.kind}</td>
+                      <td class="font-mono">{match.path}</td>
+                      <td>{position(match)}</td>
+                    </tr>
+                  )}
+                </For>
+              </tbody>
+            </table>
+          </div>
+        </Card>
+      </Flex>
+    </Container>
+  );
+}